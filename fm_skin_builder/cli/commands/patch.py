@@ -24,6 +24,8 @@ def run(args) -> None:
         dry_run=args.dry_run,
         use_scan_cache=not args.no_scan_cache,
         refresh_scan_cache=args.refresh_scan_cache,
+        only_bundles=getattr(args, "only_bundle", None),
+        target=getattr(args, "target", None),
     )
 
     if result.summary_lines:
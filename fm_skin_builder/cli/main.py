@@ -9,6 +9,8 @@ from .commands import (
     scan as cmd_scan,
     catalogue as cmd_catalogue,
 )
+from ..core.bundle_paths import PLATFORM_TARGETS
+from ..core.logger import set_verbosity
 import os
 import sys
 import gc
@@ -74,6 +76,31 @@ def main() -> None:
         action="store_true",
         help="Force refresh of scan cache before patching (if a skin config is present)",
     )
+    p.add_argument(
+        "--only-bundle",
+        action="append",
+        default=None,
+        metavar="NAME",
+        help="Only patch the bundle with this file name (repeatable)",
+    )
+    p.add_argument(
+        "--target",
+        type=str,
+        choices=PLATFORM_TARGETS,
+        default=None,
+        help="Unity build target of the game install (defaults to the host OS layout)",
+    )
+    verbosity = p.add_mutually_exclusive_group()
+    verbosity.add_argument(
+        "-v",
+        "--verbose",
+        action="count",
+        default=0,
+        help="Log debug output (-vv also from third-party libraries)",
+    )
+    verbosity.add_argument(
+        "--quiet", action="store_true", help="Only log progress, warnings and errors"
+    )
 
     s = sub.add_parser("scan", help="Scan bundles and index stylesheet usage")
     s.add_argument(
@@ -174,6 +201,7 @@ def main() -> None:
     d.add_argument("--pretty", action="store_true", help="Pretty-print JSON output")
 
     args = parser.parse_args()
+    set_verbosity(getattr(args, "verbose", 0), getattr(args, "quiet", False))
 
     if args.command == "build":
         cmd_build.run(args)
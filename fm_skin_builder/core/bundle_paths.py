@@ -2,15 +2,69 @@ from __future__ import annotations
 
 import os
 from pathlib import Path
-from typing import List
+from typing import List, Optional
 
 from .logger import get_logger
 
 log = get_logger(__name__)
 
+# Unity build targets the game ships with; the bundles folder is named after the target
+PLATFORM_TARGETS = (
+    "StandaloneWindows64",
+    "StandaloneOSX",
+    "StandaloneOSXUniversal",
+    "StandaloneLinux64",
+)
 
-def infer_bundle_files(css_dir: Path) -> List[Path]:
-    """Infer bundle file(s) from Football Manager 26 default install locations."""
+
+def _wine_candidates() -> List[Path]:
+    """Windows builds installed through Proton (Steam) or Wine (Heroic) on Linux/macOS."""
+
+    roots = [
+        "~/.local/share/Steam/steamapps/common/Football Manager 26",
+        "~/.steam/steam/steamapps/common/Football Manager 26",
+        "~/Library/Application Support/Steam/steamapps/common/Football Manager 26",
+        "~/Games/Heroic/Football Manager 26",
+        "~/Games/Heroic/FootballManager26",
+    ]
+    return [
+        Path(os.path.expanduser(root))
+        / data_dir
+        / "StreamingAssets/aa/StandaloneWindows64"
+        for root in roots
+        for data_dir in ("data", "fm_Data")
+    ]
+
+
+def resolve_bundle_dir(bundle: Path, target: Optional[str] = None) -> Path:
+    """Pick the folder for ``target`` when an explicit bundle path points at another one.
+
+    A folder named after a different Unity target, or one holding a folder per
+    target (e.g. ``StreamingAssets/aa``), resolves to the ``target`` folder next
+    to or inside it when that folder has bundles. Anything else is returned as is.
+    """
+
+    if not target or not bundle.is_dir() or bundle.name == target:
+        return bundle
+
+    candidates = [bundle / target]
+    if bundle.name in PLATFORM_TARGETS:
+        candidates.append(bundle.parent / target)
+    for candidate in candidates:
+        if candidate.is_dir() and any(
+            p.suffix == ".bundle" for p in candidate.iterdir()
+        ):
+            log.info("Using %s bundles from %s", target, candidate)
+            return candidate
+    return bundle
+
+
+def infer_bundle_files(css_dir: Path, target: Optional[str] = None) -> List[Path]:
+    """Infer bundle file(s) from Football Manager 26 default install locations.
+
+    When ``target`` is given only installs built for that Unity target are
+    considered, including Windows builds running under Proton/Wine.
+    """
 
     try:
         import platform as _plat
@@ -67,6 +121,11 @@ def infer_bundle_files(css_dir: Path) -> List[Path]:
             ]
         )
 
+    if target:
+        if target == "StandaloneWindows64" and sysname != "Windows":
+            candidates.extend(_wine_candidates())
+        candidates = [base for base in candidates if base.name == target]
+
     for base in candidates:
         if base.exists() and base.is_dir():
             bundles = sorted([p for p in base.iterdir() if p.suffix == ".bundle"])
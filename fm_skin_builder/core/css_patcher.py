@@ -15,7 +15,8 @@ try:
 except Exception:  # pragma: no cover - UnityPy may not expose ColorRGBA in tests
     UnityColorRGBA = None
 
-from .logger import get_logger
+from .logger import PROGRESS, get_logger
+from .progress import report_progress
 from .cache import load_or_cache_config, cache_dir
 from .context import BundleContext, PatchReport
 from .services import (
@@ -57,7 +58,7 @@ from .scan_cache import (
     load_cached_bundle_index,
 )
 from .css_sources import CollectedCss, collect_css_from_dir, load_targeting_hints
-from .bundle_paths import infer_bundle_files
+from .bundle_paths import infer_bundle_files, resolve_bundle_dir
 
 log = get_logger(__name__)
 # -----------------------------
@@ -2803,6 +2804,8 @@ class PipelineOptions:
     dry_run: bool = False
     use_scan_cache: bool = True
     refresh_scan_cache: bool = False
+    only_bundles: Optional[List[str]] = None
+    target: Optional[str] = None
 
 
 @dataclass
@@ -2878,18 +2881,29 @@ class SkinPatchPipeline:
 
         bundle_files: List[Path] = []
         if bundle is not None:
+            bundle = resolve_bundle_dir(bundle, self.options.target)
             if bundle.is_dir():
                 bundle_files = [p for p in bundle.iterdir() if p.suffix == ".bundle"]
             else:
                 bundle_files = [bundle]
         else:
-            bundle_files = infer_bundle_files(self.css_dir)
+            bundle_files = infer_bundle_files(self.css_dir, self.options.target)
             if not bundle_files:
                 log.error(
                     "No bundle specified and none could be inferred from config. Provide --bundle."
                 )
                 return PipelineResult.empty()
 
+        if self.options.only_bundles:
+            wanted = set(self.options.only_bundles)
+            bundle_files = [p for p in bundle_files if p.name in wanted]
+            if not bundle_files:
+                log.error(
+                    "None of the selected bundles were found: %s",
+                    ", ".join(sorted(wanted)),
+                )
+                return PipelineResult.empty()
+
         bundle_files = self._sorted_bundle_files(bundle_files)
 
         bundles_requested = len(bundle_files)
@@ -2965,8 +2979,12 @@ class SkinPatchPipeline:
         log.info(f"\n📦 Processing {len(bundle_files)} bundle(s)...")
 
         for bundle_index, bundle_path in enumerate(bundle_files, start=1):
-            log.info(
-                f"\n=== Processing bundle {bundle_index} of {len(bundle_files)}: {bundle_path.name} ==="
+            log.log(
+                PROGRESS,
+                f"\n=== Processing bundle {bundle_index} of {len(bundle_files)}: {bundle_path.name} ===",
+            )
+            report_progress(
+                bundle_index, len(bundle_files), f"Processing bundle {bundle_path.name}"
             )
             sys.stdout.flush()  # Ensure immediate output for real-time streaming
             report = self._process_bundle(
@@ -2982,6 +3000,10 @@ class SkinPatchPipeline:
                 want_icons=want_icons,
                 want_bgs=want_bgs,
             )
+            # Machine-readable marker the desktop app uses to resume interrupted
+            # builds, so it must survive --quiet
+            log.log(PROGRESS, f"=== Bundle complete: {bundle_path.name} ===")
+            sys.stdout.flush()
 
             if report is None:
                 continue
@@ -3163,6 +3185,8 @@ def run_patch(
     dry_run: bool = False,
     use_scan_cache: bool = True,
     refresh_scan_cache: bool = False,
+    only_bundles: Optional[List[str]] = None,
+    target: Optional[str] = None,
 ) -> PipelineResult:
     """High-level entry to patch bundles based on CSS in css_dir."""
 
@@ -3173,6 +3197,8 @@ def run_patch(
         dry_run=dry_run,
         use_scan_cache=use_scan_cache,
         refresh_scan_cache=refresh_scan_cache,
+        only_bundles=only_bundles,
+        target=target,
     )
     pipeline = SkinPatchPipeline(css_dir, out_dir, options)
     return pipeline.run(bundle=bundle)
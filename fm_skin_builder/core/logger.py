@@ -1,5 +1,13 @@
 import logging
 
+# Progress markers the app parses; still logged with --quiet
+PROGRESS = 25
+logging.addLevelName(PROGRESS, "PROGRESS")
+
+# Level for every fm_skin_builder logger, changed by set_verbosity()
+_level = logging.INFO
+_loggers: list[logging.Logger] = []
+
 
 def get_logger(name: str) -> logging.Logger:
     logger = logging.getLogger(name)
@@ -7,5 +15,23 @@ def get_logger(name: str) -> logging.Logger:
         handler = logging.StreamHandler()
         handler.setFormatter(logging.Formatter("[%(levelname)s] %(message)s"))
         logger.addHandler(handler)
-        logger.setLevel(logging.INFO)
+        logger.setLevel(_level)
+        _loggers.append(logger)
     return logger
+
+
+def set_verbosity(verbose: int = 0, quiet: bool = False) -> None:
+    """Apply -v/-vv/--quiet: progress and warnings only, info (default), debug,
+    or debug including third-party libraries."""
+    global _level
+    if quiet:
+        _level = PROGRESS
+    elif verbose > 0:
+        _level = logging.DEBUG
+    else:
+        _level = logging.INFO
+    for logger in _loggers:
+        logger.setLevel(_level)
+    if verbose > 1:
+        logging.basicConfig(format="[%(levelname)s] %(name)s: %(message)s")
+        logging.getLogger().setLevel(logging.DEBUG)
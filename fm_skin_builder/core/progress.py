@@ -0,0 +1,25 @@
+"""Machine-readable progress for the desktop app.
+
+When FM_PROGRESS_FILE is set, each report is appended to that file as one JSON
+line, so the app never has to pick progress out of the human-readable log.
+"""
+
+from __future__ import annotations
+
+import json
+import os
+
+PROGRESS_FILE_ENV = "FM_PROGRESS_FILE"
+
+
+def report_progress(current: int, total: int, status: str) -> None:
+    path = os.environ.get(PROGRESS_FILE_ENV)
+    if not path:
+        return
+    record = json.dumps({"current": current, "total": total, "status": status})
+    try:
+        with open(path, "a", encoding="utf-8") as handle:
+            handle.write(record + "\n")
+    except OSError:
+        # Progress is best-effort; the build itself must not fail over it
+        pass
@@ -0,0 +1,306 @@
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+use tauri::{AppHandle, Emitter, State};
+use walkdir::WalkDir;
+use zip::ZipWriter;
+
+use crate::events::ProgressEvent;
+use crate::export::{add_file, add_text};
+use crate::path_guard;
+use crate::portable;
+use crate::process::ProcessState;
+use crate::settings;
+use crate::storage;
+
+const APP_DATA_MANIFEST_FILE: &str = "app_data_manifest.json";
+const APP_DATA_FORMAT_VERSION: u32 = 1;
+
+/// Files in the app data folder that describe this machine's running app
+/// rather than the user's data, so they're never exported
+const MACHINE_LOCAL_FILES: &[&str] = &["automation.json"];
+
+#[derive(Serialize, Deserialize)]
+struct AppDataManifest {
+    format_version: u32,
+    app_version: String,
+    created_at: u64,
+    include_backups: bool,
+    files: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct AppDataExportResult {
+    pub archive_path: String,
+    /// Settings, profiles, history and other data files
+    pub data_files: Vec<String>,
+    pub backup_file_count: usize,
+    pub total_size: u64,
+}
+
+#[derive(Serialize)]
+pub struct AppDataImportResult {
+    pub data_files: Vec<String>,
+    pub backup_file_count: usize,
+    pub created_at: u64,
+    /// Some data (locale, automation, game profiles) is only read on startup
+    pub restart_recommended: bool,
+}
+
+fn emit_progress(app_handle: &AppHandle, current: u32, total: u32, status: String) {
+    let _ = app_handle.emit(
+        "app_data_progress",
+        ProgressEvent {
+            current,
+            total,
+            status,
+            run_id: None,
+        },
+    );
+}
+
+/// Top-level JSON data files: settings, build and game profiles, history,
+/// recents and favorites, projects, installed skins, plugin settings
+fn data_files(data_dir: &Path) -> Vec<(PathBuf, String)> {
+    let mut files: Vec<(PathBuf, String)> = std::fs::read_dir(data_dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.is_file())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+                .filter_map(|path| {
+                    let name = path.file_name()?.to_string_lossy().to_string();
+                    (!MACHINE_LOCAL_FILES.contains(&name.as_str())).then_some((path, name))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort_by(|a, b| a.1.cmp(&b.1));
+    files
+}
+
+fn export_data(
+    app_handle: &AppHandle,
+    destination: &Path,
+    include_backups: bool,
+) -> Result<AppDataExportResult, String> {
+    let data_dir = portable::app_data_dir(app_handle)?;
+    let archive_path = if destination.is_dir() {
+        destination.join(format!("fm-skin-builder-data-{}.zip", storage::unix_now()))
+    } else {
+        destination.to_path_buf()
+    };
+
+    let mut entries: Vec<(PathBuf, String)> = data_files(&data_dir)
+        .into_iter()
+        .map(|(path, name)| (path, format!("data/{}", name)))
+        .collect();
+    let data_count = entries.len();
+
+    if include_backups {
+        let backups_dir = data_dir.join("backups");
+        entries.extend(
+            WalkDir::new(&backups_dir)
+                .into_iter()
+                .flatten()
+                .filter(|entry| entry.file_type().is_file())
+                .filter_map(|entry| {
+                    let relative = entry.path().strip_prefix(&backups_dir).ok()?;
+                    let archive_path =
+                        format!("backups/{}", relative.to_string_lossy().replace('\\', "/"));
+                    Some((entry.path().to_path_buf(), archive_path))
+                }),
+        );
+    }
+
+    if entries.is_empty() {
+        return Err("No app data to export yet".to_string());
+    }
+
+    let file = std::fs::File::create(&archive_path)
+        .map_err(|e| format!("Failed to create archive: {}", e))?;
+    let mut writer = ZipWriter::new(file);
+    let total = entries.len() as u32;
+    let mut total_size = 0;
+
+    for (index, (source, archive_entry)) in entries.iter().enumerate() {
+        emit_progress(
+            app_handle,
+            index as u32,
+            total,
+            format!("Exporting {}", archive_entry),
+        );
+        total_size += add_file(&mut writer, source, archive_entry)?.size;
+    }
+
+    let manifest = AppDataManifest {
+        format_version: APP_DATA_FORMAT_VERSION,
+        app_version: app_handle.package_info().version.to_string(),
+        created_at: storage::unix_now(),
+        include_backups,
+        files: entries.iter().map(|(_, name)| name.clone()).collect(),
+    };
+    let manifest_json = serde_json::to_string_pretty(&manifest)
+        .map_err(|e| format!("Failed to serialize app data manifest: {}", e))?;
+    add_text(&mut writer, APP_DATA_MANIFEST_FILE, &manifest_json)?;
+    writer
+        .finish()
+        .map_err(|e| format!("Failed to finalize archive: {}", e))?;
+
+    emit_progress(app_handle, total, total, "Export complete".to_string());
+
+    Ok(AppDataExportResult {
+        archive_path: archive_path.to_string_lossy().to_string(),
+        data_files: entries[..data_count]
+            .iter()
+            .map(|(_, name)| name.trim_start_matches("data/").to_string())
+            .collect(),
+        backup_file_count: entries.len() - data_count,
+        total_size,
+    })
+}
+
+/// Where an archive entry belongs in the app data folder, or None for entries
+/// outside `data/*.json` and `backups/`
+fn import_target(data_dir: &Path, relative: &Path) -> Option<PathBuf> {
+    let parts: Vec<&str> = relative
+        .components()
+        .map(|component| match component {
+            Component::Normal(part) => part.to_str(),
+            _ => None,
+        })
+        .collect::<Option<_>>()?;
+
+    match parts.as_slice() {
+        ["data", name] if name.ends_with(".json") && !MACHINE_LOCAL_FILES.contains(name) => {
+            Some(data_dir.join(name))
+        }
+        ["backups", rest @ ..] if !rest.is_empty() => Some(
+            rest.iter()
+                .fold(data_dir.join("backups"), |path, part| path.join(part)),
+        ),
+        _ => None,
+    }
+}
+
+fn import_data(app_handle: &AppHandle, archive_path: &Path) -> Result<AppDataImportResult, String> {
+    let file =
+        std::fs::File::open(archive_path).map_err(|e| format!("Failed to open archive: {}", e))?;
+    let mut archive =
+        zip::ZipArchive::new(file).map_err(|e| format!("Failed to read archive: {}", e))?;
+
+    let manifest: AppDataManifest = {
+        let entry = archive.by_name(APP_DATA_MANIFEST_FILE).map_err(|_| {
+            "Not an FM Skin Builder data export: app_data_manifest.json is missing".to_string()
+        })?;
+        serde_json::from_reader(entry)
+            .map_err(|e| format!("Invalid {}: {}", APP_DATA_MANIFEST_FILE, e))?
+    };
+    if manifest.format_version > APP_DATA_FORMAT_VERSION {
+        return Err(format!(
+            "This export was made by a newer version of FM Skin Builder ({}); update the app to import it",
+            manifest.app_version
+        ));
+    }
+
+    let data_dir = portable::app_data_dir(app_handle)?;
+    std::fs::create_dir_all(&data_dir)
+        .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+
+    let total = archive.len() as u32;
+    let mut data_files = Vec::new();
+    let mut backup_file_count = 0;
+
+    for index in 0..archive.len() {
+        let mut entry = archive
+            .by_index(index)
+            .map_err(|e| format!("Failed to read archive entry: {}", e))?;
+        if entry.is_dir() {
+            continue;
+        }
+        // enclosed_name() returns None for absolute paths and ".." components (zip-slip)
+        let Some(target) = entry
+            .enclosed_name()
+            .and_then(|relative| import_target(&data_dir, &relative))
+        else {
+            if entry.name() != APP_DATA_MANIFEST_FILE {
+                println!(
+                    "[WARNING] Skipping unexpected archive entry: {}",
+                    entry.name()
+                );
+            }
+            continue;
+        };
+
+        emit_progress(
+            app_handle,
+            index as u32,
+            total,
+            format!("Importing {}", entry.name()),
+        );
+
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create directory: {}", e))?;
+        }
+        let mut out_file = std::fs::File::create(&target)
+            .map_err(|e| format!("Failed to write {:?}: {}", target, e))?;
+        std::io::copy(&mut entry, &mut out_file)
+            .and_then(|_| out_file.flush())
+            .map_err(|e| format!("Failed to write {:?}: {}", target, e))?;
+
+        if target.parent() == Some(data_dir.as_path()) {
+            data_files.push(entry.name().trim_start_matches("data/").to_string());
+        } else {
+            backup_file_count += 1;
+        }
+    }
+
+    if let Err(e) = settings::reload_settings(app_handle) {
+        println!("[WARNING] {}", e);
+    }
+    emit_progress(app_handle, total, total, "Import complete".to_string());
+
+    Ok(AppDataImportResult {
+        data_files,
+        backup_file_count,
+        created_at: manifest.created_at,
+        restart_recommended: true,
+    })
+}
+
+/// Package settings, profiles, build history, favorites and optionally the
+/// bundle backups into one zip for moving to another machine
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub async fn export_app_data(
+    app_handle: AppHandle,
+    path: String,
+    include_backups: Option<bool>,
+) -> Result<AppDataExportResult, String> {
+    let destination = path_guard::clean_path(&path, "Destination")?;
+    tokio::task::spawn_blocking(move || {
+        export_data(&app_handle, &destination, include_backups.unwrap_or(false))
+    })
+    .await
+    .map_err(|e| format!("Export task failed: {}", e))?
+}
+
+/// Restore an archive made by `export_app_data`, replacing the matching files
+#[tauri::command]
+#[tracing::instrument(skip(app_handle, state), err)]
+pub async fn import_app_data(
+    app_handle: AppHandle,
+    state: State<'_, ProcessState>,
+    path: String,
+) -> Result<AppDataImportResult, String> {
+    if state.is_busy().await {
+        return Err("Cannot import app data while a build is running".to_string());
+    }
+
+    let archive_path = path_guard::existing_file(&path, "Archive")?;
+    tokio::task::spawn_blocking(move || import_data(&app_handle, &archive_path))
+        .await
+        .map_err(|e| format!("Import task failed: {}", e))?
+}
@@ -0,0 +1,173 @@
+use serde::Serialize;
+use std::process::Command;
+use std::sync::Mutex;
+use tauri::{Emitter, Manager, Theme, Window};
+
+/// System colour scheme and accent colour as a `#rrggbb` string
+#[derive(Serialize, Clone, PartialEq)]
+pub struct Appearance {
+    pub theme: String, // "dark", "light"
+    pub accent_color: Option<String>,
+}
+
+/// Last appearance sent to the UI, so focus changes only emit real changes
+#[derive(Default)]
+pub struct AppearanceState {
+    last: Mutex<Option<Appearance>>,
+}
+
+fn theme_name(theme: &Theme) -> String {
+    match theme {
+        Theme::Dark => "dark",
+        _ => "light",
+    }
+    .to_string()
+}
+
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let mut command = Command::new(program);
+    command.args(args);
+
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let output = command.output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// The DWM accent colour, stored as a 0xAABBGGRR DWORD
+fn windows_accent_color() -> Option<String> {
+    let output = command_output(
+        "reg",
+        &[
+            "query",
+            r"HKCU\Software\Microsoft\Windows\DWM",
+            "/v",
+            "AccentColor",
+        ],
+    )?;
+    let value = output
+        .split_whitespace()
+        .find_map(|part| part.strip_prefix("0x"))
+        .and_then(|hex| u32::from_str_radix(hex, 16).ok())?;
+    Some(format!(
+        "#{:02x}{:02x}{:02x}",
+        value & 0xff,
+        (value >> 8) & 0xff,
+        (value >> 16) & 0xff
+    ))
+}
+
+/// AppleAccentColor is an index into the system palette; unset means blue
+fn macos_accent_color() -> Option<String> {
+    let index = command_output("defaults", &["read", "-g", "AppleAccentColor"])
+        .and_then(|value| value.parse::<i32>().ok())
+        .unwrap_or(4);
+    let color = match index {
+        -1 => "#8c8c8c",
+        0 => "#ff5257",
+        1 => "#f7821b",
+        2 => "#ffc600",
+        3 => "#62ba46",
+        5 => "#a550a7",
+        6 => "#f74f9e",
+        _ => "#007aff",
+    };
+    Some(color.to_string())
+}
+
+/// GNOME 47+ names its accent colours; other desktops have no accent setting
+fn linux_accent_color() -> Option<String> {
+    let output = command_output(
+        "gsettings",
+        &["get", "org.gnome.desktop.interface", "accent-color"],
+    )?;
+    let color = match output.trim_matches('\'') {
+        "blue" => "#3584e4",
+        "teal" => "#2190a4",
+        "green" => "#3a944a",
+        "yellow" => "#c88800",
+        "orange" => "#ed5b00",
+        "red" => "#e62d42",
+        "pink" => "#d56199",
+        "purple" => "#9141ac",
+        "slate" => "#6f8396",
+        _ => return None,
+    };
+    Some(color.to_string())
+}
+
+fn accent_color() -> Option<String> {
+    if cfg!(windows) {
+        windows_accent_color()
+    } else if cfg!(target_os = "macos") {
+        macos_accent_color()
+    } else {
+        linux_accent_color()
+    }
+}
+
+fn read_appearance(theme: &Theme) -> Appearance {
+    Appearance {
+        theme: theme_name(theme),
+        accent_color: accent_color(),
+    }
+}
+
+/// Re-read the appearance and emit `appearance_changed` if it differs from
+/// what the UI last saw. Reading the accent colour shells out, so it runs
+/// off the event loop.
+fn refresh(window: &Window, theme: Theme) {
+    let window = window.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let appearance = read_appearance(&theme);
+        let state = window.app_handle().state::<AppearanceState>();
+        let Ok(mut last) = state.last.lock() else {
+            return;
+        };
+        if last.as_ref() == Some(&appearance) {
+            return;
+        }
+        *last = Some(appearance.clone());
+        drop(last);
+        let _ = window.emit("appearance_changed", appearance);
+    });
+}
+
+/// The OS switched between dark and light mode
+pub fn handle_theme_changed(window: &Window, theme: &Theme) {
+    refresh(window, *theme);
+}
+
+/// Accent colour changes raise no window event, so check when the window
+/// regains focus (the user is usually coming back from system settings)
+pub fn handle_focus(window: &Window) {
+    if let Ok(theme) = window.theme() {
+        refresh(window, theme);
+    }
+}
+
+/// Current system colour scheme and accent colour. Live changes arrive as
+/// `appearance_changed` events.
+#[tauri::command]
+#[tracing::instrument(skip(window), err)]
+pub async fn get_appearance(window: Window) -> Result<Appearance, String> {
+    let theme = window
+        .theme()
+        .map_err(|e| format!("Failed to read system theme: {}", e))?;
+    let appearance = tokio::task::spawn_blocking(move || read_appearance(&theme))
+        .await
+        .map_err(|e| format!("Appearance task failed: {}", e))?;
+
+    if let Ok(mut last) = window.app_handle().state::<AppearanceState>().last.lock() {
+        *last = Some(appearance.clone());
+    }
+    Ok(appearance)
+}
@@ -0,0 +1,348 @@
+use futures_util::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, EventId, Listener, Manager, State};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{broadcast, mpsc, watch};
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::process::{run_python_task, stop_python_task, ProcessState, TaskConfig};
+use crate::settings;
+use crate::skins::list_skins;
+use crate::storage;
+
+/// Connection details for external tools, written next to the other app data
+const AUTOMATION_INFO_FILE: &str = "automation.json";
+
+/// Build events forwarded to subscribed clients
+const FORWARDED_EVENTS: &[&str] = &[
+    "task_started",
+    "build_log",
+    "build_progress",
+    "build_complete",
+];
+
+#[derive(Serialize, Clone)]
+pub struct AutomationInfo {
+    pub running: bool,
+    pub url: Option<String>,
+    pub port: Option<u16>,
+    pub token: Option<String>,
+    pub info_path: Option<String>,
+}
+
+struct RunningServer {
+    info: AutomationInfo,
+    shutdown: watch::Sender<bool>,
+    listeners: Vec<EventId>,
+}
+
+#[derive(Default)]
+pub struct AutomationState {
+    server: Mutex<Option<RunningServer>>,
+}
+
+#[derive(Deserialize)]
+struct AutomationRequest {
+    #[serde(default)]
+    id: Value,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+/// 244 random bits from the OS generator; clients must present it before anything else
+fn generate_token() -> String {
+    format!(
+        "{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    )
+}
+
+fn info_path(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    storage::data_file(app_handle, AUTOMATION_INFO_FILE)
+}
+
+fn write_info_file(path: &Path, info: &AutomationInfo) -> Result<(), String> {
+    // Anyone who can read the token can drive builds
+    storage::save_private_json(path, info)
+}
+
+async fn dispatch(app_handle: &AppHandle, method: &str, params: Value) -> Result<Value, String> {
+    let state = app_handle.state::<ProcessState>();
+    match method {
+        "run_task" => {
+            if state.is_busy().await {
+                return Err("A build is already running".to_string());
+            }
+            let config: TaskConfig = serde_json::from_value(params)
+                .map_err(|e| format!("Invalid task config: {}", e))?;
+            let result = run_python_task(app_handle.clone(), config, state, None).await?;
+            serde_json::to_value(result).map_err(|e| e.to_string())
+        }
+        "stop_task" => stop_python_task(app_handle.clone(), state)
+            .await
+            .map(Value::String)
+            .map_err(String::from),
+        "status" => {
+            let running = state.is_busy().await;
+            let active_skin = state
+                .active_skin
+                .lock()
+                .await
+                .as_ref()
+                .filter(|_| running)
+                .map(|path| path.to_string_lossy().to_string());
+            Ok(json!({ "running": running, "active_skin": active_skin }))
+        }
+        "list_skins" => {
+            let skins = list_skins(app_handle.clone(), None)?;
+            serde_json::to_value(skins).map_err(|e| e.to_string())
+        }
+        _ => Err(format!("Unknown method: {}", method)),
+    }
+}
+
+async fn handle_connection(
+    app_handle: AppHandle,
+    stream: TcpStream,
+    token: String,
+    events: broadcast::Sender<String>,
+    mut shutdown: watch::Receiver<bool>,
+) -> Result<(), String> {
+    let socket = tokio_tungstenite::accept_async(stream)
+        .await
+        .map_err(|e| format!("WebSocket handshake failed: {}", e))?;
+    let (mut sink, mut source) = socket.split();
+
+    // The first message must be {"token": "..."}
+    let authenticated = match source.next().await {
+        Some(Ok(Message::Text(text))) => serde_json::from_str::<Value>(&text)
+            .ok()
+            .and_then(|value| value.get("token")?.as_str().map(str::to_string))
+            .is_some_and(|presented| presented == token),
+        _ => false,
+    };
+    if !authenticated {
+        let _ = sink
+            .send(Message::Text(
+                json!({ "error": "Invalid token" }).to_string(),
+            ))
+            .await;
+        return Err("Automation client failed to authenticate".to_string());
+    }
+    let _ = sink
+        .send(Message::Text(json!({ "authenticated": true }).to_string()))
+        .await;
+
+    // Responses come back from per-request tasks so long builds don't block the socket
+    let (outgoing, mut outgoing_rx) = mpsc::unbounded_channel::<String>();
+    let mut event_rx: Option<broadcast::Receiver<String>> = None;
+
+    loop {
+        tokio::select! {
+            _ = shutdown.changed() => break,
+            Some(text) = outgoing_rx.recv() => {
+                if sink.send(Message::Text(text)).await.is_err() {
+                    break;
+                }
+            }
+            Some(event) = async {
+                match event_rx.as_mut() {
+                    Some(rx) => rx.recv().await.ok(),
+                    None => std::future::pending().await,
+                }
+            } => {
+                if sink.send(Message::Text(event)).await.is_err() {
+                    break;
+                }
+            }
+            message = source.next() => {
+                let text = match message {
+                    Some(Ok(Message::Text(text))) => text,
+                    Some(Ok(Message::Close(_))) | None | Some(Err(_)) => break,
+                    Some(Ok(_)) => continue,
+                };
+
+                let request: AutomationRequest = match serde_json::from_str(&text) {
+                    Ok(request) => request,
+                    Err(e) => {
+                        let _ = outgoing.send(json!({ "error": format!("Invalid request: {}", e) }).to_string());
+                        continue;
+                    }
+                };
+
+                if request.method == "subscribe" {
+                    event_rx = Some(events.subscribe());
+                    let _ = outgoing.send(json!({ "id": request.id, "result": true }).to_string());
+                    continue;
+                }
+
+                let app_handle = app_handle.clone();
+                let outgoing = outgoing.clone();
+                tauri::async_runtime::spawn(async move {
+                    let response = match dispatch(&app_handle, &request.method, request.params).await {
+                        Ok(result) => json!({ "id": request.id, "result": result }),
+                        Err(error) => json!({ "id": request.id, "error": error }),
+                    };
+                    let _ = outgoing.send(response.to_string());
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Start the loopback WebSocket server on the configured port (`automationPort`, 0 for any)
+pub async fn start_server(
+    app_handle: &AppHandle,
+    state: &AutomationState,
+) -> Result<AutomationInfo, String> {
+    let existing = state
+        .server
+        .lock()
+        .map_err(|e| e.to_string())?
+        .as_ref()
+        .map(|server| server.info.clone());
+    if let Some(info) = existing {
+        return Ok(info);
+    }
+
+    let port: u16 = settings::get_setting(app_handle, "automationPort").unwrap_or(0);
+    let listener = TcpListener::bind(("127.0.0.1", port))
+        .await
+        .map_err(|e| format!("Failed to start automation server: {}", e))?;
+    let port = listener
+        .local_addr()
+        .map_err(|e| format!("Failed to read automation server address: {}", e))?
+        .port();
+
+    let token = generate_token();
+    let path = info_path(app_handle)?;
+    let info = AutomationInfo {
+        running: true,
+        url: Some(format!("ws://127.0.0.1:{}", port)),
+        port: Some(port),
+        token: Some(token.clone()),
+        info_path: Some(path.to_string_lossy().to_string()),
+    };
+    write_info_file(&path, &info)?;
+
+    let (events, _) = broadcast::channel::<String>(256);
+    let mut listeners = Vec::new();
+    for name in FORWARDED_EVENTS {
+        let events = events.clone();
+        let name = name.to_string();
+        listeners.push(app_handle.listen_any(name.clone(), move |event| {
+            let payload: Value = serde_json::from_str(event.payload()).unwrap_or(Value::Null);
+            let _ = events.send(json!({ "event": name, "payload": payload }).to_string());
+        }));
+    }
+
+    let (shutdown, shutdown_rx) = watch::channel(false);
+    let server_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut server_shutdown = shutdown_rx.clone();
+        loop {
+            let stream = tokio::select! {
+                _ = server_shutdown.changed() => break,
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, _)) => stream,
+                    Err(e) => {
+                        println!("[WARNING] Automation server accept failed: {}", e);
+                        continue;
+                    }
+                },
+            };
+
+            let connection = handle_connection(
+                server_handle.clone(),
+                stream,
+                token.clone(),
+                events.clone(),
+                shutdown_rx.clone(),
+            );
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = connection.await {
+                    println!("[WARNING] {}", e);
+                }
+            });
+        }
+        println!("[DEBUG] Automation server stopped");
+    });
+
+    println!("[DEBUG] Automation server listening on 127.0.0.1:{}", port);
+    *state.server.lock().map_err(|e| e.to_string())? = Some(RunningServer {
+        info: info.clone(),
+        shutdown,
+        listeners,
+    });
+    Ok(info)
+}
+
+/// Start the server at launch when the user has opted in
+pub fn start_if_enabled(app_handle: &AppHandle) {
+    if settings::get_setting::<bool>(app_handle, "automationApiEnabled") != Some(true) {
+        return;
+    }
+
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        let state = app_handle.state::<AutomationState>();
+        if let Err(e) = start_server(&app_handle, &state).await {
+            println!("[WARNING] {}", e);
+        }
+    });
+}
+
+/// Start the local automation API and return its address and token
+#[tauri::command]
+#[tracing::instrument(skip(app_handle, state), err)]
+pub async fn start_automation_server(
+    app_handle: AppHandle,
+    state: State<'_, AutomationState>,
+) -> Result<AutomationInfo, String> {
+    start_server(&app_handle, &state).await
+}
+
+/// Stop the automation API, disconnect clients and invalidate the token
+#[tauri::command]
+#[tracing::instrument(skip(app_handle, state), err)]
+pub fn stop_automation_server(
+    app_handle: AppHandle,
+    state: State<'_, AutomationState>,
+) -> Result<AutomationInfo, String> {
+    if let Some(server) = state.server.lock().map_err(|e| e.to_string())?.take() {
+        let _ = server.shutdown.send(true);
+        for id in server.listeners {
+            app_handle.unlisten(id);
+        }
+    }
+
+    let path = info_path(&app_handle)?;
+    if path.exists() {
+        std::fs::remove_file(&path)
+            .map_err(|e| format!("Failed to remove automation info file: {}", e))?;
+    }
+    get_automation_status(state)
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(state), err)]
+pub fn get_automation_status(state: State<'_, AutomationState>) -> Result<AutomationInfo, String> {
+    let server = state.server.lock().map_err(|e| e.to_string())?;
+    Ok(match server.as_ref() {
+        Some(server) => server.info.clone(),
+        None => AutomationInfo {
+            running: false,
+            url: None,
+            port: None,
+            token: None,
+            info_path: None,
+        },
+    })
+}
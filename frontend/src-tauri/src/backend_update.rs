@@ -0,0 +1,278 @@
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, State};
+use walkdir::WalkDir;
+
+use crate::commands::current_platform;
+use crate::extract::{self, ArchiveKind};
+use crate::file_copy;
+use crate::portable;
+use crate::process::ProcessState;
+use crate::release_notes::compare_versions;
+use crate::storage;
+
+const BACKEND_FEED_URL: &str = "https://release.fmskinbuilder.com/backend/latest.json";
+
+/// Same minisign key the app updater uses (see tauri.conf.json)
+const BACKEND_PUBKEY: &str = "dW50cnVzdGVkIGNvbW1lbnQ6IG1pbmlzaWduIHB1YmxpYyBrZXk6IDRGNDVERkY5MzMwNzFERjYKUldUMkhRY3orZDlGVDUyNTVxMGJySzd4Z1JPMndIKytJVlFkMENwK2hiOGYvWmg0WFFDWnJWOFMK";
+
+#[derive(Serialize, Deserialize)]
+pub struct BackendRelease {
+    pub version: String,
+    #[serde(default)]
+    pub notes: Option<String>,
+    pub platforms: HashMap<String, BackendPlatformInfo>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct BackendPlatformInfo {
+    pub url: String,
+    pub signature: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct InstalledBackend {
+    version: String,
+}
+
+#[derive(Serialize)]
+pub struct BackendUpdateStatus {
+    pub current_version: String,
+    pub latest_version: String,
+    pub update_available: bool,
+    pub using_updated_backend: bool,
+    pub notes: Option<String>,
+}
+
+fn backend_binary_name() -> &'static str {
+    if cfg!(windows) {
+        "fm_skin_builder.exe"
+    } else {
+        "fm_skin_builder"
+    }
+}
+
+/// Directory holding independently updated backend binaries
+fn backend_dir(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    Ok(portable::app_data_dir(app_handle)?.join("backend"))
+}
+
+fn read_installed_backend(app_handle: &AppHandle) -> Option<InstalledBackend> {
+    let dir = backend_dir(app_handle).ok()?;
+    let content = std::fs::read_to_string(dir.join("version.json")).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Path to an updated backend binary in app data, if one exists and is
+/// newer than the backend bundled with this app version
+pub fn updated_backend_binary(app_handle: &AppHandle) -> Option<PathBuf> {
+    let installed = read_installed_backend(app_handle)?;
+    if compare_versions(&installed.version, env!("CARGO_PKG_VERSION")) == Ordering::Less {
+        return None;
+    }
+
+    let binary = backend_dir(app_handle).ok()?.join(backend_binary_name());
+    if binary.exists() {
+        Some(binary)
+    } else {
+        None
+    }
+}
+
+/// Version of the backend that `run_python_task` will launch
+pub fn current_backend_version(app_handle: &AppHandle) -> (String, bool) {
+    match (
+        updated_backend_binary(app_handle),
+        read_installed_backend(app_handle),
+    ) {
+        (Some(_), Some(installed)) => (installed.version, true),
+        _ => (env!("CARGO_PKG_VERSION").to_string(), false),
+    }
+}
+
+fn decode_base64_text(value: &str) -> Result<String, String> {
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(value.trim())
+        .map_err(|e| format!("Failed to decode base64: {}", e))?;
+    String::from_utf8(bytes).map_err(|e| format!("Invalid UTF-8 in decoded value: {}", e))
+}
+
+/// Verify a minisign signature (base64-encoded, as produced by the Tauri signer)
+fn verify_signature(data: &[u8], signature: &str) -> Result<(), String> {
+    let public_key = minisign_verify::PublicKey::decode(&decode_base64_text(BACKEND_PUBKEY)?)
+        .map_err(|e| format!("Invalid backend public key: {}", e))?;
+    let signature = minisign_verify::Signature::decode(&decode_base64_text(signature)?)
+        .map_err(|e| format!("Invalid backend signature: {}", e))?;
+
+    public_key
+        .verify(data, &signature, true)
+        .map_err(|e| format!("Backend signature verification failed: {}", e))
+}
+
+/// Extract a downloaded backend archive and stage its binary at `download_path`
+fn unpack_backend(
+    app_handle: &AppHandle,
+    bytes: &[u8],
+    kind: ArchiveKind,
+    dir: &Path,
+    download_path: &Path,
+) -> Result<(), String> {
+    let archive_path = dir.join("backend.archive");
+    let staging_dir = dir.join("staging");
+    let _ = std::fs::remove_dir_all(&staging_dir);
+    std::fs::write(&archive_path, bytes)
+        .map_err(|e| format!("Failed to save backend download: {}", e))?;
+
+    let cancel = file_copy::begin_transfer(app_handle);
+    let result = extract::extract_archive(
+        &archive_path,
+        kind,
+        &staging_dir,
+        &cancel,
+        &mut |_, _, name| println!("[DEBUG] Unpacking {}", name),
+    )
+    .and_then(|_| {
+        let binary = WalkDir::new(&staging_dir)
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .find(|entry| entry.file_type().is_file() && entry.file_name() == backend_binary_name())
+            .ok_or_else(|| format!("Backend archive does not contain {}", backend_binary_name()))?;
+        std::fs::rename(binary.path(), download_path)
+            .map_err(|e| format!("Failed to stage backend binary: {}", e))
+    });
+
+    let _ = std::fs::remove_file(&archive_path);
+    let _ = std::fs::remove_dir_all(&staging_dir);
+    result
+}
+
+async fn fetch_backend_release() -> Result<BackendRelease, String> {
+    let response = reqwest::get(BACKEND_FEED_URL)
+        .await
+        .map_err(|e| format!("Failed to check for backend update: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!(
+            "Backend update check failed with status: {}",
+            response.status()
+        ));
+    }
+
+    response
+        .json::<BackendRelease>()
+        .await
+        .map_err(|e| format!("Failed to parse backend update metadata: {}", e))
+}
+
+/// Check whether a newer backend than the one currently in use is published
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub async fn check_backend_update(app_handle: AppHandle) -> Result<BackendUpdateStatus, String> {
+    let release = fetch_backend_release().await?;
+    let (current_version, using_updated_backend) = current_backend_version(&app_handle);
+    let platform = current_platform()?;
+
+    let update_available = release.platforms.contains_key(platform)
+        && compare_versions(&release.version, &current_version) == Ordering::Greater;
+
+    Ok(BackendUpdateStatus {
+        current_version,
+        latest_version: release.version,
+        update_available,
+        using_updated_backend,
+        notes: release.notes,
+    })
+}
+
+/// Download, verify, and install the latest backend binary into app data
+#[tauri::command]
+#[tracing::instrument(skip(app_handle, state), err)]
+pub async fn install_backend_update(
+    app_handle: AppHandle,
+    state: State<'_, ProcessState>,
+) -> Result<String, String> {
+    if state.is_busy().await {
+        return Err("Cannot update the backend while a build is running".to_string());
+    }
+
+    let release = fetch_backend_release().await?;
+    let platform = current_platform()?;
+    let platform_info = release
+        .platforms
+        .get(platform)
+        .ok_or_else(|| format!("No backend update available for platform: {}", platform))?;
+
+    println!("Downloading backend update from: {}", platform_info.url);
+
+    let response = reqwest::get(&platform_info.url)
+        .await
+        .map_err(|e| format!("Failed to download backend update: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!(
+            "Backend download failed with status: {}",
+            response.status()
+        ));
+    }
+
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|e| format!("Failed to read backend download: {}", e))?;
+
+    // Never write an unverified binary where process.rs could pick it up
+    verify_signature(&bytes, &platform_info.signature)?;
+
+    let dir = backend_dir(&app_handle)?;
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create backend directory: {}", e))?;
+
+    let binary_path = dir.join(backend_binary_name());
+    let download_path = dir.join(format!("{}.download", backend_binary_name()));
+
+    // Releases may ship the binary alone or packed with its support files
+    let asset_name = platform_info
+        .url
+        .split(['?', '#'])
+        .next()
+        .unwrap_or_default();
+    match ArchiveKind::from_name(asset_name) {
+        Some(kind) => {
+            let app_handle = app_handle.clone();
+            let (dir, download_path) = (dir.clone(), download_path.clone());
+            tokio::task::spawn_blocking(move || {
+                unpack_backend(&app_handle, &bytes, kind, &dir, &download_path)
+            })
+            .await
+            .map_err(|e| format!("Backend unpack task failed: {}", e))??;
+        }
+        None => std::fs::write(&download_path, &bytes)
+            .map_err(|e| format!("Failed to save backend binary: {}", e))?,
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = std::fs::metadata(&download_path)
+            .map_err(|e| format!("Failed to get file permissions: {}", e))?
+            .permissions();
+        perms.set_mode(0o755);
+        std::fs::set_permissions(&download_path, perms)
+            .map_err(|e| format!("Failed to set executable permissions: {}", e))?;
+    }
+
+    std::fs::rename(&download_path, &binary_path)
+        .map_err(|e| format!("Failed to install backend binary: {}", e))?;
+
+    let installed = InstalledBackend {
+        version: release.version.clone(),
+    };
+    storage::save_json(&dir.join("version.json"), &installed)?;
+
+    println!("Backend updated to version {}", release.version);
+
+    Ok(format!("Backend updated to version {}", release.version))
+}
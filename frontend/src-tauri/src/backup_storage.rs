@@ -0,0 +1,235 @@
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, State};
+
+use crate::backups;
+use crate::cache::calculate_dir_size;
+use crate::diagnostics::MIN_FREE_SPACE;
+use crate::disk_space;
+use crate::game_profiles;
+use crate::import::move_dir;
+use crate::path_guard;
+use crate::portable;
+use crate::process::ProcessState;
+use crate::restore_points;
+use crate::settings;
+
+#[derive(Serialize)]
+pub struct InstallBackupStats {
+    pub bundles_path: String,
+    pub backup_path: String,
+    pub original_size: u64,
+    pub restore_points: usize,
+    pub restore_points_size: u64,
+    pub free_bytes: Option<u64>,
+    /// The backup drive has no room for another full set of original bundles
+    pub low_space: bool,
+}
+
+#[derive(Serialize)]
+pub struct BackupStats {
+    pub backup_root: String,
+    pub installs: Vec<InstallBackupStats>,
+    pub total_size: u64,
+    /// Restore points kept per install; `None` keeps every point
+    pub retention: Option<usize>,
+    pub compression: bool,
+}
+
+#[derive(Serialize)]
+pub struct PruneResult {
+    pub removed: Vec<String>,
+    pub freed_bytes: u64,
+}
+
+/// Backup areas of every install, in the default root and the profile backup folders
+fn install_backup_dirs(app_handle: &AppHandle) -> Result<Vec<PathBuf>, String> {
+    let mut roots = vec![backups::default_backup_root(app_handle)?];
+    for root in game_profiles::backup_roots(app_handle) {
+        if !roots.contains(&root) {
+            roots.push(root);
+        }
+    }
+
+    Ok(roots
+        .iter()
+        .filter_map(|root| std::fs::read_dir(root).ok())
+        .flat_map(|entries| entries.flatten().map(|entry| entry.path()))
+        .filter(|dir| backups::load_manifest(dir).is_some())
+        .collect())
+}
+
+fn install_stats(backup_dir: &Path) -> Option<InstallBackupStats> {
+    let manifest = backups::load_manifest(backup_dir)?;
+    let points_size = calculate_dir_size(&backup_dir.join("points")).unwrap_or(0);
+    let original_size = calculate_dir_size(backup_dir)
+        .unwrap_or(0)
+        .saturating_sub(points_size);
+    let free_bytes = disk_space::free_space(backup_dir).ok();
+
+    Some(InstallBackupStats {
+        bundles_path: manifest.bundles_path,
+        backup_path: backup_dir.to_string_lossy().to_string(),
+        original_size,
+        restore_points: restore_points::read_points(backup_dir).len(),
+        restore_points_size: points_size,
+        low_space: free_bytes.is_some_and(|free| free < original_size.max(MIN_FREE_SPACE)),
+        free_bytes,
+    })
+}
+
+/// Disk usage of the original bundle backups and restore points of every install
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub async fn get_backup_stats(app_handle: AppHandle) -> Result<BackupStats, String> {
+    tokio::task::spawn_blocking(move || {
+        let installs: Vec<InstallBackupStats> = install_backup_dirs(&app_handle)?
+            .iter()
+            .filter_map(|dir| install_stats(dir))
+            .collect();
+
+        Ok(BackupStats {
+            backup_root: backups::default_backup_root(&app_handle)?
+                .to_string_lossy()
+                .to_string(),
+            total_size: installs
+                .iter()
+                .map(|install| install.original_size + install.restore_points_size)
+                .sum(),
+            installs,
+            retention: settings::get_setting::<usize>(&app_handle, "backupRetention")
+                .filter(|keep| *keep > 0),
+            compression: settings::get_setting(&app_handle, "backupCompression").unwrap_or(false),
+        })
+    })
+    .await
+    .map_err(|e| format!("Backup stats task failed: {}", e))?
+}
+
+/// Delete the oldest restore points beyond `keep` (the `backupRetention`
+/// setting by default), for one install or all of them. Original backups and
+/// points installed skins rely on are never deleted.
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub async fn prune_backups(
+    app_handle: AppHandle,
+    bundles_path: Option<String>,
+    keep: Option<usize>,
+) -> Result<PruneResult, String> {
+    let keep = keep
+        .or_else(|| settings::get_setting(&app_handle, "backupRetention"))
+        .ok_or("No retention count given and none set in settings")?;
+
+    tokio::task::spawn_blocking(move || {
+        let dirs = match bundles_path {
+            Some(path) => vec![backups::install_backup_dir(
+                &app_handle,
+                &path_guard::clean_path(&path, "Bundles folder")?,
+            )?],
+            None => install_backup_dirs(&app_handle)?,
+        };
+        let in_use = restore_points::points_in_use(&app_handle);
+
+        let mut result = PruneResult {
+            removed: Vec::new(),
+            freed_bytes: 0,
+        };
+        for dir in dirs {
+            let (removed, freed) = restore_points::prune_points(&dir, keep, &in_use)?;
+            result.removed.extend(removed);
+            result.freed_bytes += freed;
+        }
+        Ok(result)
+    })
+    .await
+    .map_err(|e| format!("Prune task failed: {}", e))?
+}
+
+/// Move the backups in the default backup root to `destination` (e.g. another
+/// drive) and keep them there from now on; `None` moves them back into app
+/// data. Backups under a game profile's own backup folder stay where they are.
+#[tauri::command]
+#[tracing::instrument(skip(app_handle, state), err)]
+pub async fn set_backup_root(
+    app_handle: AppHandle,
+    state: State<'_, ProcessState>,
+    destination: Option<String>,
+) -> Result<String, String> {
+    if state.is_busy().await {
+        return Err("Cannot move backups while a build is running".to_string());
+    }
+
+    let previous = backups::default_backup_root(&app_handle)?;
+    let destination = match destination.as_deref().map(str::trim) {
+        Some(path) if !path.is_empty() => path_guard::clean_path(path, "Backup folder")?,
+        _ => portable::app_data_dir(&app_handle)?.join("backups"),
+    };
+    if destination == previous {
+        return Ok(destination.to_string_lossy().to_string());
+    }
+    if destination.starts_with(&previous) {
+        return Err("The new backup folder can't be inside the current one".to_string());
+    }
+
+    let (source, target) = (previous.clone(), destination.clone());
+    tokio::task::spawn_blocking(move || {
+        std::fs::create_dir_all(&target)
+            .map_err(|e| format!("Failed to create backup folder: {}", e))?;
+        let installs: Vec<PathBuf> = std::fs::read_dir(&source)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|entry| entry.path())
+                    .filter(|dir| backups::load_manifest(dir).is_some())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let needed: u64 = installs
+            .iter()
+            .map(|dir| calculate_dir_size(dir).unwrap_or(0))
+            .sum();
+        backups::check_backup_space(&target, needed)?;
+
+        // Originals can't be taken again from patched bundles, so a failed
+        // move puts back what was already moved
+        let mut moved = Vec::new();
+        for dir in installs {
+            let Some(name) = dir.file_name() else {
+                continue;
+            };
+            let destination = target.join(name);
+            let result = if destination.exists() {
+                Err(format!(
+                    "The new backup folder already has a backup for this install: {}",
+                    destination.display()
+                ))
+            } else {
+                move_dir(&dir, &destination)
+            };
+            if let Err(e) = result {
+                for (from, to) in moved.iter().rev() {
+                    if let Err(e) = move_dir(to, from) {
+                        println!("[WARNING] Failed to move backup {:?} back: {}", to, e);
+                    }
+                }
+                return Err(e);
+            }
+            moved.push((dir, destination));
+        }
+        Ok::<_, String>(())
+    })
+    .await
+    .map_err(|e| format!("Backup move task failed: {}", e))??;
+
+    settings::set_setting(
+        &app_handle,
+        "backupRoot",
+        destination.to_string_lossy().to_string(),
+    )?;
+    println!(
+        "[DEBUG] Backups moved from {:?} to {:?}",
+        previous, destination
+    );
+    Ok(destination.to_string_lossy().to_string())
+}
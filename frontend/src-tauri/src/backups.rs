@@ -0,0 +1,326 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter};
+
+use crate::diagnostics::MIN_FREE_SPACE;
+use crate::disk_space;
+use crate::events::ProgressEvent;
+use crate::file_copy;
+use crate::game_profiles;
+use crate::hashing::{self, HashAlgorithm};
+use crate::path_guard;
+use crate::portable;
+use crate::settings;
+use crate::skins::modified_secs;
+use crate::storage;
+
+const BACKUP_MANIFEST_FILE: &str = "manifest.json";
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct BackedUpBundle {
+    pub size: u64,
+    pub sha256: String,
+    pub backed_up_at: u64,
+    #[serde(default)]
+    pub modified: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct BackupManifest {
+    pub bundles_path: String,
+    pub created_at: u64,
+    pub files: BTreeMap<String, BackedUpBundle>,
+}
+
+#[derive(Serialize)]
+pub struct BackupStatus {
+    pub bundles_path: String,
+    pub backup_path: String,
+    pub has_backup: bool,
+    pub created_at: Option<u64>,
+    pub file_count: usize,
+    pub total_size: u64,
+    /// Bundles in the game directory that have no pristine copy yet
+    pub missing: Vec<String>,
+}
+
+/// Backup root for installs whose game profile has no backup folder: the
+/// `backupRoot` setting (see `set_backup_root`), else `<app data>/backups`
+pub fn default_backup_root(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    match settings::get_setting::<String>(app_handle, "backupRoot")
+        .filter(|root| !root.trim().is_empty())
+    {
+        Some(root) => Ok(PathBuf::from(root.trim())),
+        None => Ok(portable::app_data_dir(app_handle)?.join("backups")),
+    }
+}
+
+/// Backup area for a game install: `<backup root>/<path hash>`, or under the
+/// backup location of the game profile that owns the install
+pub fn install_backup_dir(app_handle: &AppHandle, bundles_dir: &Path) -> Result<PathBuf, String> {
+    let digest = Sha256::digest(bundles_dir.to_string_lossy().as_bytes());
+    let root = match game_profiles::backup_root_for(app_handle, bundles_dir) {
+        Some(root) => root,
+        None => default_backup_root(app_handle)?,
+    };
+    Ok(root.join(&format!("{:x}", digest)[..16]))
+}
+
+/// Fail before copying when the backup volume can't hold `needed` more bytes
+/// and still keep the minimum free space
+pub fn check_backup_space(backup_dir: &Path, needed: u64) -> Result<(), String> {
+    let free = disk_space::free_space(backup_dir)?;
+    if free < needed + MIN_FREE_SPACE {
+        return Err(format!(
+            "Not enough space on the backup drive for {}: {} needed, {} free. Choose another backup location in settings.",
+            backup_dir.display(),
+            disk_space::format_size(needed + MIN_FREE_SPACE),
+            disk_space::format_size(free)
+        ));
+    }
+    Ok(())
+}
+
+pub fn load_manifest(backup_dir: &Path) -> Option<BackupManifest> {
+    let path = backup_dir.join(BACKUP_MANIFEST_FILE);
+    if !path.is_file() {
+        return None;
+    }
+    Some(storage::load_json(&path))
+}
+
+/// `.bundle` files directly inside a directory, by file name
+pub fn list_bundle_files(bundles_dir: &Path) -> BTreeMap<String, PathBuf> {
+    std::fs::read_dir(bundles_dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.is_file())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "bundle"))
+                .filter_map(|path| {
+                    let name = path.file_name()?.to_string_lossy().to_string();
+                    Some((name, path))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Cheap check for a bundle that no longer matches its backed up copy:
+/// a different size, or a different mtime when one was recorded
+pub fn differs_from_backup(path: &Path, file: &BackedUpBundle) -> bool {
+    std::fs::metadata(path).map(|meta| meta.len()).ok() != Some(file.size)
+        || (file.modified.is_some() && modified_secs(path) != file.modified)
+}
+
+/// Copy any bundles without a pristine copy into the backup area. Bundles already
+/// in the manifest are never overwritten: the first copy taken is the original.
+pub fn ensure_backup(app_handle: &AppHandle, bundles_dir: &Path) -> Result<BackupStatus, String> {
+    if !bundles_dir.is_dir() {
+        return Err(format!(
+            "Bundles directory not found: {}",
+            bundles_dir.display()
+        ));
+    }
+
+    let backup_dir = install_backup_dir(app_handle, bundles_dir)?;
+    std::fs::create_dir_all(&backup_dir)
+        .map_err(|e| format!("Failed to create backup directory: {}", e))?;
+
+    // Builds sharing an install (a batch) take its backup one at a time
+    let manifest_path = backup_dir.join(BACKUP_MANIFEST_FILE);
+    storage::with_lock(&manifest_path, || {
+        copy_missing(app_handle, bundles_dir, &backup_dir, &manifest_path)
+    })?;
+    backup_status_for(app_handle, bundles_dir)
+}
+
+/// Copy the bundles missing from an install's backup, saving the manifest as
+/// each one lands
+fn copy_missing(
+    app_handle: &AppHandle,
+    bundles_dir: &Path,
+    backup_dir: &Path,
+    manifest_path: &Path,
+) -> Result<(), String> {
+    let mut manifest = load_manifest(backup_dir).unwrap_or_else(|| BackupManifest {
+        bundles_path: bundles_dir.to_string_lossy().to_string(),
+        created_at: storage::unix_now(),
+        files: BTreeMap::new(),
+    });
+
+    let pending: Vec<(String, PathBuf)> = list_bundle_files(bundles_dir)
+        .into_iter()
+        .filter(|(name, _)| !manifest.files.contains_key(name))
+        .collect();
+    let total = pending.len() as u32;
+    let cancel = file_copy::begin_transfer(app_handle);
+
+    // Copy-on-write clones (APFS, Btrfs, XFS, ReFS) are instant and share the
+    // original's disk space, so only hashing them has to read any data
+    let mut cloned = Vec::new();
+    let mut streamed = Vec::new();
+    for (name, source) in pending {
+        if file_copy::clone_file(&source, &backup_dir.join(&name))? {
+            cloned.push((name, source));
+        } else {
+            streamed.push((name, source));
+        }
+    }
+
+    if !cloned.is_empty() {
+        println!(
+            "[DEBUG] Cloned {} bundle(s) into the backup area",
+            cloned.len()
+        );
+        let _ = app_handle.emit(
+            "backup_progress",
+            ProgressEvent {
+                current: 0,
+                total,
+                status: format!("Checksumming {} cloned bundle(s)", cloned.len()),
+                run_id: None,
+            },
+        );
+        let clones: Vec<PathBuf> = cloned
+            .iter()
+            .map(|(name, _)| backup_dir.join(name))
+            .collect();
+        let hashes = hashing::hash_files(app_handle, &clones, HashAlgorithm::Sha256, &cancel);
+        // A clone that fails to hash is left unrecorded and re-taken next time
+        let mut first_error = None;
+        for (((name, source), clone), hash) in cloned.iter().zip(&clones).zip(hashes) {
+            match hash {
+                Ok(sha256) => {
+                    manifest.files.insert(
+                        name.clone(),
+                        BackedUpBundle {
+                            size: std::fs::metadata(clone)
+                                .map(|metadata| metadata.len())
+                                .unwrap_or(0),
+                            sha256,
+                            backed_up_at: storage::unix_now(),
+                            modified: modified_secs(source),
+                        },
+                    );
+                }
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        storage::save_json(manifest_path, &manifest)?;
+        if let Some(e) = first_error {
+            return Err(e);
+        }
+    }
+
+    let needed: u64 = streamed
+        .iter()
+        .filter_map(|(_, source)| std::fs::metadata(source).ok())
+        .map(|meta| meta.len())
+        .sum();
+    if needed > 0 {
+        check_backup_space(backup_dir, needed)?;
+    }
+
+    let cloned_count = cloned.len();
+    for (index, (name, source)) in streamed.into_iter().enumerate() {
+        let _ = app_handle.emit(
+            "backup_progress",
+            ProgressEvent {
+                current: (cloned_count + index) as u32,
+                total,
+                status: format!("Backing up {}", name),
+                run_id: None,
+            },
+        );
+
+        let outcome = file_copy::copy_file(
+            &source,
+            &backup_dir.join(&name),
+            false,
+            &cancel,
+            &mut file_copy::progress_emitter(app_handle, &name),
+        )?;
+        if outcome.cloned {
+            println!("[DEBUG] Cloned {} into the backup area", name);
+        }
+        manifest.files.insert(
+            name,
+            BackedUpBundle {
+                size: outcome.bytes,
+                sha256: outcome.sha256,
+                backed_up_at: storage::unix_now(),
+                modified: modified_secs(&source),
+            },
+        );
+
+        // Save as we go so an interrupted backup resumes where it stopped
+        storage::save_json(manifest_path, &manifest)?;
+    }
+
+    if total > 0 {
+        let _ = app_handle.emit(
+            "backup_progress",
+            ProgressEvent {
+                current: total,
+                total,
+                status: "Backup complete".to_string(),
+                run_id: None,
+            },
+        );
+    }
+
+    storage::save_json(manifest_path, &manifest)
+}
+
+fn backup_status_for(app_handle: &AppHandle, bundles_dir: &Path) -> Result<BackupStatus, String> {
+    let backup_dir = install_backup_dir(app_handle, bundles_dir)?;
+    let manifest = load_manifest(&backup_dir);
+    let files = manifest
+        .as_ref()
+        .map(|manifest| manifest.files.clone())
+        .unwrap_or_default();
+
+    let missing = list_bundle_files(bundles_dir)
+        .into_keys()
+        .filter(|name| !files.contains_key(name))
+        .collect();
+
+    Ok(BackupStatus {
+        bundles_path: bundles_dir.to_string_lossy().to_string(),
+        backup_path: backup_dir.to_string_lossy().to_string(),
+        has_backup: manifest.is_some(),
+        created_at: manifest.as_ref().map(|manifest| manifest.created_at),
+        file_count: files.len(),
+        total_size: files.values().map(|file| file.size).sum(),
+        missing,
+    })
+}
+
+/// Whether pristine copies of a game install's bundles have been taken
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub fn backup_status(app_handle: AppHandle, bundles_path: String) -> Result<BackupStatus, String> {
+    backup_status_for(
+        &app_handle,
+        &path_guard::clean_path(&bundles_path, "Bundles folder")?,
+    )
+}
+
+/// Back up original bundles now instead of waiting for the first build
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub async fn backup_original_bundles(
+    app_handle: AppHandle,
+    bundles_path: String,
+) -> Result<BackupStatus, String> {
+    let bundles_dir = path_guard::existing_dir(&bundles_path, "Bundles folder")?;
+    tokio::task::spawn_blocking(move || ensure_backup(&app_handle, &bundles_dir))
+        .await
+        .map_err(|e| format!("Backup task failed: {}", e))?
+}
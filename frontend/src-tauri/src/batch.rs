@@ -0,0 +1,242 @@
+use std::collections::{BTreeSet, VecDeque};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::backups;
+use crate::error::{AppError, ErrorKind};
+use crate::events::{BatchCompleteEvent, BatchProjectResult};
+use crate::process::{self, ProcessState, OUTPUT_MODE_STAGING};
+use crate::projects::{self, Project};
+use crate::settings;
+
+/// Builds run at once when neither the request nor the `batchConcurrency` setting says
+const DEFAULT_CONCURRENCY: usize = 1;
+
+#[derive(Default)]
+pub struct BatchState {
+    running: AtomicBool,
+    stopped: AtomicBool,
+}
+
+/// Whether a batch build is in progress
+pub fn is_running(app_handle: &AppHandle) -> bool {
+    app_handle
+        .state::<BatchState>()
+        .running
+        .load(Ordering::SeqCst)
+}
+
+/// Start no more batch builds and stop the running ones, waiting for each to
+/// record its history
+pub async fn stop_workers(app_handle: &AppHandle) {
+    app_handle
+        .state::<BatchState>()
+        .stopped
+        .store(true, Ordering::SeqCst);
+    for worker in app_handle.state::<ProcessState>().batch_workers() {
+        process::shutdown_running_task(&worker).await;
+    }
+}
+
+/// Build the queued projects one after another until the queue is empty or
+/// the batch is stopped
+async fn run_worker(
+    app_handle: AppHandle,
+    worker: Arc<ProcessState>,
+    queue: Arc<Mutex<VecDeque<(usize, Project)>>>,
+    bundles_path: Option<String>,
+) -> Vec<(usize, BatchProjectResult)> {
+    let mut results = Vec::new();
+    loop {
+        if app_handle
+            .state::<BatchState>()
+            .stopped
+            .load(Ordering::SeqCst)
+        {
+            break;
+        }
+        let Some((index, project)) = queue.lock().ok().and_then(|mut queue| queue.pop_front())
+        else {
+            break;
+        };
+
+        let mut config = project.task_config();
+        if let Some(bundles_path) = &bundles_path {
+            config.bundles_path = bundles_path.clone();
+        }
+        // Installing every skin into the same game would leave only the last one
+        config.output_mode = Some(OUTPUT_MODE_STAGING.to_string());
+
+        let started = Instant::now();
+        let result = process::run_task(app_handle.clone(), config, &worker, None).await;
+        if let Err(e) = projects::record_run(&app_handle, &project.id, &result) {
+            println!(
+                "[WARNING] Failed to save the result of {}: {}",
+                project.name, e
+            );
+        }
+
+        let (success, exit_code, error) = match result {
+            Ok(output) => (output.success, Some(output.status), None),
+            Err(e) => (false, None, Some(e.message)),
+        };
+        results.push((
+            index,
+            BatchProjectResult {
+                project_id: project.id,
+                name: project.name,
+                success,
+                exit_code,
+                error,
+                duration_ms: started.elapsed().as_millis() as u64,
+            },
+        ));
+    }
+    results
+}
+
+/// Back up each install the batch patches once, up front, so builds sharing
+/// one find its backup already taken
+async fn back_up_installs(
+    app_handle: &AppHandle,
+    projects: &[Project],
+    bundles_path: &Option<String>,
+) {
+    let installs: BTreeSet<PathBuf> = projects
+        .iter()
+        .filter(|project| !project.dry_run)
+        .map(|project| {
+            bundles_path
+                .as_deref()
+                .unwrap_or(&project.bundles_path)
+                .trim()
+        })
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_dir())
+        .collect();
+
+    for bundles_dir in installs {
+        let backup_handle = app_handle.clone();
+        let dir = bundles_dir.clone();
+        let backup =
+            tokio::task::spawn_blocking(move || backups::ensure_backup(&backup_handle, &dir))
+                .await
+                .map_err(|e| format!("Backup task failed: {}", e))
+                .and_then(|result| result);
+        if let Err(e) = backup {
+            println!("[WARNING] Failed to back up {:?}: {}", bundles_dir, e);
+        }
+    }
+}
+
+/// Build several projects, `concurrency` at a time (the `batchConcurrency`
+/// setting, else one), optionally all against the same bundles folder. The
+/// built bundles stay in each project's output folder. Emits `batch_complete`
+/// with every project's result when done.
+#[tauri::command]
+#[tracing::instrument(skip(app_handle, state, projects), err)]
+pub async fn run_batch(
+    app_handle: AppHandle,
+    state: State<'_, BatchState>,
+    projects: Vec<Project>,
+    bundles_path: Option<String>,
+    concurrency: Option<usize>,
+) -> Result<BatchCompleteEvent, AppError> {
+    if projects.is_empty() {
+        return Err(AppError::invalid_input("No projects to build"));
+    }
+    let process_state = app_handle.state::<ProcessState>();
+    if process_state.run_active.load(Ordering::SeqCst) {
+        return Err(AppError::new(
+            ErrorKind::Conflict,
+            "A build is already running",
+        ));
+    }
+    if state.running.swap(true, Ordering::SeqCst) {
+        return Err(AppError::new(
+            ErrorKind::Conflict,
+            "A batch build is already running",
+        ));
+    }
+    state.stopped.store(false, Ordering::SeqCst);
+
+    let total = projects.len();
+    let concurrency = concurrency
+        .or_else(|| settings::get_setting(&app_handle, "batchConcurrency"))
+        .filter(|limit| *limit > 0)
+        .unwrap_or(DEFAULT_CONCURRENCY)
+        .min(total);
+    let bundles_path = bundles_path
+        .map(|path| path.trim().to_string())
+        .filter(|path| !path.is_empty());
+    println!(
+        "[DEBUG] Starting batch of {} project(s), {} at a time",
+        total, concurrency
+    );
+
+    let started = Instant::now();
+    // Registered first so guards treat the up-front backups as part of the batch
+    let workers: Vec<Arc<ProcessState>> = (0..concurrency)
+        .map(|_| Arc::new(ProcessState::default()))
+        .collect();
+    if let Ok(mut slots) = process_state.batch_workers.lock() {
+        *slots = workers.clone();
+    }
+    back_up_installs(&app_handle, &projects, &bundles_path).await;
+    let queue = Arc::new(Mutex::new(
+        projects.into_iter().enumerate().collect::<VecDeque<_>>(),
+    ));
+
+    let handles: Vec<_> = workers
+        .into_iter()
+        .map(|worker| {
+            tauri::async_runtime::spawn(run_worker(
+                app_handle.clone(),
+                worker,
+                queue.clone(),
+                bundles_path.clone(),
+            ))
+        })
+        .collect();
+    let mut results = Vec::new();
+    for handle in handles {
+        match handle.await {
+            Ok(worker_results) => results.extend(worker_results),
+            Err(e) => eprintln!("[RUST] Batch worker failed: {}", e),
+        }
+    }
+    results.sort_by_key(|(index, _)| *index);
+    let results: Vec<BatchProjectResult> = results.into_iter().map(|(_, result)| result).collect();
+
+    if let Ok(mut slots) = process_state.batch_workers.lock() {
+        slots.clear();
+    }
+    state.running.store(false, Ordering::SeqCst);
+
+    let succeeded = results.iter().filter(|result| result.success).count();
+    let event = BatchCompleteEvent {
+        batch_id: uuid::Uuid::new_v4().to_string(),
+        succeeded,
+        failed: results.len() - succeeded,
+        skipped: total - results.len(),
+        results,
+        duration_ms: started.elapsed().as_millis() as u64,
+    };
+    let _ = app_handle.emit("batch_complete", event.clone());
+    Ok(event)
+}
+
+/// Stop a running batch: no further projects start and running builds are cancelled
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub async fn stop_batch(app_handle: AppHandle) -> Result<(), String> {
+    if !is_running(&app_handle) {
+        return Err("No batch build is running".to_string());
+    }
+    stop_workers(&app_handle).await;
+    Ok(())
+}
@@ -0,0 +1,210 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+use tauri::AppHandle;
+
+use crate::backups;
+use crate::build_summary::BuildSummary;
+use crate::hashing::{self, HashAlgorithm};
+use crate::portable;
+use crate::process::get_log_level;
+use crate::run_logs;
+use crate::storage;
+
+/// Build manifests kept on disk before the oldest are deleted
+const MAX_BUILD_MANIFESTS: usize = 50;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct OutputBundle {
+    pub size: u64,
+    /// BLAKE3; only ever compared with other manifests
+    pub hash: String,
+}
+
+/// What one build produced: `<app data>/build_manifests/<run id>.json`
+#[derive(Serialize, Deserialize, Clone)]
+pub struct BuildManifest {
+    pub run_id: String,
+    pub skin_path: String,
+    pub output_dir: String,
+    pub finished_at: u64,
+    pub summary: BuildSummary,
+    pub bundles: BTreeMap<String, OutputBundle>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct BundleDifference {
+    pub name: String,
+    pub status: String, // "added", "removed", "changed"
+    pub size_a: Option<u64>,
+    pub size_b: Option<u64>,
+}
+
+#[derive(Serialize)]
+pub struct BuildComparison {
+    pub run_a: String,
+    pub run_b: String,
+    /// Every bundle in both builds has the same contents
+    pub identical: bool,
+    pub unchanged: u32,
+    pub bundles: Vec<BundleDifference>,
+    pub summary_a: BuildSummary,
+    pub summary_b: BuildSummary,
+    /// Warnings logged by build B but not by build A
+    pub warnings_introduced: Vec<String>,
+    pub warnings_resolved: Vec<String>,
+}
+
+fn manifests_dir(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    Ok(portable::app_data_dir(app_handle)?.join("build_manifests"))
+}
+
+fn manifest_path(app_handle: &AppHandle, run_id: &str) -> Result<PathBuf, String> {
+    run_logs::check_run_id(run_id)?;
+    Ok(manifests_dir(app_handle)?.join(format!("{}.json", run_id)))
+}
+
+fn prune_manifests(dir: &Path) {
+    let mut files: Vec<(PathBuf, Option<std::time::SystemTime>)> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "json"))
+                .map(|entry| {
+                    let modified = entry.metadata().and_then(|m| m.modified()).ok();
+                    (entry.path(), modified)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    if files.len() <= MAX_BUILD_MANIFESTS {
+        return;
+    }
+    files.sort_by_key(|(_, modified)| *modified);
+    let excess = files.len() - MAX_BUILD_MANIFESTS;
+    for (path, _) in files.into_iter().take(excess) {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+/// Hash the bundles a successful build left in its output folder and save them
+/// with its summary and warnings, for `compare_builds`
+pub fn record_manifest(
+    app_handle: &AppHandle,
+    run_id: &str,
+    skin_path: &str,
+    output_dir: &Path,
+    summary: &BuildSummary,
+    lines: &[String],
+) -> Result<(), String> {
+    let files: Vec<(String, PathBuf)> =
+        backups::list_bundle_files(output_dir).into_iter().collect();
+    let paths: Vec<PathBuf> = files.iter().map(|(_, path)| path.clone()).collect();
+    let hashes = hashing::hash_files(
+        app_handle,
+        &paths,
+        HashAlgorithm::Blake3,
+        &AtomicBool::new(false),
+    );
+
+    let mut bundles = BTreeMap::new();
+    for ((name, path), hash) in files.into_iter().zip(hashes) {
+        let size = std::fs::metadata(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?
+            .len();
+        bundles.insert(name, OutputBundle { size, hash: hash? });
+    }
+
+    let mut warnings: Vec<String> = lines
+        .iter()
+        .filter(|line| get_log_level(line) == "warning")
+        .map(|line| line.trim().to_string())
+        .collect();
+    warnings.dedup();
+
+    let dir = manifests_dir(app_handle)?;
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create build manifests directory: {}", e))?;
+    prune_manifests(&dir);
+    storage::save_json(
+        &manifest_path(app_handle, run_id)?,
+        &BuildManifest {
+            run_id: run_id.to_string(),
+            skin_path: skin_path.trim().to_string(),
+            output_dir: output_dir.to_string_lossy().to_string(),
+            finished_at: storage::unix_now(),
+            summary: summary.clone(),
+            bundles,
+            warnings,
+        },
+    )
+}
+
+fn load_manifest(app_handle: &AppHandle, run_id: &str) -> Result<BuildManifest, String> {
+    let path = manifest_path(app_handle, run_id.trim())?;
+    let content = std::fs::read_to_string(&path)
+        .map_err(|_| format!("No build manifest found for run {}", run_id.trim()))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse build manifest: {}", e))
+}
+
+/// Diff the bundles, summaries and warnings of two successful builds, e.g. to
+/// confirm a refactor of a skin produces the same bundles
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub fn compare_builds(
+    app_handle: AppHandle,
+    run_a: String,
+    run_b: String,
+) -> Result<BuildComparison, String> {
+    let a = load_manifest(&app_handle, &run_a)?;
+    let b = load_manifest(&app_handle, &run_b)?;
+
+    let mut unchanged = 0;
+    let mut bundles = Vec::new();
+    let names: BTreeSet<&String> = a.bundles.keys().chain(b.bundles.keys()).collect();
+    for name in names {
+        let (before, after) = (a.bundles.get(name), b.bundles.get(name));
+        let status = match (before, after) {
+            (Some(before), Some(after)) if before.hash == after.hash => {
+                unchanged += 1;
+                continue;
+            }
+            (Some(_), Some(_)) => "changed",
+            (None, _) => "added",
+            (_, None) => "removed",
+        };
+        bundles.push(BundleDifference {
+            name: name.clone(),
+            status: status.to_string(),
+            size_a: before.map(|bundle| bundle.size),
+            size_b: after.map(|bundle| bundle.size),
+        });
+    }
+
+    let warnings_introduced = b
+        .warnings
+        .iter()
+        .filter(|warning| !a.warnings.contains(warning))
+        .cloned()
+        .collect();
+    let warnings_resolved = a
+        .warnings
+        .iter()
+        .filter(|warning| !b.warnings.contains(warning))
+        .cloned()
+        .collect();
+
+    Ok(BuildComparison {
+        run_a: a.run_id,
+        run_b: b.run_id,
+        identical: bundles.is_empty(),
+        unchanged,
+        bundles,
+        summary_a: a.summary,
+        summary_b: b.summary,
+        warnings_introduced,
+        warnings_resolved,
+    })
+}
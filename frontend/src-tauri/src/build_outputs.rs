@@ -0,0 +1,274 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+use walkdir::WalkDir;
+
+use crate::build_summary;
+use crate::path_guard;
+use crate::skins::read_skin_info;
+use crate::storage;
+
+const OUTPUT_SETTINGS_FILE: &str = "build_outputs.json";
+
+/// Marks a folder as a build output the app created, so retention never
+/// deletes anything else that lives under the output directory
+pub const OUTPUT_MARKER_FILE: &str = "build_output.json";
+
+const DEFAULT_KEEP_BUILDS: u32 = 5;
+
+fn default_keep_builds() -> u32 {
+    DEFAULT_KEEP_BUILDS
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct OutputSettings {
+    /// Root for per-build output folders; unset keeps the backend's default
+    /// of `<skin>/packages`
+    pub directory: Option<String>,
+    /// Builds kept per skin before the oldest are deleted
+    #[serde(default = "default_keep_builds")]
+    pub keep_builds: u32,
+}
+
+impl Default for OutputSettings {
+    fn default() -> Self {
+        Self {
+            directory: None,
+            keep_builds: DEFAULT_KEEP_BUILDS,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+struct OutputMarker {
+    skin_path: String,
+    created_at: u64,
+}
+
+#[derive(Serialize)]
+pub struct BuildOutput {
+    pub skin_path: String,
+    pub path: String,
+    pub created_at: u64,
+    pub size: u64,
+    pub file_count: u32,
+}
+
+fn load_settings(app_handle: &AppHandle) -> Result<(PathBuf, OutputSettings), String> {
+    let path = storage::data_file(app_handle, OUTPUT_SETTINGS_FILE)?;
+    let settings = storage::load_json(&path);
+    Ok((path, settings))
+}
+
+/// The configured output directory, if any
+pub fn output_root(app_handle: &AppHandle) -> Option<PathBuf> {
+    load_settings(app_handle)
+        .ok()?
+        .1
+        .directory
+        .filter(|directory| !directory.trim().is_empty())
+        .map(PathBuf::from)
+}
+
+/// Folder name for a skin's builds: its display name, made filesystem-safe
+fn skin_folder_name(skin_path: &str) -> String {
+    let skin_dir = Path::new(skin_path.trim());
+    let name = read_skin_info(skin_dir)
+        .map(|info| info.name)
+        .ok()
+        .filter(|name| !name.trim().is_empty())
+        .or_else(|| {
+            skin_dir
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+        })
+        .unwrap_or_else(|| "skin".to_string());
+
+    name.trim()
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '-' | '_' | ' ' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>()
+        .trim_matches('.')
+        .to_string()
+}
+
+/// Build output folders under the root, newest first
+fn list_outputs(root: &Path) -> Vec<(PathBuf, OutputMarker)> {
+    let mut outputs: Vec<(PathBuf, OutputMarker)> = WalkDir::new(root)
+        .min_depth(2)
+        .max_depth(2)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_dir())
+        .filter_map(|entry| {
+            let marker = entry.path().join(OUTPUT_MARKER_FILE);
+            let content = std::fs::read_to_string(marker).ok()?;
+            let marker: OutputMarker = serde_json::from_str(&content).ok()?;
+            Some((entry.into_path(), marker))
+        })
+        .collect();
+    outputs.sort_by(|a, b| b.1.created_at.cmp(&a.1.created_at));
+    outputs
+}
+
+/// Create `<root>/<skin name>/<timestamp>` for a new build. Returns `None` when
+/// no output directory is configured.
+pub fn new_build_dir(app_handle: &AppHandle, skin_path: &str) -> Result<Option<PathBuf>, String> {
+    let Some(root) = output_root(app_handle) else {
+        return Ok(None);
+    };
+
+    let skin_dir = root.join(skin_folder_name(skin_path));
+    let mut name = chrono::Local::now().format("%Y%m%d-%H%M%S").to_string();
+    while skin_dir.join(&name).exists() {
+        name.push('_');
+    }
+    let dir = skin_dir.join(name);
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create build output folder: {}", e))?;
+    storage::save_json(
+        &dir.join(OUTPUT_MARKER_FILE),
+        &OutputMarker {
+            skin_path: skin_path.trim().to_string(),
+            created_at: storage::unix_now(),
+        },
+    )?;
+
+    Ok(Some(dir))
+}
+
+/// Where a skin's latest build is: `--out` if given, else its newest managed
+/// output folder, else `<skin>/packages`
+pub fn current_output_dir(
+    app_handle: &AppHandle,
+    skin_path: &str,
+    extra_args: &[String],
+) -> PathBuf {
+    if let Some(dir) = build_summary::explicit_output_dir(extra_args) {
+        return dir;
+    }
+
+    output_root(app_handle)
+        .and_then(|root| {
+            list_outputs(&root)
+                .into_iter()
+                .find(|(_, marker)| marker.skin_path == skin_path.trim())
+        })
+        .map(|(dir, _)| dir)
+        .unwrap_or_else(|| PathBuf::from(skin_path.trim()).join("packages"))
+}
+
+/// Delete a skin's oldest build outputs beyond the retention count, along with
+/// any output folder a failed build left empty
+pub fn prune_outputs(app_handle: &AppHandle, skin_path: &str) -> Result<u32, String> {
+    let Some(root) = output_root(app_handle) else {
+        return Ok(0);
+    };
+    let (_, settings) = load_settings(app_handle)?;
+
+    let mut kept = 0;
+    let mut removed = 0;
+    for (dir, _) in list_outputs(&root)
+        .into_iter()
+        .filter(|(_, marker)| marker.skin_path == skin_path.trim())
+    {
+        let is_empty = std::fs::read_dir(&dir)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .all(|entry| entry.file_name() == OUTPUT_MARKER_FILE)
+            })
+            .unwrap_or(false);
+        if !is_empty && kept < settings.keep_builds.max(1) {
+            kept += 1;
+            continue;
+        }
+
+        path_guard::ensure_destructive_allowed(app_handle, &dir, "Build output folder")?;
+        std::fs::remove_dir_all(&dir)
+            .map_err(|e| format!("Failed to delete old build output: {}", e))?;
+        removed += 1;
+    }
+
+    Ok(removed)
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub fn get_output_settings(app_handle: AppHandle) -> Result<OutputSettings, String> {
+    Ok(load_settings(&app_handle)?.1)
+}
+
+/// Set (or clear) the output directory and how many builds to keep per skin
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub fn set_output_settings(
+    app_handle: AppHandle,
+    directory: Option<String>,
+    keep_builds: Option<u32>,
+) -> Result<OutputSettings, String> {
+    let directory = match directory.filter(|directory| !directory.trim().is_empty()) {
+        Some(directory) => {
+            let dir = path_guard::clean_path(&directory, "Output directory")?;
+            std::fs::create_dir_all(&dir)
+                .map_err(|e| format!("Failed to create output directory: {}", e))?;
+            Some(dir.to_string_lossy().to_string())
+        }
+        None => None,
+    };
+
+    let path = storage::data_file(&app_handle, OUTPUT_SETTINGS_FILE)?;
+    storage::update_json(&path, |settings: &mut OutputSettings| {
+        settings.directory = directory;
+        if let Some(keep_builds) = keep_builds {
+            settings.keep_builds = keep_builds.max(1);
+        }
+        Ok(settings.clone())
+    })
+}
+
+/// Build outputs in the output directory, newest first, optionally for one skin
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub fn list_build_outputs(
+    app_handle: AppHandle,
+    skin_path: Option<String>,
+) -> Result<Vec<BuildOutput>, String> {
+    let Some(root) = output_root(&app_handle) else {
+        return Ok(Vec::new());
+    };
+    let skin_path = skin_path.map(|path| path.trim().to_string());
+
+    Ok(list_outputs(&root)
+        .into_iter()
+        .filter(|(_, marker)| {
+            skin_path
+                .as_ref()
+                .is_none_or(|path| &marker.skin_path == path)
+        })
+        .map(|(dir, marker)| {
+            let (size, file_count) = WalkDir::new(&dir)
+                .into_iter()
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.file_type().is_file())
+                .filter(|entry| entry.file_name() != OUTPUT_MARKER_FILE)
+                .filter_map(|entry| entry.metadata().ok())
+                .fold((0, 0), |(size, count), metadata| {
+                    (size + metadata.len(), count + 1)
+                });
+            BuildOutput {
+                skin_path: marker.skin_path,
+                path: dir.to_string_lossy().to_string(),
+                created_at: marker.created_at,
+                size,
+                file_count,
+            }
+        })
+        .collect())
+}
@@ -0,0 +1,124 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, UNIX_EPOCH};
+use walkdir::WalkDir;
+
+use crate::output_verify::OutputVerification;
+use crate::process::get_log_level;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PhaseTiming {
+    pub name: String, // "prepare", "backend", "finalize"
+    pub elapsed_ms: u64,
+}
+
+/// Numbers for the results screen, parsed from the backend's summary output
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct BuildSummary {
+    pub bundles_processed: u32,
+    pub bundles_modified: u32,
+    pub assets_replaced: u32,
+    pub css_assets_modified: u32,
+    pub textures_replaced: u32,
+    pub fonts_replaced: u32,
+    pub bytes_written: u64,
+    pub files_written: u32,
+    pub warnings: u32,
+    pub errors: u32,
+    pub output_path: Option<String>,
+    pub phases: Vec<PhaseTiming>,
+    /// Set once the written bundles have been checked
+    pub verification: Option<OutputVerification>,
+}
+
+/// First number following `label` in the line, e.g. "Bundles processed: 12"
+fn number_after(line: &str, label: &str) -> Option<u32> {
+    let rest = &line[line.find(label)? + label.len()..];
+    let digits: String = rest
+        .trim_start_matches([':', ' '])
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect();
+    digits.parse().ok()
+}
+
+impl BuildSummary {
+    /// Read the counters the backend logs at the end of a patch run
+    pub fn from_output(lines: &[String]) -> Self {
+        let mut summary = Self::default();
+
+        for line in lines {
+            match get_log_level(line).as_str() {
+                "error" => summary.errors += 1,
+                "warning" => summary.warnings += 1,
+                _ => {}
+            }
+
+            // Totals are logged more than once; the overall summary comes last
+            if let Some(count) = number_after(line, "Bundles processed") {
+                summary.bundles_processed = count;
+            } else if let Some(count) = number_after(line, "CSS bundles modified") {
+                summary.bundles_modified = count;
+            } else if let Some(count) = number_after(line, "Textures replaced") {
+                summary.textures_replaced = count;
+            } else if let Some(count) = number_after(line, "Fonts replaced") {
+                summary.fonts_replaced = count;
+            } else if let Some(count) = number_after(line, "Would replace") {
+                if line.contains("textures") {
+                    summary.textures_replaced = count;
+                } else if line.contains("fonts") {
+                    summary.fonts_replaced = count;
+                }
+            } else if let Some(count) = number_after(line, "Assets modified") {
+                // Logged once per bundle
+                summary.css_assets_modified += count;
+            }
+        }
+
+        summary.assets_replaced =
+            summary.css_assets_modified + summary.textures_replaced + summary.fonts_replaced;
+        summary
+    }
+
+    /// Total size of files under the output folder written since the build started
+    pub fn measure_output(&mut self, output_dir: &Path, started_at: u64) {
+        self.output_path = Some(output_dir.to_string_lossy().to_string());
+        let since = UNIX_EPOCH + Duration::from_secs(started_at);
+
+        for entry in WalkDir::new(output_dir)
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+        {
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if metadata.modified().is_ok_and(|modified| modified >= since) {
+                self.bytes_written += metadata.len();
+                self.files_written += 1;
+            }
+        }
+    }
+
+    pub fn add_phase(&mut self, name: &str, elapsed: Duration) {
+        self.phases.push(PhaseTiming {
+            name: name.to_string(),
+            elapsed_ms: elapsed.as_millis() as u64,
+        });
+    }
+}
+
+/// The `--out` folder passed in the extra arguments, if any
+pub fn explicit_output_dir(extra_args: &[String]) -> Option<PathBuf> {
+    let mut args = extra_args.iter().map(|arg| arg.trim());
+    while let Some(arg) = args.next() {
+        if arg == "--out" {
+            if let Some(out) = args.next().filter(|out| !out.is_empty()) {
+                return Some(PathBuf::from(out));
+            }
+        } else if let Some(out) = arg.strip_prefix("--out=") {
+            return Some(PathBuf::from(out));
+        }
+    }
+    None
+}
@@ -1,14 +1,31 @@
 use std::fs;
-use tauri::{AppHandle, Manager};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, State};
 use tauri_plugin_shell::ShellExt;
 
+use crate::error::{AppError, ErrorKind};
+use crate::fs_tasks::{check_cancelled, FsTaskState};
+use crate::portable;
+
 /// Recursively calculate the size of a directory in bytes
 /// Excludes the WebView2 folder (EBWebView) on Windows
-fn calculate_dir_size(path: &std::path::Path) -> Result<u64, std::io::Error> {
+pub fn calculate_dir_size(path: &Path) -> Result<u64, std::io::Error> {
+    dir_size(path, &AtomicBool::new(false))
+}
+
+/// `calculate_dir_size` that stops with `Interrupted` once `cancel` is set
+fn dir_size(path: &Path, cancel: &AtomicBool) -> Result<u64, std::io::Error> {
     let mut total_size = 0u64;
 
     if path.is_dir() {
         for entry in fs::read_dir(path)? {
+            if cancel.load(Ordering::Relaxed) {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::Interrupted,
+                    "Cancelled",
+                ));
+            }
             let entry = entry?;
             let entry_path = entry.path();
 
@@ -21,7 +38,7 @@ fn calculate_dir_size(path: &std::path::Path) -> Result<u64, std::io::Error> {
             }
 
             if entry_path.is_dir() {
-                total_size += calculate_dir_size(&entry_path)?;
+                total_size += dir_size(&entry_path, cancel)?;
             } else {
                 total_size += entry.metadata()?.len();
             }
@@ -31,52 +48,69 @@ fn calculate_dir_size(path: &std::path::Path) -> Result<u64, std::io::Error> {
     Ok(total_size)
 }
 
+fn cache_size(cache_dir: &Path, cancel: &AtomicBool) -> Result<u64, AppError> {
+    let size = dir_size(cache_dir, cancel);
+    check_cancelled(cancel, "Cache size calculation")?;
+    size.map_err(|e| AppError::io("Failed to calculate cache size", &e).with_path(cache_dir))
+}
+
 /// Get the size of the cache directory in bytes
 #[tauri::command]
-pub fn get_cache_size(app_handle: AppHandle) -> Result<u64, String> {
-    let cache_dir = app_handle
-        .path()
-        .app_cache_dir()
-        .map_err(|e| format!("Failed to get cache directory: {}", e))?;
+#[tracing::instrument(skip(app_handle, state), err)]
+pub async fn get_cache_size(
+    app_handle: AppHandle,
+    state: State<'_, FsTaskState>,
+) -> Result<u64, AppError> {
+    let cache_dir = portable::app_cache_dir(&app_handle)?;
 
     println!("[DEBUG] Cache directory path: {:?}", cache_dir);
 
-    if !cache_dir.exists() {
-        println!("[DEBUG] Cache directory does not exist");
-        return Ok(0);
-    }
-
-    let size = calculate_dir_size(&cache_dir)
-        .map_err(|e| format!("Failed to calculate cache size: {}", e))?;
-    println!(
-        "[DEBUG] Calculated cache size: {} bytes ({:.2} MB)",
-        size,
-        size as f64 / 1_048_576.0
-    );
+    state
+        .run("Cache size calculation", move |cancel| {
+            if !cache_dir.exists() {
+                println!("[DEBUG] Cache directory does not exist");
+                return Ok(0);
+            }
 
-    Ok(size)
+            let size = cache_size(&cache_dir, cancel)?;
+            println!(
+                "[DEBUG] Calculated cache size: {} bytes ({:.2} MB)",
+                size,
+                size as f64 / 1_048_576.0
+            );
+            Ok(size)
+        })
+        .await
 }
 
 /// Clear specific cache folders, excluding WebView2 runtime folder
 /// Only deletes: cache/, bundles/, skins/, temp/
 /// Never deletes: EBWebView/ (WebView2 runtime - locked on Windows)
 #[tauri::command]
-pub fn clear_cache(app_handle: AppHandle) -> Result<String, String> {
-    let cache_dir = app_handle
-        .path()
-        .app_cache_dir()
-        .map_err(|e| format!("Failed to get cache directory: {}", e))?;
+#[tracing::instrument(skip(app_handle, state), err)]
+pub async fn clear_cache(
+    app_handle: AppHandle,
+    state: State<'_, FsTaskState>,
+) -> Result<String, AppError> {
+    let cache_dir = portable::app_cache_dir(&app_handle)?;
 
     println!("[DEBUG] clear_cache called for: {:?}", cache_dir);
 
+    state
+        .run("Clearing the cache", move |cancel| {
+            clear_cache_folders(&cache_dir, cancel)
+        })
+        .await
+}
+
+fn clear_cache_folders(cache_dir: &Path, cancel: &AtomicBool) -> Result<String, AppError> {
     if !cache_dir.exists() {
         println!("[DEBUG] Cache directory does not exist");
         return Ok("Cache directory is already empty".to_string());
     }
 
     // Get size before clearing (excludes EBWebView)
-    let size_before = calculate_dir_size(&cache_dir)
-        .map_err(|e| format!("Failed to calculate cache size: {}", e))?;
+    let size_before = cache_size(cache_dir, cancel)?;
 
     println!(
         "[DEBUG] Size before clearing: {} bytes ({:.2} MB)",
@@ -91,8 +125,9 @@ pub fn clear_cache(app_handle: AppHandle) -> Result<String, String> {
     let mut items_deleted = 0;
     let mut total_errors = 0;
 
-    // Only delete whitelisted folders
+    // Only delete whitelisted folders; a folder being removed is always finished
     for folder_name in &folders_to_clear {
+        check_cancelled(cancel, "Clearing the cache")?;
         let folder_path = cache_dir.join(folder_name);
 
         if folder_path.exists() {
@@ -126,7 +161,7 @@ pub fn clear_cache(app_handle: AppHandle) -> Result<String, String> {
     );
 
     // Calculate actual size cleared
-    let size_after = calculate_dir_size(&cache_dir).unwrap_or(0);
+    let size_after = dir_size(cache_dir, cancel).unwrap_or(0);
     let size_cleared = size_before.saturating_sub(size_after);
     let mb_cleared = size_cleared as f64 / 1_048_576.0;
 
@@ -148,20 +183,11 @@ pub fn clear_cache(app_handle: AppHandle) -> Result<String, String> {
     Ok(result_message)
 }
 
-/// Open the cache directory in the system file browser
-#[tauri::command]
-pub async fn open_cache_dir(app_handle: AppHandle) -> Result<(), String> {
-    let cache_dir = app_handle
-        .path()
-        .app_cache_dir()
-        .map_err(|e| format!("Failed to get cache directory: {}", e))?;
-
-    // Create cache directory if it doesn't exist
-    if !cache_dir.exists() {
-        fs::create_dir_all(&cache_dir)
-            .map_err(|e| format!("Failed to create cache directory: {}", e))?;
-    }
-
+/// Open a directory in the system file browser
+pub fn open_in_file_browser(
+    app_handle: &AppHandle,
+    path: &std::path::Path,
+) -> Result<(), AppError> {
     // Use tauri-plugin-shell to open the directory
     let shell = app_handle.shell();
 
@@ -169,40 +195,76 @@ pub async fn open_cache_dir(app_handle: AppHandle) -> Result<(), String> {
     {
         shell
             .command("open")
-            .arg(cache_dir.to_string_lossy().to_string())
+            .arg(path.to_string_lossy().to_string())
             .spawn()
-            .map_err(|e| format!("Failed to open cache directory: {}", e))?;
+            .map_err(|e| {
+                AppError::new(
+                    ErrorKind::Io,
+                    format!("Failed to open {}: {}", path.display(), e),
+                )
+                .with_path(path)
+            })?;
     }
 
     #[cfg(target_os = "windows")]
     {
         shell
             .command("explorer")
-            .arg(cache_dir.to_string_lossy().to_string())
+            .arg(path.to_string_lossy().to_string())
             .spawn()
-            .map_err(|e| format!("Failed to open cache directory: {}", e))?;
+            .map_err(|e| {
+                AppError::new(
+                    ErrorKind::Io,
+                    format!("Failed to open {}: {}", path.display(), e),
+                )
+                .with_path(path)
+            })?;
     }
 
     #[cfg(target_os = "linux")]
     {
         shell
             .command("xdg-open")
-            .arg(cache_dir.to_string_lossy().to_string())
+            .arg(path.to_string_lossy().to_string())
             .spawn()
-            .map_err(|e| format!("Failed to open cache directory: {}", e))?;
+            .map_err(|e| {
+                AppError::new(
+                    ErrorKind::Io,
+                    format!("Failed to open {}: {}", path.display(), e),
+                )
+                .with_path(path)
+            })?;
     }
 
     Ok(())
 }
 
+/// Open the cache directory in the system file browser
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub async fn open_cache_dir(app_handle: AppHandle) -> Result<(), AppError> {
+    let cache_dir = portable::app_cache_dir(&app_handle)?;
+
+    // Create cache directory if it doesn't exist
+    if !cache_dir.exists() {
+        fs::create_dir_all(&cache_dir).map_err(|e| {
+            AppError::io("Failed to create cache directory", &e).with_path(&cache_dir)
+        })?;
+    }
+
+    open_in_file_browser(&app_handle, &cache_dir)
+}
+
 /// Get app version from Cargo.toml
 #[tauri::command]
+#[tracing::instrument]
 pub fn get_app_version() -> String {
     env!("CARGO_PKG_VERSION").to_string()
 }
 
 /// Get platform information
 #[tauri::command]
+#[tracing::instrument]
 pub fn get_platform_info() -> serde_json::Value {
     serde_json::json!({
         "os": std::env::consts::OS,
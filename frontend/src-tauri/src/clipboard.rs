@@ -0,0 +1,52 @@
+use tauri::AppHandle;
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+use crate::run_logs;
+
+/// Lines copied from the end of a run log when the caller doesn't say
+const DEFAULT_TAIL_LINES: usize = 200;
+
+fn write_text(app_handle: &AppHandle, text: String) -> Result<(), String> {
+    app_handle
+        .clipboard()
+        .write_text(text)
+        .map_err(|e| format!("Failed to copy to the clipboard: {}", e))
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app_handle, text), err)]
+pub fn copy_to_clipboard(app_handle: AppHandle, text: String) -> Result<(), String> {
+    write_text(&app_handle, text)
+}
+
+/// Copy the last `tail_lines` lines of a run log (0 for all of it) as plain
+/// text, headed with the app version and platform for pasting into a bug
+/// report. Returns the number of lines copied.
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub fn copy_run_log_to_clipboard(
+    app_handle: AppHandle,
+    run_id: String,
+    tail_lines: Option<usize>,
+) -> Result<usize, String> {
+    let events = run_logs::read_run_log(&app_handle, run_id.trim())?;
+    let tail = match tail_lines.unwrap_or(DEFAULT_TAIL_LINES) {
+        0 => events.len(),
+        tail => tail.min(events.len()),
+    };
+    let lines = &events[events.len() - tail..];
+
+    let mut text = format!(
+        "FM Skin Builder {} ({} {}), run {}: last {} of {} log lines\n",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        run_id.trim(),
+        lines.len(),
+        events.len()
+    );
+    text.extend(lines.iter().map(run_logs::text_line));
+
+    write_text(&app_handle, text)?;
+    Ok(lines.len())
+}
@@ -0,0 +1,243 @@
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+use walkdir::WalkDir;
+
+use crate::commands::{copy_dir_recursive, default_skins_dir};
+use crate::long_paths;
+use crate::path_guard;
+use crate::settings;
+
+/// Folder names that sync clients put in a path, and the provider they belong to
+const SYNC_FOLDERS: &[(&str, &str)] = &[
+    ("onedrive", "OneDrive"),
+    ("dropbox", "Dropbox"),
+    ("google drive", "Google Drive"),
+    ("googledrive", "Google Drive"),
+    ("icloud drive", "iCloud Drive"),
+    ("iclouddrive", "iCloud Drive"),
+    ("mobile documents", "iCloud Drive"),
+];
+
+// Windows cloud files attributes that mark a placeholder whose contents aren't on disk
+#[cfg(windows)]
+const FILE_ATTRIBUTE_OFFLINE: u32 = 0x1000;
+#[cfg(windows)]
+const FILE_ATTRIBUTE_RECALL_ON_OPEN: u32 = 0x40000;
+#[cfg(windows)]
+const FILE_ATTRIBUTE_RECALL_ON_DATA_ACCESS: u32 = 0x400000;
+
+#[derive(Serialize)]
+pub struct CloudSyncStatus {
+    pub path: String,
+    /// Sync client the folder belongs to, e.g. "OneDrive"
+    pub provider: Option<String>,
+    pub warning: Option<String>,
+    /// A local folder the skins library could move to
+    pub suggested_local_dir: Option<String>,
+}
+
+#[derive(Default)]
+pub struct HydrationReport {
+    /// Cloud-only files that were downloaded
+    pub hydrated: u32,
+    pub failed: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct RelocationResult {
+    pub previous_dir: String,
+    pub skins_dir: String,
+}
+
+/// OneDrive's folders as set by its client, e.g. a redirected Documents folder
+/// named after the organisation ("OneDrive - Contoso")
+fn onedrive_roots() -> Vec<PathBuf> {
+    ["OneDrive", "OneDriveConsumer", "OneDriveCommercial"]
+        .iter()
+        .filter_map(|name| std::env::var_os(name))
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .collect()
+}
+
+/// Sync client a path is kept in sync by, if any
+pub fn cloud_provider(path: &Path) -> Option<String> {
+    if onedrive_roots().iter().any(|root| path.starts_with(root)) {
+        return Some("OneDrive".to_string());
+    }
+
+    let components: Vec<String> = path
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().to_lowercase())
+        .collect();
+    // macOS File Provider sync clients live in ~/Library/CloudStorage/<Provider-Account>
+    if let Some(index) = components.iter().position(|name| name == "cloudstorage") {
+        if index > 0 && components[index - 1] == "library" {
+            // Folders are named "<Provider>-<account>"
+            let folder = components.get(index + 1)?;
+            let provider = SYNC_FOLDERS
+                .iter()
+                .find(|(name, _)| folder.starts_with(name))
+                .map(|(_, provider)| provider.to_string());
+            return provider.or_else(|| folder.split('-').next().map(str::to_string));
+        }
+    }
+    components.iter().find_map(|name| {
+        SYNC_FOLDERS
+            .iter()
+            .find(|(folder, _)| name == folder || name.starts_with(&format!("{} - ", folder)))
+            .map(|(_, provider)| provider.to_string())
+    })
+}
+
+/// Whether a file's contents are only in the cloud
+#[cfg(windows)]
+fn is_placeholder(metadata: &std::fs::Metadata) -> bool {
+    use std::os::windows::fs::MetadataExt;
+    metadata.file_attributes()
+        & (FILE_ATTRIBUTE_OFFLINE
+            | FILE_ATTRIBUTE_RECALL_ON_OPEN
+            | FILE_ATTRIBUTE_RECALL_ON_DATA_ACCESS)
+        != 0
+}
+
+#[cfg(not(windows))]
+fn is_placeholder(_metadata: &std::fs::Metadata) -> bool {
+    false
+}
+
+/// Download every cloud-only file in a folder by reading it, so the backend
+/// doesn't stall on (or fail to open) placeholders halfway through a build
+pub fn hydrate(dir: &Path) -> HydrationReport {
+    let mut report = HydrationReport::default();
+    // Only Windows cloud files placeholders can be told apart from the metadata
+    if !cfg!(windows) {
+        return report;
+    }
+    for entry in WalkDir::new(long_paths::extended(dir))
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+    {
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if !is_placeholder(&metadata) {
+            continue;
+        }
+        let result = std::fs::File::open(entry.path())
+            .and_then(|mut file| std::io::copy(&mut file, &mut std::io::sink()));
+        match result {
+            Ok(_) => report.hydrated += 1,
+            Err(e) => report.failed.push(format!(
+                "{}: {}",
+                long_paths::strip_extended(&entry.path().to_string_lossy()),
+                e
+            )),
+        }
+    }
+    report
+}
+
+/// A folder for the skins library outside any synced folder
+fn suggested_local_dir(app_handle: &AppHandle) -> Option<PathBuf> {
+    let candidates = [
+        app_handle.path().home_dir().ok(),
+        app_handle.path().local_data_dir().ok(),
+    ];
+    candidates
+        .into_iter()
+        .flatten()
+        .map(|dir| dir.join("FM Skin Builder"))
+        .find(|dir| cloud_provider(dir).is_none())
+}
+
+fn sync_warning(provider: &str) -> String {
+    format!(
+        "This folder is synced by {}. Syncing can lock files mid-build and leave files cloud-only until opened; keep skins in a local folder, or set them to \"Always keep on this device\".",
+        provider
+    )
+}
+
+/// Whether a folder (the skins library by default) is synced to the cloud
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub fn check_cloud_sync(
+    app_handle: AppHandle,
+    path: Option<String>,
+) -> Result<CloudSyncStatus, String> {
+    let dir = match path.filter(|path| !path.trim().is_empty()) {
+        Some(path) => path_guard::clean_path(&path, "Folder")?,
+        None => default_skins_dir(&app_handle)?,
+    };
+    let provider = cloud_provider(&dir);
+    Ok(CloudSyncStatus {
+        path: dir.to_string_lossy().to_string(),
+        warning: provider.as_deref().map(sync_warning),
+        suggested_local_dir: provider
+            .as_ref()
+            .and_then(|_| suggested_local_dir(&app_handle))
+            .map(|dir| dir.to_string_lossy().to_string()),
+        provider,
+    })
+}
+
+/// Copy the skins library to a local folder and use that from now on. The
+/// synced copy is left in place for the user to remove.
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub async fn relocate_skins_dir(
+    app_handle: AppHandle,
+    destination: String,
+) -> Result<RelocationResult, String> {
+    let destination = path_guard::clean_path(&destination, "New skins folder")?;
+    if let Some(provider) = cloud_provider(&destination) {
+        return Err(format!(
+            "{} is synced by {}; choose a local folder",
+            destination.display(),
+            provider
+        ));
+    }
+    if destination.is_dir()
+        && std::fs::read_dir(&destination)
+            .map(|mut entries| entries.next().is_some())
+            .unwrap_or(false)
+    {
+        return Err(format!(
+            "New skins folder is not empty: {}",
+            destination.display()
+        ));
+    }
+
+    let previous = default_skins_dir(&app_handle)?;
+    if destination.starts_with(&previous) {
+        return Err("The new skins folder can't be inside the current one".to_string());
+    }
+    let (source, target) = (previous.clone(), destination.clone());
+    tokio::task::spawn_blocking(move || {
+        if source.is_dir() {
+            copy_dir_recursive(&source, &target).map_err(String::from)
+        } else {
+            std::fs::create_dir_all(&target)
+                .map_err(|e| format!("Failed to create skins directory: {}", e))
+        }
+    })
+    .await
+    .map_err(|e| format!("Relocation task failed: {}", e))??;
+
+    settings::set_setting(
+        &app_handle,
+        "skinsDir",
+        destination.to_string_lossy().to_string(),
+    )?;
+    println!(
+        "[DEBUG] Skins library moved from {:?} to {:?}",
+        previous, destination
+    );
+
+    Ok(RelocationResult {
+        previous_dir: previous.to_string_lossy().to_string(),
+        skins_dir: destination.to_string_lossy().to_string(),
+    })
+}
@@ -1,7 +1,14 @@
-use rfd::FileDialog;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use tauri::{AppHandle, Manager};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager, State};
+
+use crate::error::{AppError, ErrorKind};
+use crate::fs_tasks::{check_cancelled, FsTaskState};
+use crate::i18n::t;
+use crate::long_paths;
+use crate::portable;
+use crate::settings;
 
 #[derive(Serialize, Deserialize)]
 pub struct UpdateMetadata {
@@ -25,46 +32,68 @@ pub struct InstallerInfo {
     pub size: u64,
 }
 
+/// Platform key used by the release manifests (`darwin-aarch64`, `windows-x86_64`, ...)
+pub fn current_platform() -> Result<&'static str, AppError> {
+    if cfg!(target_os = "macos") {
+        if cfg!(target_arch = "aarch64") {
+            Ok("darwin-aarch64")
+        } else {
+            Ok("darwin-x86_64")
+        }
+    } else if cfg!(target_os = "windows") {
+        Ok("windows-x86_64")
+    } else if cfg!(target_os = "linux") {
+        Ok("linux-x86_64")
+    } else {
+        Err(AppError::new(
+            ErrorKind::Unsupported,
+            "Unsupported platform",
+        ))
+    }
+}
+
 #[tauri::command]
+#[tracing::instrument(skip(app_handle, metadata), fields(version = %metadata.version), err)]
 pub async fn download_and_install_update(
+    app_handle: AppHandle,
     metadata: UpdateMetadata,
     _channel: String,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     use std::process::Command;
 
     // Determine the current platform
-    let platform = if cfg!(target_os = "macos") {
-        if cfg!(target_arch = "aarch64") {
-            "darwin-aarch64"
-        } else {
-            "darwin-x86_64"
-        }
-    } else if cfg!(target_os = "windows") {
-        "windows-x86_64"
-    } else if cfg!(target_os = "linux") {
-        "linux-x86_64"
-    } else {
-        return Err("Unsupported platform".to_string());
-    };
+    let platform = current_platform()?;
 
     // Get the platform-specific info
-    let platform_info = metadata
-        .platforms
-        .get(platform)
-        .ok_or_else(|| format!("No update available for platform: {}", platform))?;
+    let platform_info = metadata.platforms.get(platform).ok_or_else(|| {
+        AppError::new(
+            ErrorKind::Unsupported,
+            format!("No update available for platform: {}", platform),
+        )
+    })?;
+
+    let is_portable = portable::is_portable();
 
     // Get the first installer (prefer MSI for Windows, DMG for macOS, AppImage for Linux)
+    // Portable installs only accept the zip distribution
     let installer = platform_info
         .installers
         .iter()
         .find(|installer| match platform {
+            _ if is_portable => installer.format == "zip",
             "windows-x86_64" => installer.format == "msi",
             "darwin-aarch64" | "darwin-x86_64" => installer.format == "dmg",
             "linux-x86_64" => installer.format == "AppImage" || installer.format == "deb",
             _ => false,
         })
-        .or_else(|| platform_info.installers.first())
-        .ok_or_else(|| "No suitable installer found".to_string())?;
+        .or_else(|| {
+            if is_portable {
+                None
+            } else {
+                platform_info.installers.first()
+            }
+        })
+        .ok_or_else(|| AppError::new(ErrorKind::Unsupported, "No suitable installer found"))?;
 
     let installer_url = &installer.url;
     let installer_format = &installer.format;
@@ -73,21 +102,27 @@ pub async fn download_and_install_update(
     println!("Installer format: {}", installer_format);
 
     // Download the installer
-    let response = reqwest::get(installer_url)
-        .await
-        .map_err(|e| format!("Failed to download update: {}", e))?;
+    let response = reqwest::get(installer_url).await.map_err(|e| {
+        AppError::new(
+            ErrorKind::Network,
+            format!("Failed to download update: {}", e),
+        )
+        .with_hint("Check your internet connection and try again.")
+    })?;
 
     if !response.status().is_success() {
-        return Err(format!(
-            "Download failed with status: {}",
-            response.status()
+        return Err(AppError::new(
+            ErrorKind::Network,
+            format!("Download failed with status: {}", response.status()),
         ));
     }
 
-    let bytes = response
-        .bytes()
-        .await
-        .map_err(|e| format!("Failed to read download: {}", e))?;
+    let bytes = response.bytes().await.map_err(|e| {
+        AppError::new(
+            ErrorKind::Network,
+            format!("Failed to read download: {}", e),
+        )
+    })?;
 
     // Save to a temporary location
     let temp_dir = std::env::temp_dir();
@@ -98,20 +133,29 @@ pub async fn download_and_install_update(
     let installer_path = temp_dir.join(installer_filename);
 
     std::fs::write(&installer_path, &bytes)
-        .map_err(|e| format!("Failed to save installer: {}", e))?;
+        .map_err(|e| AppError::io("Failed to save installer", &e).with_path(&installer_path))?;
 
     println!("Update downloaded to: {:?}", installer_path);
 
+    // Portable mode: extract next to the executable and swap files once the app has exited
+    if is_portable {
+        let staging_dir = portable::stage_portable_update(&installer_path)?;
+        portable::schedule_portable_swap(&staging_dir)?;
+        println!("Portable update scheduled, exiting...");
+        app_handle.exit(0);
+        return Ok(());
+    }
+
     // Make executable on Unix systems
     #[cfg(unix)]
     {
         use std::os::unix::fs::PermissionsExt;
         let mut perms = std::fs::metadata(&installer_path)
-            .map_err(|e| format!("Failed to get file permissions: {}", e))?
+            .map_err(|e| AppError::io("Failed to get file permissions", &e))?
             .permissions();
         perms.set_mode(0o755);
         std::fs::set_permissions(&installer_path, perms)
-            .map_err(|e| format!("Failed to set executable permissions: {}", e))?;
+            .map_err(|e| AppError::io("Failed to set executable permissions", &e))?;
     }
 
     // Run the installer based on format
@@ -128,7 +172,7 @@ pub async fn download_and_install_update(
                     "/norestart",
                 ])
                 .status()
-                .map_err(|e| format!("Failed to run MSI installer: {}", e))
+                .map_err(|e| AppError::io("Failed to run MSI installer", &e))
         }
         "dmg" => {
             // macOS DMG installer
@@ -138,19 +182,19 @@ pub async fn download_and_install_update(
             // Linux AppImage - just make it executable and run
             Command::new(&installer_path)
                 .status()
-                .map_err(|e| format!("Failed to run AppImage: {}", e))
+                .map_err(|e| AppError::io("Failed to run AppImage", &e))
         }
         "deb" => {
             // Linux DEB package
             Command::new("sudo")
                 .args(["dpkg", "-i", &installer_path.to_string_lossy()])
                 .status()
-                .map_err(|e| format!("Failed to install DEB package: {}", e))
+                .map_err(|e| AppError::io("Failed to install DEB package", &e))
         }
         _ => {
-            return Err(format!(
-                "Unsupported installer format: {}",
-                installer_format
+            return Err(AppError::new(
+                ErrorKind::Unsupported,
+                format!("Unsupported installer format: {}", installer_format),
             ));
         }
     };
@@ -160,25 +204,32 @@ pub async fn download_and_install_update(
             println!("Update installed successfully");
             Ok(())
         }
-        Ok(status) => Err(format!(
-            "Installer exited with code: {}",
-            status.code().unwrap_or(-1)
-        )),
-        Err(e) => Err(format!("Failed to run installer: {}", e)),
+        Ok(status) => Err(AppError::new(
+            ErrorKind::Internal,
+            format!(
+                "Installer exited with code: {}",
+                status.code().unwrap_or(-1)
+            ),
+        )
+        .with_hint("Download the latest release from the website and install it manually.")),
+        Err(e) => Err(AppError {
+            message: format!("Failed to run installer: {}", e.message),
+            ..e
+        }),
     }
 }
 
-fn install_from_dmg(dmg_path: &std::path::Path) -> Result<std::process::ExitStatus, String> {
+fn install_from_dmg(dmg_path: &std::path::Path) -> Result<std::process::ExitStatus, AppError> {
     use std::process::Command;
 
     // Create a temporary mount point
     let mount_point = std::env::temp_dir().join("fm-skin-builder-mount");
     if mount_point.exists() {
         std::fs::remove_dir_all(&mount_point)
-            .map_err(|e| format!("Failed to clean mount point: {}", e))?;
+            .map_err(|e| AppError::io("Failed to clean mount point", &e))?;
     }
     std::fs::create_dir_all(&mount_point)
-        .map_err(|e| format!("Failed to create mount point: {}", e))?;
+        .map_err(|e| AppError::io("Failed to create mount point", &e))?;
 
     // Mount the DMG
     let mount_result = Command::new("hdiutil")
@@ -190,15 +241,15 @@ fn install_from_dmg(dmg_path: &std::path::Path) -> Result<std::process::ExitStat
             "-nobrowse",
         ])
         .status()
-        .map_err(|e| format!("Failed to mount DMG: {}", e))?;
+        .map_err(|e| AppError::io("Failed to mount DMG", &e))?;
 
     if !mount_result.success() {
-        return Err("Failed to mount DMG".to_string());
+        return Err(AppError::new(ErrorKind::Io, "Failed to mount DMG").with_path(dmg_path));
     }
 
     // Find the .app bundle in the mounted volume
     let app_entries = std::fs::read_dir(&mount_point)
-        .map_err(|e| format!("Failed to read mount point: {}", e))?
+        .map_err(|e| AppError::io("Failed to read mount point", &e))?
         .filter_map(|entry| entry.ok())
         .filter(|entry| entry.path().extension().and_then(|ext| ext.to_str()) == Some("app"))
         .collect::<Vec<_>>();
@@ -208,14 +259,14 @@ fn install_from_dmg(dmg_path: &std::path::Path) -> Result<std::process::ExitStat
         let _ = Command::new("hdiutil")
             .args(["detach", &mount_point.to_string_lossy()])
             .status();
-        return Err("No .app bundle found in DMG".to_string());
+        return Err(AppError::not_found("No .app bundle found in DMG").with_path(dmg_path));
     }
 
     let app_path = app_entries[0].path();
     let app_name = app_path
         .file_name()
         .and_then(|n| n.to_str())
-        .ok_or_else(|| "Invalid app name".to_string())?;
+        .ok_or_else(|| AppError::new(ErrorKind::Internal, "Invalid app name"))?;
 
     // Copy to Applications folder
     let applications_dir = std::path::PathBuf::from("/Applications");
@@ -223,13 +274,16 @@ fn install_from_dmg(dmg_path: &std::path::Path) -> Result<std::process::ExitStat
 
     // Remove existing app if it exists
     if target_path.exists() {
-        std::fs::remove_dir_all(&target_path)
-            .map_err(|e| format!("Failed to remove existing app: {}", e))?;
+        std::fs::remove_dir_all(&target_path).map_err(|e| {
+            AppError::io("Failed to remove existing app", &e).with_path(&target_path)
+        })?;
     }
 
     // Copy the new app
-    copy_dir_recursive(&app_path, &target_path)
-        .map_err(|e| format!("Failed to copy app: {}", e))?;
+    copy_dir_recursive(&app_path, &target_path).map_err(|e| AppError {
+        message: format!("Failed to copy app: {}", e.message),
+        ..e
+    })?;
 
     // Unmount the DMG
     let unmount_result = Command::new("hdiutil")
@@ -241,18 +295,22 @@ fn install_from_dmg(dmg_path: &std::path::Path) -> Result<std::process::ExitStat
 
     match unmount_result {
         Ok(status) if status.success() => Ok(std::process::ExitStatus::default()),
-        _ => Err("Failed to unmount DMG".to_string()),
+        _ => Err(AppError::new(ErrorKind::Io, "Failed to unmount DMG").with_path(&mount_point)),
     }
 }
 
-fn copy_dir_recursive(src: &std::path::Path, dst: &std::path::Path) -> Result<(), String> {
+pub fn copy_dir_recursive(src: &std::path::Path, dst: &std::path::Path) -> Result<(), AppError> {
+    let src = &long_paths::extended(src);
+    let dst = &long_paths::extended(dst);
     if src.is_dir() {
-        std::fs::create_dir_all(dst).map_err(|e| format!("Failed to create directory: {}", e))?;
+        std::fs::create_dir_all(dst)
+            .map_err(|e| AppError::io("Failed to create directory", &e).with_path(dst))?;
 
-        for entry in
-            std::fs::read_dir(src).map_err(|e| format!("Failed to read directory: {}", e))?
+        for entry in std::fs::read_dir(src)
+            .map_err(|e| AppError::io("Failed to read directory", &e).with_path(src))?
         {
-            let entry = entry.map_err(|e| format!("Failed to read entry: {}", e))?;
+            let entry =
+                entry.map_err(|e| AppError::io("Failed to read entry", &e).with_path(src))?;
             let src_path = entry.path();
             let dst_path = dst.join(entry.file_name());
 
@@ -260,69 +318,157 @@ fn copy_dir_recursive(src: &std::path::Path, dst: &std::path::Path) -> Result<()
                 copy_dir_recursive(&src_path, &dst_path)?;
             } else {
                 std::fs::copy(&src_path, &dst_path)
-                    .map_err(|e| format!("Failed to copy file: {}", e))?;
+                    .map_err(|e| AppError::io("Failed to copy file", &e).with_path(&src_path))?;
             }
         }
     } else {
-        std::fs::copy(src, dst).map_err(|e| format!("Failed to copy file: {}", e))?;
+        std::fs::copy(src, dst)
+            .map_err(|e| AppError::io("Failed to copy file", &e).with_path(src))?;
     }
 
     Ok(())
 }
 
-#[tauri::command]
-pub fn select_folder(dialog_title: Option<String>, initial_path: Option<String>) -> Option<String> {
-    let mut dialog = FileDialog::new();
+/// The "FM Skin Builder" folder inside the user's documents directory, or
+/// where the library was moved to (`skinsDir`, e.g. out of a synced Documents)
+pub fn default_skins_dir(app_handle: &AppHandle) -> Result<PathBuf, AppError> {
+    if let Some(skins_dir) =
+        settings::get_setting::<String>(app_handle, "skinsDir").filter(|dir| !dir.trim().is_empty())
+    {
+        return Ok(PathBuf::from(skins_dir.trim()));
+    }
+    let document_dir = app_handle.path().document_dir().map_err(|e| {
+        AppError::not_found(format!("Failed to get documents directory: {}", e))
+            .with_hint("Set a skins folder in Settings.")
+    })?;
+
+    Ok(document_dir.join("FM Skin Builder"))
+}
+
+/// A folder skins are listed from
+#[derive(Serialize, Clone)]
+pub struct SkinRoot {
+    pub path: String,
+    pub label: String,
+    /// The "FM Skin Builder" folder in Documents
+    pub is_default: bool,
+    pub exists: bool,
+}
+
+/// A `skinRoots` settings entry: a bare path, or a path with a label
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SkinRootSetting {
+    Path(String),
+    Labeled {
+        path: String,
+        #[serde(default)]
+        label: Option<String>,
+    },
+}
 
-    if let Some(title) = dialog_title {
-        dialog = dialog.set_title(&title);
+/// The default skins folder followed by the roots added in settings (`skinRoots`)
+pub fn skin_roots(app_handle: &AppHandle) -> Vec<SkinRoot> {
+    let mut roots = Vec::new();
+    if let Ok(default_dir) = default_skins_dir(app_handle) {
+        roots.push(SkinRoot {
+            path: default_dir.to_string_lossy().to_string(),
+            label: t(app_handle, "skins.default_root", &[]),
+            is_default: true,
+            exists: default_dir.is_dir(),
+        });
     }
 
-    if let Some(path) = initial_path {
-        if !path.trim().is_empty() {
-            dialog = dialog.set_directory(path);
+    let configured: Vec<SkinRootSetting> =
+        settings::get_setting(app_handle, "skinRoots").unwrap_or_default();
+    for setting in configured {
+        let (path, label) = match setting {
+            SkinRootSetting::Path(path) => (path, None),
+            SkinRootSetting::Labeled { path, label } => (path, label),
+        };
+        let path = path.trim().to_string();
+        if path.is_empty() || roots.iter().any(|root| root.path == path) {
+            continue;
         }
+        let dir = PathBuf::from(&path);
+        let label = label
+            .map(|label| label.trim().to_string())
+            .filter(|label| !label.is_empty())
+            .or_else(|| {
+                dir.file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+            })
+            .unwrap_or_else(|| path.clone());
+        roots.push(SkinRoot {
+            exists: dir.is_dir(),
+            path,
+            label,
+            is_default: false,
+        });
     }
-
-    dialog
-        .pick_folder()
-        .map(|folder| folder.to_string_lossy().to_string())
+    roots
 }
 
 #[tauri::command]
-pub fn get_default_skins_dir(app_handle: AppHandle) -> Result<String, String> {
-    let document_dir = app_handle
-        .path()
-        .document_dir()
-        .map_err(|e| format!("Failed to get documents directory: {}", e))?;
-
-    let skins_dir = document_dir.join("FM Skin Builder");
+#[tracing::instrument(skip(app_handle), err)]
+pub fn get_default_skins_dir(app_handle: AppHandle) -> Result<String, AppError> {
+    let skins_dir = default_skins_dir(&app_handle)?;
     Ok(skins_dir.to_string_lossy().to_string())
 }
 
+/// Every skins root with its label, the default one first
 #[tauri::command]
-pub fn ensure_skins_dir(app_handle: AppHandle) -> Result<String, String> {
-    let document_dir = app_handle
-        .path()
-        .document_dir()
-        .map_err(|e| format!("Failed to get documents directory: {}", e))?;
-
-    let skins_dir = document_dir.join("FM Skin Builder");
+#[tracing::instrument(skip(app_handle))]
+pub fn list_skin_roots(app_handle: AppHandle) -> Vec<SkinRoot> {
+    skin_roots(&app_handle)
+}
 
-    if !skins_dir.exists() {
-        std::fs::create_dir_all(&skins_dir)
-            .map_err(|e| format!("Failed to create skins directory: {}", e))?;
-    }
+/// Create a skins root if it doesn't exist yet: the default one, or `root`
+/// when it is one of the roots added in settings
+#[tauri::command]
+#[tracing::instrument(skip(app_handle, state), err)]
+pub async fn ensure_skins_dir(
+    app_handle: AppHandle,
+    state: State<'_, FsTaskState>,
+    root: Option<String>,
+) -> Result<String, AppError> {
+    // Checking the roots touches every configured folder, which may be on a slow drive
+    state
+        .run("Creating the skins folder", move |cancel| {
+            let skins_dir = match root.filter(|root| !root.trim().is_empty()) {
+                Some(root) => {
+                    let root = root.trim();
+                    skin_roots(&app_handle)
+                        .into_iter()
+                        .find(|configured| configured.path == root)
+                        .map(|configured| PathBuf::from(configured.path))
+                        .ok_or_else(|| {
+                            AppError::invalid_input(format!(
+                                "Not a configured skins folder: {}",
+                                root
+                            ))
+                            .with_path(Path::new(root))
+                        })?
+                }
+                None => default_skins_dir(&app_handle)?,
+            };
+            check_cancelled(cancel, "Creating the skins folder")?;
+
+            if !skins_dir.exists() {
+                std::fs::create_dir_all(&skins_dir).map_err(|e| {
+                    AppError::io("Failed to create skins directory", &e).with_path(&skins_dir)
+                })?;
+            }
 
-    Ok(skins_dir.to_string_lossy().to_string())
+            Ok(skins_dir.to_string_lossy().to_string())
+        })
+        .await
 }
 
 #[tauri::command]
-pub fn get_cache_dir(app_handle: AppHandle) -> Result<String, String> {
-    let cache_dir = app_handle
-        .path()
-        .app_cache_dir()
-        .map_err(|e| format!("Failed to get cache directory: {}", e))?;
+#[tracing::instrument(skip(app_handle), err)]
+pub fn get_cache_dir(app_handle: AppHandle) -> Result<String, AppError> {
+    let cache_dir = portable::app_cache_dir(&app_handle)?;
 
     Ok(cache_dir.to_string_lossy().to_string())
 }
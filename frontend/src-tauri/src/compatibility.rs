@@ -0,0 +1,208 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tauri::AppHandle;
+use tokio::time::Duration;
+
+use crate::i18n::t;
+use crate::path_guard;
+use crate::portable;
+use crate::skins::read_skin_info;
+use crate::steam;
+use crate::storage;
+
+const COMPATIBILITY_FEED_URL: &str = "https://release.fmskinbuilder.com/skins/compatibility.json";
+
+/// How long a fetched feed is reused before hitting the network again
+const FEED_CACHE_TTL_SECS: u64 = 6 * 60 * 60;
+
+/// How often the background task looks for a stale feed
+const REFRESH_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Game builds one version of a skin is known to work or break on
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CompatibilityEntry {
+    /// Skin id from its config, or its name
+    pub id: String,
+    /// Skin version the entry covers; any version when absent
+    #[serde(default)]
+    pub version: Option<String>,
+    /// Game versions or Steam build ids
+    #[serde(default)]
+    pub supported: Vec<String>,
+    #[serde(default)]
+    pub broken: Vec<String>,
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct CompatibilityFeed {
+    #[serde(default)]
+    pub skins: Vec<CompatibilityEntry>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CachedFeed {
+    fetched_at: u64,
+    feed: CompatibilityFeed,
+}
+
+#[derive(Serialize)]
+pub struct CompatibilityReport {
+    pub skin_id: String,
+    pub skin_version: Option<String>,
+    pub game_version: String,
+    pub status: String, // "supported", "broken", "unknown"
+    pub note: Option<String>,
+    /// Game builds the skin is known to work on, for suggesting a version to use
+    pub supported: Vec<String>,
+    /// Shown before building when the skin is known to be broken
+    pub warning: Option<String>,
+    pub fetched_at: Option<u64>,
+}
+
+async fn fetch_feed() -> Result<CompatibilityFeed, String> {
+    let response = reqwest::get(COMPATIBILITY_FEED_URL)
+        .await
+        .map_err(|e| format!("Failed to fetch skin compatibility feed: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!(
+            "Skin compatibility request failed with status: {}",
+            response.status()
+        ));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse skin compatibility feed: {}", e))
+}
+
+/// Load the compatibility feed, using the on-disk cache while it is fresh and
+/// falling back to a stale cache when offline
+async fn load_feed(app_handle: &AppHandle) -> Result<Option<CachedFeed>, String> {
+    let cache_path = portable::app_cache_dir(app_handle)?
+        .join("compatibility")
+        .join("feed.json");
+
+    let cached: Option<CachedFeed> = storage::load_json(&cache_path);
+    let fresh = cached.as_ref().is_some_and(|cached| {
+        storage::unix_now().saturating_sub(cached.fetched_at) < FEED_CACHE_TTL_SECS
+    });
+    if fresh {
+        return Ok(cached);
+    }
+
+    let feed = match fetch_feed().await {
+        Ok(feed) => feed,
+        Err(e) => {
+            println!("[WARNING] {}", e);
+            return Ok(cached);
+        }
+    };
+
+    let fetched = CachedFeed {
+        fetched_at: storage::unix_now(),
+        feed,
+    };
+    if let Some(parent) = cache_path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Err(e) = storage::save_json(&cache_path, &fetched) {
+        println!("[WARNING] Failed to cache skin compatibility feed: {}", e);
+    }
+
+    Ok(Some(fetched))
+}
+
+/// The entry for a skin: one for its exact version first, then one for any version
+fn find_entry<'a>(
+    feed: &'a CompatibilityFeed,
+    skin_id: &str,
+    skin_version: Option<&str>,
+) -> Option<&'a CompatibilityEntry> {
+    let entries: Vec<&CompatibilityEntry> = feed
+        .skins
+        .iter()
+        .filter(|entry| entry.id.eq_ignore_ascii_case(skin_id))
+        .collect();
+    entries
+        .iter()
+        .find(|entry| entry.version.is_some() && entry.version.as_deref() == skin_version)
+        .or_else(|| entries.iter().find(|entry| entry.version.is_none()))
+        .copied()
+}
+
+/// Keep the cached feed fresh in the background for as long as the app runs
+pub fn start_refresh(app_handle: &AppHandle) {
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(REFRESH_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(e) = load_feed(&app_handle).await {
+                println!("[WARNING] Failed to refresh skin compatibility feed: {}", e);
+            }
+        }
+    });
+}
+
+/// Look up a skin in the compatibility feed for a game version. Without
+/// `game_version`, the Steam build id of `bundles_path` is used.
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub async fn check_skin_compatibility(
+    app_handle: AppHandle,
+    skin: String,
+    game_version: Option<String>,
+    bundles_path: Option<String>,
+) -> Result<CompatibilityReport, String> {
+    let info = read_skin_info(&path_guard::existing_dir(&skin, "Skin folder")?)?;
+    let game_version = game_version
+        .map(|version| version.trim().to_string())
+        .filter(|version| !version.is_empty())
+        .or_else(|| {
+            bundles_path
+                .as_deref()
+                .and_then(|path| steam::build_id(Path::new(path.trim())))
+        })
+        .ok_or("No game version given and no Steam build found for the bundles folder")?;
+
+    let skin_id = info.id.clone().unwrap_or_else(|| info.name.clone());
+    let cached = load_feed(&app_handle).await?;
+    let entry = cached
+        .as_ref()
+        .and_then(|cached| find_entry(&cached.feed, &skin_id, info.version.as_deref()));
+
+    let status = match entry {
+        Some(entry) if entry.broken.contains(&game_version) => "broken",
+        Some(entry) if entry.supported.contains(&game_version) => "supported",
+        _ => "unknown",
+    };
+    let warning = (status == "broken").then(|| {
+        let mut warning = t(
+            &app_handle,
+            "compatibility.broken",
+            &[("skin", &info.name), ("version", &game_version)],
+        );
+        if let Some(note) = entry.and_then(|entry| entry.note.as_ref()) {
+            warning.push(' ');
+            warning.push_str(note);
+        }
+        warning
+    });
+
+    Ok(CompatibilityReport {
+        skin_id,
+        skin_version: info.version,
+        game_version,
+        status: status.to_string(),
+        note: entry.and_then(|entry| entry.note.clone()),
+        supported: entry
+            .map(|entry| entry.supported.clone())
+            .unwrap_or_default(),
+        warning,
+        fetched_at: cached.as_ref().map(|cached| cached.fetched_at),
+    })
+}
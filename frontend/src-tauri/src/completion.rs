@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+use crate::events::CompletionEvent;
+use crate::storage;
+
+const LAST_COMPLETION_FILE: &str = "last_completion.json";
+
+/// How the most recent build ended, kept so the UI can show it again after
+/// the webview was unloaded (window closed to the tray) or reloaded
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LastCompletion {
+    pub completion: CompletionEvent,
+    pub skin_path: String,
+    pub finished_at: u64,
+    /// The window was hidden or minimized when the build finished
+    pub in_background: bool,
+}
+
+fn window_in_background(app_handle: &AppHandle) -> bool {
+    app_handle
+        .get_webview_window("main")
+        .map(|window| {
+            !window.is_visible().unwrap_or(false) || window.is_minimized().unwrap_or(false)
+        })
+        .unwrap_or(true)
+}
+
+pub fn record_completion(app_handle: &AppHandle, skin_path: &str, completion: &CompletionEvent) {
+    let result = storage::data_file(app_handle, LAST_COMPLETION_FILE).and_then(|path| {
+        storage::save_json(
+            &path,
+            &LastCompletion {
+                completion: completion.clone(),
+                skin_path: skin_path.trim().to_string(),
+                finished_at: storage::unix_now(),
+                in_background: window_in_background(app_handle),
+            },
+        )
+    });
+    if let Err(e) = result {
+        eprintln!("[RUST] Failed to record build completion: {}", e);
+    }
+}
+
+/// The completion of the most recent build, if any
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub fn get_last_completion(app_handle: AppHandle) -> Result<Option<LastCompletion>, String> {
+    let path = storage::data_file(&app_handle, LAST_COMPLETION_FILE)?;
+    let Ok(content) = std::fs::read_to_string(&path) else {
+        return Ok(None);
+    };
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("Failed to parse last build completion: {}", e))
+}
@@ -0,0 +1,139 @@
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use crate::backups::list_bundle_files;
+use crate::diff::skin_file_map;
+use crate::skins::SKIN_CONFIG_FILE;
+
+/// Source files that describe a skin rather than override game assets
+const NON_ASSET_FILES: &[&str] = &[SKIN_CONFIG_FILE, "README.md", "README.txt", "LICENSE"];
+
+#[derive(Serialize)]
+pub struct SkinConflict {
+    pub kind: String, // "bundle", "asset", "variable"
+    pub target: String,
+    pub skins: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct SkinConflictReport {
+    pub skins: Vec<String>,
+    pub has_conflicts: bool,
+    pub conflicts: Vec<SkinConflict>,
+}
+
+/// Bundles a skin writes: its build output, falling back to the config's output bundle
+fn bundle_targets(skin_dir: &Path) -> BTreeSet<String> {
+    let built: BTreeSet<String> = list_bundle_files(&skin_dir.join("packages"))
+        .into_keys()
+        .collect();
+    if !built.is_empty() {
+        return built;
+    }
+
+    std::fs::read_to_string(skin_dir.join(SKIN_CONFIG_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .and_then(|config| {
+            config
+                .get("output_bundle")
+                .or_else(|| config.get("target_bundle"))
+                .and_then(|bundle| bundle.as_str())
+                .map(|bundle| bundle.to_string())
+        })
+        .into_iter()
+        .collect()
+}
+
+/// CSS custom properties (`--name: value;`) declared in a stylesheet
+fn declared_variables(content: &str) -> BTreeSet<String> {
+    content
+        .split(['{', ';', '}'])
+        .filter_map(|declaration| {
+            let (name, _) = declaration.split_once(':')?;
+            let name = name.trim();
+            (name.starts_with("--") && name.len() > 2).then(|| name.to_string())
+        })
+        .collect()
+}
+
+/// Every bundle, asset path, and CSS variable a skin overrides
+fn skin_targets(skin_dir: &Path) -> Vec<(&'static str, String)> {
+    let mut targets: Vec<(&'static str, String)> = bundle_targets(skin_dir)
+        .into_iter()
+        .map(|bundle| ("bundle", bundle))
+        .collect();
+
+    for (relative, (path, _)) in skin_file_map(skin_dir) {
+        if NON_ASSET_FILES.contains(&relative.as_str()) {
+            continue;
+        }
+
+        let is_stylesheet = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("uss") || ext.eq_ignore_ascii_case("css"));
+
+        if is_stylesheet {
+            if let Ok(content) = std::fs::read_to_string(&path) {
+                targets.extend(
+                    declared_variables(&content)
+                        .into_iter()
+                        .map(|variable| ("variable", variable)),
+                );
+            }
+        } else {
+            targets.push(("asset", relative.to_lowercase()));
+        }
+    }
+
+    targets
+}
+
+fn find_conflicts(skin_paths: &[String]) -> Result<SkinConflictReport, String> {
+    let mut owners: BTreeMap<(&'static str, String), BTreeSet<String>> = BTreeMap::new();
+
+    for skin_path in skin_paths {
+        let skin_dir = Path::new(skin_path);
+        if !skin_dir.is_dir() {
+            return Err(format!("Skin folder not found: {}", skin_path));
+        }
+        for target in skin_targets(skin_dir) {
+            owners.entry(target).or_default().insert(skin_path.clone());
+        }
+    }
+
+    let conflicts: Vec<SkinConflict> = owners
+        .into_iter()
+        .filter(|(_, skins)| skins.len() > 1)
+        .map(|((kind, target), skins)| SkinConflict {
+            kind: kind.to_string(),
+            target,
+            skins: skins.into_iter().collect(),
+        })
+        .collect();
+
+    Ok(SkinConflictReport {
+        skins: skin_paths.to_vec(),
+        has_conflicts: !conflicts.is_empty(),
+        conflicts,
+    })
+}
+
+/// Find bundles, assets, and CSS variables that more than one of the given skins overrides
+#[tauri::command]
+#[tracing::instrument(err)]
+pub async fn check_skin_conflicts(skin_paths: Vec<String>) -> Result<SkinConflictReport, String> {
+    let skin_paths: Vec<String> = skin_paths
+        .into_iter()
+        .map(|path| path.trim().to_string())
+        .filter(|path| !path.is_empty())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+
+    tokio::task::spawn_blocking(move || find_conflicts(&skin_paths))
+        .await
+        .map_err(|e| format!("Conflict check failed: {}", e))?
+}
@@ -0,0 +1,442 @@
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tauri::AppHandle;
+
+use crate::events::LogEvent;
+use crate::portable;
+use crate::run_logs;
+use crate::storage;
+
+const CRASH_REPORT_URL: &str = "https://release.fmskinbuilder.com/crash-reports";
+
+/// Log lines attached to each crash report
+const RECENT_LOG_LINES: usize = 100;
+
+/// Times the OS crash artifacts are looked for, and the wait in between
+const ARTIFACT_ATTEMPTS: u32 = 3;
+const ARTIFACT_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CrashReport {
+    pub id: String,
+    pub kind: String, // "panic", "backend"
+    pub created_at: u64,
+    pub app_version: String,
+    pub os: String,
+    pub arch: String,
+    pub message: String,
+    pub backtrace: Option<String>,
+    pub exit_code: Option<i32>,
+    pub run_id: Option<String>,
+    pub recent_logs: Vec<String>,
+    /// Core files, minidumps or crash logs the OS wrote for the crashed process
+    #[serde(default)]
+    pub artifacts: Vec<String>,
+}
+
+impl CrashReport {
+    fn new(kind: &str, message: String) -> Self {
+        Self {
+            id: String::new(),
+            kind: kind.to_string(),
+            created_at: storage::unix_now(),
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            message,
+            backtrace: None,
+            exit_code: None,
+            run_id: None,
+            recent_logs: Vec::new(),
+            artifacts: Vec::new(),
+        }
+    }
+}
+
+fn crash_dir(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    Ok(portable::app_data_dir(app_handle)?.join("crash_reports"))
+}
+
+/// Last lines of a run log, or of the newest run log when no run is given
+fn recent_log_lines(logs_dir: &Path, run_id: Option<&str>) -> Vec<String> {
+    let path = match run_id {
+        Some(run_id) => Some(logs_dir.join(format!("{}.jsonl", run_id))),
+        None => std::fs::read_dir(logs_dir).ok().and_then(|entries| {
+            entries
+                .flatten()
+                .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "jsonl"))
+                .max_by_key(|entry| entry.metadata().and_then(|m| m.modified()).ok())
+                .map(|entry| entry.path())
+        }),
+    };
+
+    let Some(file) = path.and_then(|path| std::fs::File::open(path).ok()) else {
+        return Vec::new();
+    };
+
+    let lines: Vec<String> = BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .map(|line| match serde_json::from_str::<LogEvent>(&line) {
+            Ok(event) => format!(
+                "{} [{}] [{}] {}",
+                event.timestamp,
+                event.level.to_uppercase(),
+                event.source,
+                event.message
+            ),
+            Err(_) => line,
+        })
+        .collect();
+    lines[lines.len().saturating_sub(RECENT_LOG_LINES)..].to_vec()
+}
+
+fn save_report(dir: &Path, mut report: CrashReport) -> Result<CrashReport, String> {
+    std::fs::create_dir_all(dir)
+        .map_err(|e| format!("Failed to create crash report directory: {}", e))?;
+
+    let mut id = format!("{}-{}", report.kind, report.created_at);
+    while dir.join(format!("{}.json", id)).exists() {
+        id.push('_');
+    }
+    report.id = id;
+    storage::save_json(&dir.join(format!("{}.json", report.id)), &report)?;
+    Ok(report)
+}
+
+/// Write a crash report for every Rust panic, then run the default hook
+pub fn install_panic_hook(app_handle: &AppHandle) {
+    let (Ok(dir), Ok(logs_dir)) = (crash_dir(app_handle), run_logs::logs_dir(app_handle)) else {
+        println!("[WARNING] Crash reporting disabled: app data directory unavailable");
+        return;
+    };
+
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let mut report = CrashReport::new("panic", info.to_string());
+        report.backtrace = Some(std::backtrace::Backtrace::force_capture().to_string());
+        report.recent_logs = recent_log_lines(&logs_dir, None);
+
+        if let Err(e) = save_report(&dir, report) {
+            eprintln!("[RUST] Failed to write crash report: {}", e);
+        }
+        default_hook(info);
+    }));
+}
+
+/// True when the backend died abnormally (killed by a signal, or a Windows
+/// NTSTATUS failure such as an access violation) rather than exiting with an error
+pub fn is_hard_crash(status: &std::process::ExitStatus) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        status.signal().is_some()
+    }
+    #[cfg(windows)]
+    {
+        status
+            .code()
+            .is_some_and(|code| (code as u32) & 0xC000_0000 == 0xC000_0000)
+    }
+}
+
+/// The crashed process, for finding what the OS wrote about it
+pub struct CrashedProcess<'a> {
+    pub pid: u32,
+    /// Executable name without extension, e.g. "fm_skin_builder"
+    pub program: &'a str,
+    pub started: SystemTime,
+    /// Its working directory, where relative core patterns put the core file
+    pub work_dir: &'a Path,
+}
+
+/// Files and folders directly inside a directory
+fn dir_entries(dir: &Path) -> Vec<PathBuf> {
+    std::fs::read_dir(dir)
+        .map(|entries| entries.flatten().map(|entry| entry.path()).collect())
+        .unwrap_or_default()
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+fn modified_since(path: &Path, started: SystemTime) -> bool {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .is_ok_and(|modified| modified >= started)
+}
+
+/// Regex for core file names produced by a `core_pattern` file name template
+#[cfg(target_os = "linux")]
+fn core_name_matcher(
+    template: &str,
+    process: &CrashedProcess,
+    append_pid: bool,
+) -> Option<regex::Regex> {
+    let mut pattern = String::from("^");
+    let mut chars = template.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            pattern.push_str(&regex::escape(&c.to_string()));
+            continue;
+        }
+        match chars.next() {
+            Some('p') => pattern.push_str(&process.pid.to_string()),
+            // The kernel truncates the command name to 15 characters
+            Some('e') => pattern.push_str(&regex::escape(
+                &process.program.chars().take(15).collect::<String>(),
+            )),
+            Some('%') => pattern.push('%'),
+            _ => pattern.push_str(".*"),
+        }
+    }
+    if append_pid {
+        pattern.push_str(&format!(r"(\.{})?", process.pid));
+    }
+    pattern.push('$');
+    regex::Regex::new(&pattern).ok()
+}
+
+/// Core dumps per `/proc/sys/kernel/core_pattern`: a file path template, or a
+/// pipe to systemd-coredump or apport
+#[cfg(target_os = "linux")]
+fn find_os_artifacts(process: &CrashedProcess) -> Vec<PathBuf> {
+    let core_pattern = std::fs::read_to_string("/proc/sys/kernel/core_pattern").unwrap_or_default();
+    let core_pattern = core_pattern.trim();
+
+    if let Some(handler) = core_pattern.strip_prefix('|') {
+        if handler.contains("systemd-coredump") {
+            // core.<comm>.<uid>.<boot id>.<pid>.<time>[.zst]
+            let pid_marker = format!(".{}.", process.pid);
+            return dir_entries(Path::new("/var/lib/systemd/coredump"))
+                .into_iter()
+                .filter(|path| file_name(path).contains(&pid_marker))
+                .collect();
+        }
+        // apport names reports after the executable path: _opt_app_fm_skin_builder.1000.crash
+        return dir_entries(Path::new("/var/crash"))
+            .into_iter()
+            .filter(|path| {
+                let name = file_name(path);
+                name.ends_with(".crash") && name.contains(process.program)
+            })
+            .filter(|path| modified_since(path, process.started))
+            .collect();
+    }
+
+    let template = Path::new(if core_pattern.is_empty() {
+        "core"
+    } else {
+        core_pattern
+    });
+    let dir = match template.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        Some(dir) if dir.is_absolute() => dir.to_path_buf(),
+        Some(dir) => process.work_dir.join(dir),
+        None => process.work_dir.to_path_buf(),
+    };
+    let append_pid = !core_pattern.contains("%p")
+        && std::fs::read_to_string("/proc/sys/kernel/core_uses_pid")
+            .is_ok_and(|value| value.trim() == "1");
+    let Some(matcher) = core_name_matcher(&file_name(template), process, append_pid) else {
+        return Vec::new();
+    };
+
+    dir_entries(&dir)
+        .into_iter()
+        .filter(|path| matcher.is_match(&file_name(path)))
+        .filter(|path| modified_since(path, process.started))
+        .collect()
+}
+
+/// Crash logs in DiagnosticReports that name the process id
+#[cfg(target_os = "macos")]
+fn find_os_artifacts(process: &CrashedProcess) -> Vec<PathBuf> {
+    let Some(home) = std::env::var_os("HOME") else {
+        return Vec::new();
+    };
+    // .ips reports carry `"pid" : 123`, older .crash logs `Process: name [123]`
+    let pid_markers = [
+        format!("\"pid\" : {}", process.pid),
+        format!("[{}]", process.pid),
+    ];
+    dir_entries(&PathBuf::from(home).join("Library/Logs/DiagnosticReports"))
+        .into_iter()
+        .filter(|path| file_name(path).starts_with(process.program))
+        .filter(|path| modified_since(path, process.started))
+        .filter(|path| {
+            std::fs::read_to_string(path)
+                .is_ok_and(|content| pid_markers.iter().any(|marker| content.contains(marker)))
+        })
+        .collect()
+}
+
+/// Windows Error Reporting: a LocalDumps minidump and the AppCrash report folders
+#[cfg(windows)]
+fn find_os_artifacts(process: &CrashedProcess) -> Vec<PathBuf> {
+    let Some(local_app_data) = std::env::var_os("LOCALAPPDATA") else {
+        return Vec::new();
+    };
+    let local_app_data = PathBuf::from(local_app_data);
+    let exe = format!("{}.exe", process.program).to_lowercase();
+
+    // LocalDumps names dumps <exe>.<pid>.dmp
+    let dump_name = format!("{}.{}.dmp", exe, process.pid);
+    let mut artifacts: Vec<PathBuf> = dir_entries(&local_app_data.join("CrashDumps"))
+        .into_iter()
+        .filter(|path| file_name(path).to_lowercase() == dump_name)
+        .collect();
+
+    let report_prefix = format!("appcrash_{}", exe);
+    for queue in ["ReportArchive", "ReportQueue"] {
+        let dir = local_app_data
+            .join("Microsoft")
+            .join("Windows")
+            .join("WER")
+            .join(queue);
+        artifacts.extend(
+            dir_entries(&dir)
+                .into_iter()
+                .filter(|path| file_name(path).to_lowercase().starts_with(&report_prefix))
+                .filter(|path| modified_since(path, process.started)),
+        );
+    }
+    artifacts
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+fn find_os_artifacts(_process: &CrashedProcess) -> Vec<PathBuf> {
+    Vec::new()
+}
+
+/// Collect what the OS wrote about a crashed backend. Dumps can appear a moment
+/// after the process is gone, so this looks a few times. Files in the task's
+/// working directory are moved into the crash reports folder, since that
+/// directory is removed when the task ends.
+pub async fn collect_crash_artifacts(
+    app_handle: &AppHandle,
+    process: &CrashedProcess<'_>,
+    run_id: &str,
+) -> Vec<String> {
+    let mut artifacts = Vec::new();
+    for attempt in 0..ARTIFACT_ATTEMPTS {
+        if attempt > 0 {
+            tokio::time::sleep(ARTIFACT_RETRY_DELAY).await;
+        }
+        artifacts = find_os_artifacts(process);
+        if !artifacts.is_empty() {
+            break;
+        }
+    }
+
+    let Ok(keep_dir) = crash_dir(app_handle).map(|dir| dir.join("artifacts")) else {
+        return Vec::new();
+    };
+    artifacts
+        .into_iter()
+        .map(|path| {
+            if !path.starts_with(process.work_dir) {
+                return path;
+            }
+            let kept = keep_dir.join(format!("{}-{}", run_id, file_name(&path)));
+            match std::fs::create_dir_all(&keep_dir).and_then(|_| std::fs::rename(&path, &kept)) {
+                Ok(()) => kept,
+                Err(e) => {
+                    println!(
+                        "[WARNING] Failed to keep crash artifact {}: {}",
+                        path.display(),
+                        e
+                    );
+                    path
+                }
+            }
+        })
+        .map(|path| path.to_string_lossy().to_string())
+        .collect()
+}
+
+/// Record a backend crash in the same pipeline as Rust panics
+pub fn record_backend_crash(
+    app_handle: &AppHandle,
+    status: &std::process::ExitStatus,
+    run_id: &str,
+    artifacts: Vec<String>,
+) -> Result<CrashReport, String> {
+    let mut report = CrashReport::new("backend", format!("Backend process crashed: {}", status));
+    report.exit_code = status.code();
+    report.run_id = Some(run_id.to_string());
+    report.artifacts = artifacts;
+    report.recent_logs = recent_log_lines(&run_logs::logs_dir(app_handle)?, Some(run_id));
+    save_report(&crash_dir(app_handle)?, report)
+}
+
+fn report_path(app_handle: &AppHandle, id: &str) -> Result<PathBuf, String> {
+    if id.is_empty() || id.contains(['/', '\\']) || id.contains("..") {
+        return Err(format!("Invalid crash report id: {}", id));
+    }
+    let path = crash_dir(app_handle)?.join(format!("{}.json", id));
+    if !path.is_file() {
+        return Err(format!("Crash report not found: {}", id));
+    }
+    Ok(path)
+}
+
+/// Crash reports that haven't been submitted or dismissed, newest first
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub fn get_pending_crash_reports(app_handle: AppHandle) -> Result<Vec<CrashReport>, String> {
+    let mut reports: Vec<CrashReport> = std::fs::read_dir(crash_dir(&app_handle)?)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+                .filter_map(|path| {
+                    let content = std::fs::read_to_string(path).ok()?;
+                    serde_json::from_str(&content).ok()
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    reports.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(reports)
+}
+
+/// Send a crash report the user has agreed to share, then remove it
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub async fn submit_crash_report(app_handle: AppHandle, id: String) -> Result<(), String> {
+    let path = report_path(&app_handle, id.trim())?;
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read crash report: {}", e))?;
+    let report: CrashReport = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse crash report: {}", e))?;
+
+    let response = reqwest::Client::new()
+        .post(CRASH_REPORT_URL)
+        .json(&report)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to submit crash report: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!(
+            "Crash report submission failed with status: {}",
+            response.status()
+        ));
+    }
+
+    std::fs::remove_file(&path).map_err(|e| format!("Failed to remove crash report: {}", e))
+}
+
+/// Discard a crash report without sending it
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub fn dismiss_crash_report(app_handle: AppHandle, id: String) -> Result<(), String> {
+    let path = report_path(&app_handle, id.trim())?;
+    std::fs::remove_file(&path).map_err(|e| format!("Failed to remove crash report: {}", e))
+}
@@ -0,0 +1,252 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, State, Url};
+use tauri_plugin_deep_link::DeepLinkExt;
+use tokio::sync::oneshot;
+use tokio::time::{timeout, Duration};
+
+use crate::events::{DeepLinkEvent, LinkImportProposedEvent};
+use crate::import::ON_EXISTING_ASK;
+use crate::repository;
+use crate::skins::SKIN_CONFIG_FILE;
+use crate::validation::validate_skin_dir;
+
+/// How long a link import waits for the user to allow it before giving up
+const APPROVAL_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Link imports waiting for the user's answer, keyed by request id
+#[derive(Default)]
+pub struct DeepLinkState {
+    pending: Mutex<HashMap<String, oneshot::Sender<bool>>>,
+}
+
+fn query_param(url: &Url, key: &str) -> Option<String> {
+    url.query_pairs()
+        .find(|(name, _)| name == key)
+        .map(|(_, value)| value.into_owned())
+        .filter(|value| !value.trim().is_empty())
+}
+
+/// `fmskinbuilder://import?...` parses with the action as the host; `fmskinbuilder:import?...` as the path
+fn link_action(url: &Url) -> String {
+    url.host_str()
+        .map(str::to_string)
+        .unwrap_or_else(|| url.path().trim_matches('/').to_string())
+        .to_lowercase()
+}
+
+fn is_sha256(value: &str) -> bool {
+    value.len() == 64 && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Emit `link_import_proposed` and wait for `respond_link_import`
+async fn request_approval(
+    app_handle: &AppHandle,
+    mut event: LinkImportProposedEvent,
+) -> Result<bool, String> {
+    let state = app_handle.state::<DeepLinkState>();
+    let request_id = uuid::Uuid::new_v4().to_string();
+    let (sender, receiver) = oneshot::channel();
+    state
+        .pending
+        .lock()
+        .map_err(|e| format!("Failed to lock link import requests: {}", e))?
+        .insert(request_id.clone(), sender);
+
+    event.request_id = request_id.clone();
+    let _ = app_handle.emit("link_import_proposed", event);
+
+    let answer = matches!(timeout(APPROVAL_TIMEOUT, receiver).await, Ok(Ok(true)));
+    if let Ok(mut pending) = state.pending.lock() {
+        pending.remove(&request_id);
+    }
+    Ok(answer)
+}
+
+/// `import?url=...`: once the user allows it, download over https and import.
+/// The checksum comes from the repository index when it lists the URL,
+/// otherwise from `sha256`. A checksum from the link itself only catches a
+/// corrupted download; it says nothing about whether the archive is safe.
+async fn import_from_link(
+    app_handle: &AppHandle,
+    url: &Url,
+) -> Result<(String, Option<String>), String> {
+    let download_url = query_param(url, "url").ok_or("Import link is missing the url parameter")?;
+    let parsed = Url::parse(&download_url).map_err(|e| format!("Invalid download URL: {}", e))?;
+    if parsed.scheme() != "https" {
+        return Err("Only https downloads can be imported from links".to_string());
+    }
+
+    // An unreachable repository index just means the link must carry its own checksum
+    let listed = repository::find_by_download_url(app_handle, &download_url)
+        .await
+        .ok()
+        .flatten();
+
+    let in_repository = listed.is_some();
+    let (name, sha256, size) = match listed {
+        Some(skin) => (skin.name, skin.sha256, skin.size),
+        None => {
+            let sha256 = query_param(url, "sha256")
+                .filter(|value| is_sha256(value))
+                .ok_or("Links to skins outside the repository must include a sha256 checksum")?;
+            let name = query_param(url, "name").unwrap_or_else(|| {
+                parsed
+                    .path_segments()
+                    .and_then(|mut segments| segments.next_back())
+                    .unwrap_or("skin")
+                    .to_string()
+            });
+            (name, sha256, None)
+        }
+    };
+
+    let message = if in_repository {
+        format!(
+            "Import the skin \"{}\" from the skin repository?\n\nSource: {}",
+            name, download_url
+        )
+    } else {
+        format!(
+            "A link wants to download and import \"{}\" from outside the skin repository. Only continue if you trust where it came from.\n\nSource: {}",
+            name, download_url
+        )
+    };
+    let approved = request_approval(
+        app_handle,
+        LinkImportProposedEvent {
+            request_id: String::new(),
+            url: download_url.clone(),
+            name: name.clone(),
+            in_repository,
+            message,
+        },
+    )
+    .await?;
+    if !approved {
+        return Err(format!("Import from {} was not allowed", download_url));
+    }
+
+    println!("[DEBUG] Importing skin from link: {}", download_url);
+    let result = repository::download_and_import(
+        app_handle,
+        &name,
+        &download_url,
+        &sha256,
+        size,
+        ON_EXISTING_ASK,
+    )
+    .await?;
+    let message = match result.action.as_str() {
+        "exists" => format!(
+            "Skin \"{}\" is already in the library; it was not imported again",
+            result.name
+        ),
+        "unchanged" => format!("Skin \"{}\" is already in the library", result.name),
+        _ => format!("Imported skin \"{}\"", result.name),
+    };
+    Ok((message, Some(result.path)))
+}
+
+/// `open-skin?path=...`: select an existing skin folder
+fn open_skin_from_link(url: &Url) -> Result<(String, Option<String>), String> {
+    let path = query_param(url, "path").ok_or("Open link is missing the path parameter")?;
+    let skin_dir = PathBuf::from(path.trim());
+    if !skin_dir.join(SKIN_CONFIG_FILE).is_file() {
+        return Err(format!("Not a skin folder: {}", skin_dir.display()));
+    }
+
+    let report = validate_skin_dir(&skin_dir);
+    let message = if report.valid {
+        "Skin folder is ready to build".to_string()
+    } else {
+        format!(
+            "Skin has {} error(s) and {} warning(s)",
+            report.error_count, report.warning_count
+        )
+    };
+    Ok((message, Some(skin_dir.to_string_lossy().to_string())))
+}
+
+async fn resolve_link(app_handle: &AppHandle, url: &Url) -> DeepLinkEvent {
+    let action = link_action(url);
+    let result = match action.as_str() {
+        "import" => import_from_link(app_handle, url).await,
+        "open-skin" => open_skin_from_link(url),
+        _ => Err(format!("Unsupported link action: {}", action)),
+    };
+
+    let (success, message, skin_path) = match result {
+        Ok((message, skin_path)) => (true, message, skin_path),
+        Err(message) => (false, message, None),
+    };
+
+    DeepLinkEvent {
+        url: url.to_string(),
+        action,
+        success,
+        message,
+        skin_path,
+    }
+}
+
+/// Bring the window forward and handle each link, emitting `deep_link_handled`
+pub fn handle_urls(app_handle: &AppHandle, urls: Vec<Url>) {
+    if let Some(window) = app_handle.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        for url in urls {
+            println!("[DEBUG] Handling deep link: {}", url);
+            let event = resolve_link(&app_handle, &url).await;
+            if !event.success {
+                println!("[WARNING] Deep link failed: {}", event.message);
+            }
+            let _ = app_handle.emit("deep_link_handled", event);
+        }
+    });
+}
+
+/// Answer a pending `link_import_proposed` prompt
+#[tauri::command]
+#[tracing::instrument(skip(state), err)]
+pub fn respond_link_import(
+    state: State<'_, DeepLinkState>,
+    request_id: String,
+    approved: bool,
+) -> Result<(), String> {
+    let sender = state
+        .pending
+        .lock()
+        .map_err(|e| format!("Failed to lock link import requests: {}", e))?
+        .remove(&request_id)
+        .ok_or_else(|| format!("No pending link import request: {}", request_id))?;
+    sender
+        .send(approved)
+        .map_err(|_| "Link import request is no longer waiting".to_string())
+}
+
+/// Register the `fmskinbuilder://` scheme and handle links, including one the app was launched with
+pub fn setup_deep_links(app_handle: &AppHandle) {
+    // Installers register the scheme on Windows and Linux; dev builds need it at runtime
+    #[cfg(any(windows, target_os = "linux"))]
+    if let Err(e) = app_handle.deep_link().register_all() {
+        println!("[WARNING] Failed to register deep link scheme: {}", e);
+    }
+
+    let handler = app_handle.clone();
+    app_handle.deep_link().on_open_url(move |event| {
+        handle_urls(&handler, event.urls());
+    });
+
+    match app_handle.deep_link().get_current() {
+        Ok(Some(urls)) => handle_urls(app_handle, urls),
+        Ok(None) => {}
+        Err(e) => println!("[WARNING] Failed to read launch deep link: {}", e),
+    }
+}
@@ -0,0 +1,210 @@
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+use tokio::process::Command;
+
+use crate::backend_update;
+use crate::cloud_sync;
+use crate::commands::default_skins_dir;
+use crate::paths::detect_bundles_path;
+use crate::portable;
+use crate::process;
+use crate::storage;
+
+/// Free space below which builds and backups are likely to fail
+pub const MIN_FREE_SPACE: u64 = 500 * 1024 * 1024;
+const LOW_FREE_SPACE: u64 = 2 * 1024 * 1024 * 1024;
+
+#[derive(Serialize)]
+pub struct DiagnosticCheck {
+    pub id: String,
+    pub status: String, // "pass", "warn", "fail"
+    pub message: String,
+}
+
+#[derive(Serialize)]
+pub struct DiagnosticsReport {
+    pub status: String,
+    pub ran_at: u64,
+    pub checks: Vec<DiagnosticCheck>,
+}
+
+fn check(id: &str, status: &str, message: String) -> DiagnosticCheck {
+    DiagnosticCheck {
+        id: id.to_string(),
+        status: status.to_string(),
+        message,
+    }
+}
+
+/// Create the directory if needed and write then remove a probe file
+fn probe_writable(dir: &Path) -> Result<(), String> {
+    std::fs::create_dir_all(dir).map_err(|e| format!("Cannot create {}: {}", dir.display(), e))?;
+    let probe = dir.join(".fm_skin_builder_write_test");
+    std::fs::write(&probe, b"ok")
+        .map_err(|e| format!("Cannot write to {}: {}", dir.display(), e))?;
+    let _ = std::fs::remove_file(&probe);
+    Ok(())
+}
+
+fn writable_check(id: &str, label: &str, dir: Result<PathBuf, String>) -> DiagnosticCheck {
+    match dir.and_then(|dir| probe_writable(&dir).map(|_| dir)) {
+        Ok(dir) => check(
+            id,
+            "pass",
+            format!("{} is writable: {}", label, dir.display()),
+        ),
+        Err(e) => check(id, "fail", e),
+    }
+}
+
+async fn backend_check(app_handle: &AppHandle) -> DiagnosticCheck {
+    if cfg!(debug_assertions) {
+        let python = process::python_command();
+        let output = Command::new(&python).arg("--version").output().await;
+        return match output {
+            Ok(output) if output.status.success() => {
+                // Older Pythons print the version to stderr
+                let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+                let version = if version.is_empty() {
+                    String::from_utf8_lossy(&output.stderr).trim().to_string()
+                } else {
+                    version
+                };
+                check(
+                    "backend",
+                    "pass",
+                    format!("{} ({})", version, python.display()),
+                )
+            }
+            Ok(output) => check(
+                "backend",
+                "fail",
+                format!("{} exited with {}", python.display(), output.status),
+            ),
+            Err(e) => check(
+                "backend",
+                "fail",
+                format!("Python not available at {}: {}", python.display(), e),
+            ),
+        };
+    }
+
+    match process::backend_binary_path(app_handle) {
+        Ok(binary) => {
+            let (version, updated) = backend_update::current_backend_version(app_handle);
+            check(
+                "backend",
+                "pass",
+                format!(
+                    "Backend {}{} found at {}",
+                    version,
+                    if updated { " (updated)" } else { "" },
+                    binary.display()
+                ),
+            )
+        }
+        Err(e) => check("backend", "fail", e.message),
+    }
+}
+
+fn disk_space_check(app_handle: &AppHandle) -> DiagnosticCheck {
+    let dir = match portable::app_cache_dir(app_handle) {
+        Ok(dir) => dir,
+        Err(e) => return check("disk_space", "fail", e),
+    };
+    // The cache folder may not exist yet; measure the nearest existing ancestor
+    let Some(existing) = dir.ancestors().find(|path| path.exists()) else {
+        return check(
+            "disk_space",
+            "warn",
+            format!("Could not find a volume for {}", dir.display()),
+        );
+    };
+
+    match fs2::available_space(existing) {
+        Ok(free) => {
+            let message = format!("{:.1} GB free", free as f64 / 1024f64.powi(3));
+            let status = if free < MIN_FREE_SPACE {
+                "fail"
+            } else if free < LOW_FREE_SPACE {
+                "warn"
+            } else {
+                "pass"
+            };
+            check("disk_space", status, message)
+        }
+        Err(e) => check(
+            "disk_space",
+            "warn",
+            format!("Could not read free disk space: {}", e),
+        ),
+    }
+}
+
+/// Check everything a build depends on so setup problems surface before the first build
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub async fn run_diagnostics(app_handle: AppHandle) -> Result<DiagnosticsReport, String> {
+    let mut checks = vec![backend_check(&app_handle).await];
+
+    checks.push(writable_check(
+        "cache_dir",
+        "Cache directory",
+        portable::app_cache_dir(&app_handle),
+    ));
+    checks.push(writable_check(
+        "skins_dir",
+        "Skins folder",
+        default_skins_dir(&app_handle).map_err(String::from),
+    ));
+
+    if let Ok(skins_dir) = default_skins_dir(&app_handle) {
+        checks.push(match cloud_sync::cloud_provider(&skins_dir) {
+            Some(provider) => check(
+                "cloud_sync",
+                "warn",
+                format!(
+                    "Skins folder is synced by {}, which can lock or withhold files during builds: {}",
+                    provider,
+                    skins_dir.display()
+                ),
+            ),
+            None => check(
+                "cloud_sync",
+                "pass",
+                "Skins folder is not in a cloud-synced folder".to_string(),
+            ),
+        });
+    }
+
+    checks.push(match detect_bundles_path(&app_handle) {
+        Some(bundles) => check("game", "pass", format!("Game bundles found at {}", bundles)),
+        None => check(
+            "game",
+            "warn",
+            "Football Manager was not detected; select the bundles folder manually".to_string(),
+        ),
+    });
+
+    checks.push(disk_space_check(&app_handle));
+
+    checks.push(match tauri::webview_version() {
+        Ok(version) => check("webview", "pass", format!("WebView {}", version)),
+        Err(e) => check("webview", "fail", format!("WebView not available: {}", e)),
+    });
+
+    let status = if checks.iter().any(|check| check.status == "fail") {
+        "fail"
+    } else if checks.iter().any(|check| check.status == "warn") {
+        "warn"
+    } else {
+        "pass"
+    };
+
+    Ok(DiagnosticsReport {
+        status: status.to_string(),
+        ran_at: storage::unix_now(),
+        checks,
+    })
+}
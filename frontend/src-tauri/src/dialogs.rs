@@ -0,0 +1,140 @@
+use rfd::{AsyncFileDialog, FileHandle};
+use serde::Deserialize;
+use tauri::WebviewWindow;
+
+/// A named group of extensions offered by a file dialog, e.g. "Skin archives"
+#[derive(Deserialize, Debug)]
+pub struct FileFilter {
+    pub name: String,
+    /// Without the dot, e.g. "zip"
+    pub extensions: Vec<String>,
+}
+
+/// Archive formats `import_skin_archive` accepts (.tar.gz files match "gz")
+const ARCHIVE_EXTENSIONS: &[&str] = &["zip", "7z", "rar", "tgz", "gz"];
+
+/// Dialog attached to the calling window, so it stays on top of it and the
+/// window can't be used while the dialog is open
+fn dialog(
+    window: &WebviewWindow,
+    dialog_title: Option<String>,
+    initial_path: Option<String>,
+) -> AsyncFileDialog {
+    let mut dialog = AsyncFileDialog::new().set_parent(window);
+
+    if let Some(title) = dialog_title {
+        dialog = dialog.set_title(title);
+    }
+
+    if let Some(path) = initial_path {
+        if !path.trim().is_empty() {
+            dialog = dialog.set_directory(path.trim());
+        }
+    }
+
+    dialog
+}
+
+fn with_filters(mut dialog: AsyncFileDialog, filters: Option<Vec<FileFilter>>) -> AsyncFileDialog {
+    match filters {
+        Some(filters) => {
+            for filter in filters {
+                dialog = dialog.add_filter(filter.name, &filter.extensions);
+            }
+        }
+        None => dialog = dialog.add_filter("Skin archives", ARCHIVE_EXTENSIONS),
+    }
+    dialog
+}
+
+/// Paths of the picked files or folders
+fn paths(handles: &[FileHandle]) -> Vec<String> {
+    handles
+        .iter()
+        .map(|handle| handle.path().to_string_lossy().to_string())
+        .collect()
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(window))]
+pub async fn select_folder(
+    window: WebviewWindow,
+    dialog_title: Option<String>,
+    initial_path: Option<String>,
+) -> Option<String> {
+    // Awaited, so the command thread isn't blocked while the dialog is open
+    dialog(&window, dialog_title, initial_path)
+        .pick_folder()
+        .await
+        .map(|folder| folder.path().to_string_lossy().to_string())
+}
+
+/// Pick a file; without `filters`, offers the archive formats skins can be imported from
+#[tauri::command]
+#[tracing::instrument(skip(window))]
+pub async fn select_file(
+    window: WebviewWindow,
+    dialog_title: Option<String>,
+    initial_path: Option<String>,
+    filters: Option<Vec<FileFilter>>,
+) -> Option<String> {
+    with_filters(dialog(&window, dialog_title, initial_path), filters)
+        .pick_file()
+        .await
+        .map(|file| file.path().to_string_lossy().to_string())
+}
+
+/// Pick several folders, e.g. skins to import at once; empty when cancelled
+#[tauri::command]
+#[tracing::instrument(skip(window))]
+pub async fn select_folders(
+    window: WebviewWindow,
+    dialog_title: Option<String>,
+    initial_path: Option<String>,
+) -> Vec<String> {
+    dialog(&window, dialog_title, initial_path)
+        .pick_folders()
+        .await
+        .map(|folders| paths(&folders))
+        .unwrap_or_default()
+}
+
+/// Pick several files; without `filters`, offers the archive formats skins can be imported from
+#[tauri::command]
+#[tracing::instrument(skip(window))]
+pub async fn select_files(
+    window: WebviewWindow,
+    dialog_title: Option<String>,
+    initial_path: Option<String>,
+    filters: Option<Vec<FileFilter>>,
+) -> Vec<String> {
+    with_filters(dialog(&window, dialog_title, initial_path), filters)
+        .pick_files()
+        .await
+        .map(|files| paths(&files))
+        .unwrap_or_default()
+}
+
+/// Ask where to write a file, e.g. an exported log or skin package; `None` when cancelled
+#[tauri::command]
+#[tracing::instrument(skip(window))]
+pub async fn select_save_path(
+    window: WebviewWindow,
+    default_name: Option<String>,
+    filters: Option<Vec<FileFilter>>,
+    dialog_title: Option<String>,
+    initial_path: Option<String>,
+) -> Option<String> {
+    let mut dialog = dialog(&window, dialog_title, initial_path);
+    if let Some(name) = default_name.filter(|name| !name.trim().is_empty()) {
+        dialog = dialog.set_file_name(name.trim());
+    }
+    for filter in filters.unwrap_or_default() {
+        dialog = dialog.add_filter(filter.name, &filter.extensions);
+    }
+
+    dialog
+        .save_file()
+        .await
+        .map(|file| file.path().to_string_lossy().to_string())
+}
@@ -0,0 +1,123 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use crate::hashing::sha256_file;
+use crate::long_paths;
+use crate::path_guard;
+use crate::validation::is_generated_dir;
+
+#[derive(Serialize)]
+pub struct FileChange {
+    pub path: String,
+    pub size_a: Option<u64>,
+    pub size_b: Option<u64>,
+    pub hash_a: Option<String>,
+    pub hash_b: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct SkinDiff {
+    pub path_a: String,
+    pub path_b: String,
+    pub added: Vec<FileChange>,
+    pub removed: Vec<FileChange>,
+    pub changed: Vec<FileChange>,
+    pub unchanged_count: usize,
+}
+
+/// Map of relative path (always `/`-separated) to absolute path and size for a skin's source files
+pub fn skin_file_map(skin_dir: &Path) -> BTreeMap<String, (PathBuf, u64)> {
+    let skin_dir = long_paths::extended(skin_dir);
+    WalkDir::new(&skin_dir)
+        .into_iter()
+        .filter_entry(|entry| !is_generated_dir(entry))
+        .flatten()
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| {
+            let relative = entry.path().strip_prefix(&skin_dir).ok()?;
+            let size = entry.metadata().ok()?.len();
+            Some((
+                relative.to_string_lossy().replace('\\', "/"),
+                (entry.path().to_path_buf(), size),
+            ))
+        })
+        .collect()
+}
+
+fn compare_skins(dir_a: &Path, dir_b: &Path) -> Result<SkinDiff, String> {
+    for dir in [dir_a, dir_b] {
+        if !dir.is_dir() {
+            return Err(format!("Skin folder not found: {}", dir.display()));
+        }
+    }
+
+    let files_a = skin_file_map(dir_a);
+    let files_b = skin_file_map(dir_b);
+
+    let mut diff = SkinDiff {
+        path_a: dir_a.to_string_lossy().to_string(),
+        path_b: dir_b.to_string_lossy().to_string(),
+        added: Vec::new(),
+        removed: Vec::new(),
+        changed: Vec::new(),
+        unchanged_count: 0,
+    };
+
+    for (relative, (path_a, size_a)) in &files_a {
+        match files_b.get(relative) {
+            None => diff.removed.push(FileChange {
+                path: relative.clone(),
+                size_a: Some(*size_a),
+                size_b: None,
+                hash_a: sha256_file(path_a).ok(),
+                hash_b: None,
+            }),
+            Some((path_b, size_b)) => {
+                let hash_a = sha256_file(path_a)?;
+                let hash_b = sha256_file(path_b)?;
+
+                if size_a == size_b && hash_a == hash_b {
+                    diff.unchanged_count += 1;
+                } else {
+                    diff.changed.push(FileChange {
+                        path: relative.clone(),
+                        size_a: Some(*size_a),
+                        size_b: Some(*size_b),
+                        hash_a: Some(hash_a),
+                        hash_b: Some(hash_b),
+                    });
+                }
+            }
+        }
+    }
+
+    for (relative, (path_b, size_b)) in &files_b {
+        if !files_a.contains_key(relative) {
+            diff.added.push(FileChange {
+                path: relative.clone(),
+                size_a: None,
+                size_b: Some(*size_b),
+                hash_a: None,
+                hash_b: sha256_file(path_b).ok(),
+            });
+        }
+    }
+
+    Ok(diff)
+}
+
+/// Compare two skin folders file by file
+#[tauri::command]
+#[tracing::instrument(err)]
+pub async fn diff_skins(path_a: String, path_b: String) -> Result<SkinDiff, String> {
+    tokio::task::spawn_blocking(move || {
+        compare_skins(
+            &path_guard::existing_dir(&path_a, "Skin folder")?,
+            &path_guard::existing_dir(&path_b, "Skin folder")?,
+        )
+    })
+    .await
+    .map_err(|e| format!("Diff task failed: {}", e))?
+}
@@ -0,0 +1,208 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tokio::sync::oneshot;
+use tokio::time::{timeout, Duration};
+
+use crate::cache::calculate_dir_size;
+use crate::diagnostics::MIN_FREE_SPACE;
+use crate::error::{AppError, ErrorKind};
+use crate::events::{CacheEvictionEntry, CacheEvictionProposedEvent};
+use crate::i18n::t;
+use crate::portable;
+use crate::storage;
+
+/// How long to wait for the user to answer an eviction proposal before failing the build
+const PROPOSAL_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Cache folders never evicted before a build: the WebView2 runtime, in-flight
+/// staging and task folders, and the change lists of the starting build
+const KEEP_ENTRIES: &[&str] = &["EBWebView", "temp", "incremental"];
+
+/// Eviction proposals waiting for an answer from the UI, keyed by request id
+#[derive(Default)]
+pub struct CacheEvictionState {
+    pending: Mutex<HashMap<String, oneshot::Sender<bool>>>,
+}
+
+/// Free bytes on the volume holding `path`, which may not exist yet
+pub fn free_space(path: &Path) -> Result<u64, String> {
+    let existing = path
+        .ancestors()
+        .find(|ancestor| ancestor.exists())
+        .ok_or_else(|| format!("Could not find a volume for {}", path.display()))?;
+    fs2::available_space(existing).map_err(|e| format!("Failed to read free disk space: {}", e))
+}
+
+/// Top-level cache entries the build doesn't need, with their sizes. The
+/// backend keeps its cache for each skin in a folder named after it.
+fn eviction_candidates(cache_dir: &Path, skin_name: &str) -> Vec<(PathBuf, u64)> {
+    let Ok(entries) = std::fs::read_dir(cache_dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            name != skin_name && !KEEP_ENTRIES.contains(&name.as_str())
+        })
+        .filter_map(|entry| {
+            let path = entry.path();
+            let size = if path.is_dir() {
+                calculate_dir_size(&path).ok()?
+            } else {
+                entry.metadata().ok()?.len()
+            };
+            (size > 0).then_some((path, size))
+        })
+        .collect()
+}
+
+fn evict(entries: &[(PathBuf, u64)]) -> u64 {
+    let mut reclaimed = 0;
+    for (path, size) in entries {
+        let removed = if path.is_dir() {
+            std::fs::remove_dir_all(path)
+        } else {
+            std::fs::remove_file(path)
+        };
+        match removed {
+            Ok(()) => reclaimed += size,
+            Err(e) => println!("[WARNING] Failed to evict {:?}: {}", path, e),
+        }
+    }
+    reclaimed
+}
+
+/// Emit `cache_eviction_proposed` and wait for `respond_cache_eviction`
+async fn request_approval(
+    app_handle: &AppHandle,
+    mut event: CacheEvictionProposedEvent,
+) -> Result<bool, String> {
+    let state = app_handle.state::<CacheEvictionState>();
+    let mut request_id = storage::unix_now().to_string();
+    let (sender, receiver) = oneshot::channel();
+    {
+        let mut pending = state
+            .pending
+            .lock()
+            .map_err(|e| format!("Failed to lock cache eviction requests: {}", e))?;
+        while pending.contains_key(&request_id) {
+            request_id.push('_');
+        }
+        pending.insert(request_id.clone(), sender);
+    }
+
+    event.request_id = request_id.clone();
+    let _ = app_handle.emit("cache_eviction_proposed", event);
+
+    let answer = matches!(timeout(PROPOSAL_TIMEOUT, receiver).await, Ok(Ok(true)));
+    if let Ok(mut pending) = state.pending.lock() {
+        pending.remove(&request_id);
+    }
+    Ok(answer)
+}
+
+/// Make sure the volume `output_dir` is on has room for a build. When it
+/// doesn't, propose evicting cache entries the build of `skin_name` won't
+/// use, and evict them once the user agrees. Returns the bytes reclaimed.
+pub async fn ensure_free_space(
+    app_handle: &AppHandle,
+    output_dir: &Path,
+    skin_name: &str,
+) -> Result<u64, AppError> {
+    let free = free_space(output_dir)?;
+    if free >= MIN_FREE_SPACE {
+        return Ok(0);
+    }
+
+    let cache_dir = portable::app_cache_dir(app_handle)?;
+    let keep = skin_name.to_string();
+    let candidates = tokio::task::spawn_blocking(move || eviction_candidates(&cache_dir, &keep))
+        .await
+        .map_err(|e| format!("Cache size task failed: {}", e))?;
+    let reclaimable: u64 = candidates.iter().map(|(_, size)| size).sum();
+
+    let not_enough_space = |free: u64| {
+        AppError::new(
+            ErrorKind::Io,
+            t(
+                app_handle,
+                "task.insufficient_space",
+                &[
+                    ("free", &format_size(free)),
+                    ("required", &format_size(MIN_FREE_SPACE)),
+                ],
+            ),
+        )
+        .with_path(output_dir)
+    };
+    // The cache may be on another volume, or too small to make a difference
+    if candidates.is_empty() || free + reclaimable < MIN_FREE_SPACE {
+        return Err(not_enough_space(free));
+    }
+
+    let proposal = CacheEvictionProposedEvent {
+        request_id: String::new(),
+        message: t(
+            app_handle,
+            "task.cache_eviction_proposed",
+            &[
+                ("free", &format_size(free)),
+                ("reclaimable", &format_size(reclaimable)),
+            ],
+        ),
+        free_bytes: free,
+        required_bytes: MIN_FREE_SPACE,
+        reclaimable_bytes: reclaimable,
+        entries: candidates
+            .iter()
+            .map(|(path, size)| CacheEvictionEntry {
+                path: path.to_string_lossy().to_string(),
+                size: *size,
+            })
+            .collect(),
+    };
+    if !request_approval(app_handle, proposal).await? {
+        return Err(not_enough_space(free));
+    }
+
+    let reclaimed = tokio::task::spawn_blocking(move || evict(&candidates))
+        .await
+        .map_err(|e| format!("Cache eviction task failed: {}", e))?;
+    println!(
+        "[DEBUG] Evicted {} bytes of cache before building",
+        reclaimed
+    );
+
+    let free = free_space(output_dir)?;
+    if free < MIN_FREE_SPACE {
+        return Err(not_enough_space(free));
+    }
+    Ok(reclaimed)
+}
+
+/// Byte count for messages, e.g. "1.5 GB"
+pub fn format_size(bytes: u64) -> String {
+    format!("{:.1} GB", bytes as f64 / 1024f64.powi(3))
+}
+
+/// Answer a pending `cache_eviction_proposed` prompt
+#[tauri::command]
+#[tracing::instrument(skip(state), err)]
+pub fn respond_cache_eviction(
+    state: State<'_, CacheEvictionState>,
+    request_id: String,
+    approved: bool,
+) -> Result<(), String> {
+    let sender = state
+        .pending
+        .lock()
+        .map_err(|e| format!("Failed to lock cache eviction requests: {}", e))?
+        .remove(&request_id)
+        .ok_or_else(|| format!("No pending cache eviction request: {}", request_id))?;
+    sender
+        .send(approved)
+        .map_err(|_| "Cache eviction request is no longer waiting".to_string())
+}
@@ -0,0 +1,147 @@
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter};
+
+use crate::backups::list_bundle_files;
+use crate::events::DroppedItemResolvedEvent;
+use crate::import::{import_archive, ON_EXISTING_ASK};
+use crate::paths::bundles_in_game_dir;
+use crate::skins::SKIN_CONFIG_FILE;
+use crate::validation::validate_skin_dir;
+
+const ARCHIVE_EXTENSIONS: &[&str] = &["zip", "7z", "rar", "tgz", "gz"];
+
+fn resolved(
+    path: &Path,
+    role: &str,
+    valid: bool,
+    message: String,
+    resolved_path: Option<String>,
+) -> DroppedItemResolvedEvent {
+    DroppedItemResolvedEvent {
+        path: path.to_string_lossy().to_string(),
+        role: role.to_string(),
+        valid,
+        message,
+        resolved_path,
+    }
+}
+
+/// Work out what a dropped path is, importing archives into the skins library
+fn resolve_dropped_item(app_handle: &AppHandle, path: &Path) -> DroppedItemResolvedEvent {
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_lowercase())
+        .unwrap_or_default();
+
+    if path.is_file() {
+        if ARCHIVE_EXTENSIONS.contains(&extension.as_str()) {
+            return match import_archive(app_handle, path, ON_EXISTING_ASK) {
+                Ok(result) => {
+                    let message = match result.action.as_str() {
+                        "unchanged" => {
+                            format!("Skin \"{}\" is already in the library", result.name)
+                        }
+                        "exists" => {
+                            let version = |version: Option<&String>| {
+                                version.map_or("no version".to_string(), |version| {
+                                    format!("v{}", version)
+                                })
+                            };
+                            let existing = result.existing.as_ref();
+                            format!(
+                                "Skin \"{}\" is already in the library ({} installed, {} in the archive); it was not imported again",
+                                result.name,
+                                version(existing.and_then(|skin| skin.installed_version.as_ref())),
+                                version(existing.and_then(|skin| skin.incoming_version.as_ref()))
+                            )
+                        }
+                        _ => format!("Imported skin \"{}\"", result.name),
+                    };
+                    resolved(path, "archive", true, message, Some(result.path))
+                }
+                Err(e) => resolved(path, "archive", false, e, None),
+            };
+        }
+
+        if extension == "bundle" {
+            let parent = path.parent().map(|p| p.to_string_lossy().to_string());
+            return resolved(
+                path,
+                "bundles",
+                parent.is_some(),
+                "Using the folder containing this bundle".to_string(),
+                parent,
+            );
+        }
+
+        return resolved(
+            path,
+            "unknown",
+            false,
+            "Drop a skin folder, a skin archive (.zip, .7z, .rar, .tar.gz), or a bundles folder"
+                .to_string(),
+            None,
+        );
+    }
+
+    if path.join(SKIN_CONFIG_FILE).is_file() {
+        let report = validate_skin_dir(path);
+        let message = if report.valid {
+            "Skin folder is ready to build".to_string()
+        } else {
+            format!(
+                "Skin has {} error(s) and {} warning(s)",
+                report.error_count, report.warning_count
+            )
+        };
+        return resolved(
+            path,
+            "skin",
+            report.valid,
+            message,
+            Some(path.to_string_lossy().to_string()),
+        );
+    }
+
+    if !list_bundle_files(path).is_empty() {
+        return resolved(
+            path,
+            "bundles",
+            true,
+            "Bundles folder selected".to_string(),
+            Some(path.to_string_lossy().to_string()),
+        );
+    }
+
+    // A game install folder: find the bundles inside it
+    if let Some(bundles_dir) = bundles_in_game_dir(path) {
+        return resolved(
+            path,
+            "bundles",
+            true,
+            "Found the game's bundles folder".to_string(),
+            Some(bundles_dir),
+        );
+    }
+
+    resolved(
+        path,
+        "unknown",
+        false,
+        format!("No skin config or bundles found in {}", path.display()),
+        None,
+    )
+}
+
+/// Classify files dropped onto the window and emit `dropped_item_resolved` for each
+pub fn handle_drop(app_handle: &AppHandle, paths: Vec<PathBuf>) {
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        for path in paths {
+            println!("[DEBUG] Resolving dropped item: {}", path.display());
+            let event = resolve_dropped_item(&app_handle, &path);
+            let _ = app_handle.emit("dropped_item_resolved", event);
+        }
+    });
+}
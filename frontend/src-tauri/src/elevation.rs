@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::mpsc;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tempfile::TempDir;
+
+use crate::events::ElevationConsentEvent;
+use crate::storage;
+
+/// How long to wait for the user to answer a consent prompt before giving up
+const CONSENT_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Consent prompts waiting for an answer from the UI, keyed by request id
+#[derive(Default)]
+pub struct ElevationState {
+    pending: Mutex<HashMap<String, mpsc::Sender<bool>>>,
+}
+
+/// Quote a path for the platform shell used by the elevated copy script. Batch
+/// files expand `%` even inside quotes, so it is doubled.
+fn quote(path: &Path) -> String {
+    if cfg!(windows) {
+        format!("\"{}\"", path.to_string_lossy().replace('%', "%%"))
+    } else {
+        format!("'{}'", path.to_string_lossy().replace('\'', "'\\''"))
+    }
+}
+
+/// Write a script that copies each file over its destination, stopping at the
+/// first failure. It is created fresh in a private folder, owner-only on Unix,
+/// so no other user can plant or swap it before it runs elevated. The folder
+/// is removed when the returned handle is dropped.
+fn write_copy_script(files: &[(PathBuf, PathBuf)]) -> Result<(TempDir, PathBuf), String> {
+    let extension = if cfg!(windows) { "cmd" } else { "sh" };
+    let script_dir = tempfile::Builder::new()
+        .prefix("fm-skin-builder-elevated-")
+        .tempdir()
+        .map_err(|e| format!("Failed to create elevated copy folder: {}", e))?;
+    let script_path = script_dir.path().join(format!("copy.{}", extension));
+
+    let script = if cfg!(windows) {
+        let mut script = String::from("@echo off\r\n");
+        for (source, destination) in files {
+            script.push_str(&format!(
+                "copy /Y {} {} >nul || exit /b 1\r\n",
+                quote(source),
+                quote(destination)
+            ));
+        }
+        script
+    } else {
+        let mut script = String::from("#!/bin/sh\nset -e\n");
+        for (source, destination) in files {
+            script.push_str(&format!("cp -f {} {}\n", quote(source), quote(destination)));
+        }
+        script
+    };
+
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options
+        .open(&script_path)
+        .and_then(|mut file| file.write_all(script.as_bytes()))
+        .map_err(|e| format!("Failed to write elevated copy script: {}", e))?;
+    Ok((script_dir, script_path))
+}
+
+/// Copy files into a protected location (Program Files, WindowsApps, system folders)
+/// by running a copy script with administrator rights. Prompts the user via UAC,
+/// the macOS authorization dialog, or pkexec.
+fn elevated_copy(files: &[(PathBuf, PathBuf)]) -> Result<(), String> {
+    if files.is_empty() {
+        return Ok(());
+    }
+
+    let (_script_dir, script_path) = write_copy_script(files)?;
+    let script = script_path.to_string_lossy().to_string();
+
+    let status = if cfg!(windows) {
+        Command::new("powershell")
+            .args([
+                "-NoProfile",
+                "-Command",
+                &format!(
+                    "$p = Start-Process -FilePath cmd.exe -ArgumentList '/c \"{}\"' -Verb RunAs -Wait -PassThru -WindowStyle Hidden; exit $p.ExitCode",
+                    script.replace('\'', "''")
+                ),
+            ])
+            .status()
+    } else if cfg!(target_os = "macos") {
+        Command::new("osascript")
+            .args([
+                "-e",
+                &format!(
+                    "do shell script \"/bin/sh {}\" with administrator privileges",
+                    quote(&script_path).replace('"', "\\\"")
+                ),
+            ])
+            .status()
+    } else {
+        Command::new("pkexec").args(["/bin/sh", &script]).status()
+    };
+
+    match status {
+        Ok(status) if status.success() => Ok(()),
+        Ok(status) => Err(format!(
+            "Elevated copy failed or was cancelled (exit code {})",
+            status.code().unwrap_or(-1)
+        )),
+        Err(e) => Err(format!("Failed to request administrator rights: {}", e)),
+    }
+}
+
+/// Ask the UI for consent before prompting for administrator rights. Emits
+/// `elevation_consent_requested` and blocks until `respond_elevation_consent`
+/// answers or the request times out, so it must run off the async runtime.
+fn request_consent(
+    app_handle: &AppHandle,
+    reason: &str,
+    files: &[(PathBuf, PathBuf)],
+) -> Result<bool, String> {
+    let state = app_handle.state::<ElevationState>();
+    let mut request_id = storage::unix_now().to_string();
+    let (sender, receiver) = mpsc::channel();
+    {
+        let mut pending = state
+            .pending
+            .lock()
+            .map_err(|e| format!("Failed to lock elevation requests: {}", e))?;
+        while pending.contains_key(&request_id) {
+            request_id.push('_');
+        }
+        pending.insert(request_id.clone(), sender);
+    }
+
+    let destination = files
+        .first()
+        .and_then(|(_, destination)| destination.parent())
+        .map(|dir| dir.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let _ = app_handle.emit(
+        "elevation_consent_requested",
+        ElevationConsentEvent {
+            request_id: request_id.clone(),
+            reason: reason.to_string(),
+            destination,
+            files: files
+                .iter()
+                .map(|(_, destination)| destination.to_string_lossy().to_string())
+                .collect(),
+        },
+    );
+
+    let answer = receiver.recv_timeout(CONSENT_TIMEOUT).unwrap_or(false);
+    if let Ok(mut pending) = state.pending.lock() {
+        pending.remove(&request_id);
+    }
+    Ok(answer)
+}
+
+/// Copy files that failed with a permission error using administrator rights,
+/// after the user agrees to it in the app
+pub fn copy_with_consent(
+    app_handle: &AppHandle,
+    files: &[(PathBuf, PathBuf)],
+    reason: &str,
+) -> Result<(), String> {
+    if files.is_empty() {
+        return Ok(());
+    }
+
+    if !request_consent(app_handle, reason, files)? {
+        return Err(
+            "Administrator rights are needed to write to the game directory, but permission was not given"
+                .to_string(),
+        );
+    }
+    elevated_copy(files)
+}
+
+/// Answer a pending `elevation_consent_requested` prompt
+#[tauri::command]
+#[tracing::instrument(skip(state), err)]
+pub fn respond_elevation_consent(
+    state: State<'_, ElevationState>,
+    request_id: String,
+    approved: bool,
+) -> Result<(), String> {
+    let sender = state
+        .pending
+        .lock()
+        .map_err(|e| format!("Failed to lock elevation requests: {}", e))?
+        .remove(&request_id)
+        .ok_or_else(|| format!("No pending elevation request: {}", request_id))?;
+    sender
+        .send(approved)
+        .map_err(|_| "Elevation request is no longer waiting".to_string())
+}
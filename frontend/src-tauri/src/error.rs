@@ -0,0 +1,115 @@
+use serde::Serialize;
+use std::fmt;
+use std::path::Path;
+
+/// What went wrong, for the frontend to branch on instead of matching messages
+#[derive(Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    /// A path, name or option sent by the frontend was rejected
+    InvalidInput,
+    NotFound,
+    PermissionDenied,
+    /// Reading or writing files failed
+    Io,
+    Network,
+    /// The backend failed to start or its output couldn't be read
+    Backend,
+    Cancelled,
+    /// Another operation is in the way (e.g. no build to stop)
+    Conflict,
+    Unsupported,
+    Internal,
+}
+
+impl ErrorKind {
+    /// Whether retrying, after following the hint, can succeed
+    fn recoverable(self) -> bool {
+        !matches!(
+            self,
+            ErrorKind::Backend | ErrorKind::Unsupported | ErrorKind::Internal
+        )
+    }
+}
+
+/// Error returned to the frontend, serialized as
+/// `{ kind, message, recoverable, hint, path }`
+#[derive(Serialize, Clone, Debug)]
+pub struct AppError {
+    pub kind: ErrorKind,
+    pub message: String,
+    pub recoverable: bool,
+    /// What the user can do about it
+    pub hint: Option<String>,
+    /// File or folder the error is about
+    pub path: Option<String>,
+}
+
+impl AppError {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            recoverable: kind.recoverable(),
+            hint: None,
+            path: None,
+        }
+    }
+
+    pub fn invalid_input(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::InvalidInput, message)
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::NotFound, message)
+    }
+
+    /// A failed file operation, classified by the OS error
+    pub fn io(context: &str, error: &std::io::Error) -> Self {
+        let message = format!("{}: {}", context, error);
+        match error.kind() {
+            std::io::ErrorKind::NotFound => Self::not_found(message),
+            std::io::ErrorKind::PermissionDenied => Self::new(ErrorKind::PermissionDenied, message)
+                .with_hint(
+                    "Check the folder isn't read-only or open in another program, then try again.",
+                ),
+            _ => Self::new(ErrorKind::Io, message),
+        }
+    }
+
+    pub fn with_hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = Some(hint.into());
+        self
+    }
+
+    pub fn with_path(mut self, path: &Path) -> Self {
+        self.path = Some(path.to_string_lossy().to_string());
+        self
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// Errors from helpers that still return plain messages
+impl From<String> for AppError {
+    fn from(message: String) -> Self {
+        Self::new(ErrorKind::Internal, message)
+    }
+}
+
+impl From<&str> for AppError {
+    fn from(message: &str) -> Self {
+        Self::new(ErrorKind::Internal, message)
+    }
+}
+
+/// For callers that still return plain messages
+impl From<AppError> for String {
+    fn from(error: AppError) -> Self {
+        error.message
+    }
+}
@@ -0,0 +1,161 @@
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::ipc::Channel;
+use tauri::{Emitter, State, WebviewWindow};
+
+/// Number of recent runs whose events are kept for replay
+const MAX_RUNS: usize = 5;
+/// Events kept per run; older events are dropped first
+const MAX_EVENTS_PER_RUN: usize = 2000;
+
+#[derive(Serialize, Clone)]
+pub struct BacklogEvent {
+    pub seq: u64,
+    pub event: String,
+    pub payload: serde_json::Value,
+}
+
+#[derive(Serialize)]
+pub struct EventBacklog {
+    pub task_id: String,
+    pub latest_seq: u64,
+    /// True when events after `since_seq` were already dropped from the buffer
+    pub truncated: bool,
+    pub events: Vec<BacklogEvent>,
+}
+
+/// Bounded buffer of the events emitted for one task
+pub struct RunBacklog {
+    pub task_id: String,
+    next_seq: AtomicU64,
+    events: Mutex<VecDeque<BacklogEvent>>,
+    /// Per-task stream handed in by the frontend for high-frequency events
+    channel: Option<Channel<BacklogEvent>>,
+}
+
+impl RunBacklog {
+    /// Keep a copy of an event for listeners that attach later
+    fn buffer<S: Serialize>(&self, event: &str, payload: &S) -> Option<BacklogEvent> {
+        let value = match serde_json::to_value(payload) {
+            Ok(value) => value,
+            Err(e) => {
+                println!("[WARNING] Failed to buffer {} event: {}", event, e);
+                return None;
+            }
+        };
+        let record = BacklogEvent {
+            seq: self.next_seq.fetch_add(1, Ordering::SeqCst) + 1,
+            event: event.to_string(),
+            payload: value,
+        };
+        if let Ok(mut events) = self.events.lock() {
+            if events.len() >= MAX_EVENTS_PER_RUN {
+                events.pop_front();
+            }
+            events.push_back(record.clone());
+        }
+        Some(record)
+    }
+
+    /// Broadcast a lifecycle event to the window and keep a copy for listeners
+    /// that attach later
+    pub fn emit<S: Serialize + Clone>(
+        &self,
+        window: &WebviewWindow,
+        event: &str,
+        payload: S,
+    ) -> tauri::Result<()> {
+        self.buffer(event, &payload);
+        window.emit(event, payload)
+    }
+
+    /// Send a high-frequency event (log lines, progress) over the task's channel,
+    /// falling back to a broadcast when the task was started without one
+    pub fn stream<S: Serialize + Clone>(
+        &self,
+        window: &WebviewWindow,
+        event: &str,
+        payload: S,
+    ) -> tauri::Result<()> {
+        match &self.channel {
+            Some(channel) => match self.buffer(event, &payload) {
+                Some(record) => channel.send(record),
+                None => Ok(()),
+            },
+            None => self.emit(window, event, payload),
+        }
+    }
+
+    fn since(&self, since_seq: u64) -> EventBacklog {
+        let events = self
+            .events
+            .lock()
+            .map(|events| events.iter().cloned().collect::<Vec<_>>())
+            .unwrap_or_default();
+        let first_seq = events.first().map(|event| event.seq).unwrap_or(1);
+
+        EventBacklog {
+            task_id: self.task_id.clone(),
+            latest_seq: self.next_seq.load(Ordering::SeqCst),
+            truncated: since_seq + 1 < first_seq,
+            events: events
+                .into_iter()
+                .filter(|event| event.seq > since_seq)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct EventBacklogState {
+    runs: Mutex<VecDeque<Arc<RunBacklog>>>,
+}
+
+impl EventBacklogState {
+    /// Start buffering events for a new task, evicting the oldest task if needed
+    pub fn start_task(
+        &self,
+        task_id: &str,
+        channel: Option<Channel<BacklogEvent>>,
+    ) -> Arc<RunBacklog> {
+        let backlog = Arc::new(RunBacklog {
+            task_id: task_id.to_string(),
+            next_seq: AtomicU64::new(0),
+            events: Mutex::new(VecDeque::new()),
+            channel,
+        });
+
+        if let Ok(mut runs) = self.runs.lock() {
+            if runs.len() >= MAX_RUNS {
+                runs.pop_front();
+            }
+            runs.push_back(backlog.clone());
+        }
+        backlog
+    }
+}
+
+/// Events emitted for a task after `since_seq`, so a webview that attached its
+/// listeners late (or reloaded) can catch up. Without a task id the most
+/// recent task is used.
+#[tauri::command]
+#[tracing::instrument(skip(state), err)]
+pub fn get_event_backlog(
+    task_id: Option<String>,
+    since_seq: Option<u64>,
+    state: State<'_, EventBacklogState>,
+) -> Result<Option<EventBacklog>, String> {
+    let runs = state
+        .runs
+        .lock()
+        .map_err(|e| format!("Failed to lock event backlog: {}", e))?;
+
+    let run = match task_id.as_deref() {
+        Some(id) => runs.iter().rev().find(|run| run.task_id == id),
+        None => runs.back(),
+    };
+
+    Ok(run.map(|run| run.since(since_seq.unwrap_or(0))))
+}
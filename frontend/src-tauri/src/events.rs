@@ -1,9 +1,17 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Clone)]
+use crate::build_summary::BuildSummary;
+
+#[derive(Serialize, Deserialize, Clone)]
 pub struct LogEvent {
     pub message: String,
-    pub level: String, // "info", "error", "warning"
+    pub level: String,  // "info", "error", "warning"
+    pub source: String, // "stdout", "stderr", "rust"
+    pub timestamp: String,
+    pub sequence: u64,
+    /// Build run the line belongs to; empty in logs written before run ids were recorded
+    #[serde(default)]
+    pub run_id: String,
 }
 
 #[derive(Serialize, Clone)]
@@ -11,18 +19,39 @@ pub struct ProgressEvent {
     pub current: u32,
     pub total: u32,
     pub status: String,
+    /// Build run the progress belongs to; `None` for progress outside builds
+    pub run_id: Option<String>,
 }
 
-#[derive(Serialize, Clone)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct CompletionEvent {
     pub success: bool,
     pub exit_code: i32,
     pub message: String,
+    pub project_id: Option<String>,
+    pub summary: BuildSummary,
+    pub run_id: String,
 }
 
 #[derive(Serialize, Clone)]
 pub struct TaskStartedEvent {
     pub message: String,
+    pub run_id: String,
+}
+
+#[derive(Serialize, Clone)]
+pub struct SkinChangedEvent {
+    pub skin_path: String,
+    pub paths: Vec<String>,
+    pub auto_rebuild: bool,
+}
+
+#[derive(Serialize, Clone)]
+pub struct WatchStatusEvent {
+    pub project_id: String,
+    pub skin_path: String,
+    pub status: String, // "watching", "building", "up_to_date", "failed"
+    pub message: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -30,4 +59,152 @@ pub struct CommandResult {
     pub stdout: String,
     pub stderr: String,
     pub status: i32,
+    /// The build passed every step (verification, install, packaging), not
+    /// just the backend exiting cleanly
+    pub success: bool,
+}
+
+#[derive(Serialize, Clone)]
+pub struct TransferProgressEvent {
+    pub file: String,
+    pub copied_bytes: u64,
+    pub total_bytes: u64,
+}
+
+#[derive(Serialize, Clone)]
+pub struct HashProgressEvent {
+    pub completed_files: u32,
+    pub total_files: u32,
+    pub hashed_bytes: u64,
+    pub total_bytes: u64,
+}
+
+#[derive(Serialize, Clone)]
+pub struct DroppedItemResolvedEvent {
+    pub path: String,
+    pub role: String, // "skin", "archive", "bundles", "unknown"
+    pub valid: bool,
+    pub message: String,
+    /// Skin folder an archive was imported to, or the bundles directory found
+    pub resolved_path: Option<String>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct DeepLinkEvent {
+    pub url: String,
+    pub action: String, // "import", "open-skin", "unknown"
+    pub success: bool,
+    pub message: String,
+    /// Imported or opened skin folder
+    pub skin_path: Option<String>,
+}
+
+/// A link asks to download and import a skin; nothing happens until the user answers
+#[derive(Serialize, Clone)]
+pub struct LinkImportProposedEvent {
+    /// Answer with `respond_link_import`
+    pub request_id: String,
+    /// Where the archive would be downloaded from
+    pub url: String,
+    pub name: String,
+    /// The download is listed in the skin repository index
+    pub in_repository: bool,
+    pub message: String,
+}
+
+#[derive(Serialize, Clone)]
+pub struct SecondInstanceEvent {
+    pub args: Vec<String>,
+    pub cwd: String,
+}
+
+#[derive(Serialize, Clone)]
+pub struct ExitRequestedEvent {
+    pub message: String,
+    /// Skin being built
+    pub skin_path: Option<String>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct ElevationConsentEvent {
+    pub request_id: String,
+    /// What the elevated copy is for, e.g. "Installing" or "Restoring"
+    pub reason: String,
+    pub destination: String,
+    pub files: Vec<String>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct ShortcutTriggeredEvent {
+    /// e.g. "run_last_build"
+    pub action: String,
+}
+
+/// An installed skin whose bundles a game update replaced
+#[derive(Serialize, Clone)]
+pub struct SkinOverwrittenEvent {
+    /// Localized prompt offering to re-apply the skin
+    pub message: String,
+    pub bundles_path: String,
+    pub skin_path: String,
+    pub skin_name: String,
+    pub installed_at: u64,
+    /// Bundle file names no longer holding the skin
+    pub overwritten: Vec<String>,
+    /// Saved project `reapply_last_skin` will build, if any
+    pub project_id: Option<String>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct CacheEvictionEntry {
+    pub path: String,
+    pub size: u64,
+}
+
+/// Not enough disk space to build, but evicting these cache entries would make room
+#[derive(Serialize, Clone)]
+pub struct CacheEvictionProposedEvent {
+    /// Answer with `respond_cache_eviction`
+    pub request_id: String,
+    /// Localized prompt asking to evict the entries
+    pub message: String,
+    pub free_bytes: u64,
+    pub required_bytes: u64,
+    pub reclaimable_bytes: u64,
+    pub entries: Vec<CacheEvictionEntry>,
+}
+
+/// Something the user can take back with `undo_last_action` until `expires_at`
+#[derive(Serialize, Clone)]
+pub struct UndoableActionEvent {
+    pub token: String,
+    /// e.g. "delete_skin"
+    pub kind: String,
+    /// Shown next to the undo button, e.g. "Moved MySkin to the trash"
+    pub description: String,
+    /// Unix time after which the token is no longer honored
+    pub expires_at: u64,
+}
+
+#[derive(Serialize, Clone)]
+pub struct BatchProjectResult {
+    pub project_id: String,
+    pub name: String,
+    pub success: bool,
+    /// `None` when the build didn't run to completion
+    pub exit_code: Option<i32>,
+    pub error: Option<String>,
+    pub duration_ms: u64,
+}
+
+/// Outcome of every project in a `run_batch`, in the order they were given
+#[derive(Serialize, Clone)]
+pub struct BatchCompleteEvent {
+    pub batch_id: String,
+    pub results: Vec<BatchProjectResult>,
+    pub succeeded: usize,
+    pub failed: usize,
+    /// Projects never started because the batch was stopped
+    pub skipped: usize,
+    pub duration_ms: u64,
 }
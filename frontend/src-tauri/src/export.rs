@@ -0,0 +1,286 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter};
+use walkdir::WalkDir;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::build_outputs::{self, OUTPUT_MARKER_FILE};
+use crate::events::ProgressEvent;
+use crate::path_guard;
+use crate::skins::read_skin_info;
+use crate::storage;
+use crate::validation::is_generated_dir;
+
+/// Machine-readable description of a package, read back when it is imported
+pub const SKIN_MANIFEST_FILE: &str = "skin_manifest.json";
+
+/// Bumped when a field changes meaning; importers ignore fields they don't know
+const SKIN_MANIFEST_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PackagedFile {
+    pub path: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SkinManifest {
+    pub format_version: u32,
+    /// Stable id from the skin config
+    #[serde(default)]
+    pub id: Option<String>,
+    pub name: String,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub author: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Game build the skin was made for
+    #[serde(default)]
+    pub target_game_version: Option<String>,
+    pub contents: String, // "bundles", "source"
+    pub created_at: u64,
+    /// App and version that wrote the package
+    #[serde(default)]
+    pub generator: Option<String>,
+    pub files: Vec<PackagedFile>,
+}
+
+#[derive(Serialize)]
+pub struct ExportResult {
+    pub package_path: String,
+    pub contents: String,
+    pub file_count: usize,
+    pub total_size: u64,
+}
+
+/// Stream a file into the archive while hashing it
+pub fn add_file<W: Write + std::io::Seek>(
+    writer: &mut ZipWriter<W>,
+    source: &Path,
+    archive_path: &str,
+) -> Result<PackagedFile, String> {
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    writer
+        .start_file(archive_path, options)
+        .map_err(|e| format!("Failed to add {} to package: {}", archive_path, e))?;
+
+    let mut file =
+        std::fs::File::open(source).map_err(|e| format!("Failed to open {:?}: {}", source, e))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 1024 * 1024];
+    let mut size = 0u64;
+
+    loop {
+        let read = file
+            .read(&mut buffer)
+            .map_err(|e| format!("Failed to read {:?}: {}", source, e))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+        writer
+            .write_all(&buffer[..read])
+            .map_err(|e| format!("Failed to write {} to package: {}", archive_path, e))?;
+        size += read as u64;
+    }
+
+    Ok(PackagedFile {
+        path: archive_path.to_string(),
+        size,
+        sha256: format!("{:x}", hasher.finalize()),
+    })
+}
+
+pub fn add_text<W: Write + std::io::Seek>(
+    writer: &mut ZipWriter<W>,
+    archive_path: &str,
+    content: &str,
+) -> Result<(), String> {
+    writer
+        .start_file(archive_path, SimpleFileOptions::default())
+        .map_err(|e| format!("Failed to add {} to package: {}", archive_path, e))?;
+    writer
+        .write_all(content.as_bytes())
+        .map_err(|e| format!("Failed to write {} to package: {}", archive_path, e))
+}
+
+/// Files to package: the built bundles when present, otherwise the skin source
+fn collect_package_files(
+    skin_dir: &Path,
+    packages_dir: &Path,
+    include_source: bool,
+) -> (String, Vec<(PathBuf, String)>) {
+    // The marker the app writes into managed output folders isn't part of the build
+    let is_build_file = |entry: &walkdir::DirEntry| {
+        entry.file_type().is_file()
+            && !(entry.depth() == 1 && entry.file_name() == OUTPUT_MARKER_FILE)
+    };
+    let use_bundles = !include_source
+        && WalkDir::new(packages_dir)
+            .into_iter()
+            .flatten()
+            .any(|entry| is_build_file(&entry));
+
+    let (contents, base, prefix) = if use_bundles {
+        ("bundles", packages_dir.to_path_buf(), "bundles")
+    } else {
+        ("source", skin_dir.to_path_buf(), "skin")
+    };
+
+    let files = WalkDir::new(&base)
+        .into_iter()
+        .filter_entry(|entry| use_bundles || !is_generated_dir(entry))
+        .flatten()
+        .filter(|entry| is_build_file(entry))
+        .filter_map(|entry| {
+            let relative = entry.path().strip_prefix(&base).ok()?;
+            let archive_path = format!(
+                "{}/{}",
+                prefix,
+                relative.to_string_lossy().replace('\\', "/")
+            );
+            Some((entry.path().to_path_buf(), archive_path))
+        })
+        .collect();
+
+    (contents.to_string(), files)
+}
+
+fn generate_readme(manifest: &SkinManifest) -> String {
+    let mut readme = format!("{}\n{}\n\n", manifest.name, "=".repeat(manifest.name.len()));
+
+    if let Some(author) = &manifest.author {
+        readme.push_str(&format!("Author: {}\n", author));
+    }
+    if let Some(version) = &manifest.version {
+        readme.push_str(&format!("Version: {}\n", version));
+    }
+    readme.push_str("Packaged with FM Skin Builder\n\n");
+
+    if manifest.contents == "bundles" {
+        readme.push_str(
+            "This package contains pre-built bundles in the bundles/ folder.\n\
+             Back up your game's original bundles, then copy these files over the\n\
+             matching files in your Football Manager StreamingAssets folder.\n",
+        );
+    } else {
+        readme.push_str(
+            "This package contains the skin source in the skin/ folder.\n\
+             Import it into FM Skin Builder (or copy it into your skins folder)\n\
+             and run a build against your game's bundles.\n",
+        );
+    }
+
+    readme.push_str("\nFile checksums are listed in checksums.sha256 and skin_manifest.json.\n");
+    readme
+}
+
+/// Zip the bundles built into `packages_dir` (or the skin source) with a
+/// skin manifest, checksums and a readme
+pub fn export_package(
+    app_handle: &AppHandle,
+    skin_dir: &Path,
+    packages_dir: &Path,
+    destination: &Path,
+    include_source: bool,
+) -> Result<ExportResult, String> {
+    let info = read_skin_info(skin_dir)?;
+
+    let package_path = if destination.is_dir() {
+        destination.join(format!("{}.zip", info.name))
+    } else {
+        destination.to_path_buf()
+    };
+
+    let (contents, files) = collect_package_files(skin_dir, packages_dir, include_source);
+    if files.is_empty() {
+        return Err("Nothing to package: the skin folder is empty".to_string());
+    }
+
+    let file = std::fs::File::create(&package_path)
+        .map_err(|e| format!("Failed to create package: {}", e))?;
+    let mut writer = ZipWriter::new(file);
+    let total = files.len() as u32;
+    let mut packaged = Vec::with_capacity(files.len());
+
+    for (index, (source, archive_path)) in files.iter().enumerate() {
+        packaged.push(add_file(&mut writer, source, archive_path)?);
+        let _ = app_handle.emit(
+            "export_progress",
+            ProgressEvent {
+                current: index as u32 + 1,
+                total,
+                status: format!("Packaging {}", archive_path),
+                run_id: None,
+            },
+        );
+    }
+
+    let manifest = SkinManifest {
+        format_version: SKIN_MANIFEST_VERSION,
+        id: info.id,
+        name: info.name,
+        version: info.version,
+        author: info.author,
+        description: info.description,
+        target_game_version: info.target_game_version,
+        contents: contents.clone(),
+        created_at: storage::unix_now(),
+        generator: Some(format!("FM Skin Builder {}", env!("CARGO_PKG_VERSION"))),
+        files: packaged,
+    };
+
+    let checksums: String = manifest
+        .files
+        .iter()
+        .map(|file| format!("{}  {}\n", file.sha256, file.path))
+        .collect();
+    let manifest_json = serde_json::to_string_pretty(&manifest)
+        .map_err(|e| format!("Failed to serialize skin manifest: {}", e))?;
+
+    add_text(&mut writer, "checksums.sha256", &checksums)?;
+    add_text(&mut writer, SKIN_MANIFEST_FILE, &manifest_json)?;
+    add_text(&mut writer, "README.txt", &generate_readme(&manifest))?;
+
+    writer
+        .finish()
+        .map_err(|e| format!("Failed to finalize package: {}", e))?;
+
+    Ok(ExportResult {
+        package_path: package_path.to_string_lossy().to_string(),
+        contents,
+        file_count: manifest.files.len(),
+        total_size: manifest.files.iter().map(|file| file.size).sum(),
+    })
+}
+
+/// Package a skin's latest built bundles (or its source) into a shareable zip
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub async fn export_skin_package(
+    app_handle: AppHandle,
+    skin: String,
+    destination: String,
+    include_source: Option<bool>,
+) -> Result<ExportResult, String> {
+    tokio::task::spawn_blocking(move || {
+        let skin_dir = path_guard::existing_dir(&skin, "Skin folder")?;
+        // Builds record the skin path as the UI sent it
+        let output_dir = build_outputs::current_output_dir(&app_handle, &skin, &[]);
+        export_package(
+            &app_handle,
+            &skin_dir,
+            &output_dir,
+            &path_guard::clean_path(&destination, "Destination")?,
+            include_source.unwrap_or(false),
+        )
+    })
+    .await
+    .map_err(|e| format!("Export task failed: {}", e))?
+}
@@ -0,0 +1,372 @@
+use flate2::read::GzDecoder;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use walkdir::WalkDir;
+
+use crate::long_paths;
+
+/// Hard cap on what one archive may expand to
+const MAX_EXTRACTED_BYTES: u64 = 4 * 1024 * 1024 * 1024;
+const MAX_ENTRIES: u32 = 100_000;
+/// Extracted size allowed per archive byte before it looks like a size bomb
+const MAX_COMPRESSION_RATIO: u64 = 100;
+/// Small archives may always expand this far, whatever the ratio
+const MIN_EXTRACT_ALLOWANCE: u64 = 64 * 1024 * 1024;
+
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Start of the error for a 7z archive using a compression method the built-in
+/// reader lacks (e.g. PPMd or BCJ2)
+const UNSUPPORTED_METHOD_ERROR: &str = "Unsupported 7z compression method";
+
+#[derive(Clone, Copy)]
+pub enum ArchiveKind {
+    Zip,
+    SevenZip,
+    TarGz,
+}
+
+impl ArchiveKind {
+    /// Archive format from a file name, e.g. `skin.zip` or `backend.tar.gz`
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.to_lowercase();
+        if name.ends_with(".zip") {
+            Some(Self::Zip)
+        } else if name.ends_with(".7z") {
+            Some(Self::SevenZip)
+        } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(Self::TarGz)
+        } else {
+            None
+        }
+    }
+
+    pub fn from_path(path: &Path) -> Option<Self> {
+        Self::from_name(&path.file_name()?.to_string_lossy())
+    }
+}
+
+pub struct ExtractSummary {
+    pub entries: u32,
+    pub bytes: u64,
+}
+
+/// Called before each entry with its index, the entry count when the format
+/// lists it up front, and the entry name
+pub type EntryCallback<'a> = &'a mut dyn FnMut(u32, Option<u32>, &str);
+
+/// Relative path for an archive entry, or `None` for absolute paths, drive
+/// letters and `..` components that would escape the destination (zip-slip)
+fn safe_relative(name: &str) -> Option<PathBuf> {
+    if name.starts_with(['/', '\\']) {
+        return None;
+    }
+    let mut path = PathBuf::new();
+    for part in name.split(['/', '\\']) {
+        match part {
+            "" | "." => continue,
+            ".." => return None,
+            _ if part.contains(':') => return None,
+            _ => path.push(part),
+        }
+    }
+    (!path.as_os_str().is_empty()).then_some(path)
+}
+
+/// Running totals checked against the size-bomb limits
+struct Budget<'a> {
+    entries: u32,
+    bytes: u64,
+    limit: u64,
+    cancel: &'a AtomicBool,
+}
+
+impl<'a> Budget<'a> {
+    /// Limits for one archive: a size-bomb ratio of its own size, within fixed bounds
+    fn for_archive(archive_path: &Path, cancel: &'a AtomicBool) -> Result<Self, String> {
+        let archive_size = std::fs::metadata(archive_path)
+            .map_err(|e| format!("Failed to open archive: {}", e))?
+            .len();
+        Ok(Self {
+            entries: 0,
+            bytes: 0,
+            limit: archive_size
+                .saturating_mul(MAX_COMPRESSION_RATIO)
+                .clamp(MIN_EXTRACT_ALLOWANCE, MAX_EXTRACTED_BYTES),
+            cancel,
+        })
+    }
+
+    fn next_entry(&mut self) -> Result<(), String> {
+        if self.cancel.load(Ordering::Relaxed) {
+            return Err("Extraction cancelled".to_string());
+        }
+        self.entries += 1;
+        if self.entries > MAX_ENTRIES {
+            return Err(format!(
+                "Archive has more than {} entries; refusing to extract",
+                MAX_ENTRIES
+            ));
+        }
+        Ok(())
+    }
+
+    /// Stream an entry to disk, stopping as soon as the archive exceeds its budget
+    fn write(&mut self, reader: &mut dyn Read, dest: &Path, relative: &Path) -> Result<(), String> {
+        // Recompose macOS-style decomposed names so they match config.json references
+        let out_path = long_paths::extended(dest).join(long_paths::nfc_path(relative));
+        if let Some(parent) = out_path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create directory: {}", e))?;
+        }
+        let mut out_file = std::fs::File::create(&out_path)
+            .map_err(|e| format!("Failed to create file: {}", e))?;
+
+        let mut buffer = vec![0u8; CHUNK_SIZE];
+        loop {
+            if self.cancel.load(Ordering::Relaxed) {
+                return Err("Extraction cancelled".to_string());
+            }
+            let read = reader
+                .read(&mut buffer)
+                .map_err(|e| format!("Failed to extract {}: {}", relative.display(), e))?;
+            if read == 0 {
+                break;
+            }
+            self.add_bytes(read as u64)?;
+            out_file
+                .write_all(&buffer[..read])
+                .map_err(|e| format!("Failed to extract {}: {}", relative.display(), e))?;
+        }
+        Ok(())
+    }
+
+    fn add_bytes(&mut self, count: u64) -> Result<(), String> {
+        self.bytes += count;
+        if self.bytes > self.limit {
+            return Err(format!(
+                "Archive expands to more than {} MB; refusing to extract",
+                self.limit / (1024 * 1024)
+            ));
+        }
+        Ok(())
+    }
+
+    fn create_dir(&self, dest: &Path, relative: &Path) -> Result<(), String> {
+        std::fs::create_dir_all(long_paths::extended(dest).join(long_paths::nfc_path(relative)))
+            .map_err(|e| format!("Failed to create directory: {}", e))
+    }
+}
+
+fn extract_zip(
+    archive_path: &Path,
+    dest: &Path,
+    budget: &mut Budget,
+    on_entry: EntryCallback,
+) -> Result<(), String> {
+    let file =
+        std::fs::File::open(archive_path).map_err(|e| format!("Failed to open archive: {}", e))?;
+    let mut archive =
+        zip::ZipArchive::new(file).map_err(|e| format!("Failed to read archive: {}", e))?;
+    let total = archive.len() as u32;
+
+    for index in 0..archive.len() {
+        budget.next_entry()?;
+        let mut entry = archive
+            .by_index(index)
+            .map_err(|e| format!("Failed to read archive entry: {}", e))?;
+        on_entry(index as u32, Some(total), entry.name());
+
+        let is_symlink = entry
+            .unix_mode()
+            .is_some_and(|mode| mode & 0o170000 == 0o120000);
+        let Some(relative) = safe_relative(entry.name()).filter(|_| !is_symlink) else {
+            println!("[WARNING] Skipping unsafe archive entry: {}", entry.name());
+            continue;
+        };
+
+        if entry.is_dir() {
+            budget.create_dir(dest, &relative)?;
+        } else {
+            budget.write(&mut entry, dest, &relative)?;
+        }
+    }
+    Ok(())
+}
+
+fn seven_zip_error(context: &str, error: sevenz_rust::Error) -> String {
+    match error {
+        sevenz_rust::Error::UnsupportedCompressionMethod(method) => {
+            format!("{}: {}", UNSUPPORTED_METHOD_ERROR, method)
+        }
+        error => format!("{}: {}", context, error),
+    }
+}
+
+/// Whether an extraction error only means the built-in reader can't decode
+/// the archive's compression method, so 7-Zip may be tried instead
+pub fn is_unsupported_method(error: &str) -> bool {
+    error.starts_with(UNSUPPORTED_METHOD_ERROR)
+}
+
+fn extract_7z(
+    archive_path: &Path,
+    dest: &Path,
+    budget: &mut Budget,
+    on_entry: EntryCallback,
+) -> Result<(), String> {
+    let mut archive = sevenz_rust::SevenZReader::open(archive_path, sevenz_rust::Password::empty())
+        .map_err(|e| seven_zip_error("Failed to read archive", e))?;
+
+    // The reader's callback can only return its own error type, so keep ours aside
+    let mut failure = None;
+    let mut index = 0;
+    archive
+        .for_each_entries(|entry, reader| {
+            let result = budget.next_entry().and_then(|_| {
+                on_entry(index, None, entry.name());
+                match safe_relative(entry.name()) {
+                    Some(relative) if entry.is_directory() => budget.create_dir(dest, &relative),
+                    Some(relative) => budget.write(reader, dest, &relative),
+                    None => {
+                        println!("[WARNING] Skipping unsafe archive entry: {}", entry.name());
+                        std::io::copy(reader, &mut std::io::sink())
+                            .map(|_| ())
+                            .map_err(|e| format!("Failed to read archive entry: {}", e))
+                    }
+                }
+            });
+            index += 1;
+            match result {
+                Ok(()) => Ok(true),
+                Err(e) => {
+                    failure = Some(e);
+                    Ok(false)
+                }
+            }
+        })
+        .map_err(|e| seven_zip_error("Failed to extract archive", e))?;
+
+    match failure {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+fn extract_tar_gz(
+    archive_path: &Path,
+    dest: &Path,
+    budget: &mut Budget,
+    on_entry: EntryCallback,
+) -> Result<(), String> {
+    let file =
+        std::fs::File::open(archive_path).map_err(|e| format!("Failed to open archive: {}", e))?;
+    let mut archive = tar::Archive::new(GzDecoder::new(file));
+    let entries = archive
+        .entries()
+        .map_err(|e| format!("Failed to read archive: {}", e))?;
+
+    for (index, entry) in entries.enumerate() {
+        budget.next_entry()?;
+        let mut entry = entry.map_err(|e| format!("Failed to read archive entry: {}", e))?;
+        let name = entry
+            .path()
+            .map(|path| path.to_string_lossy().to_string())
+            .map_err(|e| format!("Failed to read archive entry: {}", e))?;
+        on_entry(index as u32, None, &name);
+
+        // Links could point outside the destination, so only plain files and folders
+        let entry_type = entry.header().entry_type();
+        let Some(relative) =
+            safe_relative(&name).filter(|_| entry_type.is_file() || entry_type.is_dir())
+        else {
+            println!("[WARNING] Skipping unsafe archive entry: {}", name);
+            continue;
+        };
+
+        if entry_type.is_dir() {
+            budget.create_dir(dest, &relative)?;
+        } else {
+            budget.write(&mut entry, dest, &relative)?;
+        }
+    }
+    Ok(())
+}
+
+/// Extract a zip, 7z or tar.gz archive into `dest`. Entries that would escape
+/// `dest` and links are skipped; extraction stops when `cancel` is set or the
+/// archive expands past the size-bomb limits. The caller owns cleaning up
+/// `dest` on failure.
+pub fn extract_archive(
+    archive_path: &Path,
+    kind: ArchiveKind,
+    dest: &Path,
+    cancel: &AtomicBool,
+    on_entry: EntryCallback,
+) -> Result<ExtractSummary, String> {
+    let mut budget = Budget::for_archive(archive_path, cancel)?;
+
+    std::fs::create_dir_all(dest).map_err(|e| format!("Failed to create directory: {}", e))?;
+    match kind {
+        ArchiveKind::Zip => extract_zip(archive_path, dest, &mut budget, on_entry)?,
+        ArchiveKind::SevenZip => extract_7z(archive_path, dest, &mut budget, on_entry)?,
+        ArchiveKind::TarGz => extract_tar_gz(archive_path, dest, &mut budget, on_entry)?,
+    }
+
+    Ok(ExtractSummary {
+        entries: budget.entries,
+        bytes: budget.bytes,
+    })
+}
+
+/// Hold what an external tool extracted into `dest` to the same rules as the
+/// built-in extractors: no links, nothing resolving outside `dest`, and no more
+/// entries or bytes than the archive may expand to
+pub fn check_extracted(
+    archive_path: &Path,
+    dest: &Path,
+    cancel: &AtomicBool,
+) -> Result<ExtractSummary, String> {
+    let mut budget = Budget::for_archive(archive_path, cancel)?;
+    let root = dest
+        .canonicalize()
+        .map_err(|e| format!("Failed to read extracted files: {}", e))?;
+
+    for entry in WalkDir::new(dest).min_depth(1) {
+        let entry = entry.map_err(|e| format!("Failed to read extracted files: {}", e))?;
+        budget.next_entry()?;
+        let relative = entry.path().strip_prefix(dest).unwrap_or(entry.path());
+
+        let resolved = entry
+            .path()
+            .canonicalize()
+            .map_err(|e| format!("Failed to read {}: {}", relative.display(), e))?;
+        if entry.path_is_symlink() || !resolved.starts_with(&root) {
+            return Err(format!(
+                "Archive entry {} links outside the archive; refusing to extract",
+                relative.display()
+            ));
+        }
+
+        if entry.file_type().is_file() {
+            let metadata = entry
+                .metadata()
+                .map_err(|e| format!("Failed to read {}: {}", relative.display(), e))?;
+            // A hard link shares its data with a file somewhere else
+            #[cfg(unix)]
+            if std::os::unix::fs::MetadataExt::nlink(&metadata) > 1 {
+                return Err(format!(
+                    "Archive entry {} links outside the archive; refusing to extract",
+                    relative.display()
+                ));
+            }
+            budget.add_bytes(metadata.len())?;
+        }
+    }
+
+    Ok(ExtractSummary {
+        entries: budget.entries,
+        bytes: budget.bytes,
+    })
+}
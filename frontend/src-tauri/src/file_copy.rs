@@ -0,0 +1,219 @@
+use sha2::{Digest, Sha256};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::events::TransferProgressEvent;
+use crate::hashing::sha256_file;
+use crate::long_paths;
+
+/// Bytes read per chunk while streaming
+const CHUNK_SIZE: usize = 1024 * 1024;
+
+/// Chunks copied between progress events
+const PROGRESS_INTERVAL: u64 = 32;
+
+/// Cancellation flag shared by every long-running copy
+pub struct TransferState {
+    pub cancelled: Arc<AtomicBool>,
+}
+
+impl Default for TransferState {
+    fn default() -> Self {
+        Self {
+            cancelled: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+pub enum CopyError {
+    PermissionDenied(String),
+    Cancelled,
+    Failed(String),
+}
+
+impl From<CopyError> for String {
+    fn from(error: CopyError) -> Self {
+        match error {
+            CopyError::PermissionDenied(message) | CopyError::Failed(message) => message,
+            CopyError::Cancelled => "Copy cancelled".to_string(),
+        }
+    }
+}
+
+pub struct CopyOutcome {
+    pub bytes: u64,
+    /// SHA-256 of the copied data
+    pub sha256: String,
+    /// True when the filesystem cloned the file instead of copying its data
+    pub cloned: bool,
+}
+
+fn io_error(action: &str, path: &Path, e: std::io::Error) -> CopyError {
+    let message = format!("Failed to {} {:?}: {}", action, path, e);
+    if e.kind() == std::io::ErrorKind::PermissionDenied {
+        CopyError::PermissionDenied(message)
+    } else {
+        CopyError::Failed(message)
+    }
+}
+
+/// Temporary name the copy is written to before being renamed into place
+fn partial_path(destination: &Path) -> PathBuf {
+    let mut name = destination
+        .file_name()
+        .map(|name| name.to_os_string())
+        .unwrap_or_default();
+    name.push(".partial");
+    destination.with_file_name(name)
+}
+
+/// Stream `source` into `partial`, hashing as it goes
+fn stream_copy(
+    source: &Path,
+    partial: &Path,
+    total: u64,
+    cancel: &AtomicBool,
+    on_progress: &mut dyn FnMut(u64, u64),
+) -> Result<String, CopyError> {
+    let mut input = std::fs::File::open(source).map_err(|e| io_error("open", source, e))?;
+    let mut output = std::fs::File::create(partial).map_err(|e| io_error("create", partial, e))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; CHUNK_SIZE];
+    let mut copied = 0u64;
+    let mut chunks = 0u64;
+
+    loop {
+        if cancel.load(Ordering::Relaxed) {
+            return Err(CopyError::Cancelled);
+        }
+
+        let read = input
+            .read(&mut buffer)
+            .map_err(|e| io_error("read", source, e))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+        output
+            .write_all(&buffer[..read])
+            .map_err(|e| io_error("write", partial, e))?;
+
+        copied += read as u64;
+        chunks += 1;
+        if chunks % PROGRESS_INTERVAL == 0 {
+            on_progress(copied, total);
+        }
+    }
+
+    output
+        .sync_all()
+        .map_err(|e| io_error("flush", partial, e))?;
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Clone a file copy-on-write without reading its data. Returns false when the
+/// filesystem can't clone, so the caller can stream a copy instead.
+pub fn clone_file(source: &Path, destination: &Path) -> Result<bool, CopyError> {
+    let source = &long_paths::extended(source);
+    let destination = &long_paths::extended(destination);
+    let partial = partial_path(destination);
+    let _ = std::fs::remove_file(&partial);
+
+    if reflink_copy::reflink(source, &partial).is_err() {
+        let _ = std::fs::remove_file(&partial);
+        return Ok(false);
+    }
+    std::fs::rename(&partial, destination).map_err(|e| {
+        let _ = std::fs::remove_file(&partial);
+        io_error("replace", destination, e)
+    })?;
+    Ok(true)
+}
+
+/// Copy a file through a `.partial` temp file so an interrupted copy never looks
+/// complete. Uses a copy-on-write clone where the filesystem supports it
+/// (APFS, Btrfs, XFS, ReFS), otherwise streams the data. With `verify`, the
+/// destination is re-read and checked against the source hash.
+pub fn copy_file(
+    source: &Path,
+    destination: &Path,
+    verify: bool,
+    cancel: &AtomicBool,
+    on_progress: &mut dyn FnMut(u64, u64),
+) -> Result<CopyOutcome, CopyError> {
+    let source = &long_paths::extended(source);
+    let destination = &long_paths::extended(destination);
+    let total = std::fs::metadata(source)
+        .map_err(|e| io_error("read", source, e))?
+        .len();
+    let partial = partial_path(destination);
+    let _ = std::fs::remove_file(&partial);
+
+    let result = match reflink_copy::reflink(source, &partial) {
+        Ok(()) => sha256_file(source)
+            .map(|sha256| (sha256, true))
+            .map_err(CopyError::Failed),
+        Err(_) => {
+            stream_copy(source, &partial, total, cancel, on_progress).map(|sha256| (sha256, false))
+        }
+    }
+    .and_then(|(sha256, cloned)| {
+        if verify && sha256_file(&partial).map_err(CopyError::Failed)? != sha256 {
+            return Err(CopyError::Failed(format!(
+                "Checksum mismatch after copying {:?}",
+                source
+            )));
+        }
+        std::fs::rename(&partial, destination).map_err(|e| io_error("replace", destination, e))?;
+        Ok((sha256, cloned))
+    });
+
+    match result {
+        Ok((sha256, cloned)) => {
+            on_progress(total, total);
+            Ok(CopyOutcome {
+                bytes: total,
+                sha256,
+                cloned,
+            })
+        }
+        Err(e) => {
+            let _ = std::fs::remove_file(&partial);
+            Err(e)
+        }
+    }
+}
+
+/// Clear any earlier cancellation before starting a new batch of copies
+pub fn begin_transfer(app_handle: &AppHandle) -> Arc<AtomicBool> {
+    let cancelled = app_handle.state::<TransferState>().cancelled.clone();
+    cancelled.store(false, Ordering::Relaxed);
+    cancelled
+}
+
+/// Progress callback that emits `transfer_progress` for a file
+pub fn progress_emitter(app_handle: &AppHandle, file: &str) -> impl FnMut(u64, u64) {
+    let app_handle = app_handle.clone();
+    let file = file.to_string();
+    move |copied_bytes, total_bytes| {
+        let _ = app_handle.emit(
+            "transfer_progress",
+            TransferProgressEvent {
+                file: file.clone(),
+                copied_bytes,
+                total_bytes,
+            },
+        );
+    }
+}
+
+/// Cancel the backup, restore, install copy or archive extraction in progress
+#[tauri::command]
+#[tracing::instrument(skip(state))]
+pub fn cancel_file_transfer(state: State<'_, TransferState>) {
+    state.cancelled.store(true, Ordering::Relaxed);
+}
@@ -0,0 +1,207 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+use walkdir::WalkDir;
+
+use crate::i18n;
+use crate::long_paths;
+use crate::path_guard;
+use crate::validation::{is_generated_dir, ValidationIssue, ValidationReport};
+
+const FONT_EXTENSIONS: &[&str] = &["ttf", "otf"];
+
+/// Missing characters listed in a `missing_glyphs` message
+const MAX_LISTED_GLYPHS: usize = 20;
+
+/// Characters a font needs for each language, beyond the basic Latin letters
+/// and digits under "en". For the CJK scripts these are just common characters
+/// that a font without the script will not have.
+const LANGUAGE_CHARACTERS: &[(&str, &str)] = &[
+    (
+        "en",
+        "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+    ),
+    ("de", "ÄÖÜäöüß"),
+    ("es", "ÁÉÍÑÓÚÜáéíñóúü¿¡"),
+    ("fr", "ÀÂÆÇÈÉÊËÎÏÔŒÙÛÜàâæçèéêëîïôœùûüÿ"),
+    ("pt", "ÀÁÂÃÇÉÊÍÓÔÕÚàáâãçéêíóôõú"),
+    ("it", "ÀÈÉÌÒÙàèéìòù"),
+    ("pl", "ĄĆĘŁŃÓŚŹŻąćęłńóśźż"),
+    ("cs", "ÁČĎÉĚÍŇÓŘŠŤÚŮÝŽáčďéěíňóřšťúůýž"),
+    ("tr", "ÇĞİÖŞÜçğıöşü"),
+    (
+        "ru",
+        "АБВГДЕЁЖЗИЙКЛМНОПРСТУФХЦЧШЩЪЫЬЭЮЯабвгдеёжзийклмнопрстуфхцчшщъыьэюя",
+    ),
+    ("zh", "的一是不了人我在有他这中大来上国个到说们"),
+    ("ja", "あいうえおかきくけこアイウエオカキクケコ日本語"),
+    ("ko", "가나다라마바사아자차카타파하한국어"),
+];
+
+fn language_characters(tag: &str) -> Option<(&'static str, &'static str)> {
+    let language = tag
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    LANGUAGE_CHARACTERS
+        .iter()
+        .find(|(code, _)| *code == language)
+        .copied()
+}
+
+fn skin_fonts(skin_dir: &Path) -> Vec<PathBuf> {
+    WalkDir::new(long_paths::extended(skin_dir))
+        .into_iter()
+        .filter_entry(|entry| !is_generated_dir(entry))
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter(|entry| {
+            entry
+                .path()
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| FONT_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+        })
+        .map(|entry| entry.into_path())
+        .collect()
+}
+
+/// First readable name record with the given ID
+fn font_name(face: &ttf_parser::Face, name_id: u16) -> Option<String> {
+    face.names()
+        .into_iter()
+        .filter(|name| name.name_id == name_id)
+        .find_map(|name| name.to_string())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+}
+
+/// Parse a font, check it covers each language, and return its family and
+/// style for the duplicate check
+fn check_font(
+    path: &Path,
+    languages: &[(&str, &str)],
+    issues: &mut Vec<ValidationIssue>,
+) -> Option<(String, String)> {
+    let data = match std::fs::read(path) {
+        Ok(data) => data,
+        Err(e) => {
+            issues.push(ValidationIssue::error(
+                "unreadable_font",
+                format!("Failed to read font: {}", e),
+                Some(path),
+            ));
+            return None;
+        }
+    };
+    let face = match ttf_parser::Face::parse(&data, 0) {
+        Ok(face) => face,
+        Err(e) => {
+            issues.push(ValidationIssue::error(
+                "invalid_font",
+                format!("Font could not be parsed: {}", e),
+                Some(path),
+            ));
+            return None;
+        }
+    };
+
+    for (language, characters) in languages {
+        let missing: Vec<char> = characters
+            .chars()
+            .filter(|c| face.glyph_index(*c).is_none())
+            .collect();
+        if missing.is_empty() {
+            continue;
+        }
+        let listed: String = missing.iter().take(MAX_LISTED_GLYPHS).collect();
+        issues.push(ValidationIssue::warning(
+            "missing_glyphs",
+            format!(
+                "Font has no glyphs for {} {} character(s): {}",
+                missing.len(),
+                language,
+                listed
+            ),
+            Some(path),
+        ));
+    }
+
+    let family = font_name(&face, ttf_parser::name_id::TYPOGRAPHIC_FAMILY)
+        .or_else(|| font_name(&face, ttf_parser::name_id::FAMILY))?;
+    let style = font_name(&face, ttf_parser::name_id::TYPOGRAPHIC_SUBFAMILY)
+        .or_else(|| font_name(&face, ttf_parser::name_id::SUBFAMILY))
+        .unwrap_or_else(|| "Regular".to_string());
+    Some((family, style))
+}
+
+fn validate_fonts_in(skin_dir: &Path, languages: &[(&str, &str)]) -> ValidationReport {
+    let mut issues = Vec::new();
+    let mut families: HashMap<(String, String), Vec<PathBuf>> = HashMap::new();
+
+    for path in skin_fonts(skin_dir) {
+        if let Some((family, style)) = check_font(&path, languages, &mut issues) {
+            families
+                .entry((family.to_lowercase(), style.to_lowercase()))
+                .or_default()
+                .push(path);
+        }
+    }
+
+    // The game picks fonts by name, so only one of the files would ever be used
+    let mut duplicates: Vec<_> = families
+        .into_iter()
+        .filter(|(_, paths)| paths.len() > 1)
+        .collect();
+    duplicates.sort();
+    for ((family, style), paths) in duplicates {
+        for path in &paths[1..] {
+            issues.push(ValidationIssue::warning(
+                "duplicate_font_family",
+                format!(
+                    "Font family \"{}\" ({}) is also defined by {}",
+                    family,
+                    style,
+                    long_paths::strip_extended(&paths[0].to_string_lossy())
+                ),
+                Some(path),
+            ));
+        }
+    }
+
+    ValidationReport::from_issues(skin_dir, issues)
+}
+
+/// Check a skin's TTF/OTF fonts parse, cover the given languages (default: the
+/// app's languages), and don't share a family name
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub async fn validate_fonts(
+    app_handle: AppHandle,
+    skin_path: String,
+    languages: Option<Vec<String>>,
+) -> Result<ValidationReport, String> {
+    let skin_dir = path_guard::existing_dir(&skin_path, "Skin folder")?;
+    let mut languages = match languages {
+        Some(languages) => languages
+            .iter()
+            .filter(|tag| !tag.trim().is_empty())
+            .map(|tag| {
+                language_characters(tag.trim())
+                    .ok_or_else(|| format!("Unsupported language: {}", tag.trim()))
+            })
+            .collect::<Result<Vec<_>, String>>()?,
+        None => i18n::available_locales(&app_handle)
+            .iter()
+            .filter_map(|tag| language_characters(tag))
+            .collect(),
+    };
+    // "pt" and "pt-BR" need the same characters
+    languages.sort();
+    languages.dedup();
+
+    tokio::task::spawn_blocking(move || validate_fonts_in(&skin_dir, &languages))
+        .await
+        .map_err(|e| format!("Font validation task failed: {}", e))
+}
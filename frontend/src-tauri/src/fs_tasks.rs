@@ -0,0 +1,70 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::State;
+
+use crate::error::{AppError, ErrorKind};
+
+/// Cancellation flags of the filesystem work commands have moved off the IPC thread
+#[derive(Default)]
+pub struct FsTaskState {
+    running: Mutex<Vec<Arc<AtomicBool>>>,
+}
+
+impl FsTaskState {
+    /// Run blocking filesystem work on the blocking thread pool so a slow disk
+    /// or network drive never stalls IPC. `task` gets a flag that
+    /// `cancel_fs_tasks` sets and should check between steps.
+    pub async fn run<T, F>(&self, label: &str, task: F) -> Result<T, AppError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&AtomicBool) -> Result<T, AppError> + Send + 'static,
+    {
+        let cancel = Arc::new(AtomicBool::new(false));
+        if let Ok(mut running) = self.running.lock() {
+            running.push(cancel.clone());
+        }
+
+        let flag = cancel.clone();
+        let result = tokio::task::spawn_blocking(move || task(&flag))
+            .await
+            .map_err(|e| {
+                AppError::new(ErrorKind::Internal, format!("{} task failed: {}", label, e))
+            });
+
+        if let Ok(mut running) = self.running.lock() {
+            running.retain(|running| !Arc::ptr_eq(running, &cancel));
+        }
+        result?
+    }
+
+    /// Flag every running task; returns how many there were
+    fn cancel_all(&self) -> usize {
+        let Ok(running) = self.running.lock() else {
+            return 0;
+        };
+        for cancel in running.iter() {
+            cancel.store(true, Ordering::Relaxed);
+        }
+        running.len()
+    }
+}
+
+/// Stop a task between steps once `cancel_fs_tasks` was called
+pub fn check_cancelled(cancel: &AtomicBool, label: &str) -> Result<(), AppError> {
+    if cancel.load(Ordering::Relaxed) {
+        return Err(AppError::new(
+            ErrorKind::Cancelled,
+            format!("{} cancelled", label),
+        ));
+    }
+    Ok(())
+}
+
+/// Cancel the cache, skins folder and game folder scans still running
+#[tauri::command]
+#[tracing::instrument(skip(state))]
+pub fn cancel_fs_tasks(state: State<'_, FsTaskState>) -> usize {
+    let cancelled = state.cancel_all();
+    println!("[DEBUG] Cancelled {} filesystem task(s)", cancelled);
+    cancelled
+}
@@ -0,0 +1,206 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+use crate::storage;
+
+const GAME_PROFILES_FILE: &str = "game_profiles.json";
+
+/// A Football Manager install and the defaults used when building against it
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct GameProfile {
+    pub game_path: String,
+    pub bundles_path: String,
+    /// Folder for pristine bundle copies; the app data folder when unset
+    pub backup_path: Option<String>,
+    pub store: Option<String>, // "steam", "epic", "gamepass"
+    pub debug_export: bool,
+    pub dry_run: bool,
+    pub extra_args: Vec<String>,
+    pub updated_at: u64,
+    /// Progress of the Game Pass workaround for installs that can't be written to
+    pub gamepass: Option<GamepassWorkaround>,
+}
+
+/// Where a profile stands with `prepare_gamepass_workaround`
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct GamepassWorkaround {
+    /// "not_needed", "staged" (builds are copied to the staging folder for a
+    /// manual copy) or "mods_enabled" (the profile now uses a writable install)
+    pub status: String,
+    pub staging_path: Option<String>,
+    pub instructions: Vec<String>,
+    pub updated_at: u64,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct GameProfileList {
+    pub active: Option<String>,
+    pub profiles: BTreeMap<String, GameProfile>,
+}
+
+fn load_game_profiles(app_handle: &AppHandle) -> Result<(PathBuf, GameProfileList), String> {
+    let path = storage::data_file(app_handle, GAME_PROFILES_FILE)?;
+    let list = storage::load_json(&path);
+    Ok((path, list))
+}
+
+/// The profile selected with `switch_profile`, if any
+pub fn active_profile(app_handle: &AppHandle) -> Option<GameProfile> {
+    let (_, mut list) = load_game_profiles(app_handle).ok()?;
+    let name = list.active.take()?;
+    list.profiles.remove(&name)
+}
+
+/// A saved profile by name
+pub fn find_profile(app_handle: &AppHandle, name: &str) -> Result<GameProfile, String> {
+    let (_, mut list) = load_game_profiles(app_handle)?;
+    list.profiles
+        .remove(name.trim())
+        .ok_or_else(|| format!("Game profile not found: {}", name))
+}
+
+/// Replace a profile that already exists, keeping whether it is active
+pub fn update_profile(
+    app_handle: &AppHandle,
+    name: &str,
+    profile: GameProfile,
+) -> Result<(), String> {
+    let path = storage::data_file(app_handle, GAME_PROFILES_FILE)?;
+    storage::update_json(&path, |list: &mut GameProfileList| {
+        let existing = list
+            .profiles
+            .get_mut(name.trim())
+            .ok_or_else(|| format!("Game profile not found: {}", name))?;
+        *existing = profile;
+        Ok(())
+    })
+}
+
+/// Game Pass staging folder of the profile that owns a bundles directory,
+/// while that profile's workaround is staging builds
+pub fn gamepass_staging_for(app_handle: &AppHandle, bundles_dir: &Path) -> Option<PathBuf> {
+    let (_, list) = load_game_profiles(app_handle).ok()?;
+    list.profiles
+        .into_values()
+        .filter(|profile| Path::new(&profile.bundles_path) == bundles_dir)
+        .filter_map(|profile| profile.gamepass)
+        .filter(|workaround| workaround.status == "staged")
+        .find_map(|workaround| workaround.staging_path)
+        .map(PathBuf::from)
+}
+
+/// Custom backup folder of the profile that owns a bundles directory
+pub fn backup_root_for(app_handle: &AppHandle, bundles_dir: &Path) -> Option<PathBuf> {
+    let (_, list) = load_game_profiles(app_handle).ok()?;
+    list.profiles
+        .into_values()
+        .filter(|profile| Path::new(&profile.bundles_path) == bundles_dir)
+        .find_map(|profile| profile.backup_path.filter(|path| !path.trim().is_empty()))
+        .map(|path| PathBuf::from(path.trim()))
+}
+
+/// Custom backup folders of every profile
+pub fn backup_roots(app_handle: &AppHandle) -> Vec<PathBuf> {
+    let Ok((_, list)) = load_game_profiles(app_handle) else {
+        return Vec::new();
+    };
+    list.profiles
+        .into_values()
+        .filter_map(|profile| profile.backup_path)
+        .filter(|path| !path.trim().is_empty())
+        .map(|path| PathBuf::from(path.trim()))
+        .collect()
+}
+
+/// Store recorded on the profile that owns a bundles directory
+pub fn store_for(app_handle: &AppHandle, bundles_dir: &Path) -> Option<String> {
+    let (_, list) = load_game_profiles(app_handle).ok()?;
+    list.profiles
+        .into_values()
+        .filter(|profile| Path::new(&profile.bundles_path) == bundles_dir)
+        .find_map(|profile| profile.store.filter(|store| !store.trim().is_empty()))
+}
+
+/// Saved game-install profiles and which one is active
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub fn list_profiles(app_handle: AppHandle) -> Result<GameProfileList, String> {
+    let (_, list) = load_game_profiles(&app_handle)?;
+    Ok(list)
+}
+
+/// Make a profile active for detection, backups and builds; `None` clears it
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub fn switch_profile(app_handle: AppHandle, name: Option<String>) -> Result<(), String> {
+    let path = storage::data_file(&app_handle, GAME_PROFILES_FILE)?;
+    let name = name
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty());
+
+    storage::update_json(&path, |list: &mut GameProfileList| {
+        if let Some(name) = &name {
+            if !list.profiles.contains_key(name) {
+                return Err(format!("Game profile not found: {}", name));
+            }
+        }
+
+        list.active = name;
+        Ok(())
+    })
+}
+
+/// Create or replace a game-install profile
+#[tauri::command]
+#[tracing::instrument(skip(app_handle, profile), err)]
+pub fn save_game_profile(
+    app_handle: AppHandle,
+    name: String,
+    mut profile: GameProfile,
+) -> Result<(), String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Profile name is required".to_string());
+    }
+
+    profile.game_path = profile.game_path.trim().to_string();
+    profile.bundles_path = profile.bundles_path.trim().to_string();
+    if profile.bundles_path.is_empty() {
+        return Err("Bundles path is required".to_string());
+    }
+    profile.updated_at = storage::unix_now();
+
+    let path = storage::data_file(&app_handle, GAME_PROFILES_FILE)?;
+    storage::update_json(&path, |list: &mut GameProfileList| {
+        // Workaround progress is tracked here, not edited by the caller, and
+        // only applies to the install it was prepared for
+        profile.gamepass = list
+            .profiles
+            .get(name)
+            .filter(|existing| existing.bundles_path == profile.bundles_path)
+            .and_then(|existing| existing.gamepass.clone());
+        list.profiles.insert(name.to_string(), profile);
+        Ok(())
+    })
+}
+
+/// Remove a game-install profile, deactivating it if it was active
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub fn delete_game_profile(app_handle: AppHandle, name: String) -> Result<bool, String> {
+    let path = storage::data_file(&app_handle, GAME_PROFILES_FILE)?;
+    let name = name.trim();
+
+    storage::update_json(&path, |list: &mut GameProfileList| {
+        let removed = list.profiles.remove(name).is_some();
+        if list.active.as_deref() == Some(name) {
+            list.active = None;
+        }
+        Ok(removed)
+    })
+}
@@ -0,0 +1,156 @@
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+use crate::backups;
+use crate::cache::open_in_file_browser;
+use crate::file_copy;
+use crate::game_profiles;
+use crate::hashing::{self, HashAlgorithm};
+use crate::path_guard;
+use crate::steam;
+
+#[derive(Serialize)]
+pub struct GameFileVerification {
+    pub store: String, // "steam", "epic", "gamepass", "unknown"
+    /// Whether the store's own verify flow was started
+    pub launched: bool,
+    pub instructions: String,
+}
+
+#[derive(Serialize)]
+pub struct OriginalsComparison {
+    pub bundles_path: String,
+    pub backup_created_at: u64,
+    pub matching: u32,
+    /// Bundles whose contents differ from the backed up original
+    pub changed: Vec<String>,
+    /// Backed up bundles no longer in the game directory
+    pub missing: Vec<String>,
+    /// Bundles in the game directory with no backed up original
+    pub untracked: Vec<String>,
+}
+
+/// Store a game install came from: its profile's setting, else a guess from
+/// where it is installed
+fn detect_store(app_handle: &AppHandle, bundles_dir: &Path) -> String {
+    if let Some(store) = game_profiles::store_for(app_handle, bundles_dir) {
+        return store.trim().to_lowercase();
+    }
+    let path = bundles_dir.to_string_lossy().to_lowercase();
+    if path.contains("steamapps") {
+        "steam"
+    } else if path.contains("epic games") {
+        "epic"
+    } else if path.contains("xboxgames") || path.contains("windowsapps") {
+        "gamepass"
+    } else {
+        "unknown"
+    }
+    .to_string()
+}
+
+/// Start the store's "verify game files" flow where it has one (Steam), and
+/// explain how to do it by hand for the others
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub fn request_game_file_verification(
+    app_handle: AppHandle,
+    bundles_path: String,
+) -> Result<GameFileVerification, String> {
+    let bundles_dir = path_guard::existing_dir(&bundles_path, "Bundles folder")?;
+    let store = detect_store(&app_handle, &bundles_dir);
+
+    let (launched, instructions) = match store.as_str() {
+        "steam" => match steam::app_id(&bundles_dir) {
+            Some(app_id) => {
+                // The system openers that show folders also hand steam:// URLs to Steam
+                let url = format!("steam://validate/{}", app_id);
+                open_in_file_browser(&app_handle, Path::new(&url))?;
+                (
+                    true,
+                    "Steam is verifying the game files. Wait for it to finish before building or installing again.".to_string(),
+                )
+            }
+            None => (
+                false,
+                "In Steam, right-click the game, choose Properties > Installed Files, then \"Verify integrity of game files\".".to_string(),
+            ),
+        },
+        "epic" => (
+            false,
+            "In the Epic Games Launcher, open the game's \"...\" menu in your Library, choose Manage, then Verify.".to_string(),
+        ),
+        "gamepass" => (
+            false,
+            "In the Xbox app, open the game's \"...\" menu, choose Manage > Files, then \"Verify and repair\".".to_string(),
+        ),
+        _ => (
+            false,
+            "Use your game store's \"verify\" or \"repair\" option for the game, or compare with the backed up originals.".to_string(),
+        ),
+    };
+
+    Ok(GameFileVerification {
+        store,
+        launched,
+        instructions,
+    })
+}
+
+fn compare(app_handle: &AppHandle, bundles_dir: &Path) -> Result<OriginalsComparison, String> {
+    let manifest = backups::load_manifest(&backups::install_backup_dir(app_handle, bundles_dir)?)
+        .ok_or("No backup of the original bundles exists for this game directory")?;
+    let game_bundles = backups::list_bundle_files(bundles_dir);
+
+    let mut missing = Vec::new();
+    let mut to_hash: Vec<(&String, &String, PathBuf)> = Vec::new();
+    for (name, file) in &manifest.files {
+        match game_bundles.get(name) {
+            Some(path) => to_hash.push((name, &file.sha256, path.clone())),
+            None => missing.push(name.clone()),
+        }
+    }
+    let untracked = game_bundles
+        .keys()
+        .filter(|name| !manifest.files.contains_key(*name))
+        .cloned()
+        .collect();
+
+    let paths: Vec<PathBuf> = to_hash.iter().map(|(_, _, path)| path.clone()).collect();
+    let cancel = file_copy::begin_transfer(app_handle);
+    let hashes = hashing::hash_files(app_handle, &paths, HashAlgorithm::Sha256, &cancel);
+
+    let mut matching = 0;
+    let mut changed = Vec::new();
+    for ((name, original, _), hash) in to_hash.into_iter().zip(hashes) {
+        if &hash? == original {
+            matching += 1;
+        } else {
+            changed.push(name.clone());
+        }
+    }
+
+    Ok(OriginalsComparison {
+        bundles_path: bundles_dir.to_string_lossy().to_string(),
+        backup_created_at: manifest.created_at,
+        matching,
+        changed,
+        missing,
+        untracked,
+    })
+}
+
+/// Hash every bundle against the original backed up before the first install,
+/// for when the store can't verify the game or the user wants a second opinion
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub async fn compare_with_originals(
+    app_handle: AppHandle,
+    bundles_path: String,
+) -> Result<OriginalsComparison, String> {
+    let bundles_dir = path_guard::existing_dir(&bundles_path, "Bundles folder")?;
+    tokio::task::spawn_blocking(move || compare(&app_handle, &bundles_dir))
+        .await
+        .map_err(|e| format!("Comparison task failed: {}", e))?
+}
@@ -0,0 +1,340 @@
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tokio::sync::mpsc;
+use tokio::time::{timeout, Duration};
+
+use crate::backups;
+use crate::build_outputs;
+use crate::error::{AppError, ErrorKind};
+use crate::events::SkinOverwrittenEvent;
+use crate::hashing::sha256_file;
+use crate::i18n::t;
+use crate::install::{self, InstallResult, InstalledSkin};
+use crate::path_guard;
+use crate::process::{run_python_task, ProcessState, TaskConfig, OUTPUT_MODE_STAGING};
+use crate::projects;
+use crate::scheduler;
+use crate::skins::read_skin_info;
+use crate::steam;
+
+/// Quiet period after the last bundle change before the install is checked,
+/// long enough for an update to finish writing
+const SETTLE_DELAY: Duration = Duration::from_secs(30);
+
+/// How often to look again while Steam is still applying an update
+const UPDATE_PENDING_RECHECK: Duration = Duration::from_secs(60);
+
+/// Watchers on the bundles folders skins are installed into
+#[derive(Default)]
+pub struct GameWatcherState {
+    watchers: Mutex<HashMap<PathBuf, RecommendedWatcher>>,
+    /// Installs already reported as overwritten, by bundles path, skin path and install time
+    reported: Mutex<HashSet<(String, String, u64)>>,
+}
+
+/// Bundles of an install that now hold something other than what it wrote.
+/// Content another installed skin wrote, or the backed up original, means the
+/// user changed the install themselves, so only unknown content counts.
+fn overwritten_bundles(
+    bundles_dir: &Path,
+    skin: &InstalledSkin,
+    others: &[&InstalledSkin],
+    originals: &backups::BackupManifest,
+) -> Result<Vec<String>, String> {
+    let mut overwritten = Vec::new();
+    for (name, bundle) in &skin.bundles {
+        let path = bundles_dir.join(name);
+        // A bundle the game no longer ships can't be re-applied anyway
+        let Ok(metadata) = std::fs::metadata(&path) else {
+            continue;
+        };
+        let current = sha256_file(&path)?;
+        if metadata.len() == bundle.size && current == bundle.sha256 {
+            continue;
+        }
+
+        let known = others.iter().any(|other| {
+            other
+                .bundles
+                .get(name)
+                .is_some_and(|other| other.sha256 == current)
+        }) || originals
+            .files
+            .get(name)
+            .is_some_and(|original| original.sha256 == current);
+        if !known {
+            overwritten.push(name.clone());
+        }
+    }
+    Ok(overwritten)
+}
+
+/// Installs in a bundles folder whose bundles a game update has replaced
+fn find_overwritten(
+    app_handle: &AppHandle,
+    bundles_dir: &Path,
+) -> Result<Vec<SkinOverwrittenEvent>, String> {
+    let bundles_path = bundles_dir.to_string_lossy().to_string();
+    let (_, mut installed_skins) = install::load_installed(app_handle)?;
+    let skins: Vec<InstalledSkin> = installed_skins
+        .remove(&bundles_path)
+        .unwrap_or_default()
+        .into_values()
+        .collect();
+    let originals = backups::load_manifest(&backups::install_backup_dir(app_handle, bundles_dir)?)
+        .unwrap_or_default();
+    let (_, projects) = projects::load_projects(app_handle)?;
+
+    let mut events = Vec::new();
+    for skin in &skins {
+        let others: Vec<&InstalledSkin> = skins
+            .iter()
+            .filter(|other| other.skin_path != skin.skin_path)
+            .collect();
+        let overwritten = overwritten_bundles(bundles_dir, skin, &others, &originals)?;
+        if overwritten.is_empty() {
+            continue;
+        }
+
+        let skin_name = read_skin_info(Path::new(&skin.skin_path))
+            .map(|info| info.name)
+            .unwrap_or_else(|_| skin.skin_path.clone());
+        events.push(SkinOverwrittenEvent {
+            message: t(
+                app_handle,
+                "game_update.skin_overwritten",
+                &[("skin", &skin_name)],
+            ),
+            bundles_path: bundles_path.clone(),
+            skin_path: skin.skin_path.clone(),
+            skin_name,
+            installed_at: skin.installed_at,
+            overwritten,
+            project_id: reapply_project(&projects, &skin.skin_path, &bundles_path)
+                .map(|project| project.id.clone()),
+        });
+    }
+    Ok(events)
+}
+
+/// Check a bundles folder once Steam has finished updating it, and report each
+/// overwritten install once per session
+async fn check_install(app_handle: &AppHandle, bundles_dir: &Path) {
+    while steam::update_status(bundles_dir).is_some_and(|status| status.update_pending) {
+        tokio::time::sleep(UPDATE_PENDING_RECHECK).await;
+    }
+
+    let handle = app_handle.clone();
+    let dir = bundles_dir.to_path_buf();
+    let events = match tokio::task::spawn_blocking(move || find_overwritten(&handle, &dir)).await {
+        Ok(Ok(events)) => events,
+        Ok(Err(e)) => {
+            println!("[WARNING] Failed to check installed skins: {}", e);
+            return;
+        }
+        Err(e) => {
+            println!("[WARNING] Installed skin check task failed: {}", e);
+            return;
+        }
+    };
+
+    let state = app_handle.state::<GameWatcherState>();
+    for event in events {
+        let key = (
+            event.bundles_path.clone(),
+            event.skin_path.clone(),
+            event.installed_at,
+        );
+        let first_report = state
+            .reported
+            .lock()
+            .map(|mut reported| reported.insert(key))
+            .unwrap_or(false);
+        if !first_report {
+            continue;
+        }
+
+        println!(
+            "[WARNING] Game update overwrote {} bundle(s) of {}",
+            event.overwritten.len(),
+            event.skin_path
+        );
+        let _ = app_handle.emit("skin_overwritten_by_update", event);
+    }
+}
+
+async fn listen_for_updates(
+    app_handle: AppHandle,
+    bundles_dir: PathBuf,
+    mut receiver: mpsc::UnboundedReceiver<()>,
+) {
+    // Catch updates installed while the app was closed
+    check_install(&app_handle, &bundles_dir).await;
+
+    while receiver.recv().await.is_some() {
+        while let Ok(Some(())) = timeout(SETTLE_DELAY, receiver.recv()).await {}
+        check_install(&app_handle, &bundles_dir).await;
+    }
+}
+
+fn watch_bundles_dir(
+    app_handle: &AppHandle,
+    bundles_dir: &Path,
+) -> Result<RecommendedWatcher, String> {
+    let (sender, receiver) = mpsc::unbounded_channel::<()>();
+
+    let mut watcher = notify::recommended_watcher(move |result: notify::Result<notify::Event>| {
+        let Ok(event) = result else {
+            return;
+        };
+        if matches!(event.kind, EventKind::Access(_)) {
+            return;
+        }
+        if event
+            .paths
+            .iter()
+            .any(|path| path.extension().is_some_and(|ext| ext == "bundle"))
+        {
+            let _ = sender.send(());
+        }
+    })
+    .map_err(|e| format!("Failed to create game folder watcher: {}", e))?;
+
+    watcher
+        .watch(bundles_dir, RecursiveMode::NonRecursive)
+        .map_err(|e| format!("Failed to watch game folder: {}", e))?;
+
+    tauri::async_runtime::spawn(listen_for_updates(
+        app_handle.clone(),
+        bundles_dir.to_path_buf(),
+        receiver,
+    ));
+    Ok(watcher)
+}
+
+/// Watch every bundles folder a skin is installed into, and stop watching the
+/// ones nothing is installed in anymore. Called at startup and after installs.
+pub fn refresh(app_handle: &AppHandle) {
+    let bundles_dirs: HashSet<PathBuf> = match install::load_installed(app_handle) {
+        Ok((_, installed_skins)) => installed_skins
+            .into_iter()
+            .filter(|(_, skins)| !skins.is_empty())
+            .map(|(bundles_path, _)| PathBuf::from(bundles_path))
+            .filter(|dir| dir.is_dir())
+            .collect(),
+        Err(e) => {
+            println!("[WARNING] Failed to load installed skins: {}", e);
+            return;
+        }
+    };
+
+    let state = app_handle.state::<GameWatcherState>();
+    let Ok(mut watchers) = state.watchers.lock() else {
+        return;
+    };
+    // Dropping a watcher ends its listener
+    watchers.retain(|dir, _| bundles_dirs.contains(dir));
+    for dir in bundles_dirs {
+        if watchers.contains_key(&dir) {
+            continue;
+        }
+        match watch_bundles_dir(app_handle, &dir) {
+            Ok(watcher) => {
+                watchers.insert(dir, watcher);
+            }
+            Err(e) => println!("[WARNING] {}", e),
+        }
+    }
+}
+
+/// The saved project that builds this skin for this game, if any
+fn reapply_project<'a>(
+    projects: &'a [projects::Project],
+    skin_path: &str,
+    bundles_path: &str,
+) -> Option<&'a projects::Project> {
+    projects
+        .iter()
+        .filter(|project| !project.dry_run)
+        .filter(|project| {
+            project.skin_path.trim() == skin_path && project.bundles_path.trim() == bundles_path
+        })
+        .max_by_key(|project| project.last_run.as_ref().map(|run| run.finished_at))
+}
+
+/// Rebuild a skin against the updated game bundles and install it again, using
+/// its saved project when there is one and the default build settings otherwise
+#[tauri::command]
+#[tracing::instrument(skip(app_handle, state), err)]
+pub async fn reapply_last_skin(
+    app_handle: AppHandle,
+    state: State<'_, ProcessState>,
+    skin_path: String,
+    bundles_path: String,
+) -> Result<InstallResult, AppError> {
+    let skin_dir = path_guard::existing_dir(&skin_path, "Skin folder")?;
+    let bundles_dir = path_guard::existing_dir(&bundles_path, "Bundles folder")?;
+    if state.is_run_active() {
+        return Err(AppError::new(
+            ErrorKind::Conflict,
+            "A build is already running",
+        ));
+    }
+
+    let skin_path = skin_dir.to_string_lossy().to_string();
+    let bundles_path = bundles_dir.to_string_lossy().to_string();
+    let (_, saved_projects) = projects::load_projects(&app_handle)?;
+    let project = reapply_project(&saved_projects, &skin_path, &bundles_path).cloned();
+
+    let (output, extra_args) = match project {
+        Some(project) => {
+            println!(
+                "[DEBUG] Re-applying {} from project {}",
+                skin_path, project.name
+            );
+            let output = projects::run_project(app_handle.clone(), state, project.id).await?;
+            (output, project.extra_args)
+        }
+        None => {
+            let config = TaskConfig {
+                skin_path: skin_path.clone(),
+                bundles_path: bundles_path.clone(),
+                debug_export: false,
+                dry_run: false,
+                snapshot_before_build: false,
+                profile: None,
+                extra_args: Vec::new(),
+                project_id: None,
+                incremental: false,
+                bundles: Vec::new(),
+                verbosity: None,
+                // Installed below, after the build
+                output_mode: Some(OUTPUT_MODE_STAGING.to_string()),
+            };
+            let output = run_python_task(app_handle.clone(), config, state, None).await?;
+            (output, Vec::new())
+        }
+    };
+    if !output.success {
+        return Err(AppError::new(
+            ErrorKind::Backend,
+            format!("Build failed with exit code {}", output.status),
+        ));
+    }
+
+    let output_dir = build_outputs::current_output_dir(&app_handle, &skin_path, &extra_args);
+    let result = tokio::task::spawn_blocking(move || {
+        let result = install::install_output(&app_handle, &skin_dir, &bundles_dir, &output_dir);
+        if result.is_ok() {
+            scheduler::refresh_game_versions(&app_handle, &bundles_dir);
+            refresh(&app_handle);
+        }
+        result
+    })
+    .await
+    .map_err(|e| format!("Install task failed: {}", e))??;
+    Ok(result)
+}
@@ -0,0 +1,138 @@
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+use crate::backups;
+use crate::game_profiles::{self, GamepassWorkaround};
+use crate::portable;
+use crate::storage;
+use crate::write_access;
+
+/// Bundles folder inside a Game Pass install with mods enabled
+const MODDABLE_BUNDLES_SUBDIR: &str = "Content/data/StreamingAssets/aa/StandaloneWindows64";
+
+/// Installs the Xbox app put in `<drive>:\XboxGames` once "Enable mods" (or
+/// advanced management) is turned on for the game. Unlike WindowsApps these
+/// belong to the user and can be patched in place.
+fn moddable_installs() -> Vec<PathBuf> {
+    if !cfg!(windows) {
+        return Vec::new();
+    }
+
+    ('C'..='Z')
+        .filter_map(|drive| std::fs::read_dir(format!("{}:\\XboxGames", drive)).ok())
+        .flat_map(|entries| entries.flatten().map(|entry| entry.path()))
+        .filter(|dir| {
+            dir.file_name()
+                .map(|name| name.to_string_lossy().to_lowercase())
+                .is_some_and(|name| name.contains("football manager"))
+        })
+        .map(|dir| dir.join(MODDABLE_BUNDLES_SUBDIR))
+        .filter(|bundles| bundles.is_dir())
+        .collect()
+}
+
+/// Staging folder for a profile's built bundles, under app data
+fn staging_dir(app_handle: &AppHandle, profile: &str) -> Result<PathBuf, String> {
+    let digest = Sha256::digest(profile.as_bytes());
+    Ok(portable::app_data_dir(app_handle)?
+        .join("gamepass")
+        .join(&format!("{:x}", digest)[..16]))
+}
+
+fn staging_steps(staging: &Path, bundles_dir: &Path) -> Vec<String> {
+    vec![
+        "Build your skin as usual; the patched bundles are copied to the staging folder instead of the game.".to_string(),
+        format!("Staging folder: {}", staging.display()),
+        "In the Xbox app, open Football Manager, choose Manage, then Files, and turn on \"Enable mods\" if it is offered. Otherwise reinstall the game with advanced management options enabled so it installs to an XboxGames folder.".to_string(),
+        "Run the Game Pass workaround again: once a moddable install is found, this profile switches to it and skins install in place.".to_string(),
+        format!(
+            "Until then, close the game and copy the .bundle files from the staging folder over the ones in the game's bundles folder ({}), using an account that can write there.",
+            bundles_dir.display()
+        ),
+        "Verify the game files in the Xbox app to undo the skin.".to_string(),
+    ]
+}
+
+/// Copy the built bundles of a profile using the workaround into its staging
+/// folder, returning how many were staged
+pub fn stage_output(output_dir: &Path, staging: &Path) -> Result<usize, String> {
+    let built = backups::list_bundle_files(output_dir);
+    if built.is_empty() {
+        return Err(format!(
+            "No built bundles found in {}. Run a build first.",
+            output_dir.display()
+        ));
+    }
+
+    std::fs::create_dir_all(staging)
+        .map_err(|e| format!("Failed to create staging folder: {}", e))?;
+    for (name, source) in &built {
+        std::fs::copy(source, staging.join(name))
+            .map_err(|e| format!("Failed to stage {}: {}", name, e))?;
+    }
+    Ok(built.len())
+}
+
+/// Work out how a Game Pass profile whose bundles can't be written to should
+/// get skins: switch it to an install with mods enabled when there is one,
+/// else stage builds in a folder with step-by-step copy instructions. The
+/// result is saved on the profile.
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub async fn prepare_gamepass_workaround(
+    app_handle: AppHandle,
+    profile: String,
+) -> Result<GamepassWorkaround, String> {
+    tokio::task::spawn_blocking(move || {
+        let name = profile.trim();
+        let mut game_profile = game_profiles::find_profile(&app_handle, name)?;
+        let bundles_dir = PathBuf::from(game_profile.bundles_path.trim());
+
+        let access = write_access::probe_access(&bundles_dir);
+        let mut workaround = if access.mode == "in_place" {
+            GamepassWorkaround {
+                status: "not_needed".to_string(),
+                instructions: vec![access.message],
+                ..Default::default()
+            }
+        } else if let Some(moddable) = moddable_installs()
+            .into_iter()
+            .find(|dir| write_access::probe_access(dir).mode == "in_place")
+        {
+            println!(
+                "[DEBUG] Switching profile {} to the moddable install {:?}",
+                name, moddable
+            );
+            game_profile.bundles_path = moddable.to_string_lossy().to_string();
+            if let Some(game_dir) = moddable.ancestors().nth(4) {
+                game_profile.game_path = game_dir.to_string_lossy().to_string();
+            }
+            GamepassWorkaround {
+                status: "mods_enabled".to_string(),
+                instructions: vec![format!(
+                    "Mods are enabled for this install; skins now install in place into {}.",
+                    moddable.display()
+                )],
+                ..Default::default()
+            }
+        } else {
+            let staging = staging_dir(&app_handle, name)?;
+            std::fs::create_dir_all(&staging)
+                .map_err(|e| format!("Failed to create staging folder: {}", e))?;
+            GamepassWorkaround {
+                status: "staged".to_string(),
+                instructions: staging_steps(&staging, &bundles_dir),
+                staging_path: Some(staging.to_string_lossy().to_string()),
+                ..Default::default()
+            }
+        };
+
+        workaround.updated_at = storage::unix_now();
+        game_profile.gamepass = Some(workaround.clone());
+        game_profiles::update_profile(&app_handle, name, game_profile)?;
+        Ok(workaround)
+    })
+    .await
+    .map_err(|e| format!("Game Pass workaround task failed: {}", e))?
+}
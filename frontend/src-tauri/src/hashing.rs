@@ -0,0 +1,140 @@
+use rayon::prelude::*;
+use sha2::{Digest, Sha256};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+use crate::events::HashProgressEvent;
+
+/// SHA-256 of a file as a lowercase hex string, read in 1 MB chunks
+pub fn sha256_file(path: &Path) -> Result<String, String> {
+    hash_file(
+        path,
+        HashAlgorithm::Sha256,
+        &AtomicBool::new(false),
+        &|_| {},
+    )
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum HashAlgorithm {
+    /// Needed wherever hashes are compared with stored or published SHA-256 values
+    Sha256,
+    /// Several times faster; used for hashes only this app reads back
+    Blake3,
+}
+
+/// Whole files hashed concurrently; more readers than this thrash spinning disks
+const MAX_PARALLEL_FILES: usize = 4;
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+fn hash_pool() -> &'static rayon::ThreadPool {
+    static POOL: OnceLock<rayon::ThreadPool> = OnceLock::new();
+    POOL.get_or_init(|| {
+        let threads = std::thread::available_parallelism()
+            .map(|count| count.get())
+            .unwrap_or(2)
+            .min(MAX_PARALLEL_FILES);
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .thread_name(|index| format!("hash-{}", index))
+            .build()
+            .expect("failed to build hashing thread pool")
+    })
+}
+
+/// Hash a file in 1 MB chunks, stopping early when `cancel` is set.
+/// `on_read` receives the size of each chunk.
+pub fn hash_file(
+    path: &Path,
+    algorithm: HashAlgorithm,
+    cancel: &AtomicBool,
+    on_read: &dyn Fn(u64),
+) -> Result<String, String> {
+    let mut file =
+        std::fs::File::open(path).map_err(|e| format!("Failed to open {:?}: {}", path, e))?;
+    let mut sha256 = Sha256::new();
+    let mut blake3 = blake3::Hasher::new();
+    let mut buffer = vec![0u8; 1024 * 1024];
+
+    loop {
+        if cancel.load(Ordering::Relaxed) {
+            return Err("Hashing cancelled".to_string());
+        }
+        let read = file
+            .read(&mut buffer)
+            .map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+        if read == 0 {
+            break;
+        }
+        match algorithm {
+            HashAlgorithm::Sha256 => sha256.update(&buffer[..read]),
+            HashAlgorithm::Blake3 => {
+                blake3.update(&buffer[..read]);
+            }
+        }
+        on_read(read as u64);
+    }
+
+    Ok(match algorithm {
+        HashAlgorithm::Sha256 => format!("{:x}", sha256.finalize()),
+        HashAlgorithm::Blake3 => blake3.finalize().to_hex().to_string(),
+    })
+}
+
+/// Hash many files on a shared thread pool, emitting throttled `hash_progress`
+/// events. Results are in the same order as `paths`; once `cancel` is set the
+/// remaining files fail with "Hashing cancelled".
+pub fn hash_files(
+    app_handle: &AppHandle,
+    paths: &[PathBuf],
+    algorithm: HashAlgorithm,
+    cancel: &AtomicBool,
+) -> Vec<Result<String, String>> {
+    let total_files = paths.len() as u32;
+    let total_bytes: u64 = paths
+        .iter()
+        .map(|path| std::fs::metadata(path).map(|meta| meta.len()).unwrap_or(0))
+        .sum();
+    let hashed_bytes = AtomicU64::new(0);
+    let completed_files = AtomicU32::new(0);
+    let last_emit = Mutex::new(Instant::now());
+
+    let emit = |force: bool| {
+        let Ok(mut last) = last_emit.lock() else {
+            return;
+        };
+        if !force && last.elapsed() < PROGRESS_INTERVAL {
+            return;
+        }
+        *last = Instant::now();
+        let _ = app_handle.emit(
+            "hash_progress",
+            HashProgressEvent {
+                completed_files: completed_files.load(Ordering::Relaxed),
+                total_files,
+                hashed_bytes: hashed_bytes.load(Ordering::Relaxed),
+                total_bytes,
+            },
+        );
+    };
+
+    let results = hash_pool().install(|| {
+        paths
+            .par_iter()
+            .map(|path| {
+                let result = hash_file(path, algorithm, cancel, &|read| {
+                    hashed_bytes.fetch_add(read, Ordering::Relaxed);
+                    emit(false);
+                });
+                completed_files.fetch_add(1, Ordering::Relaxed);
+                result
+            })
+            .collect()
+    });
+    emit(true);
+    results
+}
@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::storage;
+
+const HISTORY_FILE: &str = "build_history.json";
+
+/// Number of build records kept on disk
+const MAX_HISTORY_ENTRIES: usize = 200;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct BuildRecord {
+    pub skin_path: String,
+    pub bundles_path: String,
+    pub dry_run: bool,
+    pub success: bool,
+    pub cancelled: bool,
+    pub exit_code: i32,
+    pub started_at: u64,
+    pub finished_at: u64,
+    #[serde(default)]
+    pub run_id: Option<String>,
+    /// OS crash dumps or logs collected when the backend crashed
+    #[serde(default)]
+    pub crash_artifacts: Vec<String>,
+}
+
+/// Load all build records, oldest first
+pub fn load_history(app_handle: &AppHandle) -> Vec<BuildRecord> {
+    match storage::data_file(app_handle, HISTORY_FILE) {
+        Ok(path) => storage::load_json(&path),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Append a build record, trimming the oldest entries beyond the retention limit
+pub fn record_build(app_handle: &AppHandle, record: BuildRecord) -> Result<(), String> {
+    let path = storage::data_file(app_handle, HISTORY_FILE)?;
+    storage::update_json(&path, |history: &mut Vec<BuildRecord>| {
+        history.push(record);
+        if history.len() > MAX_HISTORY_ENTRIES {
+            let excess = history.len() - MAX_HISTORY_ENTRIES;
+            history.drain(..excess);
+        }
+        Ok(())
+    })
+}
+
+/// Point existing records at a skin's new location after a rename
+pub fn rename_skin_path(
+    app_handle: &AppHandle,
+    old_path: &str,
+    new_path: &str,
+) -> Result<(), String> {
+    let path = storage::data_file(app_handle, HISTORY_FILE)?;
+    storage::update_json(&path, |history: &mut Vec<BuildRecord>| {
+        for record in history
+            .iter_mut()
+            .filter(|record| record.skin_path == old_path)
+        {
+            record.skin_path = new_path.to_string();
+        }
+        Ok(())
+    })
+}
+
+/// Most recent build record for a skin folder
+pub fn last_build_for<'a>(history: &'a [BuildRecord], skin_path: &str) -> Option<&'a BuildRecord> {
+    history
+        .iter()
+        .rev()
+        .find(|record| record.skin_path == skin_path)
+}
+
+/// Get build history, newest first, optionally filtered to a single skin
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub fn get_build_history(
+    app_handle: AppHandle,
+    skin_path: Option<String>,
+) -> Result<Vec<BuildRecord>, String> {
+    let history = load_history(&app_handle);
+
+    Ok(history
+        .into_iter()
+        .rev()
+        .filter(|record| {
+            skin_path
+                .as_ref()
+                .is_none_or(|path| &record.skin_path == path)
+        })
+        .collect())
+}
@@ -0,0 +1,184 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::RwLock;
+use tauri::{path::BaseDirectory, AppHandle, Manager};
+
+use crate::storage;
+use crate::tray;
+
+const DEFAULT_LOCALE: &str = "en";
+const LOCALE_FILE: &str = "locale.json";
+
+/// English is compiled in so messages still render if the resource folder is missing
+const BUNDLED_ENGLISH: &str = include_str!("../resources/locales/en.json");
+
+type MessageTable = HashMap<String, String>;
+
+struct Catalog {
+    locale: String,
+    messages: MessageTable,
+    fallback: MessageTable,
+}
+
+#[derive(Default)]
+pub struct I18nState {
+    catalog: RwLock<Option<Catalog>>,
+}
+
+/// Locale chosen in the app, overriding the OS locale
+#[derive(Serialize, Deserialize, Default)]
+struct LocaleOverride {
+    locale: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct LocaleInfo {
+    pub locale: String,
+    pub system_locale: Option<String>,
+    pub overridden: bool,
+    pub available: Vec<String>,
+}
+
+fn locales_dir(app_handle: &AppHandle) -> Option<PathBuf> {
+    app_handle
+        .path()
+        .resolve("resources/locales", BaseDirectory::Resource)
+        .ok()
+}
+
+fn load_table(app_handle: &AppHandle, locale: &str) -> Option<MessageTable> {
+    let path = locales_dir(app_handle)?.join(format!("{}.json", locale));
+    let content = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+pub fn available_locales(app_handle: &AppHandle) -> Vec<String> {
+    let mut locales: Vec<String> = locales_dir(app_handle)
+        .and_then(|dir| std::fs::read_dir(dir).ok())
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+                .filter_map(|path| path.file_stem()?.to_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+    if !locales.iter().any(|locale| locale == DEFAULT_LOCALE) {
+        locales.push(DEFAULT_LOCALE.to_string());
+    }
+    locales.sort();
+    locales
+}
+
+/// Best table for a locale tag: exact match ("pt-BR"), then the language ("pt"), then English
+fn resolve_catalog(app_handle: &AppHandle, requested: &str) -> Catalog {
+    let fallback: MessageTable = serde_json::from_str(BUNDLED_ENGLISH).unwrap_or_default();
+    let tag = requested.trim().replace('_', "-");
+    let language = tag.split('-').next().unwrap_or_default().to_lowercase();
+
+    for candidate in [tag.as_str(), language.as_str()] {
+        if candidate.is_empty() {
+            continue;
+        }
+        if let Some(messages) = load_table(app_handle, candidate) {
+            return Catalog {
+                locale: candidate.to_string(),
+                messages,
+                fallback,
+            };
+        }
+    }
+
+    Catalog {
+        locale: DEFAULT_LOCALE.to_string(),
+        messages: MessageTable::new(),
+        fallback,
+    }
+}
+
+fn override_path(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    storage::data_file(app_handle, LOCALE_FILE)
+}
+
+fn saved_override(app_handle: &AppHandle) -> Option<String> {
+    let path = override_path(app_handle).ok()?;
+    storage::load_json::<LocaleOverride>(&path).locale
+}
+
+/// Load the saved locale, or the OS locale, at startup
+pub fn init(app_handle: &AppHandle) {
+    let requested = saved_override(app_handle)
+        .or_else(sys_locale::get_locale)
+        .unwrap_or_else(|| DEFAULT_LOCALE.to_string());
+    let catalog = resolve_catalog(app_handle, &requested);
+    println!(
+        "[DEBUG] Using locale {} (requested {})",
+        catalog.locale, requested
+    );
+
+    if let Ok(mut current) = app_handle.state::<I18nState>().catalog.write() {
+        *current = Some(catalog);
+    }
+}
+
+/// Translate a message key, filling `{name}` placeholders from `args`
+pub fn t(app_handle: &AppHandle, key: &str, args: &[(&str, &str)]) -> String {
+    let state = app_handle.state::<I18nState>();
+    let template = state
+        .catalog
+        .read()
+        .ok()
+        .and_then(|catalog| {
+            let catalog = catalog.as_ref()?;
+            catalog
+                .messages
+                .get(key)
+                .or_else(|| catalog.fallback.get(key))
+                .cloned()
+        })
+        .unwrap_or_else(|| key.to_string());
+
+    args.iter().fold(template, |message, (name, value)| {
+        message.replace(&format!("{{{}}}", name), value)
+    })
+}
+
+/// The active locale and the ones bundled with the app
+#[tauri::command]
+#[tracing::instrument(skip(app_handle, state))]
+pub fn get_locale(app_handle: AppHandle, state: tauri::State<'_, I18nState>) -> LocaleInfo {
+    let locale = state
+        .catalog
+        .read()
+        .ok()
+        .and_then(|catalog| catalog.as_ref().map(|catalog| catalog.locale.clone()))
+        .unwrap_or_else(|| DEFAULT_LOCALE.to_string());
+
+    LocaleInfo {
+        locale,
+        system_locale: sys_locale::get_locale(),
+        overridden: saved_override(&app_handle).is_some(),
+        available: available_locales(&app_handle),
+    }
+}
+
+/// Switch the language of Rust-side messages; `None` goes back to the OS locale
+#[tauri::command]
+#[tracing::instrument(skip(app_handle, state), err)]
+pub fn set_locale(
+    app_handle: AppHandle,
+    state: tauri::State<'_, I18nState>,
+    locale: Option<String>,
+) -> Result<LocaleInfo, String> {
+    let locale = locale
+        .map(|locale| locale.trim().to_string())
+        .filter(|locale| !locale.is_empty());
+
+    storage::save_json(&override_path(&app_handle)?, &LocaleOverride { locale })?;
+
+    init(&app_handle);
+    tray::refresh_menu(&app_handle);
+    Ok(get_locale(app_handle, state))
+}
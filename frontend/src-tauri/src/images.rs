@@ -0,0 +1,377 @@
+use serde::Serialize;
+use std::io::{BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use crate::long_paths;
+use crate::path_guard;
+use crate::validation::{is_generated_dir, ValidationIssue, ValidationReport};
+
+/// Extensions treated as images when scanning a skin
+pub const IMAGE_EXTENSIONS: &[&str] = &[
+    "png", "jpg", "jpeg", "svg", "webp", "gif", "bmp", "tga", "tif", "tiff", "psd", "dds", "heic",
+    "avif",
+];
+
+/// What the file's bytes say it is, whatever its extension claims
+fn sniff_format(path: &Path) -> Option<&'static str> {
+    let mut header = [0u8; 12];
+    let mut file = std::fs::File::open(path).ok()?;
+    let read = file.read(&mut header).ok()?;
+    let header = &header[..read];
+
+    if header.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("png")
+    } else if header.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("jpeg")
+    } else if header.starts_with(b"RIFF") && header.get(8..12) == Some(b"WEBP") {
+        Some("webp")
+    } else if header.starts_with(b"GIF8") {
+        Some("gif")
+    } else if header.starts_with(b"BM") {
+        Some("bmp")
+    } else if header.starts_with(b"II*\0") || header.starts_with(b"MM\0*") {
+        Some("tiff")
+    } else if header.starts_with(b"DDS ") {
+        Some("dds")
+    } else if header.starts_with(b"8BPS") {
+        Some("psd")
+    } else if header.get(4..8) == Some(b"ftyp") {
+        Some("heic/avif")
+    } else {
+        None
+    }
+}
+
+/// Header fields of a PNG or JPEG that decide whether the game can load it
+pub struct ImageHeader {
+    pub width: u32,
+    pub height: u32,
+    pub interlaced: bool,
+    pub bit_depth: u8,
+    /// Colour channels in a JPEG frame: 1 grey, 3 YCbCr, 4 CMYK
+    pub components: u8,
+}
+
+fn png_header(path: &Path) -> Option<ImageHeader> {
+    let file = std::fs::File::open(path).ok()?;
+    let reader = png::Decoder::new(BufReader::new(file)).read_info().ok()?;
+    let info = reader.info();
+    Some(ImageHeader {
+        width: info.width,
+        height: info.height,
+        interlaced: info.interlaced,
+        bit_depth: info.bit_depth as u8,
+        components: info.color_type.samples() as u8,
+    })
+}
+
+/// Read a JPEG's start-of-frame segment
+fn jpeg_header(path: &Path) -> Option<ImageHeader> {
+    let mut reader = BufReader::new(std::fs::File::open(path).ok()?);
+    let mut marker = [0u8; 2];
+    reader.read_exact(&mut marker).ok()?;
+    if marker != [0xFF, 0xD8] {
+        return None;
+    }
+
+    loop {
+        reader.read_exact(&mut marker).ok()?;
+        if marker[0] != 0xFF {
+            return None;
+        }
+        // Standalone markers carry no length
+        if marker[1] == 0x01 || (0xD0..=0xD7).contains(&marker[1]) || marker[1] == 0xFF {
+            continue;
+        }
+
+        let mut length = [0u8; 2];
+        reader.read_exact(&mut length).ok()?;
+        let length = u16::from_be_bytes(length) as usize;
+        let mut segment = vec![0u8; length.checked_sub(2)?];
+        reader.read_exact(&mut segment).ok()?;
+
+        // SOF0-SOF15, except DHT (C4), JPG (C8) and DAC (CC)
+        if (0xC0..=0xCF).contains(&marker[1]) && !matches!(marker[1], 0xC4 | 0xC8 | 0xCC) {
+            return Some(ImageHeader {
+                width: u16::from_be_bytes([*segment.get(3)?, *segment.get(4)?]) as u32,
+                height: u16::from_be_bytes([*segment.get(1)?, *segment.get(2)?]) as u32,
+                // Progressive frames (SOF2, SOF6, SOF10, SOF14)
+                interlaced: matches!(marker[1], 0xC2 | 0xC6 | 0xCA | 0xCE),
+                bit_depth: *segment.first()?,
+                components: *segment.get(5)?,
+            });
+        }
+    }
+}
+
+pub fn read_header(path: &Path) -> Option<ImageHeader> {
+    match sniff_format(path)? {
+        "png" => png_header(path),
+        "jpeg" => jpeg_header(path),
+        _ => None,
+    }
+}
+
+/// Pixel dimensions of a PNG or JPEG, read from its header only
+pub fn image_dimensions(path: &Path) -> Option<(u32, u32)> {
+    read_header(path).map(|header| (header.width, header.height))
+}
+
+fn extension_of(path: &Path) -> String {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_lowercase())
+        .unwrap_or_default()
+}
+
+/// Problems with one image that would make the build fail or the game
+/// render it wrongly
+fn check_image(path: &Path) -> Vec<ValidationIssue> {
+    let extension = extension_of(path);
+    if extension == "svg" {
+        return Vec::new();
+    }
+
+    let Some(format) = sniff_format(path) else {
+        return vec![ValidationIssue::error(
+            "unreadable_image",
+            "File is not a recognisable image".to_string(),
+            Some(path),
+        )];
+    };
+
+    let mut issues = Vec::new();
+    let claimed = if extension == "jpg" {
+        "jpeg"
+    } else {
+        &extension
+    };
+    if format != claimed && matches!(claimed, "png" | "jpeg") {
+        issues.push(ValidationIssue::error(
+            "mismatched_image_format",
+            format!(
+                "File is named .{} but contains {} data",
+                extension,
+                format.to_uppercase()
+            ),
+            Some(path),
+        ));
+    }
+
+    match format {
+        "png" | "jpeg" => {
+            let Some(header) = read_header(path) else {
+                issues.push(ValidationIssue::error(
+                    "unreadable_image",
+                    format!("{} header could not be read", format.to_uppercase()),
+                    Some(path),
+                ));
+                return issues;
+            };
+            if format == "png" && header.interlaced {
+                issues.push(ValidationIssue::error(
+                    "interlaced_png",
+                    "Interlaced PNGs can't be imported; save it without interlacing".to_string(),
+                    Some(path),
+                ));
+            }
+            if format == "png" && header.bit_depth > 8 {
+                issues.push(ValidationIssue::warning(
+                    "high_bit_depth_png",
+                    format!(
+                        "{}-bit PNG will be reduced to 8 bits per channel",
+                        header.bit_depth
+                    ),
+                    Some(path),
+                ));
+            }
+            if format == "jpeg" && header.components == 4 {
+                issues.push(ValidationIssue::error(
+                    "cmyk_jpeg",
+                    "CMYK JPEGs render with wrong colours; convert it to RGB".to_string(),
+                    Some(path),
+                ));
+            }
+        }
+        other => issues.push(ValidationIssue::error(
+            "unsupported_image_format",
+            format!(
+                "{} images can't be used; convert it to PNG",
+                other.to_uppercase()
+            ),
+            Some(path),
+        )),
+    }
+
+    issues
+}
+
+fn skin_images(skin_dir: &Path) -> Vec<PathBuf> {
+    WalkDir::new(long_paths::extended(skin_dir))
+        .into_iter()
+        .filter_entry(|entry| !is_generated_dir(entry))
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter(|entry| IMAGE_EXTENSIONS.contains(&extension_of(entry.path()).as_str()))
+        .map(|entry| entry.into_path())
+        .collect()
+}
+
+#[derive(Serialize)]
+pub struct ImageConversion {
+    pub path: String,
+    /// Where the converted image was written
+    pub output: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Uncompressed 32-bit BGRA DDS, which every DDS reader understands
+fn write_dds(path: &Path, image: &image::RgbaImage) -> Result<(), String> {
+    let (width, height) = image.dimensions();
+    let mut data = Vec::with_capacity(128 + image.as_raw().len());
+    data.extend_from_slice(b"DDS ");
+    let mut header = [0u32; 31];
+    header[0] = 124; // header size
+    header[1] = 0x100F; // CAPS | HEIGHT | WIDTH | PITCH | PIXELFORMAT
+    header[2] = height;
+    header[3] = width;
+    header[4] = width * 4; // pitch
+    header[18] = 32; // pixel format size
+    header[19] = 0x41; // RGB | ALPHAPIXELS
+    header[21] = 32; // bits per pixel
+    header[22] = 0x00FF0000; // red mask
+    header[23] = 0x0000FF00; // green mask
+    header[24] = 0x000000FF; // blue mask
+    header[25] = 0xFF000000; // alpha mask
+    header[26] = 0x1000; // TEXTURE
+    for value in header {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    for pixel in image.pixels() {
+        let [r, g, b, a] = pixel.0;
+        data.extend_from_slice(&[b, g, r, a]);
+    }
+
+    let mut file =
+        std::fs::File::create(path).map_err(|e| format!("Failed to create {:?}: {}", path, e))?;
+    file.write_all(&data)
+        .map_err(|e| format!("Failed to write {:?}: {}", path, e))
+}
+
+/// Decode an image and write it as a plain 8-bit PNG (or DDS) beside the
+/// original, moving the original to the trash
+fn convert_image(path: &Path, target: &str) -> Result<PathBuf, String> {
+    let image = image::ImageReader::open(path)
+        .and_then(|reader| reader.with_guessed_format())
+        .map_err(|e| format!("Failed to open image: {}", e))?
+        .decode()
+        .map_err(|e| format!("Failed to decode image: {}", e))?
+        .to_rgba8();
+
+    let output = path.with_extension(target);
+    let partial = path.with_extension(format!("{}.converting", target));
+    let written = if target == "dds" {
+        write_dds(&partial, &image)
+    } else {
+        image
+            .save_with_format(&partial, image::ImageFormat::Png)
+            .map_err(|e| format!("Failed to write PNG: {}", e))
+    };
+    if let Err(e) = written {
+        let _ = std::fs::remove_file(&partial);
+        return Err(e);
+    }
+
+    // Trash rather than delete so a bad conversion can be undone
+    if let Err(e) = trash::delete(path) {
+        let _ = std::fs::remove_file(&partial);
+        return Err(format!("Failed to move original to trash: {}", e));
+    }
+    if output.exists() {
+        let _ = trash::delete(&output);
+    }
+    std::fs::rename(&partial, &output)
+        .map_err(|e| format!("Failed to save converted image: {}", e))?;
+    Ok(output)
+}
+
+/// Check every image in a skin for formats the game can't use: WebP and other
+/// unsupported types, files whose contents don't match their extension,
+/// interlaced PNGs and CMYK JPEGs
+#[tauri::command]
+#[tracing::instrument(err)]
+pub async fn validate_images(skin_path: String) -> Result<ValidationReport, String> {
+    let skin_dir = path_guard::existing_dir(&skin_path, "Skin folder")?;
+    tokio::task::spawn_blocking(move || {
+        let issues = skin_images(&skin_dir)
+            .iter()
+            .flat_map(|path| check_image(path))
+            .collect();
+        ValidationReport::from_issues(&skin_dir, issues)
+    })
+    .await
+    .map_err(|e| format!("Image validation task failed: {}", e))
+}
+
+/// Convert images to PNG (or DDS with `format: "dds"`): the given paths, or
+/// every image `validate_images` flags as an error
+#[tauri::command]
+#[tracing::instrument(err)]
+pub async fn convert_images(
+    skin_path: String,
+    paths: Option<Vec<String>>,
+    format: Option<String>,
+) -> Result<Vec<ImageConversion>, String> {
+    let skin_dir = path_guard::existing_dir(&skin_path, "Skin folder")?;
+    let target = match format.as_deref().map(str::trim) {
+        None | Some("") | Some("png") => "png",
+        Some("dds") => "dds",
+        Some(other) => return Err(format!("Unsupported conversion format: {}", other)),
+    };
+
+    tokio::task::spawn_blocking(move || {
+        let images: Vec<PathBuf> = match paths {
+            Some(paths) => paths
+                .iter()
+                .map(|path| path_guard::existing_file(path, "Image"))
+                .collect::<Result<Vec<_>, _>>()?
+                .into_iter()
+                .map(|path| long_paths::extended(&path))
+                .collect(),
+            None => skin_images(&skin_dir)
+                .into_iter()
+                .filter(|path| {
+                    check_image(path)
+                        .iter()
+                        .any(|issue| issue.severity == "error" && issue.code != "unreadable_image")
+                })
+                .collect(),
+        };
+
+        let skin_root = long_paths::extended(&skin_dir);
+        if let Some(outside) = images.iter().find(|path| !path.starts_with(&skin_root)) {
+            return Err(format!(
+                "Image is outside the skin folder: {}",
+                long_paths::strip_extended(&outside.to_string_lossy())
+            ));
+        }
+
+        Ok(images
+            .iter()
+            .map(|path| {
+                let result = convert_image(path, target);
+                ImageConversion {
+                    path: long_paths::strip_extended(&path.to_string_lossy()),
+                    output: result
+                        .as_ref()
+                        .ok()
+                        .map(|output| long_paths::strip_extended(&output.to_string_lossy())),
+                    error: result.err(),
+                }
+            })
+            .collect())
+    })
+    .await
+    .map_err(|e| format!("Image conversion task failed: {}", e))?
+}
@@ -0,0 +1,555 @@
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+use tauri::{AppHandle, Emitter, Manager};
+use walkdir::WalkDir;
+
+use crate::commands::{copy_dir_recursive, default_skins_dir};
+use crate::diff::skin_file_map;
+use crate::events::ProgressEvent;
+use crate::export::{SkinManifest, SKIN_MANIFEST_FILE};
+use crate::extract::{self, ArchiveKind};
+use crate::file_copy;
+use crate::hashing;
+use crate::long_paths;
+use crate::package_verify::{self, PackageVerification};
+use crate::path_guard;
+use crate::portable;
+use crate::process::ProcessState;
+use crate::skins::{ensure_not_building, read_skin_info, validate_skin_name, SKIN_CONFIG_FILE};
+use crate::snapshots::{self, SnapshotInfo};
+use crate::validation::is_generated_dir;
+
+/// Leave the library alone when the skin is already in it and report the match
+pub const ON_EXISTING_ASK: &str = "ask";
+/// Replace the library skin's files, after snapshotting them, when it has the
+/// incoming skin's id. A skin that only shares the name is reported as for "ask".
+pub const ON_EXISTING_UPDATE: &str = "update";
+/// Replace the library skin's files even when only the name matched, once the
+/// user has confirmed it is the same skin
+pub const ON_EXISTING_REPLACE: &str = "replace";
+/// Import next to the library skin under a new name
+pub const ON_EXISTING_COPY: &str = "copy";
+
+/// A library skin the archive is another copy or version of
+#[derive(Serialize)]
+pub struct ExistingSkin {
+    pub name: String,
+    pub path: String,
+    pub installed_version: Option<String>,
+    pub incoming_version: Option<String>,
+    /// Same source files, byte for byte
+    pub identical: bool,
+    /// Matched by skin id; otherwise only the name matched, which may be a
+    /// different skin
+    pub same_id: bool,
+}
+
+#[derive(Serialize)]
+pub struct ImportResult {
+    pub name: String,
+    pub path: String,
+    pub renamed: bool,
+    /// "imported", "updated" (an existing skin was replaced), "unchanged" (the
+    /// library already has identical files) or "exists" (left for the caller to decide)
+    pub action: String,
+    pub existing: Option<ExistingSkin>,
+    /// Taken of the library skin before an update replaced it
+    pub snapshot: Option<SnapshotInfo>,
+    /// Set when the archive is a package exported by this app
+    pub manifest: Option<SkinManifest>,
+    /// Set when the archive carried checksums, which all matched
+    pub verification: Option<PackageVerification>,
+}
+
+fn emit_progress(app_handle: &AppHandle, current: u32, total: u32, status: String) {
+    let _ = app_handle.emit(
+        "import_progress",
+        ProgressEvent {
+            current,
+            total,
+            status,
+            run_id: None,
+        },
+    );
+}
+
+/// Extract rar archives (and 7z archives using codecs the built-in reader
+/// lacks) with an installed 7-Zip or unrar binary
+fn extract_with_external_tool(
+    archive_path: &Path,
+    dest: &Path,
+    cancel: &AtomicBool,
+) -> Result<(), String> {
+    let archive = archive_path.to_string_lossy().to_string();
+    let output_flag = format!("-o{}", dest.to_string_lossy());
+
+    let candidates: Vec<(&str, Vec<String>)> = vec![
+        (
+            "7z",
+            vec![
+                "x".to_string(),
+                "-y".to_string(),
+                output_flag.clone(),
+                archive.clone(),
+            ],
+        ),
+        (
+            "7za",
+            vec![
+                "x".to_string(),
+                "-y".to_string(),
+                output_flag,
+                archive.clone(),
+            ],
+        ),
+        (
+            "unrar",
+            vec![
+                "x".to_string(),
+                "-o+".to_string(),
+                archive,
+                format!("{}{}", dest.to_string_lossy(), std::path::MAIN_SEPARATOR),
+            ],
+        ),
+    ];
+
+    for (program, args) in candidates {
+        match std::process::Command::new(program).args(&args).status() {
+            Ok(status) if status.success() => {
+                // The tool applies none of our limits, so check what it wrote
+                let summary = extract::check_extracted(archive_path, dest, cancel)?;
+                println!(
+                    "[DEBUG] {} extracted {} entries ({} bytes)",
+                    program, summary.entries, summary.bytes
+                );
+                return Ok(());
+            }
+            Ok(status) => {
+                return Err(format!(
+                    "{} failed to extract archive (exit code {})",
+                    program,
+                    status.code().unwrap_or(-1)
+                ))
+            }
+            Err(_) => continue,
+        }
+    }
+
+    Err("Extracting .7z and .rar archives requires 7-Zip or unrar to be installed".to_string())
+}
+
+/// Find the folder inside an extracted archive that holds the skin config,
+/// descending through single-folder wrappers such as `MySkin-v2/MySkin/`
+fn find_skin_root(extracted: &Path) -> PathBuf {
+    let mut queue = vec![extracted.to_path_buf()];
+
+    while let Some(dir) = queue.pop() {
+        if dir.join(SKIN_CONFIG_FILE).is_file() {
+            return dir;
+        }
+        if let Ok(entries) = std::fs::read_dir(&dir) {
+            let mut children: Vec<PathBuf> = entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.is_dir())
+                .collect();
+            children.sort();
+            queue.splice(0..0, children);
+        }
+    }
+
+    // No config anywhere: unwrap single top-level folders
+    let mut root = extracted.to_path_buf();
+    loop {
+        let entries: Vec<PathBuf> = match std::fs::read_dir(&root) {
+            Ok(entries) => entries.flatten().map(|entry| entry.path()).collect(),
+            Err(_) => break,
+        };
+        if entries.len() == 1 && entries[0].is_dir() {
+            root = entries[0].clone();
+        } else {
+            break;
+        }
+    }
+    root
+}
+
+/// The skin manifest at the top of an extracted package, if it has one
+fn read_manifest(extracted: &Path) -> Option<SkinManifest> {
+    let content = std::fs::read_to_string(extracted.join(SKIN_MANIFEST_FILE)).ok()?;
+    match serde_json::from_str(&content) {
+        Ok(manifest) => Some(manifest),
+        Err(e) => {
+            println!("[WARNING] Ignoring unreadable skin manifest: {}", e);
+            None
+        }
+    }
+}
+
+/// Library skin with the same id as the incoming one, else the same name, and
+/// whether the id matched
+fn find_existing(
+    skins_dir: &Path,
+    id: Option<&str>,
+    name: &str,
+    incoming_name: Option<&str>,
+) -> Option<(PathBuf, bool)> {
+    let skins: Vec<(PathBuf, Option<String>, String)> = std::fs::read_dir(skins_dir)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .filter_map(|path| {
+            let info = read_skin_info(&path).ok()?;
+            Some((path, info.id, info.name))
+        })
+        .collect();
+
+    if let Some(id) = id {
+        if let Some((path, _, _)) = skins
+            .iter()
+            .find(|(_, skin_id, _)| skin_id.as_deref() == Some(id))
+        {
+            return Some((path.clone(), true));
+        }
+    }
+    skins
+        .into_iter()
+        .find(|(path, skin_id, skin_name)| {
+            // A different id means a different skin that happens to share the name
+            (skin_id.is_none() || id.is_none())
+                && (path.file_name().is_some_and(|folder| folder == name)
+                    || Some(skin_name.as_str()) == incoming_name)
+        })
+        .map(|(path, _, _)| (path, false))
+}
+
+/// Whether two skins have the same source files with the same contents
+fn same_files(a: &Path, b: &Path) -> bool {
+    let (files_a, files_b) = (skin_file_map(a), skin_file_map(b));
+    files_a.len() == files_b.len()
+        && files_a.iter().all(|(relative, (path_a, size_a))| {
+            files_b.get(relative).is_some_and(|(path_b, size_b)| {
+                size_a == size_b
+                    && hashing::sha256_file(path_a).is_ok_and(|hash| {
+                        hashing::sha256_file(path_b).is_ok_and(|other| other == hash)
+                    })
+            })
+        })
+}
+
+/// Replace a library skin's source files with the incoming ones, leaving its
+/// build output and hidden folders alone
+fn replace_skin_files(skin_dir: &Path, source: &Path) -> Result<(), String> {
+    let skin_dir = long_paths::extended(skin_dir);
+    for entry in WalkDir::new(&skin_dir)
+        .contents_first(true)
+        .into_iter()
+        .filter_entry(|entry| !is_generated_dir(entry))
+        .flatten()
+        .filter(|entry| entry.depth() > 0)
+    {
+        if entry.file_type().is_dir() {
+            let _ = std::fs::remove_dir(entry.path());
+        } else {
+            std::fs::remove_file(entry.path())
+                .map_err(|e| format!("Failed to remove {:?}: {}", entry.path(), e))?;
+        }
+    }
+
+    for (relative, (path, _)) in skin_file_map(source) {
+        let target = skin_dir.join(&relative);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create directory: {}", e))?;
+        }
+        std::fs::copy(&path, &target)
+            .map_err(|e| format!("Failed to update {}: {}", relative, e))?;
+    }
+    Ok(())
+}
+
+/// Pick a folder name in the skins directory that doesn't collide with an existing skin
+pub fn unique_destination(skins_dir: &Path, name: &str) -> (PathBuf, bool) {
+    let candidate = skins_dir.join(name);
+    if !candidate.exists() {
+        return (candidate, false);
+    }
+
+    let mut counter = 2;
+    loop {
+        let candidate = skins_dir.join(format!("{} ({})", name, counter));
+        if !candidate.exists() {
+            return (candidate, true);
+        }
+        counter += 1;
+    }
+}
+
+/// Move a directory, falling back to copy + delete across volumes
+pub fn move_dir(src: &Path, dst: &Path) -> Result<(), String> {
+    if std::fs::rename(src, dst).is_ok() {
+        return Ok(());
+    }
+    copy_dir_recursive(src, dst)?;
+    std::fs::remove_dir_all(src).map_err(|e| format!("Failed to clean up source: {}", e))
+}
+
+/// Extract into the staging folder and move the detected skin root into the library
+fn extract_and_install(
+    app_handle: &AppHandle,
+    archive_path: &Path,
+    extension: &str,
+    staging_dir: &Path,
+    on_existing: &str,
+) -> Result<ImportResult, String> {
+    let cancel = file_copy::begin_transfer(app_handle);
+    let mut on_entry = |index: u32, total: Option<u32>, name: &str| {
+        // Formats that don't list their entries up front report a total of 0
+        emit_progress(
+            app_handle,
+            index,
+            total.unwrap_or(0),
+            format!("Extracting {}", name),
+        );
+    };
+    match ArchiveKind::from_path(archive_path) {
+        Some(kind) => {
+            match extract::extract_archive(archive_path, kind, staging_dir, &cancel, &mut on_entry)
+            {
+                Ok(summary) => println!(
+                    "[DEBUG] Extracted {} entries ({} bytes)",
+                    summary.entries, summary.bytes
+                ),
+                // Only a codec the built-in reader lacks; a refused size bomb stays refused
+                Err(e) if extract::is_unsupported_method(&e) => {
+                    println!(
+                        "[WARNING] Built-in 7z extraction failed ({}), trying 7-Zip",
+                        e
+                    );
+                    extract_with_external_tool(archive_path, staging_dir, &cancel)?;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        None if extension == "rar" => {
+            emit_progress(app_handle, 0, 1, "Extracting archive...".to_string());
+            extract_with_external_tool(archive_path, staging_dir, &cancel)?;
+        }
+        None => return Err(format!("Unsupported archive format: .{}", extension)),
+    }
+
+    let manifest = read_manifest(staging_dir);
+    emit_progress(app_handle, 0, 1, "Verifying package checksums".to_string());
+    let verification =
+        package_verify::verify_package(app_handle, staging_dir, manifest.as_ref(), &cancel)?;
+    let skin_root = match &manifest {
+        // Source packages keep the skin in skin/, next to the manifest
+        Some(manifest) if manifest.contents == "source" && staging_dir.join("skin").is_dir() => {
+            staging_dir.join("skin")
+        }
+        _ => find_skin_root(staging_dir),
+    };
+
+    let manifest_name = manifest
+        .as_ref()
+        .and_then(|manifest| validate_skin_name(&manifest.name).ok())
+        .map(str::to_string);
+    let name = if let Some(name) = manifest_name {
+        name
+    } else if skin_root == staging_dir {
+        archive_path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| "Imported Skin".to_string())
+    } else {
+        skin_root
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| "Imported Skin".to_string())
+    };
+
+    let skins_dir = default_skins_dir(app_handle)?;
+    std::fs::create_dir_all(&skins_dir)
+        .map_err(|e| format!("Failed to create skins directory: {}", e))?;
+
+    let incoming = read_skin_info(&skin_root).ok();
+    let id = manifest
+        .as_ref()
+        .and_then(|manifest| manifest.id.clone())
+        .or_else(|| incoming.as_ref().and_then(|info| info.id.clone()));
+    let existing = if on_existing == ON_EXISTING_COPY {
+        None
+    } else {
+        find_existing(
+            &skins_dir,
+            id.as_deref(),
+            &name,
+            incoming.as_ref().map(|info| info.name.as_str()),
+        )
+    };
+    let existing = existing.map(|(path, same_id)| {
+        emit_progress(
+            app_handle,
+            0,
+            1,
+            "Comparing with the library skin".to_string(),
+        );
+        let info = read_skin_info(&path).ok();
+        ExistingSkin {
+            name: path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            path: path.to_string_lossy().to_string(),
+            installed_version: info.and_then(|info| info.version),
+            incoming_version: manifest
+                .as_ref()
+                .and_then(|manifest| manifest.version.clone())
+                .or_else(|| incoming.as_ref().and_then(|info| info.version.clone())),
+            identical: same_files(&path, &skin_root),
+            same_id,
+        }
+    });
+
+    let mut result = ImportResult {
+        name: name.clone(),
+        path: String::new(),
+        renamed: false,
+        action: "imported".to_string(),
+        existing: None,
+        snapshot: None,
+        manifest,
+        verification,
+    };
+    if let Some(existing) = existing {
+        let skin_dir = PathBuf::from(&existing.path);
+        result.name = existing.name.clone();
+        result.path = existing.path.clone();
+        result.action = if existing.identical {
+            "unchanged".to_string()
+        } else if on_existing == ON_EXISTING_REPLACE
+            || (on_existing == ON_EXISTING_UPDATE && existing.same_id)
+        {
+            path_guard::ensure_destructive_allowed(app_handle, &skin_dir, "Skin folder")?;
+            tauri::async_runtime::block_on(ensure_not_building(
+                &app_handle.state::<ProcessState>(),
+                &skin_dir,
+            ))?;
+            emit_progress(
+                app_handle,
+                1,
+                1,
+                format!("Updating library skin: {}", skin_dir.display()),
+            );
+            result.snapshot = Some(snapshots::create_snapshot(
+                app_handle,
+                &skin_dir,
+                "pre-update",
+            )?);
+            replace_skin_files(&skin_dir, &skin_root)?;
+            "updated".to_string()
+        } else {
+            "exists".to_string()
+        };
+        println!(
+            "[DEBUG] Archive matches library skin {}: {}",
+            existing.path, result.action
+        );
+        result.existing = Some(existing);
+        return Ok(result);
+    }
+
+    let (destination, renamed) = unique_destination(&skins_dir, &name);
+    emit_progress(
+        app_handle,
+        1,
+        1,
+        format!("Moving skin into library: {}", destination.display()),
+    );
+    move_dir(&skin_root, &destination)?;
+
+    if let Some(file_name) = destination.file_name() {
+        result.name = file_name.to_string_lossy().to_string();
+    }
+    result.path = destination.to_string_lossy().to_string();
+    result.renamed = renamed;
+    Ok(result)
+}
+
+/// Validate an `on_existing` value from the UI; missing means ask
+pub fn on_existing_option(on_existing: Option<String>) -> Result<String, String> {
+    let on_existing = on_existing.unwrap_or_else(|| ON_EXISTING_ASK.to_string());
+    if ![
+        ON_EXISTING_ASK,
+        ON_EXISTING_UPDATE,
+        ON_EXISTING_REPLACE,
+        ON_EXISTING_COPY,
+    ]
+    .contains(&on_existing.as_str())
+    {
+        return Err(format!("Unknown import option: {}", on_existing));
+    }
+    Ok(on_existing)
+}
+
+/// Import a skin archive into the library. When the library already has the
+/// skin, `on_existing` (one of the `ON_EXISTING_*` values) decides what happens.
+pub fn import_archive(
+    app_handle: &AppHandle,
+    archive_path: &Path,
+    on_existing: &str,
+) -> Result<ImportResult, String> {
+    if !archive_path.is_file() {
+        return Err(format!("Archive not found: {}", archive_path.display()));
+    }
+
+    let extension = archive_path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_lowercase())
+        .unwrap_or_default();
+
+    let staging_dir = portable::app_cache_dir(app_handle)?
+        .join("temp")
+        .join(format!("import-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&staging_dir)
+        .map_err(|e| format!("Failed to create staging directory: {}", e))?;
+
+    let result = extract_and_install(
+        app_handle,
+        archive_path,
+        &extension,
+        &staging_dir,
+        on_existing,
+    );
+
+    let _ = std::fs::remove_dir_all(&staging_dir);
+
+    result
+}
+
+/// Extract a downloaded skin archive into the skins directory. A skin already
+/// in the library is reported rather than duplicated unless `on_existing` is
+/// "update" (replace it, keeping a snapshot, when the ids match), "replace"
+/// (the same, for a skin that only matched by name) or "copy" (import as
+/// `Name (2)`).
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub async fn import_skin_archive(
+    app_handle: AppHandle,
+    path: String,
+    on_existing: Option<String>,
+) -> Result<ImportResult, String> {
+    let on_existing = on_existing_option(on_existing)?;
+    tokio::task::spawn_blocking(move || {
+        import_archive(
+            &app_handle,
+            &path_guard::existing_file(&path, "Archive")?,
+            &on_existing,
+        )
+    })
+    .await
+    .map_err(|e| format!("Import task failed: {}", e))?
+}
@@ -0,0 +1,263 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+use tauri::AppHandle;
+use walkdir::WalkDir;
+
+use crate::backups::list_bundle_files;
+use crate::build_outputs;
+use crate::hashing::{self, HashAlgorithm};
+use crate::portable;
+use crate::process::TaskConfig;
+use crate::skins::modified_secs;
+use crate::storage;
+use crate::validation::is_generated_dir;
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct FileStamp {
+    pub size: u64,
+    pub modified: Option<u64>,
+    /// BLAKE3 of the file contents; empty for game bundles
+    #[serde(default)]
+    pub hash: String,
+}
+
+/// State of a skin and the game bundles at the last successful build
+#[derive(Serialize, Deserialize, Default)]
+struct BuildManifest {
+    skin_path: String,
+    bundles_path: String,
+    /// Backend arguments the build ran with; any change needs a full rebuild
+    args_key: String,
+    built_at: u64,
+    files: BTreeMap<String, FileStamp>,
+    bundles: BTreeMap<String, FileStamp>,
+}
+
+#[derive(Serialize, Default)]
+pub struct ChangeSet {
+    pub changed: Vec<String>,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    /// Set when an incremental build isn't possible and everything must be rebuilt
+    pub full_rebuild_reason: Option<String>,
+    pub last_built_at: Option<u64>,
+}
+
+impl ChangeSet {
+    pub fn is_up_to_date(&self) -> bool {
+        self.full_rebuild_reason.is_none()
+            && self.changed.is_empty()
+            && self.added.is_empty()
+            && self.removed.is_empty()
+    }
+
+    /// Skin files the backend needs to re-apply
+    pub fn changed_files(&self) -> Vec<String> {
+        self.changed.iter().chain(&self.added).cloned().collect()
+    }
+}
+
+fn manifest_path(app_handle: &AppHandle, skin_dir: &Path) -> Result<PathBuf, String> {
+    let digest = Sha256::digest(skin_dir.to_string_lossy().as_bytes());
+    Ok(portable::app_data_dir(app_handle)?
+        .join("incremental")
+        .join(format!("{}.json", &format!("{:x}", digest)[..16])))
+}
+
+fn load_manifest(app_handle: &AppHandle, skin_dir: &Path) -> Result<Option<BuildManifest>, String> {
+    let path = manifest_path(app_handle, skin_dir)?;
+    Ok(path.is_file().then(|| storage::load_json(&path)))
+}
+
+/// Key identifying the backend arguments a build ran with
+pub fn args_key(cli_args: &[String]) -> String {
+    cli_args.join("\u{0}")
+}
+
+/// Size and mtime of every source file in the skin, hashing only files whose
+/// stamp differs from `previous` (unchanged ones keep their recorded hash)
+fn stamp_skin_files(
+    app_handle: &AppHandle,
+    skin_dir: &Path,
+    previous: &BTreeMap<String, FileStamp>,
+) -> Result<BTreeMap<String, FileStamp>, String> {
+    let mut files = BTreeMap::new();
+    let mut to_hash = Vec::new();
+    for entry in WalkDir::new(skin_dir)
+        .into_iter()
+        .filter_entry(|entry| !is_generated_dir(entry))
+        .flatten()
+        .filter(|entry| entry.file_type().is_file())
+    {
+        let Ok(relative) = entry.path().strip_prefix(skin_dir) else {
+            continue;
+        };
+        let relative = relative.to_string_lossy().replace('\\', "/");
+        let size = entry.metadata().map(|meta| meta.len()).unwrap_or(0);
+        let modified = modified_secs(entry.path());
+
+        let hash = match previous.get(&relative) {
+            Some(stamp) if stamp.size == size && stamp.modified == modified => stamp.hash.clone(),
+            _ => {
+                to_hash.push((relative.clone(), entry.path().to_path_buf()));
+                String::new()
+            }
+        };
+        files.insert(
+            relative,
+            FileStamp {
+                size,
+                modified,
+                hash,
+            },
+        );
+    }
+
+    let paths: Vec<PathBuf> = to_hash.iter().map(|(_, path)| path.clone()).collect();
+    let cancel = AtomicBool::new(false);
+    let hashes = hashing::hash_files(app_handle, &paths, HashAlgorithm::Blake3, &cancel);
+    for ((relative, _), hash) in to_hash.into_iter().zip(hashes) {
+        if let Some(stamp) = files.get_mut(&relative) {
+            stamp.hash = hash?;
+        }
+    }
+    Ok(files)
+}
+
+/// Bundles are only compared by size and mtime; hashing the game install on
+/// every build would cost more than the rebuild it saves
+fn stamp_bundles(bundles_dir: &Path) -> BTreeMap<String, FileStamp> {
+    list_bundle_files(bundles_dir)
+        .into_iter()
+        .map(|(name, path)| {
+            let stamp = FileStamp {
+                size: std::fs::metadata(&path).map(|meta| meta.len()).unwrap_or(0),
+                modified: modified_secs(&path),
+                hash: String::new(),
+            };
+            (name, stamp)
+        })
+        .collect()
+}
+
+/// Compare a skin against the manifest from its last successful build
+pub fn detect_changes(
+    app_handle: &AppHandle,
+    skin_dir: &Path,
+    bundles_dir: &Path,
+    output_dir: &Path,
+    args_key: &str,
+) -> Result<ChangeSet, String> {
+    let Some(manifest) = load_manifest(app_handle, skin_dir)? else {
+        return Ok(ChangeSet {
+            full_rebuild_reason: Some("No previous build recorded".to_string()),
+            ..Default::default()
+        });
+    };
+
+    let full_rebuild_reason = if manifest.bundles_path != bundles_dir.to_string_lossy() {
+        Some("Bundles folder changed".to_string())
+    } else if manifest.args_key != args_key {
+        Some("Build options changed".to_string())
+    } else if stamp_bundles(bundles_dir) != manifest.bundles {
+        Some("Game bundles changed (game update or verify)".to_string())
+    } else if list_bundle_files(output_dir).is_empty() {
+        Some("Previous build output is missing".to_string())
+    } else {
+        None
+    };
+
+    let current = stamp_skin_files(app_handle, skin_dir, &manifest.files)?;
+    let mut changes = ChangeSet {
+        full_rebuild_reason,
+        last_built_at: Some(manifest.built_at),
+        ..Default::default()
+    };
+    for (name, stamp) in &current {
+        match manifest.files.get(name) {
+            Some(previous) if previous.hash == stamp.hash => {}
+            Some(_) => changes.changed.push(name.clone()),
+            None => changes.added.push(name.clone()),
+        }
+    }
+    changes.removed = manifest
+        .files
+        .keys()
+        .filter(|name| !current.contains_key(*name))
+        .cloned()
+        .collect();
+    // A removed override has to be reverted in every bundle it touched
+    if !changes.removed.is_empty() && changes.full_rebuild_reason.is_none() {
+        changes.full_rebuild_reason = Some("Skin files were removed".to_string());
+    }
+
+    Ok(changes)
+}
+
+/// Record the skin and bundles as they were for a successful build
+pub fn record_build(
+    app_handle: &AppHandle,
+    skin_dir: &Path,
+    bundles_dir: &Path,
+    args_key: &str,
+) -> Result<(), String> {
+    let path = manifest_path(app_handle, skin_dir)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create incremental build directory: {}", e))?;
+    }
+
+    // The new stamps reuse the previous ones, so no other build may save in between
+    storage::with_lock(&path, || {
+        let previous = load_manifest(app_handle, skin_dir)?.unwrap_or_default();
+        let manifest = BuildManifest {
+            skin_path: skin_dir.to_string_lossy().to_string(),
+            bundles_path: bundles_dir.to_string_lossy().to_string(),
+            args_key: args_key.to_string(),
+            built_at: storage::unix_now(),
+            files: stamp_skin_files(app_handle, skin_dir, &previous.files)?,
+            bundles: stamp_bundles(bundles_dir),
+        };
+        storage::save_json(&path, &manifest)
+    })
+}
+
+/// Write the changed skin files to a JSON list the backend can read from
+/// `FM_CHANGED_FILES` to limit re-patching to the assets they touch
+pub fn write_changed_files(
+    app_handle: &AppHandle,
+    run_id: &str,
+    changes: &ChangeSet,
+) -> Result<PathBuf, String> {
+    let dir = portable::app_cache_dir(app_handle)?.join("incremental");
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create incremental build directory: {}", e))?;
+    let path = dir.join(format!("changed-{}.json", run_id));
+    storage::save_json(&path, &changes.changed_files())?;
+    Ok(path)
+}
+
+/// What changed in a skin since its last successful build with these settings
+#[tauri::command]
+#[tracing::instrument(skip(app_handle, config), fields(skin_path = %config.skin_path), err)]
+pub async fn check_skin_changes(
+    app_handle: AppHandle,
+    mut config: TaskConfig,
+) -> Result<ChangeSet, String> {
+    config.dry_run = false;
+    let cli_args = config.resolve_cli_args(&app_handle)?;
+    tokio::task::spawn_blocking(move || {
+        detect_changes(
+            &app_handle,
+            &PathBuf::from(config.skin_path.trim()),
+            &PathBuf::from(config.bundles_path.trim()),
+            &build_outputs::current_output_dir(&app_handle, &config.skin_path, &config.extra_args),
+            &args_key(&cli_args),
+        )
+    })
+    .await
+    .map_err(|e| format!("Change detection task failed: {}", e))?
+}
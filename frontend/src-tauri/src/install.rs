@@ -0,0 +1,408 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter, State};
+
+use crate::backups;
+use crate::build_outputs;
+use crate::elevation;
+use crate::events::ProgressEvent;
+use crate::file_copy::{self, CopyError};
+use crate::game_watcher;
+use crate::hashing::sha256_file;
+use crate::path_guard;
+use crate::process::ProcessState;
+use crate::restore_points::{self, ExpandedCopies, ORIGINAL_POINT_ID};
+use crate::scheduler;
+use crate::skins::read_skin_info;
+use crate::steam;
+use crate::storage;
+
+const INSTALLED_SKINS_FILE: &str = "installed_skins.json";
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct InstalledBundle {
+    pub size: u64,
+    pub sha256: String,
+}
+
+/// Bundles a skin wrote into a game install
+#[derive(Serialize, Deserialize, Clone)]
+pub struct InstalledSkin {
+    pub skin_path: String,
+    pub installed_at: u64,
+    pub restore_point_id: Option<String>,
+    pub bundles: BTreeMap<String, InstalledBundle>,
+}
+
+/// Installed skins keyed by bundles path, then skin path
+pub type InstalledSkins = BTreeMap<String, BTreeMap<String, InstalledSkin>>;
+
+#[derive(Serialize)]
+pub struct InstallResult {
+    pub skin_path: String,
+    pub bundles_path: String,
+    pub installed: Vec<String>,
+    /// Bundles in the output folder that don't exist in the game directory
+    pub skipped: Vec<String>,
+    /// Restore point holding the modified bundles this install replaced
+    pub restore_point_id: Option<String>,
+    pub elevated: bool,
+    /// e.g. Steam about to update the game over the patched bundles
+    pub warnings: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct UninstallResult {
+    pub skin_path: String,
+    pub restored: Vec<String>,
+    /// Bundles since overwritten by something else, left as they are
+    pub kept: Vec<String>,
+    pub elevated: bool,
+}
+
+fn emit_progress(app_handle: &AppHandle, current: u32, total: u32, status: String) {
+    let _ = app_handle.emit(
+        "install_progress",
+        ProgressEvent {
+            current,
+            total,
+            status,
+            run_id: None,
+        },
+    );
+}
+
+pub fn load_installed(app_handle: &AppHandle) -> Result<(PathBuf, InstalledSkins), String> {
+    let path = storage::data_file(app_handle, INSTALLED_SKINS_FILE)?;
+    let installed = storage::load_json(&path);
+    Ok((path, installed))
+}
+
+/// Copy `(name, source)` pairs into the game directory, retrying any that hit a
+/// permission error with administrator rights. Returns whether elevation was used.
+fn copy_bundles(
+    app_handle: &AppHandle,
+    bundles_dir: &Path,
+    files: &[(String, PathBuf)],
+    verb: &str,
+) -> Result<bool, String> {
+    let total = files.len() as u32;
+    let mut needs_elevation = Vec::new();
+    let cancel = file_copy::begin_transfer(app_handle);
+
+    for (index, (name, source)) in files.iter().enumerate() {
+        emit_progress(
+            app_handle,
+            index as u32,
+            total,
+            format!("{} {}", verb, name),
+        );
+
+        let destination = bundles_dir.join(name);
+        match file_copy::copy_file(
+            source,
+            &destination,
+            true,
+            &cancel,
+            &mut file_copy::progress_emitter(app_handle, name),
+        ) {
+            Ok(_) => {}
+            Err(CopyError::PermissionDenied(_)) => {
+                needs_elevation.push((source.clone(), destination));
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    let elevated = !needs_elevation.is_empty();
+    if elevated {
+        emit_progress(
+            app_handle,
+            total - needs_elevation.len() as u32,
+            total,
+            "Waiting for permission to write to the game directory...".to_string(),
+        );
+        elevation::copy_with_consent(app_handle, &needs_elevation, verb)?;
+    }
+
+    emit_progress(app_handle, total, total, format!("{} complete", verb));
+    Ok(elevated)
+}
+
+/// Copy the built bundles over the game's, backing up whatever gets replaced
+pub fn install_output(
+    app_handle: &AppHandle,
+    skin_dir: &Path,
+    bundles_dir: &Path,
+    output_dir: &Path,
+) -> Result<InstallResult, String> {
+    if !bundles_dir.is_dir() {
+        return Err(format!(
+            "Bundles directory not found: {}",
+            bundles_dir.display()
+        ));
+    }
+
+    let built = backups::list_bundle_files(output_dir);
+    if built.is_empty() {
+        return Err(format!(
+            "No built bundles found in {}. Run a build first.",
+            output_dir.display()
+        ));
+    }
+
+    let game_bundles = backups::list_bundle_files(bundles_dir);
+    let (targets, skipped): (Vec<_>, Vec<_>) = built
+        .into_iter()
+        .partition(|(name, _)| game_bundles.contains_key(name));
+    let skipped: Vec<String> = skipped.into_iter().map(|(name, _)| name).collect();
+    for name in &skipped {
+        println!(
+            "[WARNING] Skipping {}: not present in the game directory",
+            name
+        );
+    }
+    if targets.is_empty() {
+        return Err("None of the built bundles match bundles in the game directory".to_string());
+    }
+
+    // A pending Steam update would overwrite the patch or fail on the changed files
+    let warnings: Vec<String> = steam::update_status(bundles_dir)
+        .and_then(|status| status.warning)
+        .into_iter()
+        .collect();
+    for warning in &warnings {
+        println!("[WARNING] {}", warning);
+    }
+
+    emit_progress(
+        app_handle,
+        0,
+        1,
+        "Backing up original bundles...".to_string(),
+    );
+    backups::ensure_backup(app_handle, bundles_dir)?;
+
+    // Bundles already replaced by an earlier install are kept as a restore point
+    let manifest = backups::load_manifest(&backups::install_backup_dir(app_handle, bundles_dir)?)
+        .unwrap_or_default();
+    let previously_modified: Vec<String> = targets
+        .iter()
+        .filter(|(name, _)| {
+            manifest
+                .files
+                .get(name)
+                .is_some_and(|file| backups::differs_from_backup(&bundles_dir.join(name), file))
+        })
+        .map(|(name, _)| name.clone())
+        .collect();
+
+    let restore_point_id = if previously_modified.is_empty() {
+        None
+    } else {
+        let skin_name = read_skin_info(skin_dir)
+            .map(|info| info.name)
+            .unwrap_or_else(|_| skin_dir.to_string_lossy().to_string());
+        let point = restore_points::create_restore_point(
+            app_handle,
+            bundles_dir,
+            &format!("Before installing {}", skin_name),
+            Some(previously_modified),
+        )?;
+        Some(point.id)
+    };
+
+    // Hash the built files so uninstall can tell whether they're still in place
+    let mut bundles = BTreeMap::new();
+    for (name, source) in &targets {
+        let size = std::fs::metadata(source)
+            .map(|metadata| metadata.len())
+            .unwrap_or(0);
+        bundles.insert(
+            name.clone(),
+            InstalledBundle {
+                size,
+                sha256: sha256_file(source)?,
+            },
+        );
+    }
+
+    let elevated = copy_bundles(app_handle, bundles_dir, &targets, "Installing")?;
+
+    let skin_path = skin_dir.to_string_lossy().to_string();
+    let bundles_path = bundles_dir.to_string_lossy().to_string();
+    let installed_path = storage::data_file(app_handle, INSTALLED_SKINS_FILE)?;
+    storage::update_json(&installed_path, |installed_skins: &mut InstalledSkins| {
+        installed_skins
+            .entry(bundles_path.clone())
+            .or_default()
+            .insert(
+                skin_path.clone(),
+                InstalledSkin {
+                    skin_path: skin_path.clone(),
+                    installed_at: storage::unix_now(),
+                    restore_point_id: restore_point_id.clone(),
+                    bundles,
+                },
+            );
+        Ok(())
+    })?;
+
+    Ok(InstallResult {
+        skin_path,
+        bundles_path,
+        installed: targets.into_iter().map(|(name, _)| name).collect(),
+        skipped,
+        restore_point_id,
+        elevated,
+        warnings,
+    })
+}
+
+/// Put back the bundles a skin installed, from the restore point taken just before
+/// it (or the original backup), skipping any bundle something else has since replaced
+fn uninstall(
+    app_handle: &AppHandle,
+    skin_dir: &Path,
+    bundles_dir: &Path,
+) -> Result<UninstallResult, String> {
+    let skin_path = skin_dir.to_string_lossy().to_string();
+    let bundles_path = bundles_dir.to_string_lossy().to_string();
+
+    let (installed_path, installed_skins) = load_installed(app_handle)?;
+    let record = installed_skins
+        .get(&bundles_path)
+        .and_then(|skins| skins.get(&skin_path))
+        .cloned()
+        .ok_or_else(|| format!("{} is not installed in {}", skin_path, bundles_path))?;
+
+    let points = restore_points::load_restore_points(app_handle, bundles_dir)?;
+    let find_point = |id: &str| points.iter().find(|point| point.id == id);
+    let previous = record.restore_point_id.as_deref().and_then(find_point);
+    let original = find_point(ORIGINAL_POINT_ID);
+
+    let mut files = Vec::new();
+    let mut kept = Vec::new();
+    let mut expanded = ExpandedCopies::default();
+
+    for (name, bundle) in &record.bundles {
+        let current = bundles_dir.join(name);
+        let still_installed = std::fs::metadata(&current)
+            .is_ok_and(|meta| meta.len() == bundle.size)
+            && sha256_file(&current).is_ok_and(|sha256| sha256 == bundle.sha256);
+        if !still_installed {
+            kept.push(name.clone());
+            continue;
+        }
+
+        let point = previous
+            .filter(|point| point.files.contains_key(name))
+            .or(original.filter(|point| point.files.contains_key(name)))
+            .ok_or_else(|| format!("No backup of {} to restore from", name))?;
+        let source = if point.id == ORIGINAL_POINT_ID {
+            backups::install_backup_dir(app_handle, bundles_dir)?.join(name)
+        } else {
+            let point_dir = restore_points::point_dir(app_handle, bundles_dir, &point.id)?;
+            restore_points::bundle_source(&point_dir, point, name, &mut expanded)?
+        };
+        files.push((name.clone(), source));
+    }
+
+    let elevated = copy_bundles(app_handle, bundles_dir, &files, "Restoring")?;
+
+    // Re-read under the lock: another install may have finished meanwhile
+    storage::update_json(&installed_path, |installed_skins: &mut InstalledSkins| {
+        if let Some(skins) = installed_skins.get_mut(&bundles_path) {
+            skins.remove(&skin_path);
+            if skins.is_empty() {
+                installed_skins.remove(&bundles_path);
+            }
+        }
+        Ok(())
+    })?;
+
+    Ok(UninstallResult {
+        skin_path,
+        restored: files.into_iter().map(|(name, _)| name).collect(),
+        kept,
+        elevated,
+    })
+}
+
+/// Copy a skin's built bundles into the game directory
+#[tauri::command]
+#[tracing::instrument(skip(app_handle, state), err)]
+pub async fn install_build_output(
+    app_handle: AppHandle,
+    state: State<'_, ProcessState>,
+    skin_path: String,
+    bundles_path: String,
+    output_path: Option<String>,
+) -> Result<InstallResult, String> {
+    if state.is_busy().await {
+        return Err("Cannot install bundles while a build is running".to_string());
+    }
+
+    let skin_dir = path_guard::existing_dir(&skin_path, "Skin folder")?;
+    let bundles_dir = path_guard::existing_dir(&bundles_path, "Bundles folder")?;
+    let output_dir = match output_path.filter(|path| !path.trim().is_empty()) {
+        Some(path) => path_guard::existing_dir(&path, "Build output folder")?,
+        None => build_outputs::current_output_dir(&app_handle, &skin_path, &[]),
+    };
+
+    tokio::task::spawn_blocking(move || {
+        let result = install_output(&app_handle, &skin_dir, &bundles_dir, &output_dir);
+        if result.is_ok() {
+            scheduler::refresh_game_versions(&app_handle, &bundles_dir);
+            game_watcher::refresh(&app_handle);
+        }
+        result
+    })
+    .await
+    .map_err(|e| format!("Install task failed: {}", e))?
+}
+
+/// Skins currently installed into a game directory
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub fn list_installed_skins(
+    app_handle: AppHandle,
+    bundles_path: String,
+) -> Result<Vec<InstalledSkin>, String> {
+    let (_, mut installed_skins) = load_installed(&app_handle)?;
+    let mut skins: Vec<InstalledSkin> = installed_skins
+        .remove(bundles_path.trim())
+        .unwrap_or_default()
+        .into_values()
+        .collect();
+    skins.sort_by(|a, b| b.installed_at.cmp(&a.installed_at));
+    Ok(skins)
+}
+
+/// Revert only the bundles a skin installed, leaving other modifications intact
+#[tauri::command]
+#[tracing::instrument(skip(app_handle, state), err)]
+pub async fn uninstall_skin(
+    app_handle: AppHandle,
+    state: State<'_, ProcessState>,
+    skin_path: String,
+    bundles_path: String,
+) -> Result<UninstallResult, String> {
+    if state.is_busy().await {
+        return Err("Cannot restore bundles while a build is running".to_string());
+    }
+
+    let skin_dir = path_guard::clean_path(&skin_path, "Skin folder")?;
+    let bundles_dir = path_guard::existing_dir(&bundles_path, "Bundles folder")?;
+    tokio::task::spawn_blocking(move || {
+        let result = uninstall(&app_handle, &skin_dir, &bundles_dir);
+        if result.is_ok() {
+            scheduler::refresh_game_versions(&app_handle, &bundles_dir);
+            game_watcher::refresh(&app_handle);
+        }
+        result
+    })
+    .await
+    .map_err(|e| format!("Uninstall task failed: {}", e))?
+}
@@ -0,0 +1,40 @@
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::drag_drop;
+use crate::events::SecondInstanceEvent;
+
+/// Another launch was redirected here: focus the existing window and treat
+/// file arguments like items dropped onto it. `fmskinbuilder://` links are
+/// forwarded to the deep link handler by the single-instance plugin itself.
+pub fn handle_second_instance(app_handle: &AppHandle, argv: Vec<String>, cwd: String) {
+    println!("[DEBUG] Second instance launched with args: {:?}", argv);
+
+    if let Some(window) = app_handle.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+
+    // The first argument is the executable itself
+    let args: Vec<String> = argv.into_iter().skip(1).collect();
+    let paths: Vec<PathBuf> = args
+        .iter()
+        .filter(|arg| !arg.starts_with('-') && !arg.contains("://"))
+        .map(|arg| {
+            let path = Path::new(arg);
+            if path.is_absolute() {
+                path.to_path_buf()
+            } else {
+                Path::new(&cwd).join(path)
+            }
+        })
+        .filter(|path| path.exists())
+        .collect();
+
+    if !paths.is_empty() {
+        drag_drop::handle_drop(app_handle, paths);
+    }
+
+    let _ = app_handle.emit("second_instance", SecondInstanceEvent { args, cwd });
+}
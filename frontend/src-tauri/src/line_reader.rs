@@ -0,0 +1,47 @@
+use encoding_rs::WINDOWS_1252;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt};
+
+/// Reads backend output line by line from raw bytes. The backend is asked for
+/// UTF-8, but native libraries and older backends on Windows still print in
+/// the ANSI code page; those lines are transcoded from Windows-1252 instead of
+/// ending the stream the way a UTF-8 line reader does.
+pub struct LineReader<R> {
+    reader: R,
+    buffer: Vec<u8>,
+    /// Lines that were not valid UTF-8
+    pub transcoded: u32,
+}
+
+impl<R: AsyncBufRead + Unpin> LineReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            buffer: Vec::new(),
+            transcoded: 0,
+        }
+    }
+
+    /// Next line without its line ending, or `None` at the end of the stream
+    pub async fn next_line(&mut self) -> std::io::Result<Option<String>> {
+        self.buffer.clear();
+        if self.reader.read_until(b'\n', &mut self.buffer).await? == 0 {
+            return Ok(None);
+        }
+        while matches!(self.buffer.last(), Some(b'\n' | b'\r')) {
+            self.buffer.pop();
+        }
+
+        let line = match std::str::from_utf8(&self.buffer) {
+            Ok(line) => line.to_string(),
+            Err(_) => {
+                self.transcoded += 1;
+                // Every byte has a Windows-1252 character, so nothing is lost
+                WINDOWS_1252
+                    .decode_without_bom_handling(&self.buffer)
+                    .0
+                    .into_owned()
+            }
+        };
+        Ok(Some(line))
+    }
+}
@@ -0,0 +1,67 @@
+use std::path::{Component, Path, PathBuf};
+use unicode_normalization::{is_nfc, UnicodeNormalization};
+
+const VERBATIM_PREFIX: &str = r"\\?\";
+const VERBATIM_UNC_PREFIX: &str = r"\\?\UNC\";
+
+/// Extended-length form of an absolute path on Windows (`\\?\C:\...` or
+/// `\\?\UNC\server\share\...`) so deep skin folders aren't cut off at MAX_PATH.
+/// Other platforms and relative paths are returned unchanged.
+pub fn extended(path: &Path) -> PathBuf {
+    if !cfg!(windows) || !path.is_absolute() {
+        return path.to_path_buf();
+    }
+
+    let raw = path.to_string_lossy();
+    if raw.starts_with(VERBATIM_PREFIX) {
+        return path.to_path_buf();
+    }
+
+    // Verbatim paths skip normalization, so resolve "." and ".." and separators here
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other.as_os_str()),
+        }
+    }
+    let normalized = normalized.to_string_lossy().replace('/', "\\");
+
+    match normalized.strip_prefix(r"\\") {
+        Some(unc) => PathBuf::from(format!("{}{}", VERBATIM_UNC_PREFIX, unc)),
+        None => PathBuf::from(format!("{}{}", VERBATIM_PREFIX, normalized)),
+    }
+}
+
+/// Undo `extended` for paths shown to the user or used as stored keys
+pub fn strip_extended(path: &str) -> String {
+    if let Some(unc) = path.strip_prefix(VERBATIM_UNC_PREFIX) {
+        format!(r"\\{}", unc)
+    } else {
+        path.strip_prefix(VERBATIM_PREFIX)
+            .unwrap_or(path)
+            .to_string()
+    }
+}
+
+/// Whether a file name is in composed (NFC) form. Skins zipped on macOS often
+/// carry decomposed (NFD) names that don't match the NFC names in config.json.
+pub fn is_nfc_name(name: &str) -> bool {
+    is_nfc(name)
+}
+
+/// Recompose each component of a relative path to NFC
+pub fn nfc_path(path: &Path) -> PathBuf {
+    path.components()
+        .map(|component| {
+            component
+                .as_os_str()
+                .to_string_lossy()
+                .nfc()
+                .collect::<String>()
+        })
+        .collect()
+}
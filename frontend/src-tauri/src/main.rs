@@ -1,28 +1,208 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod app_data;
+mod appearance;
+mod automation;
+mod backend_update;
+mod backup_storage;
+mod backups;
+mod batch;
+mod build_compare;
+mod build_outputs;
+mod build_summary;
 mod cache;
+mod clipboard;
+mod cloud_sync;
 mod commands;
+mod compatibility;
+mod completion;
+mod conflicts;
+mod crash;
+mod deep_link;
+mod diagnostics;
+mod dialogs;
+mod diff;
+mod disk_space;
+mod drag_drop;
+mod elevation;
+mod error;
+mod event_backlog;
 mod events;
+mod export;
+mod extract;
+mod file_copy;
+mod fonts;
+mod fs_tasks;
+mod game_profiles;
+mod game_verify;
+mod game_watcher;
+mod gamepass;
+mod hashing;
+mod history;
+mod i18n;
+mod images;
+mod import;
+mod incremental;
+mod install;
+mod instance;
+mod line_reader;
+mod long_paths;
+mod migrations;
+mod onboarding;
+mod output_verify;
+mod package_verify;
+mod patch_detection;
+mod path_guard;
 mod paths;
+mod plugins;
+mod portable;
 mod process;
+mod profiles;
+mod progress_file;
+mod projects;
+mod recents;
+mod release_notes;
+mod repository;
+mod restore_points;
+mod resume;
+mod run_logs;
+mod scheduler;
+mod serialized_file;
+mod settings;
+mod shortcuts;
+mod shutdown;
+mod skin_stats;
+mod skins;
+mod snapshots;
+mod steam;
+mod storage;
+mod taskbar;
+mod telemetry;
+mod texture_preview;
+mod tray;
+mod undo;
+mod unity_bundle;
+mod validation;
+mod watcher;
+mod webhook;
+mod write_access;
 
+use app_data::{export_app_data, import_app_data};
+use appearance::{get_appearance, AppearanceState};
+use automation::{
+    get_automation_status, start_automation_server, stop_automation_server, AutomationState,
+};
+use backend_update::{check_backend_update, install_backend_update};
+use backup_storage::{get_backup_stats, prune_backups, set_backup_root};
+use backups::{backup_original_bundles, backup_status};
+use batch::{run_batch, stop_batch, BatchState};
+use build_compare::compare_builds;
+use build_outputs::{get_output_settings, list_build_outputs, set_output_settings};
 use cache::{clear_cache, get_app_version, get_cache_size, get_platform_info, open_cache_dir};
+use clipboard::{copy_run_log_to_clipboard, copy_to_clipboard};
+use cloud_sync::{check_cloud_sync, relocate_skins_dir};
 use commands::{
     download_and_install_update, ensure_skins_dir, get_cache_dir, get_default_skins_dir,
-    select_folder,
+    list_skin_roots,
 };
+use compatibility::check_skin_compatibility;
+use completion::get_last_completion;
+use conflicts::check_skin_conflicts;
+use crash::{dismiss_crash_report, get_pending_crash_reports, submit_crash_report};
+use deep_link::{respond_link_import, DeepLinkState};
+use diagnostics::run_diagnostics;
+use dialogs::{select_file, select_files, select_folder, select_folders, select_save_path};
+use diff::diff_skins;
+use disk_space::{respond_cache_eviction, CacheEvictionState};
+use elevation::{respond_elevation_consent, ElevationState};
+use event_backlog::{get_event_backlog, EventBacklogState};
+use export::export_skin_package;
+use file_copy::{cancel_file_transfer, TransferState};
+use fonts::validate_fonts;
+use fs_tasks::{cancel_fs_tasks, FsTaskState};
+use game_profiles::{delete_game_profile, list_profiles, save_game_profile, switch_profile};
+use game_verify::{compare_with_originals, request_game_file_verification};
+use game_watcher::{reapply_last_skin, GameWatcherState};
+use gamepass::prepare_gamepass_workaround;
+use history::get_build_history;
+use i18n::{get_locale, set_locale, I18nState};
+use images::{convert_images, validate_images};
+use import::import_skin_archive;
+use incremental::check_skin_changes;
+use install::{install_build_output, list_installed_skins, uninstall_skin};
+use migrations::get_migration_report;
+use onboarding::{bootstrap_app, complete_first_run, get_first_run_status};
+use patch_detection::detect_patched_bundles;
 use paths::{detect_game_installation, find_bundles_in_game_dir};
+use plugins::{list_plugins, set_plugin_enabled};
+use portable::get_portable_info;
 use process::{run_python_task, stop_python_task, ProcessState};
+use profiles::{delete_skin_profile, get_skin_profiles, save_skin_profile};
+use projects::{delete_project, list_projects, run_project, save_project};
+use recents::{get_recent_projects, toggle_favorite};
+use release_notes::get_release_notes;
+use repository::{browse_skin_repository, download_skin};
+use restore_points::{
+    delete_restore_point, list_restore_points, restore_point, save_restore_point,
+};
+use resume::{get_interrupted_build, resume_last_build};
+use run_logs::{export_logs, list_run_logs, search_logs};
+use scheduler::{delete_schedule, list_schedules, save_schedule};
+use settings::save_settings;
+use shortcuts::{get_shortcuts, set_shortcut, ShortcutsState};
+use shutdown::{confirm_exit, ShutdownState};
+use skin_stats::analyze_skin;
+use skins::{delete_skin, duplicate_skin, get_skin_info, list_skins, rename_skin};
+use snapshots::{list_skin_snapshots, restore_skin_snapshot};
+use steam::check_steam_update;
 use tauri::Manager;
+use telemetry::{get_performance_metrics, TelemetryState};
+use texture_preview::extract_asset_preview;
+use undo::{undo_last_action, UndoState};
+use unity_bundle::inspect_bundle;
+use validation::validate_skin;
+use watcher::{start_skin_watcher, start_watch_mode, stop_skin_watcher, WatcherState};
+use webhook::send_test_webhook;
+use write_access::check_write_access;
 
 fn main() {
     tauri::Builder::default()
+        // Must be registered first so a second launch exits before doing any work
+        .plugin(tauri_plugin_single_instance::init(|app, argv, cwd| {
+            instance::handle_second_instance(app, argv, cwd);
+        }))
         .plugin(tauri_plugin_store::Builder::new().build())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_http::init())
         .plugin(tauri_plugin_updater::Builder::new().build())
+        .plugin(tauri_plugin_deep_link::init())
+        .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
+        .plugin(
+            tauri_plugin_global_shortcut::Builder::new()
+                .with_handler(|app, shortcut, event| {
+                    shortcuts::handle_shortcut(app, shortcut, event)
+                })
+                .build(),
+        )
         .manage(ProcessState::default())
+        .manage(WatcherState::default())
+        .manage(TransferState::default())
+        .manage(EventBacklogState::default())
+        .manage(AutomationState::default())
+        .manage(I18nState::default())
+        .manage(ElevationState::default())
+        .manage(AppearanceState::default())
+        .manage(ShutdownState::default())
+        .manage(TelemetryState::default())
+        .manage(FsTaskState::default())
+        .manage(ShortcutsState::default())
+        .manage(GameWatcherState::default())
+        .manage(CacheEvictionState::default())
+        .manage(UndoState::default())
+        .manage(BatchState::default())
+        .manage(DeepLinkState::default())
         .invoke_handler(tauri::generate_handler![
             run_python_task,
             stop_python_task,
@@ -37,19 +217,179 @@ fn main() {
             open_cache_dir,
             get_app_version,
             get_platform_info,
-            download_and_install_update
+            get_portable_info,
+            download_and_install_update,
+            get_release_notes,
+            check_backend_update,
+            install_backend_update,
+            get_build_history,
+            list_skins,
+            get_skin_info,
+            validate_skin,
+            import_skin_archive,
+            export_skin_package,
+            duplicate_skin,
+            rename_skin,
+            delete_skin,
+            start_skin_watcher,
+            stop_skin_watcher,
+            diff_skins,
+            list_skin_snapshots,
+            restore_skin_snapshot,
+            browse_skin_repository,
+            download_skin,
+            get_recent_projects,
+            toggle_favorite,
+            get_skin_profiles,
+            save_skin_profile,
+            delete_skin_profile,
+            backup_status,
+            backup_original_bundles,
+            list_restore_points,
+            save_restore_point,
+            restore_point,
+            delete_restore_point,
+            detect_patched_bundles,
+            install_build_output,
+            list_installed_skins,
+            uninstall_skin,
+            check_skin_conflicts,
+            cancel_file_transfer,
+            list_projects,
+            save_project,
+            delete_project,
+            run_project,
+            list_run_logs,
+            export_logs,
+            search_logs,
+            get_pending_crash_reports,
+            submit_crash_report,
+            dismiss_crash_report,
+            get_event_backlog,
+            delete_game_profile,
+            list_profiles,
+            save_game_profile,
+            switch_profile,
+            get_automation_status,
+            start_automation_server,
+            stop_automation_server,
+            get_locale,
+            set_locale,
+            run_diagnostics,
+            respond_elevation_consent,
+            list_plugins,
+            set_plugin_enabled,
+            export_app_data,
+            import_app_data,
+            get_appearance,
+            get_migration_report,
+            check_skin_changes,
+            inspect_bundle,
+            extract_asset_preview,
+            get_output_settings,
+            set_output_settings,
+            list_build_outputs,
+            analyze_skin,
+            validate_images,
+            convert_images,
+            validate_fonts,
+            confirm_exit,
+            check_steam_update,
+            request_game_file_verification,
+            compare_with_originals,
+            get_interrupted_build,
+            resume_last_build,
+            compare_builds,
+            list_schedules,
+            save_schedule,
+            delete_schedule,
+            start_watch_mode,
+            get_last_completion,
+            list_skin_roots,
+            check_cloud_sync,
+            relocate_skins_dir,
+            get_performance_metrics,
+            cancel_fs_tasks,
+            select_file,
+            select_folders,
+            select_files,
+            select_save_path,
+            copy_to_clipboard,
+            copy_run_log_to_clipboard,
+            get_shortcuts,
+            set_shortcut,
+            reapply_last_skin,
+            check_skin_compatibility,
+            respond_cache_eviction,
+            respond_link_import,
+            bootstrap_app,
+            complete_first_run,
+            get_first_run_status,
+            send_test_webhook,
+            get_backup_stats,
+            prune_backups,
+            set_backup_root,
+            check_write_access,
+            prepare_gamepass_workaround,
+            undo_last_action,
+            run_batch,
+            stop_batch,
+            save_settings
         ])
+        .on_window_event(|window, event| match event {
+            // Ask before closing mid-build; once the window is gone there is no UI to ask
+            tauri::WindowEvent::CloseRequested { api, .. } if window.label() == "main" => {
+                if !shutdown::request_exit(window.app_handle(), true) {
+                    api.prevent_close();
+                }
+            }
+            tauri::WindowEvent::DragDrop(tauri::DragDropEvent::Drop { paths, .. }) => {
+                drag_drop::handle_drop(window.app_handle(), paths.clone());
+            }
+            tauri::WindowEvent::Resized(_) => tray::handle_minimize(window),
+            tauri::WindowEvent::ThemeChanged(theme) => {
+                appearance::handle_theme_changed(window, theme);
+            }
+            tauri::WindowEvent::Focused(focused) => {
+                if *focused {
+                    appearance::handle_focus(window);
+                }
+                if window.label() == "main" {
+                    shortcuts::set_active(window.app_handle(), *focused);
+                }
+            }
+            _ => {}
+        })
         .setup(|app| {
             // Create skins directory on app startup
             let app_handle = app.handle().clone();
-            if let Ok(document_dir) = app_handle.path().document_dir() {
-                let skins_dir = document_dir.join("FM Skin Builder");
+            crash::install_panic_hook(&app_handle);
+            telemetry::init(&app_handle);
+            migrations::run_migrations(&app_handle);
+            process::clear_task_work_dirs(&app_handle);
+            if let Ok(skins_dir) = commands::default_skins_dir(&app_handle) {
                 if !skins_dir.exists() {
                     let _ = std::fs::create_dir_all(&skins_dir);
                 }
             }
+
+            i18n::init(&app_handle);
+            tray::create_tray(&app_handle)?;
+            deep_link::setup_deep_links(&app_handle);
+            automation::start_if_enabled(&app_handle);
+            scheduler::start_scheduler(&app_handle);
+            game_watcher::refresh(&app_handle);
+            compatibility::start_refresh(&app_handle);
             Ok(())
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while running tauri application")
+        .run(|app_handle, event| {
+            // Quit from the tray or the OS while a build runs
+            if let tauri::RunEvent::ExitRequested { api, .. } = event {
+                if !shutdown::request_exit(app_handle, false) {
+                    api.prevent_exit();
+                }
+            }
+        });
 }
@@ -0,0 +1,197 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tauri::{AppHandle, Emitter};
+
+use crate::commands::default_skins_dir;
+use crate::storage;
+
+const LAYOUT_FILE: &str = "data_layout.json";
+const MIGRATION_REPORT_FILE: &str = "migration_report.json";
+
+/// Folder names the documents folder had before it became "FM Skin Builder"
+const LEGACY_DOCUMENT_FOLDERS: &[&str] = &["FMSkinBuilder", "FM-Skin-Builder", "fm-skin-builder"];
+
+/// A step that upgrades the on-disk layout to `version`. Migrations must be
+/// safe to re-run: one that fails is retried on the next launch.
+struct Migration {
+    version: u32,
+    id: &'static str,
+    description: &'static str,
+    run: fn(&AppHandle) -> Result<MigrationChanges, String>,
+}
+
+const MIGRATIONS: &[Migration] = &[Migration {
+    version: 1,
+    id: "documents-folder-name",
+    description: "Move skins from older documents folder names into \"FM Skin Builder\"",
+    run: migrate_documents_folder,
+}];
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+struct DataLayout {
+    version: u32,
+}
+
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct MigrationChanges {
+    pub moved: Vec<String>,
+    /// Items left where they were because the destination already exists
+    pub skipped: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AppliedMigration {
+    pub version: u32,
+    pub id: String,
+    pub description: String,
+    pub changes: MigrationChanges,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct MigrationReport {
+    pub from_version: u32,
+    pub to_version: u32,
+    pub ran_at: u64,
+    pub migrations: Vec<AppliedMigration>,
+}
+
+/// Move `source` to `target`, merging into `target` item by item when it
+/// already exists and leaving clashing items in place
+fn merge_dir(source: &Path, target: &Path, changes: &mut MigrationChanges) -> Result<(), String> {
+    if !target.exists() {
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        std::fs::rename(source, target).map_err(|e| {
+            format!(
+                "Failed to move {} to {}: {}",
+                source.display(),
+                target.display(),
+                e
+            )
+        })?;
+        changes.moved.push(source.to_string_lossy().to_string());
+        return Ok(());
+    }
+
+    let entries = std::fs::read_dir(source)
+        .map_err(|e| format!("Failed to read {}: {}", source.display(), e))?;
+    for entry in entries.flatten() {
+        let from = entry.path();
+        let to = target.join(entry.file_name());
+        if to.exists() {
+            changes.skipped.push(from.to_string_lossy().to_string());
+            continue;
+        }
+        std::fs::rename(&from, &to)
+            .map_err(|e| format!("Failed to move {}: {}", from.display(), e))?;
+        changes.moved.push(from.to_string_lossy().to_string());
+    }
+
+    // Only succeeds once everything has been moved out
+    let _ = std::fs::remove_dir(source);
+    Ok(())
+}
+
+fn migrate_documents_folder(app_handle: &AppHandle) -> Result<MigrationChanges, String> {
+    let skins_dir = default_skins_dir(app_handle)?;
+    let Some(document_dir) = skins_dir.parent() else {
+        return Ok(MigrationChanges::default());
+    };
+
+    let mut changes = MigrationChanges::default();
+    for name in LEGACY_DOCUMENT_FOLDERS {
+        let legacy = document_dir.join(name);
+        if legacy.is_dir() {
+            merge_dir(&legacy, &skins_dir, &mut changes)?;
+        }
+    }
+    Ok(changes)
+}
+
+/// Run every migration newer than the recorded layout version, in order.
+/// Called on startup before anything else touches the data folders; emits
+/// `migration_report` when a migration changed something or failed.
+pub fn run_migrations(app_handle: &AppHandle) {
+    let layout_path = match storage::data_file(app_handle, LAYOUT_FILE) {
+        Ok(path) => path,
+        Err(e) => {
+            println!("[WARNING] Skipping data migrations: {}", e);
+            return;
+        }
+    };
+    let mut layout: DataLayout = storage::load_json(&layout_path);
+    let from_version = layout.version;
+    let mut applied = Vec::new();
+
+    for migration in MIGRATIONS.iter().filter(|m| m.version > from_version) {
+        println!("[DEBUG] Running data migration {}", migration.id);
+        let (changes, error) = match (migration.run)(app_handle) {
+            Ok(changes) => (changes, None),
+            Err(e) => (MigrationChanges::default(), Some(e)),
+        };
+        let failed = error.is_some();
+        applied.push(AppliedMigration {
+            version: migration.version,
+            id: migration.id.to_string(),
+            description: migration.description.to_string(),
+            changes,
+            error,
+        });
+        if failed {
+            eprintln!("[RUST] Data migration {} failed", migration.id);
+            break;
+        }
+        layout.version = migration.version;
+    }
+
+    if layout.version != from_version {
+        if let Err(e) = storage::save_json(&layout_path, &layout) {
+            println!("[WARNING] Failed to record data layout version: {}", e);
+        }
+    }
+
+    let noteworthy = applied.iter().any(|migration| {
+        migration.error.is_some()
+            || !migration.changes.moved.is_empty()
+            || !migration.changes.skipped.is_empty()
+    });
+    if !noteworthy {
+        return;
+    }
+
+    let report = MigrationReport {
+        from_version,
+        to_version: layout.version,
+        ran_at: storage::unix_now(),
+        migrations: applied,
+    };
+    match storage::data_file(app_handle, MIGRATION_REPORT_FILE) {
+        Ok(path) => {
+            if let Err(e) = storage::save_json(&path, &report) {
+                println!("[WARNING] Failed to save migration report: {}", e);
+            }
+        }
+        Err(e) => println!("[WARNING] Failed to save migration report: {}", e),
+    }
+    let _ = app_handle.emit("migration_report", report);
+}
+
+/// The last migration run that changed something, for UIs that attach after
+/// the startup `migration_report` event
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub fn get_migration_report(app_handle: AppHandle) -> Result<Option<MigrationReport>, String> {
+    let path = storage::data_file(&app_handle, MIGRATION_REPORT_FILE)?;
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read migration report: {}", e))?;
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("Failed to parse migration report: {}", e))
+}
@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::backend_update;
+use crate::cache::get_platform_info;
+use crate::commands::{default_skins_dir, skin_roots, SkinRoot};
+use crate::diagnostics::{run_diagnostics, DiagnosticsReport};
+use crate::paths::detect_bundles_path;
+use crate::portable::{get_portable_info, PortableInfo};
+use crate::storage;
+
+const FIRST_RUN_FILE: &str = "first_run.json";
+
+#[derive(Serialize, Deserialize, Default)]
+struct FirstRunRecord {
+    completed_at: Option<u64>,
+    /// App version the onboarding was completed with
+    app_version: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct FirstRunStatus {
+    /// True until `complete_first_run` is called
+    pub first_run: bool,
+    pub completed_at: Option<u64>,
+    pub completed_version: Option<String>,
+}
+
+/// Everything the onboarding wizard shows, gathered in one call
+#[derive(Serialize)]
+pub struct BootstrapInfo {
+    pub first_run: FirstRunStatus,
+    pub app_version: String,
+    pub platform: serde_json::Value,
+    pub portable: PortableInfo,
+    /// Default skins folder, created if it didn't exist
+    pub skins_dir: Option<String>,
+    pub skins_dir_error: Option<String>,
+    pub skin_roots: Vec<SkinRoot>,
+    /// Bundles folder of the detected game install
+    pub game_bundles_path: Option<String>,
+    pub backend_version: String,
+    pub backend_updated: bool,
+    /// Includes the backend check
+    pub diagnostics: DiagnosticsReport,
+}
+
+fn first_run_status(app_handle: &AppHandle) -> Result<FirstRunStatus, String> {
+    let path = storage::data_file(app_handle, FIRST_RUN_FILE)?;
+    let record: FirstRunRecord = storage::load_json(&path);
+    Ok(FirstRunStatus {
+        first_run: record.completed_at.is_none(),
+        completed_at: record.completed_at,
+        completed_version: record.app_version,
+    })
+}
+
+/// Whether the onboarding wizard still needs to be shown
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub fn get_first_run_status(app_handle: AppHandle) -> Result<FirstRunStatus, String> {
+    first_run_status(&app_handle)
+}
+
+/// Mark the onboarding wizard as finished
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub fn complete_first_run(app_handle: AppHandle) -> Result<FirstRunStatus, String> {
+    let path = storage::data_file(&app_handle, FIRST_RUN_FILE)?;
+    storage::update_json(&path, |record: &mut FirstRunRecord| {
+        record.completed_at = Some(storage::unix_now());
+        record.app_version = Some(env!("CARGO_PKG_VERSION").to_string());
+        Ok(())
+    })?;
+    first_run_status(&app_handle)
+}
+
+/// Detect the game, create the skins folder, check the backend and run the
+/// diagnostics, so onboarding needs a single call
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub async fn bootstrap_app(app_handle: AppHandle) -> Result<BootstrapInfo, String> {
+    let handle = app_handle.clone();
+    let (skins_dir, skin_roots, game_bundles_path) = tokio::task::spawn_blocking(move || {
+        let skins_dir = default_skins_dir(&handle)
+            .map_err(|e| e.message)
+            .and_then(|dir| {
+                std::fs::create_dir_all(&dir)
+                    .map_err(|e| format!("Failed to create skins directory: {}", e))?;
+                Ok(dir.to_string_lossy().to_string())
+            });
+        (skins_dir, skin_roots(&handle), detect_bundles_path(&handle))
+    })
+    .await
+    .map_err(|e| format!("Bootstrap task failed: {}", e))?;
+
+    let (backend_version, backend_updated) = backend_update::current_backend_version(&app_handle);
+    let diagnostics = run_diagnostics(app_handle.clone()).await?;
+
+    Ok(BootstrapInfo {
+        first_run: first_run_status(&app_handle)?,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        platform: get_platform_info(),
+        portable: get_portable_info(app_handle.clone())?,
+        skins_dir_error: skins_dir.as_ref().err().cloned(),
+        skins_dir: skins_dir.ok(),
+        skin_roots,
+        game_bundles_path,
+        backend_version,
+        backend_updated,
+        diagnostics,
+    })
+}
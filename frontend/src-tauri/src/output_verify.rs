@@ -0,0 +1,65 @@
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, UNIX_EPOCH};
+use walkdir::WalkDir;
+
+use crate::unity_bundle::BundleReader;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct BundleCheckFailure {
+    pub bundle: String,
+    pub error: String,
+}
+
+/// Integrity check of the bundles a build wrote
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct OutputVerification {
+    pub checked: u32,
+    pub failures: Vec<BundleCheckFailure>,
+}
+
+/// Bundles under the output folder written since the build started
+fn written_bundles(output_dir: &Path, started_at: u64) -> Vec<PathBuf> {
+    let since = UNIX_EPOCH + Duration::from_secs(started_at);
+    WalkDir::new(output_dir)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "bundle"))
+        .filter(|entry| {
+            entry
+                .metadata()
+                .ok()
+                .and_then(|metadata| metadata.modified().ok())
+                .is_some_and(|modified| modified >= since)
+        })
+        .map(|entry| entry.into_path())
+        .collect()
+}
+
+/// Re-open every bundle the build wrote and check its container parses,
+/// its recorded size matches the file and all blocks decompress
+pub fn verify_outputs(output_dir: &Path, started_at: u64) -> OutputVerification {
+    let bundles = written_bundles(output_dir, started_at);
+    let mut failures: Vec<BundleCheckFailure> = bundles
+        .par_iter()
+        .filter_map(|path| {
+            let error = BundleReader::open(path)
+                .and_then(|mut reader| reader.verify())
+                .err()?;
+            let bundle = path
+                .strip_prefix(output_dir)
+                .unwrap_or(path)
+                .to_string_lossy()
+                .to_string();
+            Some(BundleCheckFailure { bundle, error })
+        })
+        .collect();
+    failures.sort_by(|a, b| a.bundle.cmp(&b.bundle));
+
+    OutputVerification {
+        checked: bundles.len() as u32,
+        failures,
+    }
+}
@@ -0,0 +1,218 @@
+use base64::Engine;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+use tauri::AppHandle;
+use walkdir::WalkDir;
+
+use crate::export::{SkinManifest, SKIN_MANIFEST_FILE};
+use crate::hashing::{self, HashAlgorithm};
+use crate::settings;
+
+/// Detached minisign signature of the skin manifest
+const MANIFEST_SIGNATURE_FILE: &str = "skin_manifest.json.minisig";
+
+/// Plain `sha256  path` list, the only checksums in packages older than the manifest
+const CHECKSUMS_FILE: &str = "checksums.sha256";
+
+/// Files an export writes next to the content; every other file must be listed
+const PACKAGE_METADATA_FILES: &[&str] = &[
+    SKIN_MANIFEST_FILE,
+    MANIFEST_SIGNATURE_FILE,
+    CHECKSUMS_FILE,
+    "README.txt",
+];
+
+/// Failures listed in the error message; the rest are counted
+const MAX_REPORTED_FAILURES: usize = 5;
+
+#[derive(Serialize, Clone)]
+pub struct PackageVerification {
+    pub checked_files: usize,
+    /// "verified", "untrusted" (signed by a key not in the `trustedSigningKeys`
+    /// setting) or "unsigned"
+    pub signature: String,
+}
+
+struct ExpectedFile {
+    path: String,
+    sha256: String,
+    size: Option<u64>,
+}
+
+/// Decode a minisign key or signature given as plain text or, as the Tauri
+/// signer writes them, base64-wrapped
+fn minisign_text(value: &str) -> String {
+    base64::engine::general_purpose::STANDARD
+        .decode(value.trim())
+        .ok()
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+        .unwrap_or_else(|| value.to_string())
+}
+
+fn parse_public_key(key: &str) -> Option<minisign_verify::PublicKey> {
+    minisign_verify::PublicKey::from_base64(key.trim())
+        .or_else(|_| minisign_verify::PublicKey::decode(&minisign_text(key)))
+        .ok()
+}
+
+/// Check the manifest's detached signature against the user's trusted keys.
+/// A signature from an unknown key is reported, not rejected; one that doesn't
+/// match the manifest it came with is.
+fn verify_signature(app_handle: &AppHandle, root: &Path) -> Result<String, String> {
+    let Ok(signature) = std::fs::read_to_string(root.join(MANIFEST_SIGNATURE_FILE)) else {
+        return Ok("unsigned".to_string());
+    };
+    let signature = minisign_verify::Signature::decode(&minisign_text(&signature))
+        .map_err(|e| format!("Invalid package signature: {}", e))?;
+    let manifest = std::fs::read(root.join(SKIN_MANIFEST_FILE))
+        .map_err(|e| format!("Failed to read skin manifest: {}", e))?;
+
+    let keys: Vec<String> =
+        settings::get_setting(app_handle, "trustedSigningKeys").unwrap_or_default();
+    for key in keys.iter().filter_map(|key| parse_public_key(key)) {
+        match key.verify(&manifest, &signature, false) {
+            Ok(()) => return Ok("verified".to_string()),
+            Err(minisign_verify::Error::UnexpectedKeyId) => continue,
+            Err(e) => {
+                return Err(format!(
+                    "Package signature doesn't match its manifest, the package may have been tampered with: {}",
+                    e
+                ))
+            }
+        }
+    }
+    println!("[WARNING] Package is signed by a key that isn't trusted");
+    Ok("untrusted".to_string())
+}
+
+/// Files the package says it contains, from the manifest or the checksums list
+fn expected_files(root: &Path, manifest: Option<&SkinManifest>) -> Option<Vec<ExpectedFile>> {
+    if let Some(manifest) = manifest {
+        return Some(
+            manifest
+                .files
+                .iter()
+                .map(|file| ExpectedFile {
+                    path: file.path.clone(),
+                    sha256: file.sha256.to_lowercase(),
+                    size: Some(file.size),
+                })
+                .collect(),
+        );
+    }
+
+    let content = std::fs::read_to_string(root.join(CHECKSUMS_FILE)).ok()?;
+    Some(
+        content
+            .lines()
+            .filter_map(|line| {
+                let (sha256, path) = line.trim().split_once(char::is_whitespace)?;
+                Some(ExpectedFile {
+                    path: path.trim().trim_start_matches('*').to_string(),
+                    sha256: sha256.to_lowercase(),
+                    size: None,
+                })
+            })
+            .collect(),
+    )
+}
+
+/// Resolve a listed path inside the package, refusing ones that escape it
+fn package_path(root: &Path, relative: &str) -> Option<PathBuf> {
+    let relative = Path::new(relative);
+    relative
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)))
+        .then(|| root.join(relative))
+}
+
+/// Files anywhere in the package, other than its own metadata, that no
+/// checksum covers. Whatever gets imported must have been listed.
+fn unlisted_files(root: &Path, listed: &HashSet<PathBuf>) -> Vec<String> {
+    let is_metadata = |entry: &walkdir::DirEntry| {
+        entry.depth() == 1
+            && PACKAGE_METADATA_FILES
+                .iter()
+                .any(|name| entry.file_name() == *name)
+    };
+    WalkDir::new(root)
+        .into_iter()
+        .flatten()
+        .filter(|entry| {
+            entry.file_type().is_file() && !is_metadata(entry) && !listed.contains(entry.path())
+        })
+        .filter_map(|entry| {
+            let relative = entry.path().strip_prefix(root).ok()?;
+            Some(relative.to_string_lossy().replace('\\', "/"))
+        })
+        .collect()
+}
+
+/// Verify an extracted package against the checksums it carries, and its
+/// manifest signature when present, before it is imported. Archives without
+/// checksums return `None`.
+pub fn verify_package(
+    app_handle: &AppHandle,
+    root: &Path,
+    manifest: Option<&SkinManifest>,
+    cancel: &AtomicBool,
+) -> Result<Option<PackageVerification>, String> {
+    let Some(expected) = expected_files(root, manifest) else {
+        return Ok(None);
+    };
+    let signature = verify_signature(app_handle, root)?;
+
+    let mut failures = Vec::new();
+    let mut to_hash = Vec::new();
+    for file in &expected {
+        match package_path(root, &file.path) {
+            None => failures.push(format!("{} (invalid path)", file.path)),
+            Some(path) if !path.is_file() => failures.push(format!("{} (missing)", file.path)),
+            Some(path) => to_hash.push((file, path)),
+        }
+    }
+
+    let paths: Vec<PathBuf> = to_hash.iter().map(|(_, path)| path.clone()).collect();
+    let hashes = hashing::hash_files(app_handle, &paths, HashAlgorithm::Sha256, cancel);
+    for ((file, path), hash) in to_hash.iter().zip(hashes) {
+        let size_matches = file
+            .size
+            .is_none_or(|size| std::fs::metadata(path).is_ok_and(|meta| meta.len() == size));
+        if !size_matches || hash? != file.sha256 {
+            failures.push(format!("{} (modified)", file.path));
+        }
+    }
+
+    let listed: HashSet<PathBuf> = paths.into_iter().collect();
+    failures.extend(
+        unlisted_files(root, &listed)
+            .into_iter()
+            .map(|path| format!("{} (not in the manifest)", path)),
+    );
+
+    if !failures.is_empty() {
+        let mut message = format!(
+            "Package failed verification and was not imported; it may be corrupt or tampered with: {}",
+            failures
+                .iter()
+                .take(MAX_REPORTED_FAILURES)
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        if failures.len() > MAX_REPORTED_FAILURES {
+            message.push_str(&format!(
+                " and {} more",
+                failures.len() - MAX_REPORTED_FAILURES
+            ));
+        }
+        return Err(message);
+    }
+
+    Ok(Some(PackageVerification {
+        checked_files: expected.len(),
+        signature,
+    }))
+}
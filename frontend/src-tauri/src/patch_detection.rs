@@ -0,0 +1,190 @@
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter};
+
+use crate::backups;
+use crate::commands::current_platform;
+use crate::events::ProgressEvent;
+use crate::file_copy;
+use crate::hashing::{self, HashAlgorithm};
+use crate::path_guard;
+use crate::skins::modified_secs;
+
+/// Published SHA-256 hashes of unmodified game bundles, per platform.
+/// Format: `{ "<bundle file name>": ["<sha256>", ...] }` covering every game version.
+const PUBLISHED_HASHES_URL: &str = "https://release.fmskinbuilder.com/bundle-hashes";
+
+#[derive(Serialize)]
+pub struct PatchedBundleReport {
+    pub bundles_path: String,
+    pub reference: String, // "backup", "published", "none"
+    pub checked: usize,
+    pub modified: Vec<String>,
+    /// Bundles with no known original hash to compare against
+    pub unknown: Vec<String>,
+    pub skin_installed: bool,
+}
+
+async fn fetch_published_hashes() -> Result<HashMap<String, Vec<String>>, String> {
+    let url = format!("{}/{}.json", PUBLISHED_HASHES_URL, current_platform()?);
+    let response = reqwest::get(&url)
+        .await
+        .map_err(|e| format!("Failed to fetch published bundle hashes: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!(
+            "Bundle hash list request failed with status: {}",
+            response.status()
+        ));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse published bundle hashes: {}", e))
+}
+
+/// Compare every bundle against its known-original hashes. Size or mtime
+/// differences from the backup are conclusive, so only matching files are hashed
+/// unless `deep` is set.
+fn detect_patched(
+    app_handle: &AppHandle,
+    bundles_dir: &Path,
+    published: Option<HashMap<String, Vec<String>>>,
+    deep: bool,
+) -> Result<PatchedBundleReport, String> {
+    if !bundles_dir.is_dir() {
+        return Err(format!(
+            "Bundles directory not found: {}",
+            bundles_dir.display()
+        ));
+    }
+
+    let manifest = backups::load_manifest(&backups::install_backup_dir(app_handle, bundles_dir)?);
+    let reference = match (&manifest, &published) {
+        (Some(_), _) => "backup",
+        (None, Some(_)) => "published",
+        (None, None) => "none",
+    };
+
+    let bundles: BTreeMap<String, PathBuf> = backups::list_bundle_files(bundles_dir);
+    let total = bundles.len() as u32;
+    let mut modified = Vec::new();
+    let mut unknown = Vec::new();
+
+    // Bundles that can only be judged by their contents, hashed together below
+    let mut to_hash: Vec<(&String, Vec<String>)> = Vec::new();
+
+    for (index, (name, path)) in bundles.iter().enumerate() {
+        let _ = app_handle.emit(
+            "patch_detection_progress",
+            ProgressEvent {
+                current: index as u32,
+                total,
+                status: format!("Checking {}", name),
+                run_id: None,
+            },
+        );
+
+        let backed_up = manifest.as_ref().and_then(|m| m.files.get(name));
+        let known_hashes: Vec<String> = match (backed_up, &published) {
+            (Some(file), _) => vec![file.sha256.clone()],
+            (None, Some(published)) => published.get(name).cloned().unwrap_or_default(),
+            (None, None) => Vec::new(),
+        };
+
+        if known_hashes.is_empty() {
+            unknown.push(name.clone());
+            continue;
+        }
+
+        if let Some(file) = backed_up {
+            let size = std::fs::metadata(path).map(|meta| meta.len()).ok();
+            if size != Some(file.size) {
+                modified.push(name.clone());
+                continue;
+            }
+            if !deep && file.modified.is_some() && modified_secs(path) == file.modified {
+                continue;
+            }
+        }
+
+        to_hash.push((name, known_hashes));
+    }
+
+    if !to_hash.is_empty() {
+        let _ = app_handle.emit(
+            "patch_detection_progress",
+            ProgressEvent {
+                current: total - to_hash.len() as u32,
+                total,
+                status: format!("Hashing {} bundle(s)", to_hash.len()),
+                run_id: None,
+            },
+        );
+        let paths: Vec<PathBuf> = to_hash
+            .iter()
+            .map(|(name, _)| bundles[*name].clone())
+            .collect();
+        let cancel = file_copy::begin_transfer(app_handle);
+        let hashes = hashing::hash_files(app_handle, &paths, HashAlgorithm::Sha256, &cancel);
+        for ((name, known_hashes), hash) in to_hash.into_iter().zip(hashes) {
+            if !known_hashes.contains(&hash?) {
+                modified.push(name.clone());
+            }
+        }
+    }
+    modified.sort();
+
+    let _ = app_handle.emit(
+        "patch_detection_progress",
+        ProgressEvent {
+            current: total,
+            total,
+            status: "Check complete".to_string(),
+            run_id: None,
+        },
+    );
+
+    Ok(PatchedBundleReport {
+        bundles_path: bundles_dir.to_string_lossy().to_string(),
+        reference: reference.to_string(),
+        checked: bundles.len(),
+        skin_installed: !modified.is_empty(),
+        modified,
+        unknown,
+    })
+}
+
+/// Report which game bundles differ from their original versions
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub async fn detect_patched_bundles(
+    app_handle: AppHandle,
+    bundles_path: String,
+    deep: Option<bool>,
+) -> Result<PatchedBundleReport, String> {
+    let bundles_dir = path_guard::existing_dir(&bundles_path, "Bundles folder")?;
+
+    // The published list is only needed when there is no local backup to compare against
+    let has_backup =
+        backups::load_manifest(&backups::install_backup_dir(&app_handle, &bundles_dir)?).is_some();
+    let published = if has_backup {
+        None
+    } else {
+        match fetch_published_hashes().await {
+            Ok(hashes) => Some(hashes),
+            Err(e) => {
+                println!("[WARNING] {}", e);
+                None
+            }
+        }
+    };
+
+    tokio::task::spawn_blocking(move || {
+        detect_patched(&app_handle, &bundles_dir, published, deep.unwrap_or(false))
+    })
+    .await
+    .map_err(|e| format!("Patch detection task failed: {}", e))?
+}
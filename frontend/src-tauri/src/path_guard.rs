@@ -0,0 +1,110 @@
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+use crate::build_outputs;
+use crate::commands::skin_roots;
+use crate::portable;
+use crate::recents::get_recent_projects;
+
+fn has_control_chars(value: &str) -> bool {
+    value.chars().any(char::is_control)
+}
+
+/// Trim and check a path received from the frontend: it must be absolute (so it
+/// can't be mistaken for a CLI flag) and free of control characters
+pub fn clean_path(raw: &str, label: &str) -> Result<PathBuf, String> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return Err(format!("{} is required.", label));
+    }
+    if has_control_chars(trimmed) {
+        return Err(format!("{} contains invalid characters.", label));
+    }
+
+    let path = PathBuf::from(trimmed);
+    if !path.is_absolute() {
+        return Err(format!("{} must be an absolute path: {}", label, trimmed));
+    }
+    Ok(path)
+}
+
+/// `clean_path` that must also name an existing directory
+pub fn existing_dir(raw: &str, label: &str) -> Result<PathBuf, String> {
+    let path = clean_path(raw, label)?;
+    if !path.is_dir() {
+        return Err(format!("{} not found: {}", label, path.display()));
+    }
+    Ok(path)
+}
+
+/// `clean_path` that must also name an existing file
+pub fn existing_file(raw: &str, label: &str) -> Result<PathBuf, String> {
+    let path = clean_path(raw, label)?;
+    if !path.is_file() {
+        return Err(format!("{} not found: {}", label, path.display()));
+    }
+    Ok(path)
+}
+
+/// Extra backend arguments are passed through as-is, but never with control characters
+pub fn check_cli_arg(arg: &str) -> Result<(), String> {
+    if has_control_chars(arg) {
+        return Err(format!("Invalid backend argument: {:?}", arg));
+    }
+    Ok(())
+}
+
+/// Folders destructive operations may touch: the skins library, the app's own
+/// data and cache, roots added in settings, and skins the user has built
+fn destructive_roots(app_handle: &AppHandle) -> Vec<PathBuf> {
+    let mut roots: Vec<PathBuf> = [
+        portable::app_data_dir(app_handle),
+        portable::app_cache_dir(app_handle),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    roots.extend(build_outputs::output_root(app_handle));
+    roots.extend(
+        skin_roots(app_handle)
+            .into_iter()
+            .map(|root| PathBuf::from(root.path)),
+    );
+    roots.extend(
+        get_recent_projects(app_handle.clone(), None)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|recent| PathBuf::from(recent.skin_path)),
+    );
+
+    roots
+        .into_iter()
+        .filter_map(|root| root.canonicalize().ok())
+        .collect()
+}
+
+/// Refuse to delete or overwrite anything outside the allowed roots. Symlinks
+/// and `..` are resolved first so they can't be used to escape.
+pub fn ensure_destructive_allowed(
+    app_handle: &AppHandle,
+    path: &Path,
+    label: &str,
+) -> Result<(), String> {
+    let resolved = path
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve {}: {}", path.display(), e))?;
+
+    if destructive_roots(app_handle)
+        .iter()
+        .any(|root| resolved.starts_with(root))
+    {
+        return Ok(());
+    }
+
+    Err(format!(
+        "{} is outside the skins library and folders the app manages: {}",
+        label,
+        path.display()
+    ))
+}
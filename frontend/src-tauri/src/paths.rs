@@ -1,4 +1,10 @@
-use std::path::PathBuf;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, State};
+
+use crate::error::AppError;
+use crate::fs_tasks::{check_cancelled, FsTaskState};
+use crate::game_profiles;
 
 /// Parse Steam's libraryfolders.vdf to find all Steam library locations
 fn parse_steam_library_folders() -> Vec<PathBuf> {
@@ -123,6 +129,17 @@ fn get_steam_bundle_paths() -> Vec<PathBuf> {
         }
         // Steam Deck
         paths.push(PathBuf::from("/run/media/mmcblk0p1/steamapps/common/Football Manager 26/fm_Data/StreamingAssets/aa/StandaloneLinux64"));
+        // Windows build run through Proton, after the native layouts so those win
+        for library in &libraries {
+            let common_path = library.join("steamapps/common");
+            for game_name in &game_names {
+                paths.push(
+                    common_path
+                        .join(game_name)
+                        .join("data/StreamingAssets/aa/StandaloneWindows64"),
+                );
+            }
+        }
     }
 
     paths
@@ -175,6 +192,13 @@ fn get_epic_bundle_paths() -> Vec<PathBuf> {
             PathBuf::from(&home)
                 .join("Games/football-manager-2026/fm_Data/StreamingAssets/aa/StandaloneLinux64"),
         );
+        // Windows build installed by Heroic into a Wine prefix
+        for game_name in &game_names {
+            paths.extend(vec![
+                PathBuf::from(&home).join(format!("Games/Heroic/{}/data/StreamingAssets/aa/StandaloneWindows64", game_name)),
+                PathBuf::from(&home).join(format!(".var/app/com.heroicgameslauncher.hgl/Games/{}/data/StreamingAssets/aa/StandaloneWindows64", game_name)),
+            ]);
+        }
     }
 
     paths
@@ -212,8 +236,55 @@ fn get_xbox_bundle_paths() -> Vec<PathBuf> {
     paths
 }
 
+/// Unity build target of a bundles folder, which is named after it
+const PLATFORM_TARGETS: &[&str] = &[
+    "StandaloneWindows64",
+    "StandaloneOSX",
+    "StandaloneOSXUniversal",
+    "StandaloneLinux64",
+];
+
+#[derive(Serialize)]
+pub struct GameInstallation {
+    pub bundles_path: String,
+    /// Build target to pass to the backend when it can't be inferred from the host OS
+    pub platform_target: Option<String>,
+}
+
+/// Build target hint for a bundles folder: set for Windows builds running under
+/// Proton/Wine and for the OSX-universal layout, where the host OS misleads the backend
+pub fn platform_target(bundles_dir: &Path) -> Option<String> {
+    let target = bundles_dir.file_name()?.to_str()?;
+    if !PLATFORM_TARGETS.contains(&target) {
+        return None;
+    }
+    let foreign_windows = target == "StandaloneWindows64" && !cfg!(target_os = "windows");
+    (foreign_windows || target == "StandaloneOSXUniversal").then(|| target.to_string())
+}
+
+/// Detected bundles folder with its build target hint
 #[tauri::command]
-pub fn detect_game_installation() -> Option<String> {
+#[tracing::instrument(skip(app_handle))]
+pub fn detect_game_installation(app_handle: AppHandle) -> Option<GameInstallation> {
+    let bundles_path = detect_bundles_path(&app_handle)?;
+    Some(GameInstallation {
+        platform_target: platform_target(Path::new(&bundles_path)),
+        bundles_path,
+    })
+}
+
+/// Bundles folder of the active game profile, falling back to searching known store locations
+pub fn detect_bundles_path(app_handle: &AppHandle) -> Option<String> {
+    if let Some(profile) = game_profiles::active_profile(app_handle) {
+        if PathBuf::from(&profile.bundles_path).exists() {
+            return Some(profile.bundles_path);
+        }
+        println!(
+            "[WARNING] Active game profile bundles path not found: {}",
+            profile.bundles_path
+        );
+    }
+
     // Gather all possible bundle paths from all sources
     let mut possible_paths: Vec<PathBuf> = Vec::new();
 
@@ -231,10 +302,8 @@ pub fn detect_game_installation() -> Option<String> {
     None
 }
 
-#[tauri::command]
-pub fn find_bundles_in_game_dir(game_dir: String) -> Option<String> {
-    let game_path = PathBuf::from(game_dir);
-
+/// Bundles folder inside a game install, in this platform's layout
+pub fn bundles_in_game_dir(game_path: &Path) -> Option<String> {
     // Platform-specific bundle paths relative to game root
     let bundle_subdirs = if cfg!(target_os = "windows") {
         vec!["data/StreamingAssets/aa/StandaloneWindows64"]
@@ -244,7 +313,11 @@ pub fn find_bundles_in_game_dir(game_dir: String) -> Option<String> {
             "fm_Data/StreamingAssets/aa/StandaloneOSXUniversal",
         ]
     } else {
-        vec!["fm_Data/StreamingAssets/aa/StandaloneLinux64"]
+        vec![
+            "fm_Data/StreamingAssets/aa/StandaloneLinux64",
+            // Windows build run through Proton or Wine
+            "data/StreamingAssets/aa/StandaloneWindows64",
+        ]
     };
 
     for subdir in bundle_subdirs {
@@ -256,3 +329,18 @@ pub fn find_bundles_in_game_dir(game_dir: String) -> Option<String> {
 
     None
 }
+
+/// `bundles_in_game_dir` off the IPC thread, as the game may be on a slow drive
+#[tauri::command]
+#[tracing::instrument(skip(state), err)]
+pub async fn find_bundles_in_game_dir(
+    state: State<'_, FsTaskState>,
+    game_dir: String,
+) -> Result<Option<String>, AppError> {
+    state
+        .run("Searching the game folder", move |cancel| {
+            check_cancelled(cancel, "Searching the game folder")?;
+            Ok(bundles_in_game_dir(Path::new(&game_dir)))
+        })
+        .await
+}
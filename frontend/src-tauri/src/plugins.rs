@@ -0,0 +1,294 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::AppHandle;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Command;
+
+use crate::i18n::t;
+use crate::portable;
+use crate::process::get_log_level;
+use crate::storage;
+
+const PLUGIN_MANIFEST_FILE: &str = "plugin.json";
+const PLUGIN_SETTINGS_FILE: &str = "plugins.json";
+const DEFAULT_STEP_TIMEOUT_SECS: u64 = 600;
+
+pub const STAGE_PRE_BUILD: &str = "pre-build";
+pub const STAGE_POST_BUILD: &str = "post-build";
+
+/// Receives each plugin output line as (message, level, source)
+pub type LogSink = Arc<dyn Fn(String, &str, &str) + Send + Sync>;
+
+/// One command a plugin runs at a build stage. `command` is resolved relative
+/// to the plugin folder when a file of that name exists there.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PluginStep {
+    pub stage: String, // "pre-build", "post-build"
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+    /// Keep building when a pre-build step fails
+    #[serde(default)]
+    pub continue_on_error: bool,
+}
+
+/// `plugin.json` in a folder under `<app data>/plugins`
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PluginManifest {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub steps: Vec<PluginStep>,
+}
+
+#[derive(Serialize)]
+pub struct PluginInfo {
+    pub path: String,
+    pub enabled: bool,
+    pub manifest: Option<PluginManifest>,
+    /// Why the manifest couldn't be loaded
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+struct PluginSettings {
+    disabled: BTreeSet<String>,
+}
+
+/// Build details passed to plugin steps as `FMSB_*` environment variables
+pub struct PluginContext {
+    pub run_id: String,
+    pub skin_path: String,
+    pub bundles_path: String,
+    pub output_dir: String,
+    pub dry_run: bool,
+    pub success: Option<bool>,
+    pub exit_code: Option<i32>,
+}
+
+pub fn plugins_dir(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    Ok(portable::app_data_dir(app_handle)?.join("plugins"))
+}
+
+fn load_settings(app_handle: &AppHandle) -> Result<(PathBuf, PluginSettings), String> {
+    let path = storage::data_file(app_handle, PLUGIN_SETTINGS_FILE)?;
+    let settings = storage::load_json(&path);
+    Ok((path, settings))
+}
+
+fn read_manifest(plugin_dir: &Path) -> Result<PluginManifest, String> {
+    let content = std::fs::read_to_string(plugin_dir.join(PLUGIN_MANIFEST_FILE))
+        .map_err(|e| format!("Failed to read {}: {}", PLUGIN_MANIFEST_FILE, e))?;
+    let manifest: PluginManifest = serde_json::from_str(&content)
+        .map_err(|e| format!("Invalid {}: {}", PLUGIN_MANIFEST_FILE, e))?;
+
+    if manifest.id.trim().is_empty() {
+        return Err("Plugin id is required".to_string());
+    }
+    if let Some(step) = manifest
+        .steps
+        .iter()
+        .find(|step| step.stage != STAGE_PRE_BUILD && step.stage != STAGE_POST_BUILD)
+    {
+        return Err(format!("Unknown plugin stage: {}", step.stage));
+    }
+    Ok(manifest)
+}
+
+fn discover_plugins(app_handle: &AppHandle) -> Result<Vec<PluginInfo>, String> {
+    let dir = plugins_dir(app_handle)?;
+    let (_, settings) = load_settings(app_handle)?;
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return Ok(Vec::new());
+    };
+
+    let mut plugins: Vec<PluginInfo> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.join(PLUGIN_MANIFEST_FILE).is_file())
+        .map(|path| {
+            let (manifest, error) = match read_manifest(&path) {
+                Ok(manifest) => (Some(manifest), None),
+                Err(e) => (None, Some(e)),
+            };
+            let enabled = manifest
+                .as_ref()
+                .is_some_and(|manifest| !settings.disabled.contains(&manifest.id));
+            PluginInfo {
+                path: path.to_string_lossy().to_string(),
+                enabled,
+                manifest,
+                error,
+            }
+        })
+        .collect();
+    plugins.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(plugins)
+}
+
+async fn run_step(
+    plugin_dir: &Path,
+    manifest: &PluginManifest,
+    step: &PluginStep,
+    context: &PluginContext,
+    log: &LogSink,
+) -> Result<(), String> {
+    let local = plugin_dir.join(&step.command);
+    let program = if local.is_file() && local.starts_with(plugin_dir) {
+        local
+    } else {
+        PathBuf::from(&step.command)
+    };
+
+    let mut command = Command::new(&program);
+    command
+        .args(&step.args)
+        .current_dir(plugin_dir)
+        .env("FMSB_STAGE", &step.stage)
+        .env("FMSB_RUN_ID", &context.run_id)
+        .env("FMSB_SKIN_PATH", &context.skin_path)
+        .env("FMSB_BUNDLES_PATH", &context.bundles_path)
+        .env("FMSB_OUTPUT_DIR", &context.output_dir)
+        .env("FMSB_DRY_RUN", context.dry_run.to_string())
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    if let Some(success) = context.success {
+        command.env("FMSB_SUCCESS", success.to_string());
+    }
+    if let Some(exit_code) = context.exit_code {
+        command.env("FMSB_EXIT_CODE", exit_code.to_string());
+    }
+
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let mut child = command
+        .spawn()
+        .map_err(|e| format!("Failed to start {}: {}", program.display(), e))?;
+
+    let source = format!("plugin:{}", manifest.id);
+    let mut readers = Vec::new();
+    if let Some(stdout) = child.stdout.take() {
+        let mut lines = BufReader::new(stdout).lines();
+        let (log, source) = (log.clone(), source.clone());
+        readers.push(tokio::spawn(async move {
+            while let Ok(Some(line)) = lines.next_line().await {
+                let level = get_log_level(&line);
+                log(line, &level, &source);
+            }
+        }));
+    }
+    if let Some(stderr) = child.stderr.take() {
+        let mut lines = BufReader::new(stderr).lines();
+        let (log, source) = (log.clone(), source.clone());
+        readers.push(tokio::spawn(async move {
+            while let Ok(Some(line)) = lines.next_line().await {
+                let level = get_log_level(&line);
+                log(line, &level, &source);
+            }
+        }));
+    }
+
+    let timeout = Duration::from_secs(step.timeout_secs.unwrap_or(DEFAULT_STEP_TIMEOUT_SECS));
+    let status = match tokio::time::timeout(timeout, child.wait()).await {
+        Ok(status) => status.map_err(|e| format!("Failed to wait for plugin step: {}", e))?,
+        Err(_) => {
+            let _ = child.kill().await;
+            return Err(format!("Timed out after {} seconds", timeout.as_secs()));
+        }
+    };
+    for reader in readers {
+        let _ = reader.await;
+    }
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("Exited with code {}", status.code().unwrap_or(-1)))
+    }
+}
+
+/// Run every enabled plugin's steps for a stage in plugin order. A failing
+/// pre-build step stops the build unless it is marked `continue_on_error`;
+/// post-build failures are only logged.
+pub async fn run_stage(
+    app_handle: &AppHandle,
+    stage: &str,
+    context: &PluginContext,
+    log: &LogSink,
+) -> Result<(), String> {
+    let plugins = discover_plugins(app_handle)?;
+
+    for plugin in plugins.into_iter().filter(|plugin| plugin.enabled) {
+        let Some(manifest) = plugin.manifest else {
+            continue;
+        };
+        let plugin_dir = PathBuf::from(&plugin.path);
+
+        for step in manifest.steps.iter().filter(|step| step.stage == stage) {
+            log(
+                t(
+                    app_handle,
+                    "plugin.running_step",
+                    &[("stage", stage), ("name", &manifest.name)],
+                ),
+                "info",
+                "rust",
+            );
+
+            if let Err(e) = run_step(&plugin_dir, &manifest, step, context, log).await {
+                let message = t(
+                    app_handle,
+                    "plugin.step_failed",
+                    &[("stage", stage), ("name", &manifest.name), ("error", &e)],
+                );
+                if stage == STAGE_PRE_BUILD && !step.continue_on_error {
+                    log(message.clone(), "error", "rust");
+                    return Err(message);
+                }
+                log(message, "warning", "rust");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Plugins found in the plugins folder, including ones whose manifest is invalid
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub fn list_plugins(app_handle: AppHandle) -> Result<Vec<PluginInfo>, String> {
+    discover_plugins(&app_handle)
+}
+
+/// Turn a plugin's build steps on or off without removing it
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub fn set_plugin_enabled(app_handle: AppHandle, id: String, enabled: bool) -> Result<(), String> {
+    let path = storage::data_file(&app_handle, PLUGIN_SETTINGS_FILE)?;
+    storage::update_json(&path, |settings: &mut PluginSettings| {
+        if enabled {
+            settings.disabled.remove(id.trim());
+        } else {
+            settings.disabled.insert(id.trim().to_string());
+        }
+        Ok(())
+    })
+}
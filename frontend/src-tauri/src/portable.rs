@@ -0,0 +1,180 @@
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+/// Marker file that switches the app into portable mode when placed next to the executable
+const PORTABLE_FLAG: &str = "portable.flag";
+
+#[derive(Serialize)]
+pub struct PortableInfo {
+    pub portable: bool,
+    pub root: Option<String>,
+    pub data_dir: String,
+    pub cache_dir: String,
+    pub settings_path: Option<String>,
+}
+
+/// Directory containing the running executable
+fn executable_dir() -> Option<PathBuf> {
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|dir| dir.to_path_buf()))
+}
+
+/// Returns the executable directory when a `portable.flag` file sits next to it
+pub fn portable_root() -> Option<PathBuf> {
+    let dir = executable_dir()?;
+    if dir.join(PORTABLE_FLAG).exists() {
+        Some(dir)
+    } else {
+        None
+    }
+}
+
+pub fn is_portable() -> bool {
+    portable_root().is_some()
+}
+
+/// App data directory, stored next to the executable in portable mode
+pub fn app_data_dir(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    match portable_root() {
+        Some(root) => Ok(root.join("data")),
+        None => app_handle
+            .path()
+            .app_data_dir()
+            .map_err(|e| format!("Failed to get app data directory: {}", e)),
+    }
+}
+
+/// App cache directory, stored next to the executable in portable mode
+pub fn app_cache_dir(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    match portable_root() {
+        Some(root) => Ok(root.join("cache")),
+        None => app_handle
+            .path()
+            .app_cache_dir()
+            .map_err(|e| format!("Failed to get cache directory: {}", e)),
+    }
+}
+
+/// Extract a downloaded portable zip into a staging folder next to the executable
+pub fn stage_portable_update(archive_path: &Path) -> Result<PathBuf, String> {
+    let root = portable_root().ok_or_else(|| "App is not running in portable mode".to_string())?;
+    let staging_dir = root.join(".update-staging");
+
+    if staging_dir.exists() {
+        std::fs::remove_dir_all(&staging_dir)
+            .map_err(|e| format!("Failed to clean update staging folder: {}", e))?;
+    }
+    std::fs::create_dir_all(&staging_dir)
+        .map_err(|e| format!("Failed to create update staging folder: {}", e))?;
+
+    let file = std::fs::File::open(archive_path)
+        .map_err(|e| format!("Failed to open update archive: {}", e))?;
+    let mut archive =
+        zip::ZipArchive::new(file).map_err(|e| format!("Failed to read update archive: {}", e))?;
+
+    // ZipArchive::extract rejects entries that would escape the staging folder
+    archive
+        .extract(&staging_dir)
+        .map_err(|e| format!("Failed to extract update archive: {}", e))?;
+
+    // Never overwrite the user's portable data with whatever the archive ships
+    for reserved in ["data", "cache", PORTABLE_FLAG] {
+        let path = staging_dir.join(reserved);
+        if path.is_dir() {
+            let _ = std::fs::remove_dir_all(&path);
+        } else if path.exists() {
+            let _ = std::fs::remove_file(&path);
+        }
+    }
+
+    println!("Portable update staged in: {:?}", staging_dir);
+
+    Ok(staging_dir)
+}
+
+/// Spawn a detached helper that waits for this process to exit, copies the
+/// staged files over the portable folder, and relaunches the app
+pub fn schedule_portable_swap(staging_dir: &Path) -> Result<(), String> {
+    let root = portable_root().ok_or_else(|| "App is not running in portable mode".to_string())?;
+    let exe = std::env::current_exe()
+        .map_err(|e| format!("Failed to resolve current executable: {}", e))?;
+    let pid = std::process::id();
+
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+        let script_path = std::env::temp_dir().join("fm-skin-builder-portable-update.cmd");
+        let script = format!(
+            "@echo off\r\n\
+             :wait\r\n\
+             tasklist /FI \"PID eq {pid}\" 2>NUL | find \"{pid}\" >NUL\r\n\
+             if not errorlevel 1 (timeout /t 1 /nobreak >NUL & goto wait)\r\n\
+             robocopy \"{staging}\" \"{root}\" /E /MOVE /NFL /NDL /NJH /NJS >NUL\r\n\
+             start \"\" \"{exe}\"\r\n",
+            pid = pid,
+            staging = staging_dir.display(),
+            root = root.display(),
+            exe = exe.display(),
+        );
+        std::fs::write(&script_path, script)
+            .map_err(|e| format!("Failed to write update script: {}", e))?;
+
+        std::process::Command::new("cmd")
+            .arg("/C")
+            .arg(&script_path)
+            .creation_flags(CREATE_NO_WINDOW)
+            .spawn()
+            .map_err(|e| format!("Failed to launch update script: {}", e))?;
+    }
+
+    #[cfg(not(windows))]
+    {
+        let script_path = std::env::temp_dir().join("fm-skin-builder-portable-update.sh");
+        let script = format!(
+            "#!/bin/sh\n\
+             while kill -0 {pid} 2>/dev/null; do sleep 1; done\n\
+             cp -R \"{staging}/.\" \"{root}/\"\n\
+             rm -rf \"{staging}\"\n\
+             \"{exe}\" >/dev/null 2>&1 &\n",
+            pid = pid,
+            staging = staging_dir.display(),
+            root = root.display(),
+            exe = exe.display(),
+        );
+        std::fs::write(&script_path, script)
+            .map_err(|e| format!("Failed to write update script: {}", e))?;
+
+        std::process::Command::new("sh")
+            .arg(&script_path)
+            .spawn()
+            .map_err(|e| format!("Failed to launch update script: {}", e))?;
+    }
+
+    Ok(())
+}
+
+/// Report whether the app is running in portable mode and where it keeps its data
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub fn get_portable_info(app_handle: AppHandle) -> Result<PortableInfo, String> {
+    let root = portable_root();
+    let data_dir = app_data_dir(&app_handle)?;
+    let cache_dir = app_cache_dir(&app_handle)?;
+
+    // The frontend store loads this absolute path instead of its default in portable mode
+    let settings_path = root
+        .as_ref()
+        .map(|_| data_dir.join("settings.json").to_string_lossy().to_string());
+
+    Ok(PortableInfo {
+        portable: root.is_some(),
+        root: root.map(|path| path.to_string_lossy().to_string()),
+        settings_path,
+        data_dir: data_dir.to_string_lossy().to_string(),
+        cache_dir: cache_dir.to_string_lossy().to_string(),
+    })
+}
@@ -1,33 +1,268 @@
-use crate::events::{CommandResult, CompletionEvent, LogEvent, ProgressEvent, TaskStartedEvent};
-use serde::Deserialize;
-use std::path::PathBuf;
+use crate::backend_update;
+use crate::backups;
+use crate::build_compare;
+use crate::build_outputs;
+use crate::build_summary::{self, BuildSummary};
+use crate::cloud_sync;
+use crate::completion;
+use crate::crash;
+use crate::disk_space;
+use crate::error::{AppError, ErrorKind};
+use crate::event_backlog::{BacklogEvent, EventBacklogState};
+use crate::events::{CommandResult, CompletionEvent, ProgressEvent, TaskStartedEvent};
+use crate::export;
+use crate::game_profiles;
+use crate::game_watcher;
+use crate::gamepass;
+use crate::history::{self, BuildRecord};
+use crate::i18n::t;
+use crate::incremental;
+use crate::install;
+use crate::line_reader::LineReader;
+use crate::long_paths;
+use crate::output_verify;
+use crate::path_guard;
+use crate::paths;
+use crate::plugins::{self, PluginContext};
+use crate::portable;
+use crate::profiles;
+use crate::progress_file::{ProgressTail, PROGRESS_FILE_ENV};
+use crate::recents;
+use crate::resume::{self, BundleTracker};
+use crate::run_logs::RunLog;
+use crate::scheduler;
+use crate::settings;
+use crate::snapshots;
+use crate::storage;
+use crate::taskbar;
+use crate::tray::{self, TrayStatus};
+use crate::webhook;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use tauri::{path::BaseDirectory, AppHandle, Emitter, Manager, State};
-use tokio::io::{AsyncBufReadExt, BufReader};
+use std::time::{Duration, Instant, SystemTime};
+use tauri::ipc::Channel;
+use tauri::{path::BaseDirectory, AppHandle, Manager, State};
+use tokio::io::BufReader;
 use tokio::process::{Child, Command};
+use tokio::sync::oneshot::{self, error::TryRecvError};
 use tokio::sync::Mutex;
 
+/// How long the backend gets to exit after being asked to stop before it is killed
+const TERMINATE_GRACE: Duration = Duration::from_secs(5);
+
+/// How long shutdown waits for a stopped run to record its history and close its log
+const RUN_WIND_DOWN: Duration = Duration::from_secs(5);
+
+/// How often the backend's progress file is checked for new records
+const PROGRESS_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
 // Global state for managing the running process
 pub struct ProcessState {
     pub child: Arc<Mutex<Option<Child>>>,
+    pub active_skin: Arc<Mutex<Option<PathBuf>>>,
+    /// Set for the whole of `run_python_task`, not just while the backend runs
+    pub run_active: Arc<AtomicBool>,
+    /// Per-build states of a running batch, registered here so every check of
+    /// this state also sees the batch's builds
+    pub batch_workers: Arc<std::sync::Mutex<Vec<Arc<ProcessState>>>>,
 }
 
 impl Default for ProcessState {
     fn default() -> Self {
         Self {
             child: Arc::new(Mutex::new(None)),
+            active_skin: Arc::new(Mutex::new(None)),
+            run_active: Arc::new(AtomicBool::new(false)),
+            batch_workers: Arc::default(),
+        }
+    }
+}
+
+/// Marks a run as in progress until dropped, whichever way the run returns
+struct ActiveRun(Arc<AtomicBool>);
+
+impl ActiveRun {
+    /// `None` when a run already holds the flag
+    fn try_start(flag: &Arc<AtomicBool>) -> Option<Self> {
+        flag.compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            .ok()
+            .map(|_| Self(flag.clone()))
+    }
+}
+
+impl Drop for ActiveRun {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::SeqCst);
+    }
+}
+
+impl ProcessState {
+    /// States of the running batch's builds, empty when no batch is running
+    pub fn batch_workers(&self) -> Vec<Arc<ProcessState>> {
+        self.batch_workers
+            .lock()
+            .map(|workers| workers.clone())
+            .unwrap_or_default()
+    }
+
+    /// True while the backend is running or a batch build is in progress
+    pub async fn is_busy(&self) -> bool {
+        !self.batch_workers().is_empty() || self.child.lock().await.is_some()
+    }
+
+    /// True for the whole of a run or batch, including setup and wind-down
+    pub fn is_run_active(&self) -> bool {
+        !self.batch_workers().is_empty() || self.run_active.load(Ordering::SeqCst)
+    }
+
+    /// True when a build, including any in a running batch, is currently
+    /// running against the given skin folder
+    pub async fn is_building_skin(&self, skin_path: &Path) -> bool {
+        if self.runs_skin(skin_path).await {
+            return true;
+        }
+        for worker in self.batch_workers() {
+            if worker.runs_skin(skin_path).await {
+                return true;
+            }
+        }
+        false
+    }
+
+    async fn runs_skin(&self, skin_path: &Path) -> bool {
+        if self.child.lock().await.is_none() {
+            return false;
         }
+        self.active_skin.lock().await.as_deref() == Some(skin_path)
     }
 }
 
-#[derive(Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct TaskConfig {
     pub skin_path: String,
     pub bundles_path: String,
     pub debug_export: bool,
     pub dry_run: bool,
+    #[serde(default)]
+    pub snapshot_before_build: bool,
+    #[serde(default)]
+    pub profile: Option<String>,
+    #[serde(default)]
+    pub extra_args: Vec<String>,
+    #[serde(default)]
+    pub project_id: Option<String>,
+    /// Skip the build when nothing changed since the last one and tell the
+    /// backend which skin files did
+    #[serde(default)]
+    pub incremental: bool,
+    /// Bundle file names to rebuild; empty rebuilds every bundle
+    #[serde(default)]
+    pub bundles: Vec<String>,
+    /// Backend output: "quiet", "normal" (the default), "verbose" or "debug"
+    #[serde(default)]
+    pub verbosity: Option<String>,
+    /// Where finished bundles go: "in_place", "staging" or "package". Falls back
+    /// to the `outputMode` setting, then to the platform's default.
+    #[serde(default)]
+    pub output_mode: Option<String>,
+}
+
+impl TaskConfig {
+    /// Fill in settings from a saved build profile; a bundles path given in
+    /// the request takes precedence over the profile's
+    fn apply_profile(&mut self, app_handle: &AppHandle) -> Result<(), AppError> {
+        let Some(name) = self.profile.as_deref().map(str::trim) else {
+            return Ok(());
+        };
+        let name = if name.is_empty() {
+            profiles::DEFAULT_PROFILE
+        } else {
+            name
+        };
+
+        let profile = profiles::find_profile(app_handle, self.skin_path.trim(), name)?
+            .ok_or_else(|| AppError::not_found(format!("Build profile not found: {}", name)))?;
+
+        if self.bundles_path.trim().is_empty() {
+            self.bundles_path = profile.bundles_path;
+        }
+        self.debug_export |= profile.debug_export;
+        self.dry_run |= profile.dry_run;
+        self.extra_args.extend(profile.extra_args);
+        Ok(())
+    }
+
+    /// Use the active game profile's install and default options; anything set
+    /// by the request or a skin profile is kept
+    fn apply_game_profile(&mut self, app_handle: &AppHandle) {
+        let Some(profile) = game_profiles::active_profile(app_handle) else {
+            return;
+        };
+
+        if self.bundles_path.trim().is_empty() {
+            self.bundles_path = profile.bundles_path;
+        }
+        self.debug_export |= profile.debug_export;
+        self.dry_run |= profile.dry_run;
+
+        let mut extra_args = profile.extra_args;
+        extra_args.append(&mut self.extra_args);
+        self.extra_args = extra_args;
+    }
+
+    fn apply_output_mode(&mut self, app_handle: &AppHandle) {
+        let mode = self
+            .output_mode
+            .as_deref()
+            .map(str::trim)
+            .filter(|mode| !mode.is_empty())
+            .map(str::to_string)
+            .or_else(|| settings::get_setting(app_handle, "outputMode"))
+            .unwrap_or_else(|| default_output_mode().to_string());
+        self.output_mode = Some(mode);
+    }
+
+    /// Apply the skin and game profiles and the output mode, then build the
+    /// backend arguments
+    pub fn resolve_cli_args(&mut self, app_handle: &AppHandle) -> Result<Vec<String>, AppError> {
+        self.apply_profile(app_handle)?;
+        self.apply_game_profile(app_handle);
+        self.apply_output_mode(app_handle);
+        let mut args = build_cli_args(self)?;
+        // The backend looks for the game itself; point it at the target of the
+        // install found here, which may be a Windows build under Proton/Wine
+        if self.bundles_path.trim().is_empty() {
+            if let Some(target) = paths::detect_bundles_path(app_handle)
+                .and_then(|path| paths::platform_target(Path::new(&path)))
+            {
+                args.push("--target".to_string());
+                args.push(target);
+            }
+        }
+        Ok(args)
+    }
+}
+
+/// The built bundles are copied into the game's bundles folder after the build
+pub const OUTPUT_MODE_IN_PLACE: &str = "in_place";
+/// The built bundles stay in the build output folder
+pub const OUTPUT_MODE_STAGING: &str = "staging";
+/// The built bundles are also zipped into a shareable package
+pub const OUTPUT_MODE_PACKAGE: &str = "package";
+
+/// Output mode used when neither the build nor the settings choose one
+fn default_output_mode() -> &'static str {
+    // The game's bundles live inside its signed app bundle on macOS, and
+    // rewriting them breaks the signature
+    if cfg!(target_os = "macos") {
+        OUTPUT_MODE_STAGING
+    } else {
+        OUTPUT_MODE_IN_PLACE
+    }
 }
 
 fn workspace_root() -> PathBuf {
@@ -38,7 +273,39 @@ fn workspace_root() -> PathBuf {
         .unwrap_or_else(|| PathBuf::from("."))
 }
 
-fn python_command() -> PathBuf {
+/// Bundled backend used by release builds, preferring an independently updated one
+pub fn backend_binary_path(app_handle: &AppHandle) -> Result<PathBuf, AppError> {
+    let binary_name = if cfg!(windows) {
+        "resources/backend/fm_skin_builder.exe"
+    } else {
+        "resources/backend/fm_skin_builder"
+    };
+
+    let backend_binary = match backend_update::updated_backend_binary(app_handle) {
+        Some(updated) => updated,
+        None => app_handle
+            .path()
+            .resolve(binary_name, BaseDirectory::Resource)
+            .map_err(|error| format!("Failed to resolve backend binary path: {error}"))?,
+    };
+
+    if !backend_binary.exists() {
+        return Err(AppError::new(
+            ErrorKind::Backend,
+            format!(
+                "Backend binary not found at: {}\nExpected binary name: {}",
+                backend_binary.display(),
+                binary_name
+            ),
+        )
+        .with_path(&backend_binary)
+        .with_hint("Reinstall FM Skin Builder to restore the backend."));
+    }
+
+    Ok(backend_binary)
+}
+
+pub fn python_command() -> PathBuf {
     let root = workspace_root();
     let mut unix_path = root.clone();
     unix_path.push(".venv/bin/python3");
@@ -60,18 +327,134 @@ fn python_command() -> PathBuf {
     }
 }
 
-fn build_cli_args(config: &TaskConfig) -> Result<Vec<String>, String> {
-    let skin = config.skin_path.trim();
-    if skin.is_empty() {
-        return Err("Skin folder is required.".to_string());
+/// A folder named in the task config, telling a malformed path apart from a missing folder
+fn task_dir(raw: &str, label: &str) -> Result<PathBuf, AppError> {
+    let path = path_guard::clean_path(raw, label).map_err(AppError::invalid_input)?;
+    if !path.is_dir() {
+        return Err(
+            AppError::not_found(format!("{} not found: {}", label, path.display()))
+                .with_path(&path)
+                .with_hint("Choose the folder again; it may have been moved or renamed."),
+        );
+    }
+    Ok(path)
+}
+
+/// Install or package the bundles of a successful build as its output mode
+/// asks, returning the message to log
+fn deliver(
+    app_handle: &AppHandle,
+    output_mode: &str,
+    skin_dir: &Path,
+    bundles_dir: &Path,
+    output_dir: &Path,
+) -> Result<String, String> {
+    if output_mode == OUTPUT_MODE_PACKAGE {
+        let package = export::export_package(
+            app_handle,
+            skin_dir,
+            output_dir,
+            &output_dir.with_extension("zip"),
+            false,
+        )?;
+        return Ok(t(
+            app_handle,
+            "task.output_packaged",
+            &[("path", &package.package_path)],
+        ));
     }
 
-    let mut args = vec!["patch".to_string(), skin.to_string()];
+    // The backend found the bundles from the skin config; there is no folder to install into
+    if bundles_dir.as_os_str().is_empty() {
+        return Ok(t(app_handle, "task.output_not_installed", &[]));
+    }
+    // Game Pass installs that can't be written to get the bundles staged for a manual copy
+    if let Some(staging) = game_profiles::gamepass_staging_for(app_handle, bundles_dir) {
+        let count = gamepass::stage_output(output_dir, &staging)?;
+        return Ok(t(
+            app_handle,
+            "task.output_staged_gamepass",
+            &[
+                ("count", &count.to_string()),
+                ("path", &staging.to_string_lossy()),
+            ],
+        ));
+    }
+    let result = install::install_output(app_handle, skin_dir, bundles_dir, output_dir)?;
+    scheduler::refresh_game_versions(app_handle, bundles_dir);
+    game_watcher::refresh(app_handle);
+    Ok(t(
+        app_handle,
+        "task.output_installed",
+        &[
+            ("count", &result.installed.len().to_string()),
+            ("path", &result.bundles_path),
+        ],
+    ))
+}
+
+/// Backend flag for a verbosity level; normal output needs none
+fn verbosity_flag(verbosity: Option<&str>) -> Result<Option<&'static str>, AppError> {
+    match verbosity.map(str::trim).unwrap_or("") {
+        "" | "normal" => Ok(None),
+        "quiet" => Ok(Some("--quiet")),
+        "verbose" => Ok(Some("-v")),
+        "debug" => Ok(Some("-vv")),
+        other => Err(AppError::invalid_input(format!(
+            "Unknown verbosity: {}",
+            other
+        ))),
+    }
+}
 
-    let bundles = config.bundles_path.trim();
-    if !bundles.is_empty() {
+/// Whether a backend line is shown live at a verbosity level. Quiet builds
+/// only show warnings and errors; every line is still kept in the run log.
+fn shown_at(verbosity: Option<&str>, level: &str) -> bool {
+    verbosity.map(str::trim) != Some("quiet") || level != "info"
+}
+
+fn build_cli_args(config: &TaskConfig) -> Result<Vec<String>, AppError> {
+    let skin = task_dir(&config.skin_path, "Skin folder")?;
+    let mut args = vec![
+        "patch".to_string(),
+        long_paths::extended(&skin).to_string_lossy().to_string(),
+    ];
+
+    let bundles_dir = if config.bundles_path.trim().is_empty() {
+        None
+    } else {
+        let bundles = task_dir(&config.bundles_path, "Bundles folder")?;
         args.push("--bundle".to_string());
-        args.push(bundles.to_string());
+        args.push(long_paths::extended(&bundles).to_string_lossy().to_string());
+        // Proton/Wine and OSX-universal installs don't match the host OS layout
+        if let Some(target) = paths::platform_target(&bundles) {
+            args.push("--target".to_string());
+            args.push(target);
+        }
+        Some(bundles)
+    };
+
+    for name in config.bundles.iter().map(|name| name.trim()) {
+        if name.is_empty() {
+            continue;
+        }
+        if name.contains(['/', '\\']) || name == "." || name == ".." {
+            return Err(AppError::invalid_input(format!(
+                "Invalid bundle name: {}",
+                name
+            )));
+        }
+        if let Some(dir) = &bundles_dir {
+            if !dir.join(name).is_file() {
+                return Err(AppError::not_found(format!(
+                    "Bundle not found in the bundles folder: {}",
+                    name
+                ))
+                .with_path(&dir.join(name)));
+            }
+        }
+        args.push("--only-bundle".to_string());
+        args.push(name.to_string());
     }
 
     if config.debug_export {
@@ -82,51 +465,90 @@ fn build_cli_args(config: &TaskConfig) -> Result<Vec<String>, String> {
         args.push("--dry-run".to_string());
     }
 
-    Ok(args)
-}
+    if let Some(flag) = verbosity_flag(config.verbosity.as_deref())? {
+        args.push(flag.to_string());
+    }
 
-/// Parse progress information from log lines
-fn parse_progress(line: &str) -> Option<(u32, u32, String)> {
-    // Pattern 1: "=== Processing bundle X of Y: ..."
-    if line.contains("=== Processing bundle") {
-        let parts: Vec<&str> = line.split_whitespace().collect();
-        for i in 0..parts.len().saturating_sub(3) {
-            if parts[i] == "bundle" && parts.get(i + 2) == Some(&"of") {
-                if let (Ok(current), Ok(total)) = (
-                    parts[i + 1].parse::<u32>(),
-                    parts[i + 3].trim_end_matches(':').parse::<u32>(),
-                ) {
-                    let status = if i + 4 < parts.len() {
-                        format!("Processing bundle {}", parts[i + 4..].join(" "))
-                    } else {
-                        "Processing bundle".to_string()
-                    };
-                    return Some((current, total, status));
+    match config.output_mode.as_deref() {
+        None | Some(OUTPUT_MODE_STAGING) | Some(OUTPUT_MODE_PACKAGE) => {}
+        // The backend still writes to the output folder; the install copies from there
+        Some(OUTPUT_MODE_IN_PLACE) => {
+            if let (Some(bundles), Some(out)) = (
+                &bundles_dir,
+                build_summary::explicit_output_dir(&config.extra_args),
+            ) {
+                let same_dir = out
+                    .canonicalize()
+                    .ok()
+                    .is_some_and(|out| bundles.canonicalize().is_ok_and(|bundles| bundles == out));
+                if same_dir {
+                    return Err(AppError::invalid_input(
+                        "The output folder can't be the game's bundles folder; the in-place output mode installs the built bundles there itself",
+                    ));
                 }
             }
         }
+        Some(other) => {
+            return Err(AppError::invalid_input(format!(
+                "Unknown output mode: {}",
+                other
+            )))
+        }
     }
 
-    // Pattern 2: Look for general "X of Y" patterns
-    if line.contains(" of ") {
-        let words: Vec<&str> = line.split_whitespace().collect();
-        for i in 0..words.len().saturating_sub(2) {
-            if words[i + 1] == "of" {
-                if let (Ok(current), Ok(total)) =
-                    (words[i].parse::<u32>(), words[i + 2].parse::<u32>())
-                {
-                    let status = line.split("===").next().unwrap_or(line).trim().to_string();
-                    return Some((current, total, status));
-                }
-            }
+    for arg in config.extra_args.iter().map(|arg| arg.trim()) {
+        if !arg.is_empty() {
+            path_guard::check_cli_arg(arg).map_err(AppError::invalid_input)?;
+            args.push(arg.to_string());
+        }
+    }
+
+    Ok(args)
+}
+
+fn task_work_root(app_handle: &AppHandle) -> Result<PathBuf, AppError> {
+    Ok(portable::app_cache_dir(app_handle)?
+        .join("temp")
+        .join("tasks"))
+}
+
+/// Isolated working directory for one backend run, removed when the task ends
+/// (including on errors and cancellation)
+struct TaskWorkDir(PathBuf);
+
+impl TaskWorkDir {
+    fn create(app_handle: &AppHandle, run_id: &str) -> Result<Self, AppError> {
+        let dir = task_work_root(app_handle)?.join(run_id);
+        std::fs::create_dir_all(&dir).map_err(|e| {
+            AppError::io("Failed to create task working directory", &e).with_path(&dir)
+        })?;
+        Ok(Self(dir))
+    }
+}
+
+impl Drop for TaskWorkDir {
+    fn drop(&mut self) {
+        if let Err(e) = std::fs::remove_dir_all(&self.0) {
+            println!(
+                "[WARNING] Failed to remove task working directory {}: {}",
+                self.0.display(),
+                e
+            );
         }
     }
+}
 
-    None
+/// Remove working directories left behind by runs that never finished
+pub fn clear_task_work_dirs(app_handle: &AppHandle) {
+    if let Ok(root) = task_work_root(app_handle) {
+        if root.is_dir() {
+            let _ = std::fs::remove_dir_all(&root);
+        }
+    }
 }
 
 /// Determine log level from line content
-fn get_log_level(line: &str) -> String {
+pub fn get_log_level(line: &str) -> String {
     let line_upper = line.to_uppercase();
 
     // Check for explicit log level markers (Python logging format)
@@ -144,12 +566,46 @@ fn get_log_level(line: &str) -> String {
 }
 
 #[tauri::command]
+#[tracing::instrument(skip(app_handle, config, state, on_event), fields(skin_path = %config.skin_path), err)]
 pub async fn run_python_task(
     app_handle: AppHandle,
     config: TaskConfig,
     state: State<'_, ProcessState>,
-) -> Result<CommandResult, String> {
+    on_event: Option<Channel<BacklogEvent>>,
+) -> Result<CommandResult, AppError> {
+    run_task(app_handle, config, &state, on_event).await
+}
+
+/// Run one build, tracking its backend process in `state`. Batch builds give
+/// each concurrent run its own state.
+pub async fn run_task(
+    app_handle: AppHandle,
+    mut config: TaskConfig,
+    state: &ProcessState,
+    on_event: Option<Channel<BacklogEvent>>,
+) -> Result<CommandResult, AppError> {
     eprintln!("[RUST] run_python_task called!");
+    // A second run would take over the child handle, leaving the first one
+    // unstoppable and both writing the same bundles
+    let active_run = if state.batch_workers().is_empty() {
+        ActiveRun::try_start(&state.run_active)
+    } else {
+        None
+    };
+    let Some(_active_run) = active_run else {
+        return Err(AppError::new(
+            ErrorKind::Conflict,
+            "A build is already running",
+        ));
+    };
+    // Persisted log for this run; its sequence numbers order the stdout/stderr lines
+    let run_log = Arc::new(RunLog::create(&app_handle));
+    // Replay buffer so events emitted before the UI attaches its listeners aren't lost
+    let backlog = app_handle
+        .state::<EventBacklogState>()
+        .start_task(&run_log.run_id, on_event);
+    let started_at = storage::unix_now();
+    let started = Instant::now();
     eprintln!(
         "[RUST] Config: skin_path={}, bundles_path={}, dry_run={}",
         config.skin_path, config.bundles_path, config.dry_run
@@ -162,61 +618,328 @@ pub async fn run_python_task(
     })?;
 
     // Emit startup event
-    window
+    backlog
         .emit(
+            &window,
             "task_started",
             TaskStartedEvent {
-                message: "Initializing backend...".to_string(),
+                message: t(&app_handle, "task.initializing", &[]),
+                run_id: run_log.run_id.clone(),
             },
         )
         .map_err(|e| format!("Failed to emit task_started: {}", e))?;
 
-    window
-        .emit(
+    backlog
+        .stream(
+            &window,
             "build_log",
-            LogEvent {
-                message: "Validating configuration...".to_string(),
-                level: "info".to_string(),
-            },
+            run_log.record(t(&app_handle, "task.validating", &[]), "info", "rust"),
         )
         .map_err(|e| format!("Failed to emit build_log: {}", e))?;
 
-    let cli_args = build_cli_args(&config).map_err(|e| {
-        let err_msg = format!("Configuration error: {}", e);
-        let _ = window.emit(
+    // Kept as requested so a resumed build applies the profiles the same way
+    let requested_config = config.clone();
+    let mut cli_args = config.resolve_cli_args(&app_handle).map_err(|e| {
+        let err_msg = t(&app_handle, "task.config_error", &[("error", &e.message)]);
+        let _ = backlog.stream(
+            &window,
             "build_log",
-            LogEvent {
-                message: err_msg.clone(),
-                level: "error".to_string(),
-            },
+            run_log.record(err_msg.clone(), "error", "rust"),
         );
-        err_msg
+        AppError {
+            message: err_msg,
+            ..e
+        }
     })?;
 
+    // Work out what changed since the last successful build of this skin
+    let build_key = incremental::args_key(&cli_args);
+    let mut output_dir =
+        build_outputs::current_output_dir(&app_handle, &config.skin_path, &config.extra_args);
+    let mut changed_files_path = None;
+    if config.incremental && !config.dry_run {
+        let incremental_handle = app_handle.clone();
+        let skin_dir = PathBuf::from(config.skin_path.trim());
+        let bundles_dir = PathBuf::from(config.bundles_path.trim());
+        let (key, output) = (build_key.clone(), output_dir.clone());
+        let changes = tokio::task::spawn_blocking(move || {
+            incremental::detect_changes(&incremental_handle, &skin_dir, &bundles_dir, &output, &key)
+        })
+        .await
+        .map_err(|e| format!("Change detection task failed: {}", e))
+        .and_then(|result| result);
+
+        match changes {
+            Ok(changes) if changes.is_up_to_date() => {
+                let message = t(&app_handle, "build.up_to_date", &[]);
+                let _ = backlog.stream(
+                    &window,
+                    "build_log",
+                    run_log.record(message.clone(), "info", "rust"),
+                );
+                let completion = CompletionEvent {
+                    success: true,
+                    exit_code: 0,
+                    message,
+                    project_id: config.project_id.clone(),
+                    summary: BuildSummary::default(),
+                    run_id: run_log.run_id.clone(),
+                };
+                completion::record_completion(&app_handle, &config.skin_path, &completion);
+                backlog
+                    .emit(&window, "build_complete", completion)
+                    .map_err(|e| format!("Failed to emit completion: {}", e))?;
+                return Ok(CommandResult {
+                    stdout: String::new(),
+                    stderr: String::new(),
+                    status: 0,
+                    success: true,
+                });
+            }
+            Ok(changes) => {
+                let message = match &changes.full_rebuild_reason {
+                    Some(reason) => t(&app_handle, "task.full_rebuild", &[("reason", reason)]),
+                    None => {
+                        changed_files_path = incremental::write_changed_files(
+                            &app_handle,
+                            &run_log.run_id,
+                            &changes,
+                        )
+                        .map_err(|e| eprintln!("[RUST] {}", e))
+                        .ok();
+                        t(
+                            &app_handle,
+                            "task.incremental_changes",
+                            &[("count", &changes.changed_files().len().to_string())],
+                        )
+                    }
+                };
+                let _ = backlog.stream(
+                    &window,
+                    "build_log",
+                    run_log.record(message, "info", "rust"),
+                );
+            }
+            Err(e) => {
+                let message = t(&app_handle, "task.full_rebuild", &[("reason", &e)]);
+                let _ = backlog.stream(
+                    &window,
+                    "build_log",
+                    run_log.record(message, "warning", "rust"),
+                );
+            }
+        }
+    }
+
+    // With an output directory configured, each build writes to its own folder
+    if !config.dry_run && build_summary::explicit_output_dir(&config.extra_args).is_none() {
+        match build_outputs::new_build_dir(&app_handle, &config.skin_path) {
+            Ok(Some(dir)) => {
+                cli_args.push("--out".to_string());
+                cli_args.push(long_paths::extended(&dir).to_string_lossy().to_string());
+                output_dir = dir;
+            }
+            Ok(None) => output_dir = PathBuf::from(config.skin_path.trim()).join("packages"),
+            Err(e) => {
+                output_dir = PathBuf::from(config.skin_path.trim()).join("packages");
+                let message = t(&app_handle, "task.output_dir_failed", &[("error", &e)]);
+                let _ = backlog.stream(
+                    &window,
+                    "build_log",
+                    run_log.record(message, "warning", "rust"),
+                );
+            }
+        }
+    }
+
+    // Make room by evicting cache the build won't use rather than failing mid-write
+    if !config.dry_run {
+        let skin_name = Path::new(config.skin_path.trim())
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        match disk_space::ensure_free_space(&app_handle, &output_dir, &skin_name).await {
+            Ok(0) => {}
+            Ok(reclaimed) => {
+                let message = t(
+                    &app_handle,
+                    "task.cache_evicted",
+                    &[("size", &disk_space::format_size(reclaimed))],
+                );
+                let _ = backlog.stream(
+                    &window,
+                    "build_log",
+                    run_log.record(message, "info", "rust"),
+                );
+            }
+            Err(e) => {
+                let _ = backlog.stream(
+                    &window,
+                    "build_log",
+                    run_log.record(e.message.clone(), "error", "rust"),
+                );
+                return Err(e);
+            }
+        }
+    }
+
+    // Snapshot the skin folder so a bad batch edit can be undone
+    if config.snapshot_before_build && !config.dry_run {
+        let snapshot_handle = app_handle.clone();
+        let skin_dir = PathBuf::from(config.skin_path.trim());
+        let snapshot = tokio::task::spawn_blocking(move || {
+            snapshots::create_snapshot(&snapshot_handle, &skin_dir, "pre-build")
+        })
+        .await
+        .map_err(|e| format!("Snapshot task failed: {}", e))
+        .and_then(|result| result);
+
+        let (message, level) = match snapshot {
+            Ok(info) => (
+                t(
+                    &app_handle,
+                    "task.snapshot_created",
+                    &[("id", &info.id), ("count", &info.file_count.to_string())],
+                ),
+                "info",
+            ),
+            Err(e) => (
+                t(&app_handle, "task.snapshot_failed", &[("error", &e)]),
+                "warning",
+            ),
+        };
+        let _ = backlog.stream(&window, "build_log", run_log.record(message, level, "rust"));
+    }
+
+    // Keep pristine copies of the game's bundles before the first patch
+    let bundles_dir = PathBuf::from(config.bundles_path.trim());
+    if !config.dry_run && !config.bundles_path.trim().is_empty() && bundles_dir.is_dir() {
+        let backup_handle = app_handle.clone();
+        let backup = tokio::task::spawn_blocking(move || {
+            backups::ensure_backup(&backup_handle, &bundles_dir)
+        })
+        .await
+        .map_err(|e| format!("Backup task failed: {}", e))
+        .and_then(|result| result);
+
+        let (message, level) = match backup {
+            Ok(status) => (
+                t(
+                    &app_handle,
+                    "task.backup_created",
+                    &[
+                        ("count", &status.file_count.to_string()),
+                        ("path", &status.backup_path),
+                    ],
+                ),
+                "info",
+            ),
+            Err(e) => (
+                t(&app_handle, "task.backup_failed", &[("error", &e)]),
+                "warning",
+            ),
+        };
+        let _ = backlog.stream(&window, "build_log", run_log.record(message, level, "rust"));
+    }
+
+    // Plugin steps log through the same pipeline as the backend
+    let plugin_log: plugins::LogSink = {
+        let (backlog, run_log, window) = (backlog.clone(), run_log.clone(), window.clone());
+        Arc::new(move |message: String, level: &str, source: &str| {
+            let _ = backlog.stream(&window, "build_log", run_log.record(message, level, source));
+        })
+    };
+    let mut plugin_context = PluginContext {
+        run_id: run_log.run_id.clone(),
+        skin_path: config.skin_path.trim().to_string(),
+        bundles_path: config.bundles_path.trim().to_string(),
+        output_dir: output_dir.to_string_lossy().to_string(),
+        dry_run: config.dry_run,
+        success: None,
+        exit_code: None,
+    };
+    plugins::run_stage(
+        &app_handle,
+        plugins::STAGE_PRE_BUILD,
+        &plugin_context,
+        &plugin_log,
+    )
+    .await?;
+
+    // Synced folders can hold cloud-only placeholders the backend would stall on
+    if let Some(provider) = cloud_sync::cloud_provider(Path::new(config.skin_path.trim())) {
+        let message = t(
+            &app_handle,
+            "task.cloud_synced_skin",
+            &[("provider", &provider)],
+        );
+        let _ = backlog.stream(
+            &window,
+            "build_log",
+            run_log.record(message, "warning", "rust"),
+        );
+
+        let skin_dir = PathBuf::from(config.skin_path.trim());
+        match tokio::task::spawn_blocking(move || cloud_sync::hydrate(&skin_dir)).await {
+            Ok(report) => {
+                if report.hydrated > 0 {
+                    let message = t(
+                        &app_handle,
+                        "task.cloud_files_downloaded",
+                        &[("count", &report.hydrated.to_string())],
+                    );
+                    let _ = backlog.stream(
+                        &window,
+                        "build_log",
+                        run_log.record(message, "info", "rust"),
+                    );
+                }
+                for failure in &report.failed {
+                    let message = t(
+                        &app_handle,
+                        "task.cloud_file_unavailable",
+                        &[("error", failure)],
+                    );
+                    let _ = backlog.stream(
+                        &window,
+                        "build_log",
+                        run_log.record(message, "warning", "rust"),
+                    );
+                }
+            }
+            Err(e) => eprintln!("[RUST] Cloud file download task failed: {}", e),
+        }
+    }
+
     // Emit status update
-    window
-        .emit(
+    backlog
+        .stream(
+            &window,
             "build_log",
-            LogEvent {
-                message: "Starting Python backend (cold start may take a moment)...".to_string(),
-                level: "info".to_string(),
-            },
+            run_log.record(t(&app_handle, "task.starting_backend", &[]), "info", "rust"),
         )
         .map_err(|e| format!("Failed to emit: {}", e))?;
 
     // Build the command
     let python_path = python_command();
 
-    window
-        .emit(
+    backlog
+        .stream(
+            &window,
             "build_log",
-            LogEvent {
-                message: format!("Using Python: {}", python_path.display()),
-                level: "info".to_string(),
-            },
+            run_log.record(
+                t(
+                    &app_handle,
+                    "task.using_python",
+                    &[("path", &python_path.display().to_string())],
+                ),
+                "info",
+                "rust",
+            ),
         )
         .map_err(|e| format!("Failed to emit: {}", e))?;
 
+    let work_dir = TaskWorkDir::create(&app_handle, &run_log.run_id)?;
     let mut command = if cfg!(debug_assertions) {
         let mut cmd = Command::new(&python_path);
         cmd.arg("-m").arg("fm_skin_builder");
@@ -224,26 +947,11 @@ pub async fn run_python_task(
         cmd.env("PYTHONPATH", "fm_skin_builder");
         cmd
     } else {
-        let binary_name = if cfg!(windows) {
-            "resources/backend/fm_skin_builder.exe"
-        } else {
-            "resources/backend/fm_skin_builder"
-        };
-
-        let backend_binary = app_handle
-            .path()
-            .resolve(binary_name, BaseDirectory::Resource)
-            .map_err(|error| format!("Failed to resolve backend binary path: {error}"))?;
-
-        if !backend_binary.exists() {
-            return Err(format!(
-                "Backend binary not found at: {}\nExpected binary name: {}",
-                backend_binary.display(),
-                binary_name
-            ));
-        }
-
-        Command::new(backend_binary)
+        // Never inherit the app's cwd: relative paths would resolve differently
+        // depending on how the app was launched
+        let mut cmd = Command::new(backend_binary_path(&app_handle)?);
+        cmd.current_dir(&work_dir.0);
+        cmd
     };
 
     command.args(&cli_args);
@@ -251,25 +959,36 @@ pub async fn run_python_task(
     command.stderr(Stdio::piped());
 
     // Set cache directory environment variable
-    let cache_dir = app_handle
-        .path()
-        .app_cache_dir()
-        .map_err(|e| format!("Failed to get cache dir: {}", e))?;
+    let cache_dir = portable::app_cache_dir(&app_handle)?;
 
     if !cache_dir.exists() {
         std::fs::create_dir_all(&cache_dir)
-            .map_err(|e| format!("Failed to create cache dir: {}", e))?;
+            .map_err(|e| AppError::io("Failed to create cache dir", &e).with_path(&cache_dir))?;
     }
 
+    // Python otherwise writes to pipes in the Windows ANSI code page
+    command.env("PYTHONIOENCODING", "utf-8");
+    command.env("PYTHONUTF8", "1");
     command.env("FM_CACHE_DIR", cache_dir.to_string_lossy().to_string());
+    if let Some(path) = &changed_files_path {
+        command.env("FM_CHANGED_FILES", path);
+    }
+    let progress_path = work_dir.0.join("progress.jsonl");
+    command.env(PROGRESS_FILE_ENV, &progress_path);
 
-    window
-        .emit(
+    backlog
+        .stream(
+            &window,
             "build_log",
-            LogEvent {
-                message: format!("Using cache directory: {}", cache_dir.display()),
-                level: "info".to_string(),
-            },
+            run_log.record(
+                t(
+                    &app_handle,
+                    "task.using_cache",
+                    &[("path", &cache_dir.display().to_string())],
+                ),
+                "info",
+                "rust",
+            ),
         )
         .map_err(|e| format!("Failed to emit: {}", e))?;
 
@@ -281,34 +1000,57 @@ pub async fn run_python_task(
         command.creation_flags(CREATE_NO_WINDOW);
     }
 
-    window
-        .emit(
+    backlog
+        .stream(
+            &window,
             "build_log",
-            LogEvent {
-                message: format!("Spawning process with args: {:?}", cli_args),
-                level: "info".to_string(),
-            },
+            run_log.record(
+                t(
+                    &app_handle,
+                    "task.spawning",
+                    &[("args", &format!("{:?}", cli_args))],
+                ),
+                "info",
+                "rust",
+            ),
         )
         .map_err(|e| format!("Failed to emit: {}", e))?;
 
+    // What the OS names crash dumps after: the executable and its working directory
+    let program = Path::new(command.as_std().get_program())
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let process_dir = command
+        .as_std()
+        .get_current_dir()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| work_dir.0.clone());
+    let spawned_at = SystemTime::now();
+
     // Spawn the process
     let mut child = command.spawn().map_err(|error| {
-        format!(
-            "Failed to spawn Python process: {}. Check that Python is installed and accessible.",
-            error
+        AppError::new(
+            ErrorKind::Backend,
+            t(
+                &app_handle,
+                "task.spawn_failed",
+                &[("error", &error.to_string())],
+            ),
         )
     })?;
 
-    window
-        .emit(
+    backlog
+        .stream(
+            &window,
             "build_log",
-            LogEvent {
-                message: "Backend process spawned successfully, processing...".to_string(),
-                level: "info".to_string(),
-            },
+            run_log.record(t(&app_handle, "task.spawned", &[]), "info", "rust"),
         )
         .map_err(|e| format!("Failed to emit: {}", e))?;
 
+    let spawned = Instant::now();
+    let backend_pid = child.id();
+
     // Take stdout and stderr BEFORE storing the child in the mutex
     let stdout = child.stdout.take().ok_or("Failed to capture stdout")?;
     let stderr = child.stderr.take().ok_or("Failed to capture stderr")?;
@@ -317,41 +1059,82 @@ pub async fn run_python_task(
     {
         let mut child_guard = state.child.lock().await;
         *child_guard = Some(child);
+        *state.active_skin.lock().await = Some(PathBuf::from(config.skin_path.trim()));
     }
+    tray::set_tray_status(&app_handle, TrayStatus::Building);
+    taskbar::clear_taskbar_progress(&window);
 
     // Create buffered readers
-    let mut stdout_reader = BufReader::new(stdout).lines();
-    let mut stderr_reader = BufReader::new(stderr).lines();
+    let mut stdout_reader = LineReader::new(BufReader::new(stdout));
+    let mut stderr_reader = LineReader::new(BufReader::new(stderr));
+
+    let bundle_tracker = Arc::new(BundleTracker::default());
+
+    // Follow the backend's progress file until the run ends; dropping the
+    // sender (on any return) stops it
+    let (stop_progress, mut progress_stopped) = oneshot::channel::<()>();
+    let window_progress = window.clone();
+    let backlog_progress = backlog.clone();
+    let run_id_progress = run_log.run_id.clone();
+    let progress_task = tokio::spawn(async move {
+        let mut tail = ProgressTail::new(progress_path);
+        loop {
+            // Read once more after the backend exits to pick up its last records
+            let finished = !matches!(progress_stopped.try_recv(), Err(TryRecvError::Empty));
+            for record in tail.read_new() {
+                if record.total == 0 {
+                    continue;
+                }
+                taskbar::set_taskbar_progress(&window_progress, record.current, record.total);
+                let _ = backlog_progress.stream(
+                    &window_progress,
+                    "build_progress",
+                    ProgressEvent {
+                        current: record.current,
+                        total: record.total,
+                        status: record.status,
+                        run_id: Some(run_id_progress.clone()),
+                    },
+                );
+            }
+            if finished {
+                break;
+            }
+            tokio::time::sleep(PROGRESS_POLL_INTERVAL).await;
+        }
+    });
 
     // Stream stdout
     let window_stdout = window.clone();
+    let run_log_stdout = run_log.clone();
+    let backlog_stdout = backlog.clone();
+    let tracker_stdout = bundle_tracker.clone();
+    let verbosity_stdout = config.verbosity.clone();
     let stdout_task = tokio::spawn(async move {
         let mut lines = Vec::new();
-        while let Ok(Some(line)) = stdout_reader.next_line().await {
-            lines.push(line.clone());
-
-            // Parse for progress information
-            if let Some((current, total, status)) = parse_progress(&line) {
-                if total > 0 {
-                    let _ = window_stdout.emit(
-                        "build_progress",
-                        ProgressEvent {
-                            current,
-                            total,
-                            status,
-                        },
-                    );
+        loop {
+            let line = match stdout_reader.next_line().await {
+                Ok(Some(line)) => line,
+                Ok(None) => break,
+                Err(e) => {
+                    eprintln!("[RUST] Failed to read backend stdout: {}", e);
+                    break;
                 }
-            }
+            };
+            lines.push(line.clone());
+            tracker_stdout.observe(&line);
 
             // Emit log event
             let level = get_log_level(&line);
-            let _ = window_stdout.emit(
-                "build_log",
-                LogEvent {
-                    message: line,
-                    level,
-                },
+            let event = run_log_stdout.record(line, &level, "stdout");
+            if shown_at(verbosity_stdout.as_deref(), &level) {
+                let _ = backlog_stdout.stream(&window_stdout, "build_log", event);
+            }
+        }
+        if stdout_reader.transcoded > 0 {
+            println!(
+                "[WARNING] {} backend stdout line(s) were not UTF-8 and were decoded as Windows-1252",
+                stdout_reader.transcoded
             );
         }
         lines
@@ -359,33 +1142,35 @@ pub async fn run_python_task(
 
     // Stream stderr
     let window_stderr = window.clone();
+    let run_log_stderr = run_log.clone();
+    let backlog_stderr = backlog.clone();
+    let tracker_stderr = bundle_tracker.clone();
+    let verbosity_stderr = config.verbosity.clone();
     let stderr_task = tokio::spawn(async move {
         let mut lines = Vec::new();
-        while let Ok(Some(line)) = stderr_reader.next_line().await {
-            lines.push(line.clone());
-
-            // Parse for progress information
-            if let Some((current, total, status)) = parse_progress(&line) {
-                if total > 0 {
-                    let _ = window_stderr.emit(
-                        "build_progress",
-                        ProgressEvent {
-                            current,
-                            total,
-                            status,
-                        },
-                    );
+        loop {
+            let line = match stderr_reader.next_line().await {
+                Ok(Some(line)) => line,
+                Ok(None) => break,
+                Err(e) => {
+                    eprintln!("[RUST] Failed to read backend stderr: {}", e);
+                    break;
                 }
-            }
+            };
+            lines.push(line.clone());
+            tracker_stderr.observe(&line);
 
             // Parse stderr for log level
             let level = get_log_level(&line);
-            let _ = window_stderr.emit(
-                "build_log",
-                LogEvent {
-                    message: line,
-                    level,
-                },
+            let event = run_log_stderr.record(line, &level, "stderr");
+            if shown_at(verbosity_stderr.as_deref(), &level) {
+                let _ = backlog_stderr.stream(&window_stderr, "build_log", event);
+            }
+        }
+        if stderr_reader.transcoded > 0 {
+            println!(
+                "[WARNING] {} backend stderr line(s) were not UTF-8 and were decoded as Windows-1252",
+                stderr_reader.transcoded
             );
         }
         lines
@@ -408,22 +1193,58 @@ pub async fn run_python_task(
                     tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
                 }
                 Err(error) => {
-                    let err_msg = format!("Failed to check process status: {error}");
-                    let _ = window.emit(
+                    let err_msg = t(
+                        &app_handle,
+                        "task.status_check_failed",
+                        &[("error", &error.to_string())],
+                    );
+                    let _ = backlog.stream(
+                        &window,
                         "build_log",
-                        LogEvent {
-                            message: err_msg.clone(),
-                            level: "error".to_string(),
-                        },
+                        run_log.record(err_msg.clone(), "error", "rust"),
                     );
                     *child_guard = None;
                     drop(child_guard);
-                    return Err(err_msg);
+                    tray::set_tray_status(&app_handle, TrayStatus::Failed);
+                    taskbar::set_taskbar_finished(&window, false);
+                    return Err(AppError::new(ErrorKind::Backend, err_msg));
                 }
             }
         } else {
             drop(child_guard);
-            return Err("Task was cancelled".to_string());
+            let _ = history::record_build(
+                &app_handle,
+                BuildRecord {
+                    skin_path: config.skin_path.trim().to_string(),
+                    bundles_path: config.bundles_path.trim().to_string(),
+                    dry_run: config.dry_run,
+                    success: false,
+                    cancelled: true,
+                    exit_code: -1,
+                    started_at,
+                    finished_at: storage::unix_now(),
+                    run_id: Some(run_log.run_id.clone()),
+                    crash_artifacts: Vec::new(),
+                },
+            );
+            if !config.dry_run {
+                if let Err(e) = resume::record_interrupted(
+                    &app_handle,
+                    &run_log.run_id,
+                    &requested_config,
+                    &config.bundles_path,
+                    &output_dir,
+                    &bundle_tracker,
+                ) {
+                    eprintln!("[RUST] Failed to record interrupted build: {}", e);
+                }
+            }
+            tray::set_tray_status(&app_handle, TrayStatus::Idle);
+            taskbar::clear_taskbar_progress(&window);
+            return Err(AppError::new(
+                ErrorKind::Cancelled,
+                t(&app_handle, "task.cancelled", &[]),
+            ));
         }
     };
 
@@ -434,50 +1255,340 @@ pub async fn run_python_task(
     let stderr_lines: Vec<String> = stderr_task
         .await
         .map_err(|error| format!("Failed to read stderr: {error}"))?;
+    drop(stop_progress);
+    let _ = progress_task.await;
 
+    let exited = Instant::now();
     let exit_code = exit_status.code().unwrap_or(-1);
-    let success = exit_status.success();
+    let mut success = exit_status.success();
 
-    // Emit completion event
-    let completion_message = if success {
-        if config.dry_run {
-            "✓ Preview completed successfully. No bundles were modified during this dry run."
-                .to_string()
+    let mut summary = BuildSummary::from_output(
+        &stdout_lines
+            .iter()
+            .chain(stderr_lines.iter())
+            .cloned()
+            .collect::<Vec<_>>(),
+    );
+    if !config.dry_run {
+        summary.measure_output(&output_dir, started_at);
+    }
+
+    // Catch truncated or corrupt writes before the game loads them
+    let mut verification_failed = false;
+    if success && !config.dry_run {
+        let verify_dir = output_dir.clone();
+        match tokio::task::spawn_blocking(move || {
+            output_verify::verify_outputs(&verify_dir, started_at)
+        })
+        .await
+        {
+            Ok(verification) => {
+                for failure in &verification.failures {
+                    let message = t(
+                        &app_handle,
+                        "task.bundle_verification_failed",
+                        &[("bundle", &failure.bundle), ("error", &failure.error)],
+                    );
+                    let _ = backlog.stream(
+                        &window,
+                        "build_log",
+                        run_log.record(message, "error", "rust"),
+                    );
+                }
+                if verification.failures.is_empty() && verification.checked > 0 {
+                    let message = t(
+                        &app_handle,
+                        "task.bundles_verified",
+                        &[("count", &verification.checked.to_string())],
+                    );
+                    let _ = backlog.stream(
+                        &window,
+                        "build_log",
+                        run_log.record(message, "info", "rust"),
+                    );
+                }
+                verification_failed = !verification.failures.is_empty();
+                success &= !verification_failed;
+                summary.verification = Some(verification);
+            }
+            Err(e) => eprintln!("[RUST] Output verification task failed: {}", e),
+        }
+    }
+
+    // The backend always writes to the output folder; the output mode decides what happens next
+    let output_mode = config.output_mode.clone().unwrap_or_default();
+    if success && !config.dry_run && output_mode != OUTPUT_MODE_STAGING {
+        let deliver_handle = app_handle.clone();
+        let skin_dir = PathBuf::from(config.skin_path.trim());
+        let bundles_dir = PathBuf::from(config.bundles_path.trim());
+        let deliver_output = output_dir.clone();
+        let delivered = tokio::task::spawn_blocking(move || {
+            deliver(
+                &deliver_handle,
+                &output_mode,
+                &skin_dir,
+                &bundles_dir,
+                &deliver_output,
+            )
+        })
+        .await
+        .map_err(|e| format!("Output task failed: {}", e))
+        .and_then(|result| result);
+
+        let (message, level) = match delivered {
+            Ok(message) => (message, "info"),
+            Err(e) => {
+                success = false;
+                (
+                    t(&app_handle, "task.output_failed", &[("error", &e)]),
+                    "error",
+                )
+            }
+        };
+        let _ = backlog.stream(&window, "build_log", run_log.record(message, level, "rust"));
+    }
+
+    let mut crash_artifacts = Vec::new();
+    if crash::is_hard_crash(&exit_status) {
+        if let Some(pid) = backend_pid {
+            let crashed = crash::CrashedProcess {
+                pid,
+                program: &program,
+                started: spawned_at,
+                work_dir: &process_dir,
+            };
+            crash_artifacts =
+                crash::collect_crash_artifacts(&app_handle, &crashed, &run_log.run_id).await;
+        }
+        match crash::record_backend_crash(
+            &app_handle,
+            &exit_status,
+            &run_log.run_id,
+            crash_artifacts.clone(),
+        ) {
+            Ok(report) => eprintln!("[RUST] Backend crashed, report saved as {}", report.id),
+            Err(e) => eprintln!("[RUST] Failed to record backend crash: {}", e),
+        }
+    }
+
+    if success && !config.dry_run {
+        let incremental_handle = app_handle.clone();
+        let skin_dir = PathBuf::from(config.skin_path.trim());
+        let bundles_dir = PathBuf::from(config.bundles_path.trim());
+        let key = build_key.clone();
+        let recorded = tokio::task::spawn_blocking(move || {
+            incremental::record_build(&incremental_handle, &skin_dir, &bundles_dir, &key)
+        })
+        .await
+        .map_err(|e| format!("Build manifest task failed: {}", e))
+        .and_then(|result| result);
+        if let Err(e) = recorded {
+            eprintln!("[RUST] Failed to record build manifest: {}", e);
+        }
+    }
+    if let Some(path) = &changed_files_path {
+        let _ = std::fs::remove_file(path);
+    }
+
+    if !config.dry_run {
+        if success {
+            resume::clear_interrupted(&app_handle, &config.skin_path);
+        } else if let Err(e) = resume::record_interrupted(
+            &app_handle,
+            &run_log.run_id,
+            &requested_config,
+            &config.bundles_path,
+            &output_dir,
+            &bundle_tracker,
+        ) {
+            eprintln!("[RUST] Failed to record interrupted build: {}", e);
+        }
+    }
+
+    if !config.dry_run {
+        if let Err(e) = recents::record_recent(
+            &app_handle,
+            config.skin_path.trim(),
+            config.bundles_path.trim(),
+        ) {
+            eprintln!("[RUST] Failed to record recent project: {}", e);
+        }
+    }
+
+    if let Err(e) = history::record_build(
+        &app_handle,
+        BuildRecord {
+            skin_path: config.skin_path.trim().to_string(),
+            bundles_path: config.bundles_path.trim().to_string(),
+            dry_run: config.dry_run,
+            success,
+            cancelled: false,
+            exit_code,
+            started_at,
+            finished_at: storage::unix_now(),
+            run_id: Some(run_log.run_id.clone()),
+            crash_artifacts,
+        },
+    ) {
+        eprintln!("[RUST] Failed to record build history: {}", e);
+    }
+
+    if !config.dry_run {
+        match build_outputs::prune_outputs(&app_handle, &config.skin_path) {
+            Ok(removed) if removed > 0 => {
+                println!("[DEBUG] Removed {} old build output(s)", removed)
+            }
+            Ok(_) => {}
+            Err(e) => eprintln!("[RUST] Failed to prune build outputs: {}", e),
+        }
+    }
+
+    plugin_context.success = Some(success);
+    plugin_context.exit_code = Some(exit_code);
+    if let Err(e) = plugins::run_stage(
+        &app_handle,
+        plugins::STAGE_POST_BUILD,
+        &plugin_context,
+        &plugin_log,
+    )
+    .await
+    {
+        eprintln!("[RUST] Post-build plugin steps failed: {}", e);
+    }
+
+    taskbar::set_taskbar_finished(&window, success);
+    tray::set_tray_status(
+        &app_handle,
+        if success {
+            TrayStatus::Succeeded
         } else {
-            "✓ Build completed successfully. All bundles have been created.".to_string()
+            TrayStatus::Failed
+        },
+    );
+
+    summary.add_phase("prepare", spawned - started);
+    summary.add_phase("backend", exited - spawned);
+    summary.add_phase("finalize", exited.elapsed());
+
+    if success && !config.dry_run {
+        let manifest_handle = app_handle.clone();
+        let (run_id, skin_path) = (run_log.run_id.clone(), config.skin_path.clone());
+        let (manifest_dir, manifest_summary) = (output_dir.clone(), summary.clone());
+        let output_lines: Vec<String> = stdout_lines
+            .iter()
+            .chain(stderr_lines.iter())
+            .cloned()
+            .collect();
+        match tokio::task::spawn_blocking(move || {
+            build_compare::record_manifest(
+                &manifest_handle,
+                &run_id,
+                &skin_path,
+                &manifest_dir,
+                &manifest_summary,
+                &output_lines,
+            )
+        })
+        .await
+        {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => eprintln!("[RUST] Failed to record build manifest: {}", e),
+            Err(e) => eprintln!("[RUST] Build manifest task failed: {}", e),
         }
-    } else if config.dry_run {
-        format!(
-            "✗ Preview failed with exit code {}. Check the logs for details.",
-            exit_code
-        )
-    } else {
-        format!(
-            "✗ Build failed with exit code {}. Check the logs for details.",
-            exit_code
-        )
+    }
+
+    // Emit completion event
+    let exit_code_text = exit_code.to_string();
+    let completion_message = match (success, config.dry_run) {
+        _ if verification_failed => t(
+            &app_handle,
+            "build.verification_failed",
+            &[(
+                "count",
+                &summary
+                    .verification
+                    .as_ref()
+                    .map(|verification| verification.failures.len())
+                    .unwrap_or(0)
+                    .to_string(),
+            )],
+        ),
+        (true, true) => t(&app_handle, "build.preview_succeeded", &[]),
+        (true, false) => t(&app_handle, "build.succeeded", &[]),
+        (false, true) => t(
+            &app_handle,
+            "build.preview_failed",
+            &[("code", &exit_code_text)],
+        ),
+        (false, false) => t(&app_handle, "build.failed", &[("code", &exit_code_text)]),
     };
 
-    window
-        .emit(
-            "build_complete",
-            CompletionEvent {
-                success,
-                exit_code,
-                message: completion_message,
-            },
-        )
+    let completion = CompletionEvent {
+        success,
+        exit_code,
+        message: completion_message,
+        project_id: config.project_id.clone(),
+        summary,
+        run_id: run_log.run_id.clone(),
+    };
+    completion::record_completion(&app_handle, &config.skin_path, &completion);
+    webhook::notify_build(&app_handle, &config, &completion, started.elapsed());
+    backlog
+        .emit(&window, "build_complete", completion)
         .map_err(|e| format!("Failed to emit completion: {}", e))?;
 
     Ok(CommandResult {
         stdout: stdout_lines.join("\n"),
         stderr: stderr_lines.join("\n"),
         status: exit_code,
+        success,
     })
 }
 
+/// Ask the backend to stop (SIGTERM on Unix) so it can clean up, and kill it
+/// if it is still running after the grace period. Windows has no signal a
+/// windowless child can receive, so it is killed straight away.
+async fn terminate_child(child: &mut Child) -> std::io::Result<()> {
+    #[cfg(unix)]
+    if let Some(pid) = child.id() {
+        let _ = std::process::Command::new("kill")
+            .arg("-TERM")
+            .arg(pid.to_string())
+            .status();
+        let deadline = Instant::now() + TERMINATE_GRACE;
+        while Instant::now() < deadline {
+            if child.try_wait()?.is_some() {
+                return Ok(());
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    }
+    child.kill().await
+}
+
+/// Stop the running build for app shutdown, then wait for the run to record
+/// its history, close its log and remove its working directory
+pub async fn shutdown_running_task(state: &ProcessState) {
+    let child = state.child.lock().await.take();
+    if let Some(mut child) = child {
+        println!("[DEBUG] Stopping backend for shutdown");
+        if let Err(e) = terminate_child(&mut child).await {
+            eprintln!("[RUST] Failed to stop backend: {}", e);
+        }
+    }
+
+    let deadline = Instant::now() + RUN_WIND_DOWN;
+    while state.run_active.load(Ordering::SeqCst) && Instant::now() < deadline {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+}
+
 #[tauri::command]
-pub async fn stop_python_task(state: State<'_, ProcessState>) -> Result<String, String> {
+#[tracing::instrument(skip(app_handle, state), err)]
+pub async fn stop_python_task(
+    app_handle: AppHandle,
+    state: State<'_, ProcessState>,
+) -> Result<String, AppError> {
     let child_ref = state.child.clone();
     let mut child_guard = child_ref.lock().await;
 
@@ -485,19 +1596,25 @@ pub async fn stop_python_task(state: State<'_, ProcessState>) -> Result<String,
         match child.kill().await {
             Ok(_) => {
                 *child_guard = None;
-                Ok("Task cancelled successfully".to_string())
+                Ok(t(&app_handle, "task.cancel_succeeded", &[]))
             }
             Err(e) => {
                 let err_str = e.to_string();
                 if err_str.contains("already exited") || err_str.contains("No such process") {
                     *child_guard = None;
-                    Ok("Task already completed".to_string())
+                    Ok(t(&app_handle, "task.already_completed", &[]))
                 } else {
-                    Err(format!("Failed to cancel task: {}", e))
+                    Err(AppError::new(
+                        ErrorKind::Backend,
+                        t(&app_handle, "task.cancel_failed", &[("error", &err_str)]),
+                    ))
                 }
             }
         }
     } else {
-        Err("No task is currently running".to_string())
+        Err(AppError::new(
+            ErrorKind::Conflict,
+            t(&app_handle, "task.not_running", &[]),
+        ))
     }
 }
@@ -0,0 +1,121 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use tauri::AppHandle;
+
+use crate::storage;
+
+const PROFILES_FILE: &str = "build_profiles.json";
+
+/// Profile used when the caller doesn't name one
+pub const DEFAULT_PROFILE: &str = "default";
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct BuildProfile {
+    pub bundles_path: String,
+    pub debug_export: bool,
+    pub dry_run: bool,
+    pub extra_args: Vec<String>,
+    pub target_store: Option<String>, // "steam", "epic", "gamepass"
+    pub updated_at: u64,
+}
+
+/// Profiles keyed by skin path, then profile name
+type ProfileStore = BTreeMap<String, BTreeMap<String, BuildProfile>>;
+
+fn load_profiles(app_handle: &AppHandle) -> Result<(PathBuf, ProfileStore), String> {
+    let path = storage::data_file(app_handle, PROFILES_FILE)?;
+    let profiles = storage::load_json(&path);
+    Ok((path, profiles))
+}
+
+/// Look up a saved profile for a skin
+pub fn find_profile(
+    app_handle: &AppHandle,
+    skin_path: &str,
+    name: &str,
+) -> Result<Option<BuildProfile>, String> {
+    let (_, profiles) = load_profiles(app_handle)?;
+    Ok(profiles
+        .get(skin_path)
+        .and_then(|skin_profiles| skin_profiles.get(name))
+        .cloned())
+}
+
+/// All saved profiles for a skin, keyed by profile name
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub fn get_skin_profiles(
+    app_handle: AppHandle,
+    skin_path: String,
+) -> Result<BTreeMap<String, BuildProfile>, String> {
+    let (_, mut profiles) = load_profiles(&app_handle)?;
+    Ok(profiles.remove(skin_path.trim()).unwrap_or_default())
+}
+
+/// Create or replace a named profile for a skin
+#[tauri::command]
+#[tracing::instrument(skip(app_handle, profile), err)]
+pub fn save_skin_profile(
+    app_handle: AppHandle,
+    skin_path: String,
+    name: Option<String>,
+    mut profile: BuildProfile,
+) -> Result<(), String> {
+    let name = name
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| DEFAULT_PROFILE.to_string());
+
+    let path = storage::data_file(&app_handle, PROFILES_FILE)?;
+    profile.bundles_path = profile.bundles_path.trim().to_string();
+    profile.updated_at = storage::unix_now();
+
+    storage::update_json(&path, |profiles: &mut ProfileStore| {
+        profiles
+            .entry(skin_path.trim().to_string())
+            .or_default()
+            .insert(name, profile);
+        Ok(())
+    })
+}
+
+/// Remove a named profile; returns false when it didn't exist
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub fn delete_skin_profile(
+    app_handle: AppHandle,
+    skin_path: String,
+    name: String,
+) -> Result<bool, String> {
+    let path = storage::data_file(&app_handle, PROFILES_FILE)?;
+    let skin_path = skin_path.trim();
+
+    storage::update_json(&path, |profiles: &mut ProfileStore| {
+        let removed = profiles
+            .get_mut(skin_path)
+            .map(|skin_profiles| skin_profiles.remove(name.trim()).is_some())
+            .unwrap_or(false);
+
+        if profiles.get(skin_path).is_some_and(|p| p.is_empty()) {
+            profiles.remove(skin_path);
+        }
+        Ok(removed)
+    })
+}
+
+/// Keep profiles attached to a skin after it's renamed
+pub fn rename_skin_path(
+    app_handle: &AppHandle,
+    old_path: &str,
+    new_path: &str,
+) -> Result<(), String> {
+    let path = storage::data_file(app_handle, PROFILES_FILE)?;
+    storage::update_json(&path, |profiles: &mut ProfileStore| {
+        if let Some(skin_profiles) = profiles.remove(old_path) {
+            profiles.insert(new_path.to_string(), skin_profiles);
+        }
+        Ok(())
+    })
+}
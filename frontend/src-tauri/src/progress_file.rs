@@ -0,0 +1,67 @@
+use serde::Deserialize;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::PathBuf;
+
+/// Environment variable telling the backend where to append progress records
+pub const PROGRESS_FILE_ENV: &str = "FM_PROGRESS_FILE";
+
+/// One line of the progress file: `{"current": 2, "total": 5, "status": "..."}`
+#[derive(Deserialize)]
+pub struct ProgressRecord {
+    pub current: u32,
+    pub total: u32,
+    #[serde(default)]
+    pub status: String,
+}
+
+/// Follows the JSON lines the backend appends to its progress file, so progress
+/// never has to be guessed from the human-readable log
+pub struct ProgressTail {
+    path: PathBuf,
+    offset: u64,
+    /// Bytes of a line the backend hasn't finished writing yet
+    partial: Vec<u8>,
+}
+
+impl ProgressTail {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            offset: 0,
+            partial: Vec::new(),
+        }
+    }
+
+    /// Records completed since the last read. The file not existing yet just
+    /// means the backend hasn't reported anything.
+    pub fn read_new(&mut self) -> Vec<ProgressRecord> {
+        let Ok(mut file) = std::fs::File::open(&self.path) else {
+            return Vec::new();
+        };
+        if file.seek(SeekFrom::Start(self.offset)).is_err() {
+            return Vec::new();
+        }
+        let mut appended = Vec::new();
+        let Ok(read) = file.read_to_end(&mut appended) else {
+            return Vec::new();
+        };
+        self.offset += read as u64;
+        self.partial.extend(appended);
+
+        let Some(end) = self.partial.iter().rposition(|byte| *byte == b'\n') else {
+            return Vec::new();
+        };
+        let complete: Vec<u8> = self.partial.drain(..=end).collect();
+        String::from_utf8_lossy(&complete)
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| match serde_json::from_str(line) {
+                Ok(record) => Some(record),
+                Err(e) => {
+                    println!("[WARNING] Ignoring malformed progress record: {}", e);
+                    None
+                }
+            })
+            .collect()
+    }
+}
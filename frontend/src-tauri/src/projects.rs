@@ -0,0 +1,209 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tauri::{AppHandle, State};
+
+use crate::error::{AppError, ErrorKind};
+use crate::events::CommandResult;
+use crate::process::{run_python_task, ProcessState, TaskConfig};
+use crate::storage;
+
+const PROJECTS_FILE: &str = "projects.json";
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ProjectRunStatus {
+    pub success: bool,
+    pub exit_code: i32,
+    pub finished_at: u64,
+}
+
+/// A named skin + bundles + options combination that can be rebuilt in one click
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Project {
+    pub id: String,
+    pub name: String,
+    pub skin_path: String,
+    pub bundles_path: String,
+    #[serde(default)]
+    pub debug_export: bool,
+    #[serde(default)]
+    pub dry_run: bool,
+    #[serde(default)]
+    pub snapshot_before_build: bool,
+    #[serde(default)]
+    pub extra_args: Vec<String>,
+    pub created_at: u64,
+    #[serde(default)]
+    pub last_run: Option<ProjectRunStatus>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectInput {
+    pub id: Option<String>,
+    pub name: String,
+    pub skin_path: String,
+    pub bundles_path: String,
+    #[serde(default)]
+    pub debug_export: bool,
+    #[serde(default)]
+    pub dry_run: bool,
+    #[serde(default)]
+    pub snapshot_before_build: bool,
+    #[serde(default)]
+    pub extra_args: Vec<String>,
+}
+
+impl Project {
+    /// Build settings for the project
+    pub fn task_config(&self) -> TaskConfig {
+        TaskConfig {
+            skin_path: self.skin_path.clone(),
+            bundles_path: self.bundles_path.clone(),
+            debug_export: self.debug_export,
+            dry_run: self.dry_run,
+            snapshot_before_build: self.snapshot_before_build,
+            profile: None,
+            extra_args: self.extra_args.clone(),
+            project_id: Some(self.id.clone()),
+            incremental: false,
+            bundles: Vec::new(),
+            verbosity: None,
+            output_mode: None,
+        }
+    }
+}
+
+pub fn load_projects(app_handle: &AppHandle) -> Result<(PathBuf, Vec<Project>), String> {
+    let path = storage::data_file(app_handle, PROJECTS_FILE)?;
+    let projects = storage::load_json(&path);
+    Ok((path, projects))
+}
+
+/// Saved projects, most recently run first
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub fn list_projects(app_handle: AppHandle) -> Result<Vec<Project>, String> {
+    let (_, mut projects) = load_projects(&app_handle)?;
+    projects.sort_by(|a, b| {
+        let last_a = a.last_run.as_ref().map(|run| run.finished_at);
+        let last_b = b.last_run.as_ref().map(|run| run.finished_at);
+        last_b.cmp(&last_a).then(b.created_at.cmp(&a.created_at))
+    });
+    Ok(projects)
+}
+
+/// Create a project, or update it when `id` matches an existing one
+#[tauri::command]
+#[tracing::instrument(skip(app_handle, project), fields(project = %project.name), err)]
+pub fn save_project(app_handle: AppHandle, project: ProjectInput) -> Result<Project, String> {
+    let name = project.name.trim().to_string();
+    if name.is_empty() {
+        return Err("Project name cannot be empty".to_string());
+    }
+    if project.skin_path.trim().is_empty() {
+        return Err("Skin folder is required.".to_string());
+    }
+
+    let path = storage::data_file(&app_handle, PROJECTS_FILE)?;
+    storage::update_json(&path, |projects: &mut Vec<Project>| {
+        let existing = project
+            .id
+            .as_deref()
+            .and_then(|id| projects.iter().position(|p| p.id == id));
+
+        let id = match existing {
+            Some(index) => projects[index].id.clone(),
+            None => {
+                let mut id = storage::unix_now().to_string();
+                while projects.iter().any(|p| p.id == id) {
+                    id.push('_');
+                }
+                id
+            }
+        };
+
+        let saved = Project {
+            id,
+            name,
+            skin_path: project.skin_path.trim().to_string(),
+            bundles_path: project.bundles_path.trim().to_string(),
+            debug_export: project.debug_export,
+            dry_run: project.dry_run,
+            snapshot_before_build: project.snapshot_before_build,
+            extra_args: project.extra_args,
+            created_at: existing
+                .map(|index| projects[index].created_at)
+                .unwrap_or_else(storage::unix_now),
+            last_run: existing.and_then(|index| projects[index].last_run.clone()),
+        };
+
+        match existing {
+            Some(index) => projects[index] = saved.clone(),
+            None => projects.push(saved.clone()),
+        }
+        Ok(saved)
+    })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub fn delete_project(app_handle: AppHandle, id: String) -> Result<(), String> {
+    let path = storage::data_file(&app_handle, PROJECTS_FILE)?;
+    storage::update_json(&path, |projects: &mut Vec<Project>| {
+        let before = projects.len();
+        projects.retain(|project| project.id != id);
+
+        if projects.len() == before {
+            return Err(format!("Project not found: {}", id));
+        }
+        Ok(())
+    })
+}
+
+/// Build a saved project; `build_complete` carries its id
+#[tauri::command]
+#[tracing::instrument(skip(app_handle, state), err)]
+pub async fn run_project(
+    app_handle: AppHandle,
+    state: State<'_, ProcessState>,
+    id: String,
+) -> Result<CommandResult, AppError> {
+    if state.is_run_active() {
+        return Err(AppError::new(
+            ErrorKind::Conflict,
+            "Cannot build the project while a build is running",
+        ));
+    }
+    let (_, projects) = load_projects(&app_handle)?;
+    let project = projects
+        .into_iter()
+        .find(|project| project.id == id)
+        .ok_or_else(|| AppError::not_found(format!("Project not found: {}", id)))?;
+
+    let result = run_python_task(app_handle.clone(), project.task_config(), state, None).await;
+    record_run(&app_handle, &id, &result)?;
+    result
+}
+
+/// Save the outcome of a project build as its last run. A task that was
+/// cancelled or failed to start leaves the previous status in place.
+pub fn record_run(
+    app_handle: &AppHandle,
+    id: &str,
+    result: &Result<CommandResult, AppError>,
+) -> Result<(), String> {
+    let Ok(output) = result else {
+        return Ok(());
+    };
+    let path = storage::data_file(app_handle, PROJECTS_FILE)?;
+    storage::update_json(&path, |projects: &mut Vec<Project>| {
+        if let Some(project) = projects.iter_mut().find(|project| project.id == id) {
+            project.last_run = Some(ProjectRunStatus {
+                success: output.success,
+                exit_code: output.status,
+                finished_at: storage::unix_now(),
+            });
+        }
+        Ok(())
+    })
+}
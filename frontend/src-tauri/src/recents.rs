@@ -0,0 +1,157 @@
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::storage;
+
+const RECENTS_FILE: &str = "recent_projects.json";
+
+/// Non-favorite entries kept before the least recently used are dropped
+const MAX_RECENT_ENTRIES: usize = 20;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RecentProject {
+    pub skin_path: String,
+    pub bundles_path: String,
+    pub favorite: bool,
+    pub last_used_at: u64,
+    pub build_count: u32,
+}
+
+fn load_recents(
+    app_handle: &AppHandle,
+) -> Result<(std::path::PathBuf, Vec<RecentProject>), String> {
+    let path = storage::data_file(app_handle, RECENTS_FILE)?;
+    let recents = storage::load_json(&path);
+    Ok((path, recents))
+}
+
+/// Favorites first, then most recently used
+fn sort_recents(recents: &mut [RecentProject]) {
+    recents.sort_by(|a, b| {
+        b.favorite
+            .cmp(&a.favorite)
+            .then(b.last_used_at.cmp(&a.last_used_at))
+    });
+}
+
+/// Record that a skin/bundles pair was just built
+pub fn record_recent(
+    app_handle: &AppHandle,
+    skin_path: &str,
+    bundles_path: &str,
+) -> Result<(), String> {
+    let path = storage::data_file(app_handle, RECENTS_FILE)?;
+    storage::update_json(&path, |recents: &mut Vec<RecentProject>| {
+        match recents
+            .iter_mut()
+            .find(|recent| recent.skin_path == skin_path && recent.bundles_path == bundles_path)
+        {
+            Some(recent) => {
+                recent.last_used_at = storage::unix_now();
+                recent.build_count += 1;
+            }
+            None => recents.push(RecentProject {
+                skin_path: skin_path.to_string(),
+                bundles_path: bundles_path.to_string(),
+                favorite: false,
+                last_used_at: storage::unix_now(),
+                build_count: 1,
+            }),
+        }
+
+        sort_recents(recents);
+
+        // Favorites are never evicted
+        let mut kept = 0;
+        recents.retain(|recent| {
+            if recent.favorite {
+                return true;
+            }
+            kept += 1;
+            kept <= MAX_RECENT_ENTRIES
+        });
+        Ok(())
+    })
+}
+
+/// Point entries at a skin's new location after a rename
+pub fn rename_skin_path(
+    app_handle: &AppHandle,
+    old_path: &str,
+    new_path: &str,
+) -> Result<(), String> {
+    let path = storage::data_file(app_handle, RECENTS_FILE)?;
+    storage::update_json(&path, |recents: &mut Vec<RecentProject>| {
+        for recent in recents
+            .iter_mut()
+            .filter(|recent| recent.skin_path == old_path)
+        {
+            recent.skin_path = new_path.to_string();
+        }
+        Ok(())
+    })
+}
+
+/// Drop entries for a skin that no longer exists
+pub fn remove_skin(app_handle: &AppHandle, skin_path: &str) -> Result<(), String> {
+    let path = storage::data_file(app_handle, RECENTS_FILE)?;
+    storage::update_json(&path, |recents: &mut Vec<RecentProject>| {
+        recents.retain(|recent| recent.skin_path != skin_path);
+        Ok(())
+    })
+}
+
+/// Recently built and favorited skin/bundles pairs, favorites first
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub fn get_recent_projects(
+    app_handle: AppHandle,
+    limit: Option<usize>,
+) -> Result<Vec<RecentProject>, String> {
+    let (_, mut recents) = load_recents(&app_handle)?;
+    sort_recents(&mut recents);
+
+    if let Some(limit) = limit {
+        recents.truncate(limit);
+    }
+
+    Ok(recents)
+}
+
+/// Toggle the favorite flag for a skin/bundles pair, adding it if needed.
+/// Returns the new favorite state.
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub fn toggle_favorite(
+    app_handle: AppHandle,
+    skin_path: String,
+    bundles_path: Option<String>,
+) -> Result<bool, String> {
+    let skin_path = skin_path.trim().to_string();
+    let bundles_path = bundles_path.unwrap_or_default().trim().to_string();
+    let path = storage::data_file(&app_handle, RECENTS_FILE)?;
+    storage::update_json(&path, |recents: &mut Vec<RecentProject>| {
+        let favorite = match recents
+            .iter_mut()
+            .find(|recent| recent.skin_path == skin_path && recent.bundles_path == bundles_path)
+        {
+            Some(recent) => {
+                recent.favorite = !recent.favorite;
+                recent.favorite
+            }
+            None => {
+                recents.push(RecentProject {
+                    skin_path,
+                    bundles_path,
+                    favorite: true,
+                    last_used_at: storage::unix_now(),
+                    build_count: 0,
+                });
+                true
+            }
+        };
+
+        sort_recents(recents);
+        Ok(favorite)
+    })
+}
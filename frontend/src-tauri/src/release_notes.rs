@@ -0,0 +1,165 @@
+use serde::Serialize;
+use std::cmp::Ordering;
+
+const CHANGELOG_URL: &str = "https://release.fmskinbuilder.com/CHANGELOG.md";
+
+#[derive(Serialize, Clone)]
+pub struct ReleaseNotesEntry {
+    pub version: String,
+    pub date: Option<String>,
+    pub highlights: Vec<String>,
+    pub breaking_changes: Vec<String>,
+}
+
+/// Compare two version strings (`0.2.0`, `v0.3.0-beta.1`)
+/// Numeric parts compare numerically; a pre-release sorts before its release
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    fn split(version: &str) -> (Vec<u64>, Option<String>) {
+        let trimmed = version.trim().trim_start_matches('v');
+        let (core, pre) = match trimmed.split_once('-') {
+            Some((core, pre)) => (core, Some(pre.to_string())),
+            None => (trimmed, None),
+        };
+        let parts = core
+            .split('.')
+            .map(|part| part.parse::<u64>().unwrap_or(0))
+            .collect();
+        (parts, pre)
+    }
+
+    let (a_parts, a_pre) = split(a);
+    let (b_parts, b_pre) = split(b);
+
+    for i in 0..a_parts.len().max(b_parts.len()) {
+        let left = a_parts.get(i).copied().unwrap_or(0);
+        let right = b_parts.get(i).copied().unwrap_or(0);
+        match left.cmp(&right) {
+            Ordering::Equal => continue,
+            other => return other,
+        }
+    }
+
+    match (a_pre, b_pre) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(left), Some(right)) => left.cmp(&right),
+    }
+}
+
+/// Parse a version heading such as `## [0.2.0] - 2025-11-01` or `## 0.2.0 (2025-11-01)`
+fn parse_heading(line: &str) -> Option<(String, Option<String>)> {
+    let heading = line.strip_prefix("## ")?.trim();
+    let mut words = heading.split_whitespace();
+
+    let version = words
+        .next()?
+        .trim_matches(|c| c == '[' || c == ']')
+        .trim_start_matches('v')
+        .to_string();
+
+    if !version.chars().next()?.is_ascii_digit() {
+        return None;
+    }
+
+    let date = words
+        .map(|word| word.trim_matches(|c| c == '(' || c == ')' || c == '-'))
+        .find(|word| word.len() == 10 && word.chars().filter(|c| *c == '-').count() == 2)
+        .map(|word| word.to_string());
+
+    Some((version, date))
+}
+
+/// Parse a markdown changelog into structured entries, newest first
+fn parse_changelog(content: &str) -> Vec<ReleaseNotesEntry> {
+    let mut entries: Vec<ReleaseNotesEntry> = Vec::new();
+    let mut in_breaking_section = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+
+        if let Some((version, date)) = parse_heading(trimmed) {
+            entries.push(ReleaseNotesEntry {
+                version,
+                date,
+                highlights: Vec::new(),
+                breaking_changes: Vec::new(),
+            });
+            in_breaking_section = false;
+            continue;
+        }
+
+        let Some(entry) = entries.last_mut() else {
+            continue;
+        };
+
+        if let Some(section) = trimmed.strip_prefix("### ") {
+            in_breaking_section = section.to_uppercase().contains("BREAKING");
+            continue;
+        }
+
+        let bullet = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "));
+
+        if let Some(text) = bullet {
+            let text = text.trim().to_string();
+            if text.is_empty() {
+                continue;
+            }
+
+            // Conventional commit style "feat!:" or inline "BREAKING" markers also count
+            let is_breaking = in_breaking_section
+                || text.to_uppercase().starts_with("BREAKING")
+                || text
+                    .split(':')
+                    .next()
+                    .is_some_and(|prefix| prefix.ends_with('!'));
+
+            if is_breaking {
+                entry.breaking_changes.push(text);
+            } else {
+                entry.highlights.push(text);
+            }
+        }
+    }
+
+    entries
+}
+
+/// Fetch the changelog feed and return the entries newer than `from_version`
+/// up to and including `to_version`
+#[tauri::command]
+#[tracing::instrument(err)]
+pub async fn get_release_notes(
+    from_version: String,
+    to_version: String,
+) -> Result<Vec<ReleaseNotesEntry>, String> {
+    let response = reqwest::get(CHANGELOG_URL)
+        .await
+        .map_err(|e| format!("Failed to fetch changelog: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!(
+            "Changelog request failed with status: {}",
+            response.status()
+        ));
+    }
+
+    let content = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read changelog: {}", e))?;
+
+    let mut entries: Vec<ReleaseNotesEntry> = parse_changelog(&content)
+        .into_iter()
+        .filter(|entry| {
+            compare_versions(&entry.version, &from_version) == Ordering::Greater
+                && compare_versions(&entry.version, &to_version) != Ordering::Greater
+        })
+        .collect();
+
+    entries.sort_by(|a, b| compare_versions(&b.version, &a.version));
+
+    Ok(entries)
+}
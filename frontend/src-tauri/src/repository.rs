@@ -0,0 +1,282 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io::Write;
+use tauri::{AppHandle, Emitter};
+
+use crate::events::ProgressEvent;
+use crate::import::{self, import_archive, ImportResult};
+use crate::portable;
+use crate::storage;
+
+const REPOSITORY_INDEX_URL: &str = "https://release.fmskinbuilder.com/skins/index.json";
+
+/// How long a fetched index is reused before hitting the network again
+const INDEX_CACHE_TTL_SECS: u64 = 60 * 60;
+
+const DEFAULT_PAGE_SIZE: usize = 20;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RepositorySkin {
+    pub id: String,
+    pub name: String,
+    pub author: Option<String>,
+    pub version: Option<String>,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub game_version: Option<String>,
+    pub preview_url: Option<String>,
+    pub download_url: String,
+    pub sha256: String,
+    pub size: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct RepositoryIndex {
+    #[serde(default)]
+    pub skins: Vec<RepositorySkin>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CachedIndex {
+    fetched_at: u64,
+    index: RepositoryIndex,
+}
+
+#[derive(Serialize)]
+pub struct RepositoryPage {
+    pub items: Vec<RepositorySkin>,
+    pub page: usize,
+    pub page_size: usize,
+    pub total: usize,
+    pub fetched_at: u64,
+}
+
+/// Load the repository index, using the on-disk cache while it is fresh
+async fn load_index(app_handle: &AppHandle, refresh: bool) -> Result<CachedIndex, String> {
+    let cache_path = portable::app_cache_dir(app_handle)?
+        .join("repository")
+        .join("index.json");
+
+    let cached: Option<CachedIndex> = storage::load_json(&cache_path);
+    if let Some(cached) = cached {
+        if !refresh && storage::unix_now().saturating_sub(cached.fetched_at) < INDEX_CACHE_TTL_SECS
+        {
+            return Ok(cached);
+        }
+    }
+
+    let response = reqwest::get(REPOSITORY_INDEX_URL)
+        .await
+        .map_err(|e| format!("Failed to fetch skin repository: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!(
+            "Skin repository request failed with status: {}",
+            response.status()
+        ));
+    }
+
+    let index = response
+        .json::<RepositoryIndex>()
+        .await
+        .map_err(|e| format!("Failed to parse skin repository index: {}", e))?;
+
+    let cached = CachedIndex {
+        fetched_at: storage::unix_now(),
+        index,
+    };
+
+    if let Some(parent) = cache_path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Err(e) = storage::save_json(&cache_path, &cached) {
+        println!("[WARNING] Failed to cache skin repository index: {}", e);
+    }
+
+    Ok(cached)
+}
+
+fn matches_query(skin: &RepositorySkin, query: &str) -> bool {
+    let query = query.to_lowercase();
+    skin.name.to_lowercase().contains(&query)
+        || skin
+            .author
+            .as_ref()
+            .is_some_and(|author| author.to_lowercase().contains(&query))
+        || skin
+            .description
+            .as_ref()
+            .is_some_and(|description| description.to_lowercase().contains(&query))
+        || skin.tags.iter().any(|tag| tag.to_lowercase() == query)
+}
+
+/// List or search community skins from the remote repository index (pages start at 1)
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub async fn browse_skin_repository(
+    app_handle: AppHandle,
+    query: Option<String>,
+    page: Option<usize>,
+    page_size: Option<usize>,
+    refresh: Option<bool>,
+) -> Result<RepositoryPage, String> {
+    let cached = load_index(&app_handle, refresh.unwrap_or(false)).await?;
+
+    let query = query.unwrap_or_default();
+    let matching: Vec<RepositorySkin> = cached
+        .index
+        .skins
+        .into_iter()
+        .filter(|skin| query.trim().is_empty() || matches_query(skin, query.trim()))
+        .collect();
+
+    let page = page.unwrap_or(1).max(1);
+    let page_size = page_size.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, 100);
+    let total = matching.len();
+
+    Ok(RepositoryPage {
+        items: matching
+            .into_iter()
+            .skip((page - 1) * page_size)
+            .take(page_size)
+            .collect(),
+        page,
+        page_size,
+        total,
+        fetched_at: cached.fetched_at,
+    })
+}
+
+/// Repository entry whose download URL matches, used to vouch for external links
+pub async fn find_by_download_url(
+    app_handle: &AppHandle,
+    url: &str,
+) -> Result<Option<RepositorySkin>, String> {
+    let cached = load_index(app_handle, false).await?;
+    Ok(cached
+        .index
+        .skins
+        .into_iter()
+        .find(|skin| skin.download_url == url))
+}
+
+/// Download an archive, verify it against the expected SHA-256, and import it
+/// into the library, `on_existing` deciding what happens to a skin it already has
+pub async fn download_and_import(
+    app_handle: &AppHandle,
+    name: &str,
+    download_url: &str,
+    sha256: &str,
+    size: Option<u64>,
+    on_existing: &str,
+) -> Result<ImportResult, String> {
+    let mut response = reqwest::get(download_url)
+        .await
+        .map_err(|e| format!("Failed to download skin: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!(
+            "Skin download failed with status: {}",
+            response.status()
+        ));
+    }
+
+    let total = response.content_length().or(size).unwrap_or(0);
+    let extension = download_url
+        .rsplit('.')
+        .next()
+        .filter(|ext| matches!(*ext, "zip" | "7z" | "rar"))
+        .unwrap_or("zip");
+
+    let download_dir = portable::app_cache_dir(app_handle)?.join("temp");
+    std::fs::create_dir_all(&download_dir)
+        .map_err(|e| format!("Failed to create download directory: {}", e))?;
+    let file_stem: String = sha256
+        .chars()
+        .filter(char::is_ascii_hexdigit)
+        .take(16)
+        .collect();
+    let download_path = download_dir.join(format!("download-{}.{}", file_stem, extension));
+
+    let mut file = std::fs::File::create(&download_path)
+        .map_err(|e| format!("Failed to create download file: {}", e))?;
+    let mut hasher = Sha256::new();
+    let mut downloaded = 0u64;
+
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| format!("Failed to read skin download: {}", e))?
+    {
+        hasher.update(&chunk);
+        file.write_all(&chunk)
+            .map_err(|e| format!("Failed to write skin download: {}", e))?;
+        downloaded += chunk.len() as u64;
+
+        // Progress is reported in KB to stay within u32
+        let _ = app_handle.emit(
+            "skin_download_progress",
+            ProgressEvent {
+                current: (downloaded / 1024) as u32,
+                total: (total / 1024) as u32,
+                status: format!("Downloading {}", name),
+                run_id: None,
+            },
+        );
+    }
+    drop(file);
+
+    let digest = format!("{:x}", hasher.finalize());
+    if !digest.eq_ignore_ascii_case(sha256.trim()) {
+        let _ = std::fs::remove_file(&download_path);
+        return Err(format!(
+            "Checksum mismatch for {} (expected {}, got {})",
+            name, sha256, digest
+        ));
+    }
+
+    let import_handle = app_handle.clone();
+    let archive_path = download_path.clone();
+    let on_existing = on_existing.to_string();
+    let result = tokio::task::spawn_blocking(move || {
+        import_archive(&import_handle, &archive_path, &on_existing)
+    })
+    .await
+    .map_err(|e| format!("Import task failed: {}", e))?;
+
+    let _ = std::fs::remove_file(&download_path);
+
+    result
+}
+
+/// Download a repository skin, verify its checksum, and import it into the
+/// library. A skin the library already has is reported (action "exists") so
+/// the UI can offer to update it, by downloading again with `on_existing`
+/// "update".
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub async fn download_skin(
+    app_handle: AppHandle,
+    id: String,
+    on_existing: Option<String>,
+) -> Result<ImportResult, String> {
+    let on_existing = import::on_existing_option(on_existing)?;
+    let cached = load_index(&app_handle, false).await?;
+    let skin = cached
+        .index
+        .skins
+        .into_iter()
+        .find(|skin| skin.id == id)
+        .ok_or_else(|| format!("Skin not found in repository: {}", id))?;
+
+    download_and_import(
+        &app_handle,
+        &skin.name,
+        &skin.download_url,
+        &skin.sha256,
+        skin.size,
+        &on_existing,
+    )
+    .await
+}
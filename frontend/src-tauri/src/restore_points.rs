@@ -0,0 +1,548 @@
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashSet};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Emitter, State};
+
+use crate::backups::{self, BackedUpBundle};
+use crate::cache::calculate_dir_size;
+use crate::elevation;
+use crate::events::ProgressEvent;
+use crate::file_copy::{self, CopyError};
+use crate::hashing::{self, HashAlgorithm};
+use crate::install;
+use crate::path_guard;
+use crate::process::ProcessState;
+use crate::settings;
+use crate::skins::modified_secs;
+use crate::storage;
+
+/// The pristine backup taken before the first patch is exposed as this point
+pub const ORIGINAL_POINT_ID: &str = "original";
+
+const RESTORE_POINT_FILE: &str = "restore_point.json";
+
+/// Suffix of bundle copies stored gzip-compressed (the `backupCompression` setting)
+const COMPRESSED_SUFFIX: &str = ".gz";
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RestorePoint {
+    pub id: String,
+    pub name: String,
+    pub bundles_path: String,
+    pub created_at: u64,
+    /// Sizes and hashes in `files` are of the uncompressed bundles
+    #[serde(default)]
+    pub compressed: bool,
+    pub files: BTreeMap<String, BackedUpBundle>,
+}
+
+#[derive(Serialize)]
+pub struct RestorePointSummary {
+    pub id: String,
+    pub name: String,
+    pub created_at: u64,
+    pub bundles: Vec<String>,
+    pub size: u64,
+}
+
+#[derive(Serialize)]
+pub struct RestorePointList {
+    pub bundles_path: String,
+    pub points: Vec<RestorePointSummary>,
+    pub total_size: u64,
+}
+
+impl RestorePoint {
+    fn summary(&self) -> RestorePointSummary {
+        RestorePointSummary {
+            id: self.id.clone(),
+            name: self.name.clone(),
+            created_at: self.created_at,
+            bundles: self.files.keys().cloned().collect(),
+            size: self.files.values().map(|file| file.size).sum(),
+        }
+    }
+}
+
+fn points_dir(app_handle: &AppHandle, bundles_dir: &Path) -> Result<PathBuf, String> {
+    Ok(backups::install_backup_dir(app_handle, bundles_dir)?.join("points"))
+}
+
+/// Folder holding a restore point's bundle copies
+pub fn point_dir(app_handle: &AppHandle, bundles_dir: &Path, id: &str) -> Result<PathBuf, String> {
+    if id == ORIGINAL_POINT_ID {
+        backups::install_backup_dir(app_handle, bundles_dir)
+    } else {
+        Ok(points_dir(app_handle, bundles_dir)?.join(id))
+    }
+}
+
+/// Restore points saved in an install's backup area, newest first, without the original
+pub fn read_points(backup_dir: &Path) -> Vec<RestorePoint> {
+    let mut points: Vec<RestorePoint> = std::fs::read_dir(backup_dir.join("points"))
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path().join(RESTORE_POINT_FILE))
+                .filter(|path| path.is_file())
+                .filter_map(|path| {
+                    let content = std::fs::read_to_string(path).ok()?;
+                    serde_json::from_str(&content).ok()
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    points.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    points
+}
+
+/// All restore points for a game install, newest first, with the original last
+pub fn load_restore_points(
+    app_handle: &AppHandle,
+    bundles_dir: &Path,
+) -> Result<Vec<RestorePoint>, String> {
+    let mut points = read_points(&backups::install_backup_dir(app_handle, bundles_dir)?);
+
+    let backup_dir = backups::install_backup_dir(app_handle, bundles_dir)?;
+    if let Some(manifest) = backups::load_manifest(&backup_dir) {
+        points.push(RestorePoint {
+            id: ORIGINAL_POINT_ID.to_string(),
+            name: "Original (pre-patch)".to_string(),
+            bundles_path: manifest.bundles_path,
+            created_at: manifest.created_at,
+            compressed: false,
+            files: manifest.files,
+        });
+    }
+
+    Ok(points)
+}
+
+fn find_restore_point(
+    app_handle: &AppHandle,
+    bundles_dir: &Path,
+    id: &str,
+) -> Result<RestorePoint, String> {
+    load_restore_points(app_handle, bundles_dir)?
+        .into_iter()
+        .find(|point| point.id == id)
+        .ok_or_else(|| format!("Restore point not found: {}", id))
+}
+
+/// Gzip a bundle into a restore point, hashing the uncompressed data so the
+/// restored copy can be checked against it
+fn compress_bundle(
+    source: &Path,
+    destination: &Path,
+    cancel: &AtomicBool,
+) -> Result<(u64, String), String> {
+    let mut input =
+        std::fs::File::open(source).map_err(|e| format!("Failed to open {:?}: {}", source, e))?;
+    let output = std::fs::File::create(destination)
+        .map_err(|e| format!("Failed to create {:?}: {}", destination, e))?;
+    // Bundles are already compressed; a fast level saves most of what there is to save
+    let mut encoder = GzEncoder::new(output, Compression::fast());
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 1024 * 1024];
+    let mut size = 0u64;
+
+    loop {
+        if cancel.load(Ordering::Relaxed) {
+            return Err("Backup cancelled".to_string());
+        }
+        let read = input
+            .read(&mut buffer)
+            .map_err(|e| format!("Failed to read {:?}: {}", source, e))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+        encoder
+            .write_all(&buffer[..read])
+            .map_err(|e| format!("Failed to write {:?}: {}", destination, e))?;
+        size += read as u64;
+    }
+
+    encoder
+        .finish()
+        .and_then(|file| file.sync_all())
+        .map_err(|e| format!("Failed to write {:?}: {}", destination, e))?;
+    Ok((size, format!("{:x}", hasher.finalize())))
+}
+
+/// Decompressed copies of compressed restore point bundles, removed when dropped
+#[derive(Default)]
+pub struct ExpandedCopies(Vec<PathBuf>);
+
+impl Drop for ExpandedCopies {
+    fn drop(&mut self) {
+        for path in &self.0 {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+/// File to copy a point's bundle back from. A compressed copy is expanded
+/// next to its archive first and removed again when `expanded` is dropped.
+pub fn bundle_source(
+    source_dir: &Path,
+    point: &RestorePoint,
+    name: &str,
+    expanded: &mut ExpandedCopies,
+) -> Result<PathBuf, String> {
+    let path = source_dir.join(name);
+    if !point.compressed {
+        return Ok(path);
+    }
+
+    let archive = source_dir.join(format!("{}{}", name, COMPRESSED_SUFFIX));
+    let input = std::fs::File::open(&archive)
+        .map_err(|e| format!("Failed to open {:?}: {}", archive, e))?;
+    let mut output =
+        std::fs::File::create(&path).map_err(|e| format!("Failed to create {:?}: {}", path, e))?;
+    expanded.0.push(path.clone());
+    std::io::copy(&mut GzDecoder::new(input), &mut output)
+        .map_err(|e| format!("Failed to decompress {:?}: {}", archive, e))?;
+    Ok(path)
+}
+
+/// Delete the oldest restore points of an install beyond `keep`, sparing the
+/// ones installed skins still need to uninstall. Returns the deleted ids and
+/// the bytes freed.
+pub fn prune_points(
+    backup_dir: &Path,
+    keep: usize,
+    in_use: &HashSet<String>,
+) -> Result<(Vec<String>, u64), String> {
+    let mut removed = Vec::new();
+    let mut freed = 0;
+    for point in read_points(backup_dir).into_iter().skip(keep) {
+        if in_use.contains(&point.id) {
+            continue;
+        }
+        let dir = backup_dir.join("points").join(&point.id);
+        let size = calculate_dir_size(&dir).unwrap_or(0);
+        std::fs::remove_dir_all(&dir)
+            .map_err(|e| format!("Failed to delete restore point {}: {}", point.id, e))?;
+        freed += size;
+        removed.push(point.id);
+    }
+    Ok((removed, freed))
+}
+
+/// Restore point ids installed skins refer to, which uninstalling restores from
+pub fn points_in_use(app_handle: &AppHandle) -> HashSet<String> {
+    install::load_installed(app_handle)
+        .map(|(_, installed)| {
+            installed
+                .values()
+                .flat_map(|skins| skins.values())
+                .filter_map(|skin| skin.restore_point_id.clone())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Save the current state of the given bundles (or every bundle that differs
+/// from the original backup) as a named restore point
+pub fn create_restore_point(
+    app_handle: &AppHandle,
+    bundles_dir: &Path,
+    name: &str,
+    bundles: Option<Vec<String>>,
+) -> Result<RestorePointSummary, String> {
+    backups::ensure_backup(app_handle, bundles_dir)?;
+    let original = backups::load_manifest(&backups::install_backup_dir(app_handle, bundles_dir)?)
+        .unwrap_or_default();
+    let game_bundles = backups::list_bundle_files(bundles_dir);
+
+    let selected: Vec<(String, PathBuf)> = match bundles {
+        Some(names) => names
+            .into_iter()
+            .map(|name| {
+                game_bundles
+                    .get(&name)
+                    .cloned()
+                    .map(|path| (name.clone(), path))
+                    .ok_or_else(|| format!("Bundle not found in game directory: {}", name))
+            })
+            .collect::<Result<_, _>>()?,
+        None => game_bundles
+            .into_iter()
+            .filter(|(name, path)| match original.files.get(name) {
+                Some(file) => backups::differs_from_backup(path, file),
+                None => true,
+            })
+            .collect(),
+    };
+
+    if selected.is_empty() {
+        return Err(
+            "No modified bundles to save: the game matches the original backup".to_string(),
+        );
+    }
+
+    let mut id = storage::unix_now().to_string();
+    while point_dir(app_handle, bundles_dir, &id)?.exists() {
+        id.push('_');
+    }
+    let dir = point_dir(app_handle, bundles_dir, &id)?;
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create restore point directory: {}", e))?;
+
+    let needed: u64 = selected
+        .iter()
+        .filter_map(|(_, source)| std::fs::metadata(source).ok())
+        .map(|meta| meta.len())
+        .sum();
+    if let Err(e) = backups::check_backup_space(&dir, needed) {
+        let _ = std::fs::remove_dir_all(&dir);
+        return Err(e);
+    }
+
+    let compressed = settings::get_setting(app_handle, "backupCompression").unwrap_or(false);
+    let cancel = file_copy::begin_transfer(app_handle);
+    let mut files = BTreeMap::new();
+    for (name, source) in selected {
+        let copied = if compressed {
+            compress_bundle(
+                &source,
+                &dir.join(format!("{}{}", name, COMPRESSED_SUFFIX)),
+                &cancel,
+            )
+        } else {
+            file_copy::copy_file(
+                &source,
+                &dir.join(&name),
+                false,
+                &cancel,
+                &mut file_copy::progress_emitter(app_handle, &name),
+            )
+            .map(|outcome| (outcome.bytes, outcome.sha256))
+            .map_err(String::from)
+        };
+        let (size, sha256) = match copied {
+            Ok(copied) => copied,
+            Err(e) => {
+                let _ = std::fs::remove_dir_all(&dir);
+                return Err(e);
+            }
+        };
+        files.insert(
+            name,
+            BackedUpBundle {
+                size,
+                sha256,
+                backed_up_at: storage::unix_now(),
+                modified: modified_secs(&source),
+            },
+        );
+    }
+
+    let point = RestorePoint {
+        id,
+        name: name.to_string(),
+        bundles_path: bundles_dir.to_string_lossy().to_string(),
+        created_at: storage::unix_now(),
+        compressed,
+        files,
+    };
+    storage::save_json(&dir.join(RESTORE_POINT_FILE), &point)?;
+
+    // Unset or 0 keeps every point
+    if let Some(keep) =
+        settings::get_setting::<usize>(app_handle, "backupRetention").filter(|keep| *keep > 0)
+    {
+        let backup_dir = backups::install_backup_dir(app_handle, bundles_dir)?;
+        match prune_points(&backup_dir, keep, &points_in_use(app_handle)) {
+            Ok((removed, _)) if !removed.is_empty() => println!(
+                "[DEBUG] Removed {} restore point(s) beyond the retention limit",
+                removed.len()
+            ),
+            Ok(_) => {}
+            Err(e) => println!("[WARNING] {}", e),
+        }
+    }
+
+    Ok(point.summary())
+}
+
+fn mismatch_error(name: &str) -> String {
+    format!(
+        "Restored {} does not match its backup checksum; the backup may be corrupt",
+        name
+    )
+}
+
+/// Copy a restore point's bundles back into the game directory, checking each
+/// copy against the hash recorded when the point was taken
+fn restore_bundles(
+    app_handle: &AppHandle,
+    bundles_dir: &Path,
+    point: &RestorePoint,
+) -> Result<Vec<String>, String> {
+    let source_dir = point_dir(app_handle, bundles_dir, &point.id)?;
+    let total = point.files.len() as u32;
+    let mut restored = Vec::new();
+    let mut needs_elevation = Vec::new();
+    let mut expanded = ExpandedCopies::default();
+    let cancel = file_copy::begin_transfer(app_handle);
+
+    for (index, (name, file)) in point.files.iter().enumerate() {
+        let _ = app_handle.emit(
+            "restore_progress",
+            ProgressEvent {
+                current: index as u32,
+                total,
+                status: format!("Restoring {}", name),
+                run_id: None,
+            },
+        );
+
+        let source = bundle_source(&source_dir, point, name, &mut expanded)?;
+        let outcome = match file_copy::copy_file(
+            &source,
+            &bundles_dir.join(name),
+            false,
+            &cancel,
+            &mut file_copy::progress_emitter(app_handle, name),
+        ) {
+            Ok(outcome) => outcome,
+            Err(CopyError::PermissionDenied(_)) => {
+                needs_elevation.push((name.clone(), source, file.sha256.clone()));
+                continue;
+            }
+            Err(e) => return Err(e.into()),
+        };
+        if outcome.sha256 != file.sha256 {
+            return Err(mismatch_error(name));
+        }
+        restored.push(name.clone());
+    }
+
+    if !needs_elevation.is_empty() {
+        let copies: Vec<(PathBuf, PathBuf)> = needs_elevation
+            .iter()
+            .map(|(name, source, _)| (source.clone(), bundles_dir.join(name)))
+            .collect();
+        elevation::copy_with_consent(app_handle, &copies, "Restoring")?;
+
+        let paths: Vec<PathBuf> = needs_elevation
+            .iter()
+            .map(|(name, _, _)| bundles_dir.join(name))
+            .collect();
+        let hashes = hashing::hash_files(app_handle, &paths, HashAlgorithm::Sha256, &cancel);
+        for ((name, _, sha256), hash) in needs_elevation.into_iter().zip(hashes) {
+            if hash? != sha256 {
+                return Err(mismatch_error(&name));
+            }
+            restored.push(name);
+        }
+    }
+
+    let _ = app_handle.emit(
+        "restore_progress",
+        ProgressEvent {
+            current: total,
+            total,
+            status: "Restore complete".to_string(),
+            run_id: None,
+        },
+    );
+
+    Ok(restored)
+}
+
+/// Restore points for a game install with their disk usage
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub fn list_restore_points(
+    app_handle: AppHandle,
+    bundles_path: String,
+) -> Result<RestorePointList, String> {
+    let bundles_dir = path_guard::clean_path(&bundles_path, "Bundles folder")?;
+    let points: Vec<RestorePointSummary> = load_restore_points(&app_handle, &bundles_dir)?
+        .iter()
+        .map(RestorePoint::summary)
+        .collect();
+
+    Ok(RestorePointList {
+        bundles_path: bundles_dir.to_string_lossy().to_string(),
+        total_size: points.iter().map(|point| point.size).sum(),
+        points,
+    })
+}
+
+/// Save the game's current bundles as a named restore point
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub async fn save_restore_point(
+    app_handle: AppHandle,
+    bundles_path: String,
+    name: String,
+    bundles: Option<Vec<String>>,
+) -> Result<RestorePointSummary, String> {
+    tokio::task::spawn_blocking(move || {
+        create_restore_point(
+            &app_handle,
+            &path_guard::existing_dir(&bundles_path, "Bundles folder")?,
+            name.trim(),
+            bundles,
+        )
+    })
+    .await
+    .map_err(|e| format!("Restore point task failed: {}", e))?
+}
+
+/// Put the bundles covered by a restore point back into the game directory
+#[tauri::command]
+#[tracing::instrument(skip(app_handle, state), err)]
+pub async fn restore_point(
+    app_handle: AppHandle,
+    state: State<'_, ProcessState>,
+    bundles_path: String,
+    point_id: String,
+) -> Result<Vec<String>, String> {
+    if state.is_busy().await {
+        return Err("Cannot restore bundles while a build is running".to_string());
+    }
+
+    tokio::task::spawn_blocking(move || {
+        let bundles_dir = path_guard::existing_dir(&bundles_path, "Bundles folder")?;
+        let point = find_restore_point(&app_handle, &bundles_dir, point_id.trim())?;
+        restore_bundles(&app_handle, &bundles_dir, &point)
+    })
+    .await
+    .map_err(|e| format!("Restore task failed: {}", e))?
+}
+
+/// Delete a restore point and its bundle copies
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub fn delete_restore_point(
+    app_handle: AppHandle,
+    bundles_path: String,
+    point_id: String,
+) -> Result<(), String> {
+    let point_id = point_id.trim();
+    if point_id == ORIGINAL_POINT_ID {
+        return Err("The original backup cannot be deleted".to_string());
+    }
+    if point_id.is_empty() || point_id.contains(['/', '\\']) || point_id.contains("..") {
+        return Err(format!("Invalid restore point id: {}", point_id));
+    }
+
+    let bundles_dir = path_guard::clean_path(&bundles_path, "Bundles folder")?;
+    let dir = point_dir(&app_handle, &bundles_dir, point_id)?;
+    if !dir.join(RESTORE_POINT_FILE).is_file() {
+        return Err(format!("Restore point not found: {}", point_id));
+    }
+
+    std::fs::remove_dir_all(&dir).map_err(|e| format!("Failed to delete restore point: {}", e))
+}
@@ -0,0 +1,162 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Mutex;
+use tauri::ipc::Channel;
+use tauri::{AppHandle, State};
+
+use crate::backups;
+use crate::build_summary;
+use crate::error::{AppError, ErrorKind};
+use crate::event_backlog::BacklogEvent;
+use crate::events::CommandResult;
+use crate::process::{run_python_task, ProcessState, TaskConfig};
+use crate::storage;
+
+const RESUME_FILE: &str = "interrupted_build.json";
+
+/// Logged by the backend after each bundle is written
+const BUNDLE_COMPLETE_MARKER: &str = "=== Bundle complete: ";
+
+/// A build that was cancelled or failed part-way, with what is left to do
+#[derive(Serialize, Deserialize, Clone)]
+pub struct InterruptedBuild {
+    pub run_id: String,
+    pub config: TaskConfig,
+    /// Output folder holding the bundles already built, reused by the resumed build
+    pub output_dir: String,
+    pub completed: Vec<String>,
+    pub remaining: Vec<String>,
+    pub interrupted_at: u64,
+}
+
+/// Bundles the current run has finished, from the backend's completion markers
+#[derive(Default)]
+pub struct BundleTracker(Mutex<Vec<String>>);
+
+impl BundleTracker {
+    pub fn observe(&self, line: &str) {
+        let Some(start) = line.find(BUNDLE_COMPLETE_MARKER) else {
+            return;
+        };
+        let name = line[start + BUNDLE_COMPLETE_MARKER.len()..]
+            .trim_end()
+            .trim_end_matches("===")
+            .trim();
+        if let Ok(mut completed) = self.0.lock() {
+            if !name.is_empty() && !completed.iter().any(|done| done == name) {
+                completed.push(name.to_string());
+            }
+        }
+    }
+
+    fn completed(&self) -> Vec<String> {
+        self.0
+            .lock()
+            .map(|completed| completed.clone())
+            .unwrap_or_default()
+    }
+}
+
+fn resume_file(app_handle: &AppHandle) -> Result<std::path::PathBuf, String> {
+    storage::data_file(app_handle, RESUME_FILE)
+}
+
+/// Remember an interrupted build if it got part-way, so it can be resumed.
+/// Builds with no finished bundle, or whose bundle list isn't known up front
+/// (no bundles folder given), just start over.
+///
+/// `config` is the build as requested, before profiles were applied, so that
+/// resuming applies them again the same way; `bundles_path` is the resolved one.
+pub fn record_interrupted(
+    app_handle: &AppHandle,
+    run_id: &str,
+    config: &TaskConfig,
+    bundles_path: &str,
+    output_dir: &Path,
+    tracker: &BundleTracker,
+) -> Result<(), String> {
+    let completed = tracker.completed();
+    let requested: Vec<String> = if config.bundles.is_empty() {
+        if bundles_path.trim().is_empty() {
+            return Ok(());
+        }
+        backups::list_bundle_files(Path::new(bundles_path.trim()))
+            .into_keys()
+            .collect()
+    } else {
+        config.bundles.clone()
+    };
+    let remaining: Vec<String> = requested
+        .into_iter()
+        .filter(|name| !completed.contains(name))
+        .collect();
+    if completed.is_empty() || remaining.is_empty() {
+        return Ok(());
+    }
+
+    storage::save_json(
+        &resume_file(app_handle)?,
+        &InterruptedBuild {
+            run_id: run_id.to_string(),
+            config: config.clone(),
+            output_dir: output_dir.to_string_lossy().to_string(),
+            completed,
+            remaining,
+            interrupted_at: storage::unix_now(),
+        },
+    )
+}
+
+/// Forget the interrupted build once a build of the same skin succeeds
+pub fn clear_interrupted(app_handle: &AppHandle, skin_path: &str) {
+    let Ok(path) = resume_file(app_handle) else {
+        return;
+    };
+    if load_interrupted(&path)
+        .is_some_and(|build| build.config.skin_path.trim() == skin_path.trim())
+    {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+fn load_interrupted(path: &Path) -> Option<InterruptedBuild> {
+    let content = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// The last build that was cancelled or crashed part-way, if it can be resumed
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub fn get_interrupted_build(app_handle: AppHandle) -> Result<Option<InterruptedBuild>, String> {
+    Ok(load_interrupted(&resume_file(&app_handle)?))
+}
+
+/// Re-run the last interrupted build for only the bundles it hadn't finished,
+/// writing into the same output folder
+#[tauri::command]
+#[tracing::instrument(skip(app_handle, state, on_event), err)]
+pub async fn resume_last_build(
+    app_handle: AppHandle,
+    state: State<'_, ProcessState>,
+    on_event: Option<Channel<BacklogEvent>>,
+) -> Result<CommandResult, AppError> {
+    if state.is_run_active() {
+        return Err(AppError::new(
+            ErrorKind::Conflict,
+            "Cannot resume while a build is running",
+        ));
+    }
+    let interrupted = load_interrupted(&resume_file(&app_handle)?)
+        .ok_or_else(|| AppError::not_found("There is no interrupted build to resume"))?;
+
+    let mut config = interrupted.config;
+    config.bundles = interrupted.remaining;
+    config.incremental = false;
+    config.snapshot_before_build = false;
+    if build_summary::explicit_output_dir(&config.extra_args).is_none() {
+        config.extra_args.push("--out".to_string());
+        config.extra_args.push(interrupted.output_dir);
+    }
+
+    run_python_task(app_handle, config, state, on_event).await
+}
@@ -0,0 +1,323 @@
+use serde::Serialize;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+use crate::events::LogEvent;
+use crate::portable;
+
+/// Run logs kept on disk before the oldest are deleted
+const MAX_RUN_LOGS: usize = 50;
+
+/// Search results returned per page when the caller doesn't ask for a size
+const DEFAULT_SEARCH_PAGE_SIZE: usize = 200;
+
+#[derive(Serialize)]
+pub struct RunLogInfo {
+    pub run_id: String,
+    pub size: u64,
+    pub modified: Option<u64>,
+}
+
+#[derive(Serialize)]
+pub struct LogSearchMatch {
+    pub run_id: String,
+    pub event: LogEvent,
+}
+
+#[derive(Serialize)]
+pub struct LogSearchResult {
+    pub total_matches: usize,
+    pub offset: usize,
+    pub has_more: bool,
+    pub matches: Vec<LogSearchMatch>,
+}
+
+/// Log lines of one build, persisted as JSON lines in `<app data>/logs/<run id>.jsonl`
+pub struct RunLog {
+    pub run_id: String,
+    sequence: AtomicU64,
+    file: Mutex<Option<std::fs::File>>,
+}
+
+pub fn logs_dir(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    Ok(portable::app_data_dir(app_handle)?.join("logs"))
+}
+
+/// Reject run ids that could point outside the directory they are stored in
+pub fn check_run_id(run_id: &str) -> Result<(), String> {
+    if run_id.is_empty()
+        || !run_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(format!("Invalid run id: {}", run_id));
+    }
+    Ok(())
+}
+
+fn run_log_path(app_handle: &AppHandle, run_id: &str) -> Result<PathBuf, String> {
+    check_run_id(run_id)?;
+    Ok(logs_dir(app_handle)?.join(format!("{}.jsonl", run_id)))
+}
+
+fn list_log_files(dir: &Path) -> Vec<(PathBuf, std::fs::Metadata)> {
+    std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "jsonl"))
+                .filter_map(|entry| Some((entry.path(), entry.metadata().ok()?)))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn prune_run_logs(dir: &Path) {
+    let mut files = list_log_files(dir);
+    if files.len() <= MAX_RUN_LOGS {
+        return;
+    }
+    files.sort_by_key(|(_, metadata)| metadata.modified().ok());
+    let excess = files.len() - MAX_RUN_LOGS;
+    for (path, _) in files.into_iter().take(excess) {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+impl RunLog {
+    /// Start a log for a new run. Logging still works if the file can't be created;
+    /// the lines just aren't persisted.
+    pub fn create(app_handle: &AppHandle) -> Self {
+        // Unique even when runs start in the same second or overlap
+        let run_id = uuid::Uuid::new_v4().to_string();
+        let file = logs_dir(app_handle).and_then(|dir| {
+            std::fs::create_dir_all(&dir)
+                .map_err(|e| format!("Failed to create logs directory: {}", e))?;
+            prune_run_logs(&dir);
+            std::fs::File::create(dir.join(format!("{}.jsonl", run_id)))
+                .map_err(|e| format!("Failed to create run log: {}", e))
+        });
+
+        let file = match file {
+            Ok(file) => Some(file),
+            Err(e) => {
+                println!("[WARNING] {}", e);
+                None
+            }
+        };
+
+        Self {
+            run_id,
+            sequence: AtomicU64::new(0),
+            file: Mutex::new(file),
+        }
+    }
+
+    /// Stamp a log line with the time and next sequence number, and persist it
+    pub fn record(&self, message: String, level: &str, source: &str) -> LogEvent {
+        let event = LogEvent {
+            message,
+            level: level.to_string(),
+            source: source.to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            sequence: self.sequence.fetch_add(1, Ordering::Relaxed),
+            run_id: self.run_id.clone(),
+        };
+
+        if let Ok(mut file) = self.file.lock() {
+            if let Some(file) = file.as_mut() {
+                if let Ok(line) = serde_json::to_string(&event) {
+                    let _ = writeln!(file, "{}", line);
+                }
+            }
+        }
+
+        event
+    }
+}
+
+/// All persisted lines of a run, in sequence order
+pub fn read_run_log(app_handle: &AppHandle, run_id: &str) -> Result<Vec<LogEvent>, String> {
+    let path = run_log_path(app_handle, run_id)?;
+    let file =
+        std::fs::File::open(&path).map_err(|_| format!("No log found for run {}", run_id))?;
+
+    let mut events: Vec<LogEvent> = BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str(&line).ok())
+        .collect();
+    events.sort_by_key(|event| event.sequence);
+    Ok(events)
+}
+
+/// A log line as plain text: `<timestamp> [LEVEL] [source] message`
+pub fn text_line(event: &LogEvent) -> String {
+    format!(
+        "{} [{}] [{}] {}\n",
+        event.timestamp,
+        event.level.to_uppercase(),
+        event.source,
+        event.message
+    )
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Persisted run logs, newest first
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub fn list_run_logs(app_handle: AppHandle) -> Result<Vec<RunLogInfo>, String> {
+    let mut logs: Vec<RunLogInfo> = list_log_files(&logs_dir(&app_handle)?)
+        .into_iter()
+        .filter_map(|(path, metadata)| {
+            Some(RunLogInfo {
+                run_id: path.file_stem()?.to_string_lossy().to_string(),
+                size: metadata.len(),
+                modified: metadata
+                    .modified()
+                    .ok()
+                    .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+                    .map(|duration| duration.as_secs()),
+            })
+        })
+        .collect();
+    logs.sort_by(|a, b| b.modified.cmp(&a.modified));
+    Ok(logs)
+}
+
+/// Write a run's complete log to a file as plain text, JSON lines, or CSV.
+/// Returns the number of lines written.
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub fn export_logs(
+    app_handle: AppHandle,
+    run_id: String,
+    format: String,
+    path: String,
+) -> Result<usize, String> {
+    let events = read_run_log(&app_handle, run_id.trim())?;
+
+    let content: String = match format.trim().to_lowercase().as_str() {
+        "text" | "txt" => events.iter().map(text_line).collect(),
+        "jsonl" | "json" => events
+            .iter()
+            .filter_map(|event| serde_json::to_string(event).ok())
+            .map(|line| line + "\n")
+            .collect(),
+        "csv" => std::iter::once("sequence,timestamp,level,source,message\n".to_string())
+            .chain(events.iter().map(|event| {
+                format!(
+                    "{},{},{},{},{}\n",
+                    event.sequence,
+                    event.timestamp,
+                    event.level,
+                    event.source,
+                    csv_field(&event.message)
+                )
+            }))
+            .collect(),
+        other => return Err(format!("Unsupported log format: {}", other)),
+    };
+
+    std::fs::write(path.trim(), content)
+        .map_err(|e| format!("Failed to write log export: {}", e))?;
+    Ok(events.len())
+}
+
+fn search_run_logs(
+    app_handle: &AppHandle,
+    query: &str,
+    level: Option<&str>,
+    run_id: Option<&str>,
+    use_regex: bool,
+    offset: usize,
+    limit: usize,
+) -> Result<LogSearchResult, String> {
+    let pattern = if use_regex {
+        query.to_string()
+    } else {
+        regex::escape(query)
+    };
+    let matcher = regex::RegexBuilder::new(&pattern)
+        .case_insensitive(true)
+        .build()
+        .map_err(|e| format!("Invalid search pattern: {}", e))?;
+
+    // A single run, or every persisted run newest first
+    let run_ids: Vec<String> = match run_id {
+        Some(run_id) => vec![run_id.to_string()],
+        None => list_run_logs(app_handle.clone())?
+            .into_iter()
+            .map(|log| log.run_id)
+            .collect(),
+    };
+
+    let mut total_matches = 0;
+    let mut matches = Vec::new();
+
+    for run_id in run_ids {
+        for event in read_run_log(app_handle, &run_id)? {
+            if level.is_some_and(|level| !event.level.eq_ignore_ascii_case(level)) {
+                continue;
+            }
+            if !matcher.is_match(&event.message) {
+                continue;
+            }
+
+            if total_matches >= offset && matches.len() < limit {
+                matches.push(LogSearchMatch {
+                    run_id: run_id.clone(),
+                    event,
+                });
+            }
+            total_matches += 1;
+        }
+    }
+
+    Ok(LogSearchResult {
+        total_matches,
+        offset,
+        has_more: offset + matches.len() < total_matches,
+        matches,
+    })
+}
+
+/// Search persisted run logs by text or regex, optionally filtered by level and run
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub async fn search_logs(
+    app_handle: AppHandle,
+    query: String,
+    level: Option<String>,
+    run_id: Option<String>,
+    regex: Option<bool>,
+    offset: Option<usize>,
+    limit: Option<usize>,
+) -> Result<LogSearchResult, String> {
+    tokio::task::spawn_blocking(move || {
+        search_run_logs(
+            &app_handle,
+            &query,
+            level
+                .as_deref()
+                .map(str::trim)
+                .filter(|level| !level.is_empty()),
+            run_id.as_deref().map(str::trim).filter(|id| !id.is_empty()),
+            regex.unwrap_or(false),
+            offset.unwrap_or(0),
+            limit.unwrap_or(DEFAULT_SEARCH_PAGE_SIZE),
+        )
+    })
+    .await
+    .map_err(|e| format!("Log search failed: {}", e))?
+}
@@ -0,0 +1,388 @@
+use chrono::{DateTime, Local, NaiveTime, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+use tokio::time::Duration;
+
+use crate::backups;
+use crate::i18n::t;
+use crate::process::ProcessState;
+use crate::projects::{self, ProjectRunStatus};
+use crate::skins::modified_secs;
+use crate::steam;
+use crate::storage;
+
+const SCHEDULES_FILE: &str = "schedules.json";
+
+/// How often due schedules are looked for
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// A saved project rebuilt automatically
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Schedule {
+    pub id: String,
+    pub project_id: String,
+    pub trigger: String, // "daily", "game_update"
+    /// Local time of day for daily builds, "HH:MM"
+    #[serde(default)]
+    pub time: Option<String>,
+    pub enabled: bool,
+    pub created_at: u64,
+    #[serde(default)]
+    pub last_run: Option<ProjectRunStatus>,
+    /// Installed game version when the schedule was saved or last ran, for
+    /// "game_update" schedules
+    #[serde(default)]
+    pub game_version: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleInput {
+    pub id: Option<String>,
+    pub project_id: String,
+    pub trigger: String,
+    #[serde(default)]
+    pub time: Option<String>,
+    pub enabled: bool,
+}
+
+fn load_schedules(app_handle: &AppHandle) -> Result<(PathBuf, Vec<Schedule>), String> {
+    let path = storage::data_file(app_handle, SCHEDULES_FILE)?;
+    let schedules = storage::load_json(&path);
+    Ok((path, schedules))
+}
+
+fn parse_time(time: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(time.trim(), "%H:%M")
+        .map_err(|_| format!("Invalid time of day (expected HH:MM): {}", time))
+}
+
+/// Version of the game a bundles directory belongs to: Steam's build id, else
+/// a fingerprint of the bundles' sizes and modification times
+fn game_version(bundles_dir: &Path) -> Option<String> {
+    if let Some(build_id) = steam::build_id(bundles_dir) {
+        return Some(format!("steam:{}", build_id));
+    }
+    let bundles = backups::list_bundle_files(bundles_dir);
+    if bundles.is_empty() {
+        return None;
+    }
+    let mut hasher = blake3::Hasher::new();
+    for (name, path) in &bundles {
+        let size = std::fs::metadata(path).map(|meta| meta.len()).unwrap_or(0);
+        hasher.update(format!("{}:{}:{:?}\n", name, size, modified_secs(path)).as_bytes());
+    }
+    Some(format!("files:{}", hasher.finalize().to_hex()))
+}
+
+/// Whether the first `time` after `since` has passed. A build missed while
+/// the app was closed runs once at the next launch.
+fn daily_due(time: NaiveTime, since: u64, now: DateTime<Local>) -> bool {
+    let Some(since) = Local.timestamp_opt(since as i64, 0).single() else {
+        return false;
+    };
+    let Some(mut next) = since
+        .date_naive()
+        .and_time(time)
+        .and_local_timezone(Local)
+        .earliest()
+    else {
+        return false;
+    };
+    if next <= since {
+        next += chrono::Duration::days(1);
+    }
+    now >= next
+}
+
+fn is_due(schedule: &Schedule, bundles_path: &str) -> bool {
+    match schedule.trigger.as_str() {
+        "daily" => {
+            let Some(time) = schedule
+                .time
+                .as_deref()
+                .and_then(|time| parse_time(time).ok())
+            else {
+                return false;
+            };
+            let since = schedule
+                .last_run
+                .as_ref()
+                .map(|run| run.finished_at)
+                .unwrap_or(schedule.created_at);
+            daily_due(time, since, Local::now())
+        }
+        "game_update" => {
+            let bundles_dir = Path::new(bundles_path);
+            // Building against half-updated bundles would only fail
+            if steam::update_status(bundles_dir).is_some_and(|status| status.update_pending) {
+                return false;
+            }
+            match (&schedule.game_version, game_version(bundles_dir)) {
+                (Some(previous), Some(current)) => *previous != current,
+                _ => false,
+            }
+        }
+        other => {
+            println!("[WARNING] Unknown schedule trigger: {}", other);
+            false
+        }
+    }
+}
+
+/// Schedules whose build is due, with their project's name
+fn due_schedules(app_handle: &AppHandle) -> Result<Vec<(Schedule, String)>, String> {
+    let (_, schedules) = load_schedules(app_handle)?;
+    let (_, projects) = projects::load_projects(app_handle)?;
+    Ok(schedules
+        .into_iter()
+        .filter(|schedule| schedule.enabled)
+        .filter_map(|schedule| {
+            let project = projects
+                .iter()
+                .find(|project| project.id == schedule.project_id)?;
+            if !is_due(&schedule, &project.bundles_path) {
+                return None;
+            }
+            Some((schedule, project.name.clone()))
+        })
+        .collect())
+}
+
+fn show_notification(app_handle: &AppHandle, title: String, body: String) {
+    if let Err(e) = app_handle
+        .notification()
+        .builder()
+        .title(title)
+        .body(body)
+        .show()
+    {
+        println!("[WARNING] Failed to show notification: {}", e);
+    }
+}
+
+/// Run due schedules one after another, never alongside a build the user started.
+/// Whatever is still due when a build is running waits for the next check.
+async fn run_due_schedules(app_handle: &AppHandle) -> Result<(), String> {
+    let handle = app_handle.clone();
+    let due = tokio::task::spawn_blocking(move || due_schedules(&handle))
+        .await
+        .map_err(|e| format!("Schedule check task failed: {}", e))??;
+
+    for (schedule, project_name) in due {
+        let state = app_handle.state::<ProcessState>();
+        if state.is_run_active() {
+            println!("[DEBUG] Postponing scheduled builds: a build is already running");
+            break;
+        }
+
+        println!(
+            "[DEBUG] Running scheduled build of project {}",
+            project_name
+        );
+        let result =
+            projects::run_project(app_handle.clone(), state, schedule.project_id.clone()).await;
+        let status = match &result {
+            Ok(output) => ProjectRunStatus {
+                success: output.success,
+                exit_code: output.status,
+                finished_at: storage::unix_now(),
+            },
+            // Recorded anyway so a daily build that can't start isn't retried every minute
+            Err(_) => ProjectRunStatus {
+                success: false,
+                exit_code: -1,
+                finished_at: storage::unix_now(),
+            },
+        };
+
+        let (title, body) = match &result {
+            Ok(_) if status.success => (
+                t(app_handle, "schedule.build_succeeded", &[]),
+                project_name.clone(),
+            ),
+            Ok(_) => (
+                t(app_handle, "schedule.build_failed", &[]),
+                t(
+                    app_handle,
+                    "schedule.exit_code",
+                    &[
+                        ("project", &project_name),
+                        ("code", &status.exit_code.to_string()),
+                    ],
+                ),
+            ),
+            Err(e) => (
+                t(app_handle, "schedule.build_failed", &[]),
+                format!("{}: {}", project_name, e),
+            ),
+        };
+        show_notification(app_handle, title, body);
+
+        let bundles_path = projects::load_projects(app_handle)?
+            .1
+            .into_iter()
+            .find(|project| project.id == schedule.project_id)
+            .map(|project| project.bundles_path)
+            .unwrap_or_default();
+        let path = storage::data_file(app_handle, SCHEDULES_FILE)?;
+        storage::update_json(&path, |schedules: &mut Vec<Schedule>| {
+            if let Some(saved) = schedules.iter_mut().find(|saved| saved.id == schedule.id) {
+                saved.last_run = Some(status);
+                if saved.trigger == "game_update" {
+                    saved.game_version = game_version(Path::new(&bundles_path));
+                }
+            }
+            Ok(())
+        })?;
+    }
+    Ok(())
+}
+
+/// Check for due scheduled builds in the background for as long as the app runs
+pub fn start_scheduler(app_handle: &AppHandle) {
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(e) = run_due_schedules(&app_handle).await {
+                println!("[WARNING] Scheduled builds failed: {}", e);
+            }
+        }
+    });
+}
+
+fn refresh_fingerprints(app_handle: &AppHandle, bundles_dir: &Path) -> Result<(), String> {
+    let (_, projects) = projects::load_projects(app_handle)?;
+    let path = storage::data_file(app_handle, SCHEDULES_FILE)?;
+    storage::update_json(&path, |schedules: &mut Vec<Schedule>| {
+        for schedule in schedules.iter_mut() {
+            let fingerprinted = schedule
+                .game_version
+                .as_deref()
+                .is_some_and(|version| version.starts_with("files:"));
+            let same_game = projects.iter().any(|project| {
+                project.id == schedule.project_id
+                    && Path::new(project.bundles_path.trim()) == bundles_dir
+            });
+            if fingerprinted && same_game {
+                schedule.game_version = game_version(bundles_dir);
+            }
+        }
+        Ok(())
+    })
+}
+
+/// Installing or restoring bundles changes them without a game update, so
+/// non-Steam installs are fingerprinted again afterwards
+pub fn refresh_game_versions(app_handle: &AppHandle, bundles_dir: &Path) {
+    if let Err(e) = refresh_fingerprints(app_handle, bundles_dir) {
+        println!(
+            "[WARNING] Failed to refresh scheduled build game versions: {}",
+            e
+        );
+    }
+}
+
+/// Whether any scheduled build could still run, which keeps the app alive in the tray
+pub fn has_enabled_schedules(app_handle: &AppHandle) -> bool {
+    load_schedules(app_handle)
+        .map(|(_, schedules)| schedules.iter().any(|schedule| schedule.enabled))
+        .unwrap_or(false)
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub fn list_schedules(app_handle: AppHandle) -> Result<Vec<Schedule>, String> {
+    let (_, mut schedules) = load_schedules(&app_handle)?;
+    schedules.sort_by_key(|schedule| schedule.created_at);
+    Ok(schedules)
+}
+
+/// Create a schedule, or update it when `id` matches an existing one
+#[tauri::command]
+#[tracing::instrument(skip(app_handle, schedule), fields(project_id = %schedule.project_id, trigger = %schedule.trigger), err)]
+pub fn save_schedule(app_handle: AppHandle, schedule: ScheduleInput) -> Result<Schedule, String> {
+    let (_, projects) = projects::load_projects(&app_handle)?;
+    let project = projects
+        .iter()
+        .find(|project| project.id == schedule.project_id)
+        .ok_or_else(|| format!("Project not found: {}", schedule.project_id))?;
+
+    let (time, game_version) = match schedule.trigger.as_str() {
+        "daily" => {
+            let time = schedule
+                .time
+                .as_deref()
+                .ok_or("A daily schedule needs a time of day")?;
+            (Some(parse_time(time)?.format("%H:%M").to_string()), None)
+        }
+        "game_update" => {
+            if project.bundles_path.trim().is_empty() {
+                return Err(
+                    "The project has no bundles folder to watch for game updates".to_string(),
+                );
+            }
+            let version = game_version(Path::new(project.bundles_path.trim()))
+                .ok_or("No game bundles found in the project's bundles folder")?;
+            (None, Some(version))
+        }
+        other => return Err(format!("Unknown schedule trigger: {}", other)),
+    };
+
+    let path = storage::data_file(&app_handle, SCHEDULES_FILE)?;
+    storage::update_json(&path, |schedules: &mut Vec<Schedule>| {
+        let existing = schedule
+            .id
+            .as_deref()
+            .and_then(|id| schedules.iter().position(|s| s.id == id));
+
+        let id = match existing {
+            Some(index) => schedules[index].id.clone(),
+            None => {
+                let mut id = storage::unix_now().to_string();
+                while schedules.iter().any(|s| s.id == id) {
+                    id.push('_');
+                }
+                id
+            }
+        };
+
+        let saved = Schedule {
+            id,
+            project_id: schedule.project_id,
+            trigger: schedule.trigger,
+            time,
+            enabled: schedule.enabled,
+            created_at: existing
+                .map(|index| schedules[index].created_at)
+                .unwrap_or_else(storage::unix_now),
+            last_run: existing.and_then(|index| schedules[index].last_run.clone()),
+            game_version,
+        };
+
+        match existing {
+            Some(index) => schedules[index] = saved.clone(),
+            None => schedules.push(saved.clone()),
+        }
+        Ok(saved)
+    })
+}
+
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub fn delete_schedule(app_handle: AppHandle, id: String) -> Result<(), String> {
+    let path = storage::data_file(&app_handle, SCHEDULES_FILE)?;
+    storage::update_json(&path, |schedules: &mut Vec<Schedule>| {
+        let before = schedules.len();
+        schedules.retain(|schedule| schedule.id != id);
+
+        if schedules.len() == before {
+            return Err(format!("Schedule not found: {}", id));
+        }
+        Ok(())
+    })
+}
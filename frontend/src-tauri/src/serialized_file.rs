@@ -0,0 +1,492 @@
+use std::collections::BTreeMap;
+
+use crate::unity_bundle::{BundleEntry, BundleReader, ByteCursor};
+
+/// Oldest serialized file format whose object table layout is read here (Unity 5.5)
+const MIN_SUPPORTED_VERSION: u32 = 17;
+/// Objects larger than this aren't decoded; real textures inline far less
+const MAX_OBJECT_SIZE: u32 = 256 * 1024 * 1024;
+
+/// Names referenced from type trees by offset into Unity's built-in string table
+const COMMON_STRINGS: &[&str] = &[
+    "AABB",
+    "AnimationClip",
+    "AnimationCurve",
+    "AnimationState",
+    "Array",
+    "Base",
+    "BitField",
+    "bitset",
+    "bool",
+    "char",
+    "ColorRGBA",
+    "Component",
+    "data",
+    "deque",
+    "double",
+    "dynamic_array",
+    "FastPropertyName",
+    "first",
+    "float",
+    "Font",
+    "GameObject",
+    "Generic Mono",
+    "GradientNEW",
+    "GUID",
+    "GUIStyle",
+    "int",
+    "list",
+    "long long",
+    "map",
+    "Matrix4x4f",
+    "MdFour",
+    "MonoBehaviour",
+    "MonoScript",
+    "m_ByteSize",
+    "m_Curve",
+    "m_EditorClassIdentifier",
+    "m_EditorHideFlags",
+    "m_Enabled",
+    "m_ExtensionPtr",
+    "m_GameObject",
+    "m_Index",
+    "m_IsArray",
+    "m_IsStatic",
+    "m_MetaFlag",
+    "m_Name",
+    "m_ObjectHideFlags",
+    "m_PrefabInternal",
+    "m_PrefabParentObject",
+    "m_Script",
+    "m_StaticEditorFlags",
+    "m_Type",
+    "m_Version",
+    "Object",
+    "pair",
+    "PPtr<Component>",
+    "PPtr<GameObject>",
+    "PPtr<Material>",
+    "PPtr<MonoBehaviour>",
+    "PPtr<MonoScript>",
+    "PPtr<Object>",
+    "PPtr<Prefab>",
+    "PPtr<Sprite>",
+    "PPtr<TextAsset>",
+    "PPtr<Texture>",
+    "PPtr<Texture2D>",
+    "PPtr<Transform>",
+    "Prefab",
+    "Quaternionf",
+    "Rectf",
+    "RectInt",
+    "RectOffset",
+    "second",
+    "set",
+    "short",
+    "size",
+    "SInt16",
+    "SInt32",
+    "SInt64",
+    "SInt8",
+    "staticvector",
+    "string",
+    "TextAsset",
+    "TextMesh",
+    "Texture",
+    "Texture2D",
+    "Transform",
+    "TypelessData",
+    "UInt16",
+    "UInt32",
+    "UInt64",
+    "UInt8",
+    "unsigned int",
+    "unsigned long long",
+    "unsigned short",
+    "vector",
+    "Vector2f",
+    "Vector3f",
+    "Vector4f",
+    "m_ScriptingClassIdentifier",
+    "Gradient",
+    "Type*",
+    "int2_storage",
+    "int3_storage",
+    "BoundsInt",
+    "m_CorrespondingSourceObject",
+    "m_PrefabInstance",
+    "m_PrefabAsset",
+    "FileSize",
+    "Hash128",
+];
+
+/// Type tree flag: align the stream to 4 bytes after reading the field
+const ALIGN_FLAG: u32 = 0x4000;
+
+fn common_string(offset: u32) -> Option<&'static str> {
+    let mut position = 0;
+    for name in COMMON_STRINGS {
+        if position == offset {
+            return Some(name);
+        }
+        position += name.len() as u32 + 1;
+    }
+    None
+}
+
+struct TypeTreeNode {
+    type_name: String,
+    name: String,
+    level: u8,
+    is_array: bool,
+    meta_flag: u32,
+}
+
+struct SerializedType {
+    class_id: i32,
+    nodes: Vec<TypeTreeNode>,
+}
+
+pub struct ObjectInfo {
+    pub path_id: i64,
+    pub class_id: i32,
+    byte_start: u64,
+    byte_size: u32,
+    type_index: usize,
+}
+
+/// A field read through the type tree
+pub enum Value {
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    String(String),
+    Bytes(Vec<u8>),
+    Array(Vec<Value>),
+    Object(BTreeMap<String, Value>),
+}
+
+impl Value {
+    /// Look up a nested field by dotted path, e.g. `m_StreamData.offset`;
+    /// numeric segments index into arrays
+    pub fn get(&self, path: &str) -> Option<&Value> {
+        path.split('.').try_fold(self, |value, key| match value {
+            Value::Object(fields) => fields.get(key),
+            Value::Array(items) => key.parse::<usize>().ok().and_then(|index| items.get(index)),
+            _ => None,
+        })
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Value::Int(value) => Some(*value),
+            Value::Float(value) => Some(*value as i64),
+            Value::Bool(value) => Some(*value as i64),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Value::Bytes(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// The object table and type trees of a serialized asset file (`CAB-...`)
+pub struct SerializedFile {
+    entry: BundleEntry,
+    big_endian: bool,
+    types: Vec<SerializedType>,
+    pub objects: Vec<ObjectInfo>,
+}
+
+fn read_type_tree(cursor: &mut ByteCursor, version: u32) -> Result<Vec<TypeTreeNode>, String> {
+    let node_count = usize::try_from(cursor.i32()?).map_err(|_| "Invalid type tree")?;
+    let string_buffer_size = usize::try_from(cursor.i32()?).map_err(|_| "Invalid type tree")?;
+
+    let mut raw_nodes = Vec::with_capacity(node_count.min(65536));
+    for _ in 0..node_count {
+        cursor.u16()?; // field version
+        let level = cursor.u8()?;
+        let type_flags = cursor.u8()?;
+        let type_offset = cursor.u32()?;
+        let name_offset = cursor.u32()?;
+        cursor.i32()?; // byte size
+        cursor.i32()?; // index
+        let meta_flag = cursor.u32()?;
+        if version >= 19 {
+            cursor.u64()?; // ref type hash
+        }
+        raw_nodes.push((level, type_flags, type_offset, name_offset, meta_flag));
+    }
+    let strings = cursor.bytes(string_buffer_size)?;
+
+    let lookup = |offset: u32| -> String {
+        if offset & 0x8000_0000 != 0 {
+            return common_string(offset & 0x7fff_ffff)
+                .unwrap_or_default()
+                .to_string();
+        }
+        let rest = strings.get(offset as usize..).unwrap_or_default();
+        let end = rest
+            .iter()
+            .position(|byte| *byte == 0)
+            .unwrap_or(rest.len());
+        String::from_utf8_lossy(&rest[..end]).to_string()
+    };
+
+    Ok(raw_nodes
+        .into_iter()
+        .map(
+            |(level, type_flags, type_offset, name_offset, meta_flag)| TypeTreeNode {
+                type_name: lookup(type_offset),
+                name: lookup(name_offset),
+                level,
+                is_array: type_flags & 1 != 0,
+                meta_flag,
+            },
+        )
+        .collect())
+}
+
+impl SerializedFile {
+    /// Read the header, type trees and object table of a serialized file
+    pub fn parse(reader: &mut BundleReader, entry: &BundleEntry) -> Result<Self, String> {
+        let header = reader.read_entry(entry, 0, entry.size.min(48) as usize)?;
+        let mut cursor = ByteCursor::new(&header, true);
+        let mut metadata_size = cursor.u32()? as u64;
+        cursor.u32()?; // file size
+        let version = cursor.u32()?;
+        cursor.u32()?; // data offset
+        if version < MIN_SUPPORTED_VERSION {
+            return Err(format!(
+                "Serialized file version {} is too old to read",
+                version
+            ));
+        }
+        let big_endian = cursor.u8()? != 0;
+        cursor.bytes(3)?;
+        let mut header_size = 20;
+        if version >= 22 {
+            metadata_size = cursor.u32()? as u64;
+            cursor.i64()?; // file size
+            cursor.i64()?; // data offset
+            cursor.i64()?;
+            header_size = 48;
+        }
+
+        if header_size + metadata_size > entry.size {
+            return Err("Serialized file metadata is larger than the file".to_string());
+        }
+        let metadata = reader.read_entry(entry, header_size, metadata_size as usize)?;
+        let mut cursor = ByteCursor::new(&metadata, big_endian);
+
+        cursor.cstring()?; // Unity version
+        cursor.i32()?; // target platform
+        let has_type_trees = cursor.bool()?;
+        if !has_type_trees {
+            return Err("This bundle was built without type trees".to_string());
+        }
+
+        let type_count = usize::try_from(cursor.i32()?).map_err(|_| "Invalid type count")?;
+        let mut types = Vec::with_capacity(type_count.min(4096));
+        for _ in 0..type_count {
+            let class_id = cursor.i32()?;
+            cursor.bool()?; // stripped
+            cursor.i16()?; // script type index
+            if class_id == 114 {
+                cursor.bytes(16)?; // script id
+            }
+            cursor.bytes(16)?; // type hash
+            let nodes = read_type_tree(&mut cursor, version)?;
+            if version >= 21 {
+                let dependencies = usize::try_from(cursor.i32()?).map_err(|_| "Invalid type")?;
+                cursor.bytes(dependencies * 4)?;
+            }
+            types.push(SerializedType { class_id, nodes });
+        }
+
+        let object_count = usize::try_from(cursor.i32()?).map_err(|_| "Invalid object count")?;
+        let mut objects = Vec::with_capacity(object_count.min(1 << 20));
+        for _ in 0..object_count {
+            cursor.align(4);
+            let path_id = cursor.i64()?;
+            let byte_start = if version >= 22 {
+                cursor.u64()?
+            } else {
+                cursor.u32()? as u64
+            };
+            let byte_size = cursor.u32()?;
+            let type_index = usize::try_from(cursor.i32()?).map_err(|_| "Invalid object type")?;
+            let class_id = types
+                .get(type_index)
+                .map(|serialized_type| serialized_type.class_id)
+                .ok_or_else(|| format!("Object {} has an unknown type", path_id))?;
+            objects.push(ObjectInfo {
+                path_id,
+                class_id,
+                byte_start,
+                byte_size,
+                type_index,
+            });
+        }
+
+        // Object offsets are relative to the data section, whose start is in the header
+        let data_offset = {
+            let mut cursor = ByteCursor::new(&header, true);
+            if version >= 22 {
+                cursor.bytes(32)?;
+                cursor.u64()?
+            } else {
+                cursor.bytes(12)?;
+                cursor.u32()? as u64
+            }
+        };
+        for object in &mut objects {
+            object.byte_start += data_offset;
+        }
+
+        Ok(Self {
+            entry: entry.clone(),
+            big_endian,
+            types,
+            objects,
+        })
+    }
+
+    /// The first `limit` bytes of an object, enough to read leading fields
+    /// like `m_Name` without loading inline image data
+    pub fn read_prefix(
+        &self,
+        reader: &mut BundleReader,
+        object: &ObjectInfo,
+        limit: u32,
+    ) -> Result<Vec<u8>, String> {
+        reader.read_entry(
+            &self.entry,
+            object.byte_start,
+            object.byte_size.min(limit) as usize,
+        )
+    }
+
+    /// The object's `m_Name`, read from the start of its data
+    pub fn object_name(
+        &self,
+        reader: &mut BundleReader,
+        object: &ObjectInfo,
+    ) -> Result<String, String> {
+        let prefix = self.read_prefix(reader, object, 1028)?;
+        let mut cursor = ByteCursor::new(&prefix, self.big_endian);
+        let name = cursor.byte_array()?;
+        Ok(String::from_utf8_lossy(name).to_string())
+    }
+
+    /// Read a whole object through its type tree
+    pub fn read_object(
+        &self,
+        reader: &mut BundleReader,
+        object: &ObjectInfo,
+    ) -> Result<Value, String> {
+        if object.byte_size > MAX_OBJECT_SIZE {
+            return Err(format!("Object {} is too large to read", object.path_id));
+        }
+        let nodes = &self.types[object.type_index].nodes;
+        if nodes.is_empty() {
+            return Err(format!("Object {} has no type tree", object.path_id));
+        }
+        let data = reader.read_entry(&self.entry, object.byte_start, object.byte_size as usize)?;
+        let mut cursor = ByteCursor::new(&data, self.big_endian);
+        read_node(nodes, 0, &mut cursor)
+    }
+}
+
+/// Indices of the direct children of `index`
+fn children(nodes: &[TypeTreeNode], index: usize) -> Vec<usize> {
+    let level = nodes[index].level;
+    nodes
+        .iter()
+        .enumerate()
+        .skip(index + 1)
+        .take_while(|(_, node)| node.level > level)
+        .filter(|(_, node)| node.level == level + 1)
+        .map(|(child, _)| child)
+        .collect()
+}
+
+fn read_node(
+    nodes: &[TypeTreeNode],
+    index: usize,
+    cursor: &mut ByteCursor,
+) -> Result<Value, String> {
+    let node = &nodes[index];
+    let mut align = node.meta_flag & ALIGN_FLAG != 0;
+
+    let value = match node.type_name.as_str() {
+        "SInt8" => Value::Int(cursor.u8()? as i8 as i64),
+        "UInt8" | "char" => Value::Int(cursor.u8()? as i64),
+        "bool" => Value::Bool(cursor.bool()?),
+        "SInt16" | "short" => Value::Int(cursor.i16()? as i64),
+        "UInt16" | "unsigned short" => Value::Int(cursor.u16()? as i64),
+        "SInt32" | "int" | "Type*" => Value::Int(cursor.i32()? as i64),
+        "UInt32" | "unsigned int" => Value::Int(cursor.u32()? as i64),
+        "SInt64" | "long long" => Value::Int(cursor.i64()?),
+        "UInt64" | "unsigned long long" | "FileSize" => Value::Int(cursor.u64()? as i64),
+        "float" => Value::Float(cursor.f32()? as f64),
+        "double" => Value::Float(cursor.f64()?),
+        "string" => {
+            let bytes = cursor.byte_array()?;
+            // The string's inner Array node carries the alignment flag
+            align |= children(nodes, index)
+                .first()
+                .is_some_and(|child| nodes[*child].meta_flag & ALIGN_FLAG != 0);
+            Value::String(String::from_utf8_lossy(bytes).to_string())
+        }
+        "TypelessData" => Value::Bytes(cursor.byte_array()?.to_vec()),
+        _ if node.is_array => {
+            let element = *children(nodes, index)
+                .get(1)
+                .ok_or_else(|| format!("Array {} has no element type", node.name))?;
+            let count =
+                usize::try_from(cursor.i32()?).map_err(|_| "Negative array length".to_string())?;
+            if matches!(nodes[element].type_name.as_str(), "UInt8" | "char") {
+                Value::Bytes(cursor.bytes(count)?.to_vec())
+            } else {
+                let mut items = Vec::with_capacity(count.min(65536));
+                for _ in 0..count {
+                    items.push(read_node(nodes, element, cursor)?);
+                }
+                Value::Array(items)
+            }
+        }
+        _ => {
+            let mut fields = BTreeMap::new();
+            for child in children(nodes, index) {
+                let value = read_node(nodes, child, cursor)?;
+                fields.insert(nodes[child].name.clone(), value);
+            }
+            // vector<T> and similar wrap a single Array field
+            match fields.remove("Array") {
+                Some(array) if fields.is_empty() => array,
+                Some(array) => {
+                    fields.insert("Array".to_string(), array);
+                    Value::Object(fields)
+                }
+                None => Value::Object(fields),
+            }
+        }
+    };
+
+    if align {
+        cursor.align(4);
+    }
+    Ok(value)
+}
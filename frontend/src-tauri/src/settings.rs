@@ -0,0 +1,105 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tauri::{AppHandle, Wry};
+use tauri_plugin_store::{Store, StoreExt};
+
+use crate::portable;
+use crate::storage;
+
+/// Store file the frontend keeps its settings in (see useStore.ts)
+fn settings_store_path(app_handle: &AppHandle) -> PathBuf {
+    match portable::app_data_dir(app_handle) {
+        Ok(data_dir) if portable::is_portable() => data_dir.join("settings.json"),
+        _ => PathBuf::from("settings.json"),
+    }
+}
+
+/// Where the store file ends up either way, for locking it
+fn settings_file(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    Ok(portable::app_data_dir(app_handle)?.join("settings.json"))
+}
+
+/// The settings store shared with the frontend. Auto-save is off: the plugin
+/// rewrites the file in place, so every change is written by `write_settings`.
+fn open_store(app_handle: &AppHandle) -> Result<Arc<Store<Wry>>, String> {
+    app_handle
+        .store_builder(settings_store_path(app_handle))
+        .disable_auto_save()
+        .build()
+        .map_err(|e| format!("Failed to open settings store: {}", e))
+}
+
+/// Apply a change to the settings store and write it to disk. Runs under the
+/// store file's lock and re-reads the file first, so saves from other threads
+/// and instances can't interleave or be lost, and the file is replaced
+/// atomically rather than rewritten in place.
+fn write_settings(app_handle: &AppHandle, change: impl FnOnce(&Store<Wry>)) -> Result<(), String> {
+    let store = open_store(app_handle)?;
+    let path = settings_file(app_handle)?;
+    storage::with_lock(&path, || {
+        if path.exists() {
+            store
+                .reload()
+                .map_err(|e| format!("Failed to reload settings: {}", e))?;
+        }
+        change(&store);
+        let entries: Map<String, Value> = store.entries().into_iter().collect();
+        storage::save_json(&path, &entries)
+    })
+}
+
+/// Read a value the frontend saved in the settings store
+pub fn get_setting<T: DeserializeOwned>(app_handle: &AppHandle, key: &str) -> Option<T> {
+    let store = open_store(app_handle).ok()?;
+    store
+        .get(key)
+        .and_then(|value| serde_json::from_value(value).ok())
+}
+
+/// Save a value to the settings store, for settings the app changes itself
+pub fn set_setting<T: Serialize>(
+    app_handle: &AppHandle,
+    key: &str,
+    value: T,
+) -> Result<(), String> {
+    let value =
+        serde_json::to_value(value).map_err(|e| format!("Failed to serialize setting: {}", e))?;
+    write_settings(app_handle, |store| store.set(key, value))
+}
+
+/// Save settings from the frontend: each key is set, or removed when null.
+/// `clear` empties the store first. The frontend reads through the plugin
+/// but writes only through here (see useStore.ts).
+#[tauri::command]
+#[tracing::instrument(skip(app_handle, values), err)]
+pub fn save_settings(
+    app_handle: AppHandle,
+    values: Map<String, Value>,
+    clear: Option<bool>,
+) -> Result<(), String> {
+    write_settings(&app_handle, |store| {
+        if clear.unwrap_or(false) {
+            store.clear();
+        }
+        for (key, value) in values {
+            if value.is_null() {
+                store.delete(&key);
+            } else {
+                store.set(key, value);
+            }
+        }
+    })
+}
+
+/// Re-read the settings store from disk after the file was replaced
+pub fn reload_settings(app_handle: &AppHandle) -> Result<(), String> {
+    let store = open_store(app_handle)?;
+    storage::with_lock(&settings_file(app_handle)?, || {
+        store
+            .reload()
+            .map_err(|e| format!("Failed to reload settings: {}", e))
+    })
+}
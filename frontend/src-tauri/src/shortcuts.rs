@@ -0,0 +1,201 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutEvent, ShortcutState};
+
+use crate::events::ShortcutTriggeredEvent;
+use crate::process::ProcessState;
+use crate::settings;
+use crate::tray;
+
+/// Actions bound out of the box. Any other action in the `shortcuts` setting
+/// is only reported to the frontend.
+const DEFAULT_SHORTCUTS: &[(&str, &str)] = &[
+    ("run_last_build", "CmdOrCtrl+B"),
+    ("cancel_build", "CmdOrCtrl+Period"),
+];
+
+#[derive(Serialize)]
+pub struct ShortcutBinding {
+    pub action: String,
+    /// None when the action is unbound
+    pub accelerator: Option<String>,
+    pub default: Option<String>,
+}
+
+/// Shortcuts registered while the main window is focused, with their actions
+#[derive(Default)]
+pub struct ShortcutsState {
+    registered: Mutex<Vec<(Shortcut, String)>>,
+}
+
+/// The `shortcuts` setting: action -> accelerator, overriding the defaults.
+/// An empty accelerator unbinds the action.
+fn overrides(app_handle: &AppHandle) -> BTreeMap<String, String> {
+    settings::get_setting(app_handle, "shortcuts").unwrap_or_default()
+}
+
+fn keymap(app_handle: &AppHandle) -> BTreeMap<String, String> {
+    let mut keymap: BTreeMap<String, String> = DEFAULT_SHORTCUTS
+        .iter()
+        .map(|(action, accelerator)| (action.to_string(), accelerator.to_string()))
+        .collect();
+    keymap.extend(overrides(app_handle));
+    keymap
+}
+
+fn parse_shortcut(accelerator: &str) -> Result<Shortcut, String> {
+    accelerator
+        .trim()
+        .parse::<Shortcut>()
+        .map_err(|e| format!("Invalid shortcut {:?}: {}", accelerator, e))
+}
+
+/// Bound actions whose accelerator parses; a hand-edited setting that doesn't is skipped
+fn bindings(app_handle: &AppHandle) -> Vec<(Shortcut, String)> {
+    keymap(app_handle)
+        .into_iter()
+        .filter(|(_, accelerator)| !accelerator.trim().is_empty())
+        .filter_map(|(action, accelerator)| match parse_shortcut(&accelerator) {
+            Ok(shortcut) => Some((shortcut, action)),
+            Err(e) => {
+                println!("[WARNING] Skipping shortcut for {}: {}", action, e);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Register the shortcuts while the main window is focused and release them
+/// when it isn't, so they never take keys from other applications
+pub fn set_active(app_handle: &AppHandle, active: bool) {
+    let shortcuts = app_handle.global_shortcut();
+    if let Err(e) = shortcuts.unregister_all() {
+        println!("[WARNING] Failed to release shortcuts: {}", e);
+    }
+
+    let mut registered = Vec::new();
+    if active {
+        for (shortcut, action) in bindings(app_handle) {
+            match shortcuts.register(shortcut) {
+                Ok(()) => registered.push((shortcut, action)),
+                Err(e) => println!(
+                    "[WARNING] Failed to register shortcut for {}: {}",
+                    action, e
+                ),
+            }
+        }
+    }
+
+    if let Ok(mut current) = app_handle.state::<ShortcutsState>().registered.lock() {
+        *current = registered;
+    }
+}
+
+/// Run the action bound to a pressed shortcut and tell the frontend about it
+pub fn handle_shortcut(app_handle: &AppHandle, shortcut: &Shortcut, event: ShortcutEvent) {
+    if event.state() != ShortcutState::Pressed {
+        return;
+    }
+    let action = app_handle
+        .state::<ShortcutsState>()
+        .registered
+        .lock()
+        .ok()
+        .and_then(|registered| {
+            registered
+                .iter()
+                .find(|(registered, _)| registered == shortcut)
+                .map(|(_, action)| action.clone())
+        });
+    let Some(action) = action else {
+        return;
+    };
+
+    println!("[DEBUG] Shortcut triggered: {}", action);
+    match action.as_str() {
+        "run_last_build" => {
+            if app_handle.state::<ProcessState>().is_run_active() {
+                println!("[DEBUG] Ignoring run_last_build: a build is already running");
+            } else {
+                tray::run_last_build(app_handle);
+            }
+        }
+        "cancel_build" => tray::stop_build(app_handle),
+        _ => {}
+    }
+    let _ = app_handle.emit("shortcut_triggered", ShortcutTriggeredEvent { action });
+}
+
+fn current_bindings(app_handle: &AppHandle) -> Vec<ShortcutBinding> {
+    keymap(app_handle)
+        .into_iter()
+        .map(|(action, accelerator)| ShortcutBinding {
+            default: DEFAULT_SHORTCUTS
+                .iter()
+                .find(|(default_action, _)| *default_action == action)
+                .map(|(_, accelerator)| accelerator.to_string()),
+            accelerator: Some(accelerator).filter(|accelerator| !accelerator.trim().is_empty()),
+            action,
+        })
+        .collect()
+}
+
+/// Every action with its shortcut
+#[tauri::command]
+#[tracing::instrument(skip(app_handle))]
+pub fn get_shortcuts(app_handle: AppHandle) -> Vec<ShortcutBinding> {
+    current_bindings(&app_handle)
+}
+
+/// Rebind an action: `accelerator` like "CmdOrCtrl+Shift+B", "" to unbind,
+/// or none to restore the default
+#[tauri::command]
+#[tracing::instrument(skip(app_handle, state), err)]
+pub fn set_shortcut(
+    app_handle: AppHandle,
+    state: State<'_, ShortcutsState>,
+    action: String,
+    accelerator: Option<String>,
+) -> Result<Vec<ShortcutBinding>, String> {
+    let action = action.trim().to_string();
+    if action.is_empty() {
+        return Err("Shortcut action is required".to_string());
+    }
+
+    let mut overrides = overrides(&app_handle);
+    match accelerator.as_deref().map(str::trim) {
+        None => {
+            overrides.remove(&action);
+        }
+        Some("") => {
+            overrides.insert(action.clone(), String::new());
+        }
+        Some(accelerator) => {
+            let shortcut = parse_shortcut(accelerator)?;
+            if let Some((_, other)) = bindings(&app_handle)
+                .into_iter()
+                .find(|(bound, other)| *bound == shortcut && *other != action)
+            {
+                return Err(format!("{} is already used by {}", accelerator, other));
+            }
+            overrides.insert(action.clone(), accelerator.to_string());
+        }
+    }
+    settings::set_setting(&app_handle, "shortcuts", &overrides)?;
+
+    // Re-register only when they are currently active
+    let active = state
+        .registered
+        .lock()
+        .map(|registered| !registered.is_empty())
+        .unwrap_or(false)
+        || app_handle
+            .get_webview_window("main")
+            .and_then(|window| window.is_focused().ok())
+            .unwrap_or(false);
+    set_active(&app_handle, active);
+
+    Ok(current_bindings(&app_handle))
+}
@@ -0,0 +1,104 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::batch;
+use crate::events::ExitRequestedEvent;
+use crate::i18n::t;
+use crate::process::{self, ProcessState};
+use crate::scheduler;
+use crate::settings;
+use crate::tray;
+
+#[derive(Default)]
+pub struct ShutdownState {
+    /// The user agreed to quit mid-build, so later exit requests go through
+    confirmed: AtomicBool,
+}
+
+/// What closing the window does to running and scheduled builds (`closePolicy`)
+fn close_policy(app_handle: &AppHandle) -> String {
+    settings::get_setting::<String>(app_handle, "closePolicy")
+        .filter(|policy| ["prompt", "cancel", "background"].contains(&policy.as_str()))
+        .unwrap_or_else(|| "prompt".to_string())
+}
+
+/// Stop the running build gracefully, let it flush its log and history, then exit
+async fn stop_and_exit(app_handle: AppHandle) {
+    app_handle
+        .state::<ShutdownState>()
+        .confirmed
+        .store(true, Ordering::SeqCst);
+    process::shutdown_running_task(&app_handle.state::<ProcessState>()).await;
+    batch::stop_workers(&app_handle).await;
+    app_handle.exit(0);
+}
+
+/// Whether the app may close now. `window_close` is the main window being
+/// closed rather than a quit from the tray or the OS.
+///
+/// While a build runs this follows the `closePolicy` setting: "cancel" stops
+/// it and then exits, "background" keeps it (and any scheduled builds) running
+/// in the tray, and "prompt" asks the UI, which answers with `confirm_exit`.
+/// A quit can't go to the background, so it asks instead.
+pub fn request_exit(app_handle: &AppHandle, window_close: bool) -> bool {
+    let confirmed = app_handle
+        .state::<ShutdownState>()
+        .confirmed
+        .load(Ordering::SeqCst);
+    if confirmed {
+        return true;
+    }
+    let state = app_handle.state::<ProcessState>();
+    let running = state.run_active.load(Ordering::SeqCst) || batch::is_running(app_handle);
+    let policy = close_policy(app_handle);
+
+    if policy == "background"
+        && window_close
+        && (running || scheduler::has_enabled_schedules(app_handle))
+    {
+        tray::move_to_background(app_handle);
+        return false;
+    }
+    if !running {
+        return true;
+    }
+    if policy == "cancel" {
+        println!("[DEBUG] Stopping the running build before exiting");
+        tauri::async_runtime::spawn(stop_and_exit(app_handle.clone()));
+        return false;
+    }
+
+    let Some(window) = app_handle.get_webview_window("main") else {
+        // Nothing can ask the user, so don't keep the app alive
+        return true;
+    };
+    let skin_path = state
+        .active_skin
+        .try_lock()
+        .ok()
+        .and_then(|skin| skin.as_ref().map(|path| path.to_string_lossy().to_string()));
+
+    let _ = window.unminimize();
+    let _ = window.show();
+    let _ = window.set_focus();
+    if let Err(e) = window.emit(
+        "exit_requested",
+        ExitRequestedEvent {
+            message: t(app_handle, "shutdown.build_running", &[]),
+            skin_path,
+        },
+    ) {
+        eprintln!("[RUST] Failed to emit exit request: {}", e);
+        return true;
+    }
+    false
+}
+
+/// Quit after the user confirmed it mid-build: stop the backend gracefully,
+/// let the run flush its log and history, then exit
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub async fn confirm_exit(app_handle: AppHandle) -> Result<(), String> {
+    stop_and_exit(app_handle).await;
+    Ok(())
+}
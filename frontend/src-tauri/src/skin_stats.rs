@@ -0,0 +1,159 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+use walkdir::WalkDir;
+
+use crate::images::{image_dimensions, IMAGE_EXTENSIONS};
+use crate::long_paths;
+use crate::path_guard;
+use crate::validation::is_generated_dir;
+
+const XML_EXTENSIONS: &[&str] = &["xml", "uxml"];
+const STYLESHEET_EXTENSIONS: &[&str] = &["css", "uss"];
+const FONT_EXTENSIONS: &[&str] = &["ttf", "otf"];
+
+/// Files listed in `largest_files`
+const LARGEST_FILE_COUNT: usize = 10;
+
+/// Images wider or taller than this are almost always a mistake in a skin:
+/// the game scales them down anyway, but the bundle keeps every pixel
+const MAX_EXPECTED_IMAGE_SIDE: u32 = 4096;
+
+#[derive(Serialize)]
+pub struct AssetTypeStats {
+    pub kind: String, // "texture", "xml", "stylesheet", "font", "other"
+    pub count: u32,
+    pub total_size: u64,
+}
+
+#[derive(Serialize)]
+pub struct SkinFileSize {
+    pub path: String,
+    pub kind: String,
+    pub size: u64,
+}
+
+#[derive(Serialize)]
+pub struct OversizedImage {
+    pub path: String,
+    pub width: u32,
+    pub height: u32,
+    pub size: u64,
+}
+
+#[derive(Serialize)]
+pub struct SkinAnalysis {
+    pub skin_path: String,
+    pub total_files: u32,
+    pub total_size: u64,
+    pub by_type: Vec<AssetTypeStats>,
+    pub largest_files: Vec<SkinFileSize>,
+    pub oversized_images: Vec<OversizedImage>,
+}
+
+fn asset_extension(path: &Path) -> String {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_lowercase())
+        .unwrap_or_default()
+}
+
+fn asset_kind(path: &Path) -> &'static str {
+    let extension = asset_extension(path);
+    let extension = extension.as_str();
+
+    if IMAGE_EXTENSIONS.contains(&extension) {
+        "texture"
+    } else if XML_EXTENSIONS.contains(&extension) {
+        "xml"
+    } else if STYLESHEET_EXTENSIONS.contains(&extension) {
+        "stylesheet"
+    } else if FONT_EXTENSIONS.contains(&extension) {
+        "font"
+    } else {
+        "other"
+    }
+}
+
+fn analyze(skin_dir: &Path) -> SkinAnalysis {
+    let root = long_paths::extended(skin_dir);
+    let relative = |path: &Path| {
+        path.strip_prefix(&root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .to_string()
+    };
+
+    let mut by_type: BTreeMap<&str, AssetTypeStats> = BTreeMap::new();
+    let mut files = Vec::new();
+    let mut oversized_images = Vec::new();
+
+    for entry in WalkDir::new(&root)
+        .into_iter()
+        .filter_entry(|entry| !is_generated_dir(entry))
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+    {
+        let path = entry.path();
+        let size = entry.metadata().map(|metadata| metadata.len()).unwrap_or(0);
+        let kind = asset_kind(path);
+
+        let stats = by_type.entry(kind).or_insert_with(|| AssetTypeStats {
+            kind: kind.to_string(),
+            count: 0,
+            total_size: 0,
+        });
+        stats.count += 1;
+        stats.total_size += size;
+
+        if kind == "texture" {
+            if let Some((width, height)) = image_dimensions(path) {
+                if width.max(height) > MAX_EXPECTED_IMAGE_SIDE {
+                    oversized_images.push(OversizedImage {
+                        path: relative(path),
+                        width,
+                        height,
+                        size,
+                    });
+                }
+            }
+        }
+
+        files.push(SkinFileSize {
+            path: relative(path),
+            kind: kind.to_string(),
+            size,
+        });
+    }
+
+    let total_files = files.len() as u32;
+    let total_size = files.iter().map(|file| file.size).sum();
+    files.sort_by(|a, b| b.size.cmp(&a.size));
+    files.truncate(LARGEST_FILE_COUNT);
+    oversized_images.sort_by(|a, b| {
+        (b.width as u64 * b.height as u64).cmp(&(a.width as u64 * a.height as u64))
+    });
+
+    let mut by_type: Vec<AssetTypeStats> = by_type.into_values().collect();
+    by_type.sort_by(|a, b| b.total_size.cmp(&a.total_size));
+
+    SkinAnalysis {
+        skin_path: skin_dir.to_string_lossy().to_string(),
+        total_files,
+        total_size,
+        by_type,
+        largest_files: files,
+        oversized_images,
+    }
+}
+
+/// Sizes per asset type, the largest files and oversized images in a skin, to
+/// explain unexpectedly large build output
+#[tauri::command]
+#[tracing::instrument(err)]
+pub async fn analyze_skin(path: String) -> Result<SkinAnalysis, String> {
+    let skin_dir = path_guard::existing_dir(&path, "Skin folder")?;
+    tokio::task::spawn_blocking(move || analyze(&skin_dir))
+        .await
+        .map_err(|e| format!("Skin analysis task failed: {}", e))
+}
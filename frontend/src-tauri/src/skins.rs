@@ -0,0 +1,435 @@
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use tauri::{AppHandle, State};
+use walkdir::WalkDir;
+
+use crate::cache::calculate_dir_size;
+use crate::commands::skin_roots;
+use crate::history::{self, BuildRecord};
+use crate::import::unique_destination;
+use crate::long_paths;
+use crate::path_guard;
+use crate::process::ProcessState;
+use crate::profiles;
+use crate::recents;
+use crate::undo::{self, UndoAction};
+use crate::validation::is_generated_dir;
+
+/// File that marks a folder as a skin (see fm_skin_builder/core/skin_config.py)
+pub const SKIN_CONFIG_FILE: &str = "config.json";
+
+#[derive(Serialize)]
+pub struct SkinEntry {
+    pub name: String,
+    pub path: String,
+    pub root: String,
+    pub root_label: String,
+    pub size: u64,
+    pub last_modified: Option<u64>,
+    pub has_config: bool,
+    pub last_build: Option<BuildRecord>,
+}
+
+/// Last modification time of a path in seconds since the Unix epoch
+pub fn modified_secs(path: &Path) -> Option<u64> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_secs())
+}
+
+/// Collect the skin folders directly inside a skins root
+fn scan_root(root: &Path, root_label: &str, history: &[BuildRecord]) -> Vec<SkinEntry> {
+    let mut entries = Vec::new();
+
+    let Ok(read_dir) = std::fs::read_dir(root) else {
+        println!("[WARNING] Failed to read skins root: {:?}", root);
+        return entries;
+    };
+
+    for entry in read_dir.flatten() {
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+
+        let name = entry.file_name().to_string_lossy().to_string();
+
+        // Skip hidden and housekeeping folders
+        if name.starts_with('.') {
+            continue;
+        }
+
+        let path_str = path.to_string_lossy().to_string();
+
+        entries.push(SkinEntry {
+            size: calculate_dir_size(&path).unwrap_or(0),
+            last_modified: modified_secs(&path),
+            has_config: path.join(SKIN_CONFIG_FILE).is_file(),
+            last_build: history::last_build_for(history, &path_str).cloned(),
+            root: root.to_string_lossy().to_string(),
+            root_label: root_label.to_string(),
+            name,
+            path: path_str,
+        });
+    }
+
+    entries
+}
+
+/// List skins across every skins root: the default one, the roots added in
+/// settings, and any `extra_roots` passed in
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub fn list_skins(
+    app_handle: AppHandle,
+    extra_roots: Option<Vec<String>>,
+) -> Result<Vec<SkinEntry>, String> {
+    let mut roots: Vec<(PathBuf, String)> = skin_roots(&app_handle)
+        .into_iter()
+        .map(|root| (PathBuf::from(root.path), root.label))
+        .collect();
+
+    for root in extra_roots.unwrap_or_default() {
+        let trimmed = root.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let path = PathBuf::from(trimmed);
+        if !roots.iter().any(|(existing, _)| *existing == path) {
+            let label = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| trimmed.to_string());
+            roots.push((path, label));
+        }
+    }
+
+    let history = history::load_history(&app_handle);
+    let mut skins = Vec::new();
+
+    for (root, label) in roots.iter().filter(|(root, _)| root.is_dir()) {
+        skins.extend(scan_root(root, label, &history));
+    }
+
+    skins.sort_by_key(|skin| skin.name.to_lowercase());
+
+    Ok(skins)
+}
+
+/// Legacy FM skin settings file, read as a fallback for metadata
+const LEGACY_SKIN_XML: &str = "skin_config.xml";
+
+#[derive(Serialize)]
+pub struct SkinInfo {
+    pub path: String,
+    /// Stable id from the config, used to look the skin up in the compatibility feed
+    pub id: Option<String>,
+    pub name: String,
+    pub author: Option<String>,
+    pub version: Option<String>,
+    pub description: Option<String>,
+    pub schema_version: Option<u64>,
+    pub target_game_version: Option<String>,
+    pub includes: Vec<String>,
+    pub categories: Vec<String>,
+    pub has_config: bool,
+}
+
+/// Read `<string id="key" value="..."/>` records from a legacy skin XML file
+fn legacy_xml_value(content: &str, key: &str) -> Option<String> {
+    let marker = format!("id=\"{}\"", key);
+    let line = content.lines().find(|line| line.contains(&marker))?;
+    let value_start = line.find("value=\"")? + "value=\"".len();
+    let value_len = line[value_start..].find('"')?;
+    Some(line[value_start..value_start + value_len].to_string())
+}
+
+fn json_string(config: &serde_json::Value, keys: &[&str]) -> Option<String> {
+    keys.iter()
+        .find_map(|key| config.get(*key).and_then(|value| value.as_str()))
+        .map(|value| value.to_string())
+}
+
+/// True when a directory contains at least one file with one of the given extensions
+fn dir_has_extension(dir: &Path, extensions: &[&str]) -> bool {
+    let Ok(read_dir) = std::fs::read_dir(dir) else {
+        return false;
+    };
+
+    read_dir.flatten().any(|entry| {
+        let path = entry.path();
+        if path.is_dir() {
+            dir_has_extension(&path, extensions)
+        } else {
+            path.extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| extensions.contains(&ext.to_lowercase().as_str()))
+        }
+    })
+}
+
+/// Determine which asset categories a skin overrides from its includes and folder layout
+fn detect_categories(skin_dir: &Path, includes: &[String]) -> Vec<String> {
+    let mut categories = Vec::new();
+    let includes_lower: Vec<String> = includes.iter().map(|inc| inc.to_lowercase()).collect();
+
+    if dir_has_extension(&skin_dir.join("colours"), &["uss", "css"])
+        || std::fs::read_dir(skin_dir)
+            .map(|entries| {
+                entries.flatten().any(|entry| {
+                    entry
+                        .path()
+                        .extension()
+                        .and_then(|ext| ext.to_str())
+                        .is_some_and(|ext| ext == "uss" || ext == "css")
+                })
+            })
+            .unwrap_or(false)
+    {
+        categories.push("colours".to_string());
+    }
+
+    for category in ["icons", "backgrounds", "fonts"] {
+        let include_path = format!("assets/{}", category);
+        let included = includes_lower.iter().any(|inc| inc.contains(&include_path));
+        if included || skin_dir.join(&include_path).is_dir() {
+            categories.push(category.to_string());
+        }
+    }
+
+    categories
+}
+
+/// Parse a skin's config (and legacy XML settings) into display metadata
+pub fn read_skin_info(skin_dir: &Path) -> Result<SkinInfo, String> {
+    if !skin_dir.is_dir() {
+        return Err(format!("Skin folder not found: {}", skin_dir.display()));
+    }
+
+    let config_path = skin_dir.join(SKIN_CONFIG_FILE);
+    let has_config = config_path.is_file();
+
+    let config: serde_json::Value = if has_config {
+        let content = std::fs::read_to_string(&config_path)
+            .map_err(|e| format!("Failed to read skin config: {}", e))?;
+        serde_json::from_str(&content).map_err(|e| format!("Failed to parse skin config: {}", e))?
+    } else {
+        serde_json::Value::Null
+    };
+
+    let legacy_xml = std::fs::read_to_string(skin_dir.join(LEGACY_SKIN_XML)).unwrap_or_default();
+
+    let includes: Vec<String> = config
+        .get("includes")
+        .and_then(|value| value.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item.as_str().map(|s| s.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let folder_name = skin_dir
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    Ok(SkinInfo {
+        path: skin_dir.to_string_lossy().to_string(),
+        id: json_string(&config, &["id"]),
+        name: json_string(&config, &["name"])
+            .or_else(|| legacy_xml_value(&legacy_xml, "skin_name"))
+            .unwrap_or(folder_name),
+        author: json_string(&config, &["author"])
+            .or_else(|| legacy_xml_value(&legacy_xml, "skin_author")),
+        version: json_string(&config, &["version"])
+            .or_else(|| legacy_xml_value(&legacy_xml, "skin_version")),
+        description: json_string(&config, &["description"]),
+        schema_version: config.get("schema_version").and_then(|v| v.as_u64()),
+        target_game_version: json_string(
+            &config,
+            &["target_game_version", "game_version", "fm_version"],
+        ),
+        categories: detect_categories(skin_dir, &includes),
+        includes,
+        has_config,
+    })
+}
+
+/// Get display metadata for a single skin folder
+#[tauri::command]
+#[tracing::instrument(err)]
+pub fn get_skin_info(path: String) -> Result<SkinInfo, String> {
+    read_skin_info(&path_guard::existing_dir(&path, "Skin folder")?)
+}
+
+/// Reject management operations on a skin that is currently being built
+pub async fn ensure_not_building(state: &ProcessState, skin_dir: &Path) -> Result<(), String> {
+    if state.is_building_skin(skin_dir).await {
+        return Err(format!(
+            "\"{}\" is currently being built. Stop the build first.",
+            skin_dir.display()
+        ));
+    }
+    Ok(())
+}
+
+/// Validate a new skin folder name (no separators or reserved names)
+pub fn validate_skin_name(name: &str) -> Result<&str, String> {
+    let trimmed = name.trim();
+    if trimmed.is_empty() || trimmed == "." || trimmed == ".." {
+        return Err("Skin name cannot be empty".to_string());
+    }
+    if trimmed
+        .chars()
+        .any(|c| matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|'))
+    {
+        return Err(format!(
+            "Skin name contains invalid characters: {}",
+            trimmed
+        ));
+    }
+    Ok(trimmed)
+}
+
+/// Copy a skin folder (without its build output) under a new name
+#[tauri::command]
+#[tracing::instrument(err)]
+pub async fn duplicate_skin(path: String, new_name: Option<String>) -> Result<SkinEntry, String> {
+    let source = path_guard::existing_dir(&path, "Skin folder")?;
+
+    let parent = source
+        .parent()
+        .ok_or_else(|| "Skin folder has no parent directory".to_string())?
+        .to_path_buf();
+    let base_name = match &new_name {
+        Some(name) => validate_skin_name(name)?.to_string(),
+        None => format!(
+            "{} copy",
+            source
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default()
+        ),
+    };
+    let (destination, _) = unique_destination(&parent, &base_name);
+
+    let source = long_paths::extended(&source);
+    let dest = long_paths::extended(&destination);
+    tokio::task::spawn_blocking(move || {
+        for entry in WalkDir::new(&source)
+            .into_iter()
+            .filter_entry(|entry| !is_generated_dir(entry))
+            .flatten()
+        {
+            let relative = entry
+                .path()
+                .strip_prefix(&source)
+                .map_err(|e| format!("Failed to resolve path: {}", e))?;
+            let target = dest.join(relative);
+            if entry.file_type().is_dir() {
+                std::fs::create_dir_all(&target)
+                    .map_err(|e| format!("Failed to create directory: {}", e))?;
+            } else {
+                std::fs::copy(entry.path(), &target)
+                    .map_err(|e| format!("Failed to copy file: {}", e))?;
+            }
+        }
+        Ok::<(), String>(())
+    })
+    .await
+    .map_err(|e| format!("Duplicate task failed: {}", e))??;
+
+    let name = destination
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    Ok(SkinEntry {
+        name,
+        path: destination.to_string_lossy().to_string(),
+        root: parent.to_string_lossy().to_string(),
+        size: calculate_dir_size(&destination).unwrap_or(0),
+        last_modified: modified_secs(&destination),
+        has_config: destination.join(SKIN_CONFIG_FILE).is_file(),
+        last_build: None,
+    })
+}
+
+/// Rename a skin folder and move its build history to the new path
+#[tauri::command]
+#[tracing::instrument(skip(app_handle, state), err)]
+pub async fn rename_skin(
+    app_handle: AppHandle,
+    state: State<'_, ProcessState>,
+    path: String,
+    new_name: String,
+) -> Result<String, String> {
+    let source = path_guard::existing_dir(&path, "Skin folder")?;
+    path_guard::ensure_destructive_allowed(&app_handle, &source, "Skin folder")?;
+    ensure_not_building(&state, &source).await?;
+
+    let name = validate_skin_name(&new_name)?;
+    let destination = source
+        .parent()
+        .ok_or_else(|| "Skin folder has no parent directory".to_string())?
+        .join(name);
+
+    if destination.exists() {
+        return Err(format!("A skin named \"{}\" already exists", name));
+    }
+
+    std::fs::rename(&source, &destination).map_err(|e| format!("Failed to rename skin: {}", e))?;
+
+    history::rename_skin_path(
+        &app_handle,
+        &source.to_string_lossy(),
+        &destination.to_string_lossy(),
+    )?;
+    recents::rename_skin_path(
+        &app_handle,
+        &source.to_string_lossy(),
+        &destination.to_string_lossy(),
+    )?;
+    profiles::rename_skin_path(
+        &app_handle,
+        &source.to_string_lossy(),
+        &destination.to_string_lossy(),
+    )?;
+
+    Ok(destination.to_string_lossy().to_string())
+}
+
+/// Move a skin folder to the OS recycle bin / trash. The move can be undone
+/// with `undo_last_action` for a short while.
+#[tauri::command]
+#[tracing::instrument(skip(app_handle, state), err)]
+pub async fn delete_skin(
+    app_handle: AppHandle,
+    state: State<'_, ProcessState>,
+    path: String,
+) -> Result<(), String> {
+    let target = path_guard::existing_dir(&path, "Skin folder")?;
+    path_guard::ensure_destructive_allowed(&app_handle, &target, "Skin folder")?;
+    ensure_not_building(&state, &target).await?;
+
+    trash::delete(&target).map_err(|e| format!("Failed to move skin to trash: {}", e))?;
+    undo::register(
+        &app_handle,
+        "delete_skin",
+        format!(
+            "Moved {} to the trash",
+            target
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default()
+        ),
+        UndoAction::RestoreFromTrash(target.clone()),
+    );
+
+    recents::remove_skin(&app_handle, &target.to_string_lossy())
+}
@@ -0,0 +1,216 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use tauri::{AppHandle, State};
+use walkdir::WalkDir;
+
+use crate::diff::skin_file_map;
+use crate::long_paths;
+use crate::path_guard;
+use crate::portable;
+use crate::process::ProcessState;
+use crate::storage;
+use crate::validation::is_generated_dir;
+
+/// Snapshots kept per skin before the oldest are pruned
+const MAX_SNAPSHOTS_PER_SKIN: usize = 10;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SnapshotInfo {
+    pub id: String,
+    pub skin_path: String,
+    pub created_at: u64,
+    pub file_count: usize,
+    pub total_size: u64,
+    pub reason: String,
+}
+
+/// Snapshot area for a skin: `<app data>/snapshots/<folder name>-<path hash>`
+fn skin_snapshot_dir(app_handle: &AppHandle, skin_dir: &Path) -> Result<PathBuf, String> {
+    let digest = Sha256::digest(skin_dir.to_string_lossy().as_bytes());
+    let key = format!(
+        "{}-{}",
+        skin_dir
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "skin".to_string()),
+        &format!("{:x}", digest)[..12]
+    );
+    Ok(portable::app_data_dir(app_handle)?
+        .join("snapshots")
+        .join(key))
+}
+
+fn load_snapshots(snapshot_root: &Path) -> Vec<SnapshotInfo> {
+    let mut snapshots: Vec<SnapshotInfo> = std::fs::read_dir(snapshot_root)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+                .filter_map(|path| {
+                    let content = std::fs::read_to_string(path).ok()?;
+                    serde_json::from_str(&content).ok()
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    snapshots.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    snapshots
+}
+
+fn modified_secs(path: &Path) -> Option<u64> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_secs())
+}
+
+/// Copy the skin into a new snapshot. Files unchanged since the previous snapshot
+/// (same size and mtime) are hard-linked to it, so repeated snapshots are cheap.
+pub fn create_snapshot(
+    app_handle: &AppHandle,
+    skin_dir: &Path,
+    reason: &str,
+) -> Result<SnapshotInfo, String> {
+    if !skin_dir.is_dir() {
+        return Err(format!("Skin folder not found: {}", skin_dir.display()));
+    }
+
+    let snapshot_root = long_paths::extended(&skin_snapshot_dir(app_handle, skin_dir)?);
+    let previous = load_snapshots(&snapshot_root)
+        .into_iter()
+        .next()
+        .map(|snapshot| snapshot_root.join(&snapshot.id));
+
+    let mut id = storage::unix_now().to_string();
+    while snapshot_root.join(&id).exists() {
+        id.push('_');
+    }
+    let snapshot_dir = snapshot_root.join(&id);
+
+    let mut file_count = 0;
+    let mut total_size = 0u64;
+
+    for (relative, (source, size)) in skin_file_map(skin_dir) {
+        let target = snapshot_dir.join(&relative);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create snapshot directory: {}", e))?;
+        }
+
+        let linked = previous.as_ref().is_some_and(|previous_dir| {
+            let previous_file = previous_dir.join(&relative);
+            std::fs::metadata(&previous_file).is_ok_and(|meta| meta.len() == size)
+                && modified_secs(&previous_file) == modified_secs(&source)
+                && std::fs::hard_link(&previous_file, &target).is_ok()
+        });
+
+        if !linked {
+            std::fs::copy(&source, &target)
+                .map_err(|e| format!("Failed to snapshot {}: {}", relative, e))?;
+            // Keep the source mtime so the next snapshot can link to this copy
+            if let Ok(modified) = std::fs::metadata(&source).and_then(|meta| meta.modified()) {
+                if let Ok(file) = std::fs::File::options().write(true).open(&target) {
+                    let _ = file.set_modified(modified);
+                }
+            }
+        }
+
+        file_count += 1;
+        total_size += size;
+    }
+
+    let info = SnapshotInfo {
+        id,
+        skin_path: skin_dir.to_string_lossy().to_string(),
+        created_at: storage::unix_now(),
+        file_count,
+        total_size,
+        reason: reason.to_string(),
+    };
+    storage::save_json(&snapshot_root.join(format!("{}.json", info.id)), &info)?;
+
+    // Retention: drop the oldest snapshots beyond the limit
+    for stale in load_snapshots(&snapshot_root)
+        .into_iter()
+        .skip(MAX_SNAPSHOTS_PER_SKIN)
+    {
+        let _ = std::fs::remove_dir_all(snapshot_root.join(&stale.id));
+        let _ = std::fs::remove_file(snapshot_root.join(format!("{}.json", stale.id)));
+    }
+
+    Ok(info)
+}
+
+/// List snapshots of a skin, newest first
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub fn list_skin_snapshots(
+    app_handle: AppHandle,
+    skin_path: String,
+) -> Result<Vec<SnapshotInfo>, String> {
+    let skin_dir = path_guard::clean_path(&skin_path, "Skin folder")?;
+    Ok(load_snapshots(&skin_snapshot_dir(&app_handle, &skin_dir)?))
+}
+
+/// Replace the skin's source files with a snapshot.
+/// The current state is snapshotted first so the restore itself can be undone.
+#[tauri::command]
+#[tracing::instrument(skip(app_handle, state), err)]
+pub async fn restore_skin_snapshot(
+    app_handle: AppHandle,
+    state: State<'_, ProcessState>,
+    skin_path: String,
+    snapshot_id: String,
+) -> Result<SnapshotInfo, String> {
+    let skin_dir = path_guard::existing_dir(&skin_path, "Skin folder")?;
+    path_guard::ensure_destructive_allowed(&app_handle, &skin_dir, "Skin folder")?;
+    if state.is_building_skin(&skin_dir).await {
+        return Err("Cannot restore a snapshot while this skin is being built".to_string());
+    }
+
+    let snapshot_dir = skin_snapshot_dir(&app_handle, &skin_dir)?.join(&snapshot_id);
+    if snapshot_id.contains(['/', '\\']) || !snapshot_dir.is_dir() {
+        return Err(format!("Snapshot not found: {}", snapshot_id));
+    }
+
+    tokio::task::spawn_blocking(move || {
+        let safety = create_snapshot(&app_handle, &skin_dir, "pre-restore")?;
+
+        // Remove current source files, leaving build output and hidden folders alone
+        let skin_dir = long_paths::extended(&skin_dir);
+        for entry in WalkDir::new(&skin_dir)
+            .contents_first(true)
+            .into_iter()
+            .filter_entry(|entry| !is_generated_dir(entry))
+            .flatten()
+            .filter(|entry| entry.depth() > 0)
+        {
+            if entry.file_type().is_dir() {
+                let _ = std::fs::remove_dir(entry.path());
+            } else {
+                std::fs::remove_file(entry.path())
+                    .map_err(|e| format!("Failed to remove {:?}: {}", entry.path(), e))?;
+            }
+        }
+
+        for (relative, (source, _)) in skin_file_map(&snapshot_dir) {
+            let target = skin_dir.join(&relative);
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to create directory: {}", e))?;
+            }
+            // Copy rather than link so edits never reach back into the snapshot
+            std::fs::copy(&source, &target)
+                .map_err(|e| format!("Failed to restore {}: {}", relative, e))?;
+        }
+
+        Ok(safety)
+    })
+    .await
+    .map_err(|e| format!("Restore task failed: {}", e))?
+}
@@ -0,0 +1,147 @@
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use crate::path_guard;
+
+// appmanifest StateFlags bits that mean Steam has work queued for the game
+const STATE_UPDATE_REQUIRED: u64 = 2;
+const STATE_UPDATE_RUNNING: u64 = 256;
+const STATE_UPDATE_PAUSED: u64 = 512;
+const STATE_UPDATE_STARTED: u64 = 1024;
+const STATE_DOWNLOADING: u64 = 1_048_576;
+const STATE_STAGING: u64 = 2_097_152;
+const STATE_COMMITTING: u64 = 4_194_304;
+
+const PENDING_UPDATE_FLAGS: u64 = STATE_UPDATE_REQUIRED
+    | STATE_UPDATE_RUNNING
+    | STATE_UPDATE_PAUSED
+    | STATE_UPDATE_STARTED
+    | STATE_DOWNLOADING
+    | STATE_STAGING
+    | STATE_COMMITTING;
+
+/// AutoUpdateBehavior value for "Only update this game when I launch it"
+const UPDATE_ON_LAUNCH: u32 = 1;
+
+#[derive(Serialize)]
+pub struct SteamUpdateStatus {
+    pub app_id: String,
+    pub manifest_path: String,
+    pub state_flags: u64,
+    pub update_pending: bool,
+    /// Steam's auto-update setting: 0 keep updated, 1 update on launch, 2 high priority
+    pub auto_update_behavior: Option<u32>,
+    pub warning: Option<String>,
+}
+
+/// Value of a top-level `"key"  "value"` pair in a Steam .acf/.vdf file
+fn vdf_value(content: &str, key: &str) -> Option<String> {
+    let quoted_key = format!("\"{}\"", key);
+    content.lines().find_map(|line| {
+        let rest = line.trim().strip_prefix(&quoted_key)?;
+        let value = rest.trim();
+        let value = value.strip_prefix('"')?.strip_suffix('"')?;
+        Some(value.replace("\\\\", "\\"))
+    })
+}
+
+/// The `steamapps/appmanifest_<id>.acf` for the game a bundles directory
+/// belongs to, found from the `steamapps/common/<installdir>` in its path
+fn find_app_manifest(bundles_dir: &Path) -> Option<PathBuf> {
+    let (steamapps, install_dir) = bundles_dir.ancestors().find_map(|dir| {
+        let common = dir.parent()?;
+        let steamapps = common.parent()?;
+        let is_common = common
+            .file_name()
+            .is_some_and(|name| name.eq_ignore_ascii_case("common"));
+        let is_steamapps = steamapps
+            .file_name()
+            .is_some_and(|name| name.eq_ignore_ascii_case("steamapps"));
+        if !(is_common && is_steamapps) {
+            return None;
+        }
+        Some((
+            steamapps.to_path_buf(),
+            dir.file_name()?.to_string_lossy().to_string(),
+        ))
+    })?;
+
+    std::fs::read_dir(&steamapps)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("appmanifest_") && name.ends_with(".acf"))
+        })
+        .find(|path| {
+            std::fs::read_to_string(path)
+                .ok()
+                .and_then(|content| vdf_value(&content, "installdir"))
+                .is_some_and(|dir| dir.eq_ignore_ascii_case(&install_dir))
+        })
+}
+
+/// Steam app id of the game a bundles directory belongs to
+pub fn app_id(bundles_dir: &Path) -> Option<String> {
+    let content = std::fs::read_to_string(find_app_manifest(bundles_dir)?).ok()?;
+    vdf_value(&content, "appid")
+}
+
+/// Steam build id of the installed game version, which changes with every update
+pub fn build_id(bundles_dir: &Path) -> Option<String> {
+    let content = std::fs::read_to_string(find_app_manifest(bundles_dir)?).ok()?;
+    vdf_value(&content, "buildid")
+}
+
+/// Whether Steam is about to update the game a bundles directory belongs to.
+/// `None` when the game isn't a Steam install or its manifest can't be read.
+pub fn update_status(bundles_dir: &Path) -> Option<SteamUpdateStatus> {
+    let manifest_path = find_app_manifest(bundles_dir)?;
+    let content = std::fs::read_to_string(&manifest_path).ok()?;
+
+    let app_id = vdf_value(&content, "appid")?;
+    let state_flags = vdf_value(&content, "StateFlags")
+        .and_then(|flags| flags.parse::<u64>().ok())
+        .unwrap_or(0);
+    let bytes = |key: &str| {
+        vdf_value(&content, key)
+            .and_then(|value| value.parse::<u64>().ok())
+            .unwrap_or(0)
+    };
+    let download_incomplete = bytes("BytesToDownload") > bytes("BytesDownloaded");
+    let update_pending = state_flags & PENDING_UPDATE_FLAGS != 0 || download_incomplete;
+    let auto_update_behavior =
+        vdf_value(&content, "AutoUpdateBehavior").and_then(|value| value.parse::<u32>().ok());
+
+    let warning = update_pending.then(|| {
+        let advice = if auto_update_behavior == Some(UPDATE_ON_LAUNCH) {
+            "Let the update finish in Steam, then rebuild and install."
+        } else {
+            "Let the update finish in Steam first, or set the game to \"Only update this game when I launch it\" in its Steam properties."
+        };
+        format!(
+            "Steam has an update pending for this game, which will overwrite the patched bundles or fail to apply. {}",
+            advice
+        )
+    });
+
+    Some(SteamUpdateStatus {
+        app_id,
+        manifest_path: manifest_path.to_string_lossy().to_string(),
+        state_flags,
+        update_pending,
+        auto_update_behavior,
+        warning,
+    })
+}
+
+/// Steam's update state for the game a bundles directory belongs to, to warn
+/// before installing; `None` for non-Steam installs
+#[tauri::command]
+#[tracing::instrument(err)]
+pub fn check_steam_update(bundles_path: String) -> Result<Option<SteamUpdateStatus>, String> {
+    let bundles_dir = path_guard::existing_dir(&bundles_path, "Bundles folder")?;
+    Ok(update_status(&bundles_dir))
+}
@@ -0,0 +1,183 @@
+use fs2::FileExt;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::AppHandle;
+
+use crate::portable;
+
+/// Resolve a file inside the app data directory, creating the directory if needed
+pub fn data_file(app_handle: &AppHandle, name: &str) -> Result<PathBuf, String> {
+    let data_dir = portable::app_data_dir(app_handle)?;
+    if !data_dir.exists() {
+        std::fs::create_dir_all(&data_dir)
+            .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+    }
+    Ok(data_dir.join(name))
+}
+
+/// Load a JSON file, falling back to the default value when missing or unreadable.
+/// An unparseable file is kept next to it as `.corrupt` so the next save can't
+/// destroy what's left of it.
+pub fn load_json<T: DeserializeOwned + Default>(path: &Path) -> T {
+    match std::fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            println!(
+                "[WARNING] Failed to parse {:?}: {} (using defaults)",
+                path, e
+            );
+            let _ = std::fs::copy(path, sibling(path, "corrupt"));
+            T::default()
+        }),
+        Err(_) => T::default(),
+    }
+}
+
+/// `settings.json` -> `settings.json.<suffix>`
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+/// Write a value as pretty-printed JSON. The content goes to a temporary file
+/// that replaces the original only once it is fully on disk, so a crash
+/// mid-write leaves the previous version intact and other processes only ever
+/// see a complete file. Waits for any update of the file in this process; code
+/// that reads the file before saving it should use `update_json` or
+/// `with_lock` instead.
+pub fn save_json<T: Serialize>(path: &Path, value: &T) -> Result<(), String> {
+    hold(path, false, || write_json(path, value, false))
+}
+
+/// Like `save_json`, but the file is readable only by the current user from
+/// the moment it is created, for files holding secrets.
+pub fn save_private_json<T: Serialize>(path: &Path, value: &T) -> Result<(), String> {
+    hold(path, false, || write_json(path, value, true))
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T, private: bool) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(value)
+        .map_err(|e| format!("Failed to serialize {:?}: {}", path, e))?;
+
+    let temp_path = sibling(path, &format!("{}.tmp", std::process::id()));
+    let mut options = OpenOptions::new();
+    if private {
+        // A leftover temp file would keep its old permissions, so always start fresh
+        let _ = std::fs::remove_file(&temp_path);
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    } else {
+        options.write(true).create(true).truncate(true);
+    }
+    let written = options.open(&temp_path).and_then(|mut file| {
+        file.write_all(content.as_bytes())?;
+        file.sync_all()
+    });
+    if let Err(e) = written.and_then(|_| std::fs::rename(&temp_path, path)) {
+        let _ = std::fs::remove_file(&temp_path);
+        return Err(format!("Failed to write {:?}: {}", path, e));
+    }
+    Ok(())
+}
+
+/// In-process lock for a file, shared by every thread updating it
+fn path_lock(path: &Path) -> Result<Arc<Mutex<()>>, String> {
+    static LOCKS: OnceLock<Mutex<HashMap<PathBuf, Arc<Mutex<()>>>>> = OnceLock::new();
+    let mut locks = LOCKS
+        .get_or_init(Mutex::default)
+        .lock()
+        .map_err(|e| format!("Failed to lock file registry: {}", e))?;
+    Ok(locks.entry(path.to_path_buf()).or_default().clone())
+}
+
+thread_local! {
+    /// Files whose locks the current thread holds, so nested saves don't wait on themselves
+    static HELD: RefCell<HashSet<PathBuf>> = RefCell::default();
+}
+
+/// Forgets a held file when the work under its lock ends, even by panicking
+struct Held(PathBuf);
+
+impl Drop for Held {
+    fn drop(&mut self) {
+        HELD.with(|held| held.borrow_mut().remove(&self.0));
+    }
+}
+
+/// Run `work` while holding a file's locks: other threads wait on an
+/// in-process lock, and other processes (a second instance, an external tool)
+/// on an exclusive lock of `<file>.lock`. Saves of the same file inside `work`
+/// reuse the locks.
+pub fn with_lock<R>(path: &Path, work: impl FnOnce() -> Result<R, String>) -> Result<R, String> {
+    hold(path, true, work)
+}
+
+/// Take a file's in-process lock, and with `lock_file` its cross-process one,
+/// for the length of `work`
+fn hold<R>(
+    path: &Path,
+    lock_file: bool,
+    work: impl FnOnce() -> Result<R, String>,
+) -> Result<R, String> {
+    if HELD.with(|held| held.borrow().contains(path)) {
+        return work();
+    }
+
+    let lock = path_lock(path)?;
+    let _guard = lock
+        .lock()
+        .map_err(|e| format!("Failed to lock {:?}: {}", path, e))?;
+
+    // Released when the handle is closed
+    let _lock_file = if lock_file {
+        let file = std::fs::File::create(sibling(path, "lock"))
+            .map_err(|e| format!("Failed to create lock file for {:?}: {}", path, e))?;
+        file.lock_exclusive()
+            .map_err(|e| format!("Failed to lock {:?}: {}", path, e))?;
+        Some(file)
+    } else {
+        None
+    };
+
+    HELD.with(|held| held.borrow_mut().insert(path.to_path_buf()));
+    let _held = Held(path.to_path_buf());
+    work()
+}
+
+/// Load, change and save a JSON file as one step under its locks (see
+/// `with_lock`). Nothing is written when `update` fails or leaves the value
+/// unchanged.
+pub fn update_json<T, R>(
+    path: &Path,
+    update: impl FnOnce(&mut T) -> Result<R, String>,
+) -> Result<R, String>
+where
+    T: Serialize + DeserializeOwned + Default,
+{
+    with_lock(path, || {
+        let mut value: T = load_json(path);
+        let before = serde_json::to_value(&value).ok();
+        let result = update(&mut value)?;
+        if before.is_none() || before != serde_json::to_value(&value).ok() {
+            write_json(path, &value, false)?;
+        }
+        Ok(result)
+    })
+}
+
+/// Current time as seconds since the Unix epoch
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
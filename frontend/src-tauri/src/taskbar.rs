@@ -0,0 +1,42 @@
+use tauri::window::{ProgressBarState, ProgressBarStatus};
+use tauri::WebviewWindow;
+
+/// Show build progress on the Windows taskbar button / macOS dock icon
+pub fn set_taskbar_progress(window: &WebviewWindow, current: u32, total: u32) {
+    if total == 0 {
+        return;
+    }
+    let percent = u64::from(current.min(total)) * 100 / u64::from(total);
+
+    let _ = window.set_progress_bar(ProgressBarState {
+        status: Some(ProgressBarStatus::Normal),
+        progress: Some(percent),
+    });
+
+    #[cfg(target_os = "macos")]
+    let _ = window.set_badge_label(Some(format!("{}%", percent)));
+}
+
+/// Clear the progress bar on success, or leave it in the error state on failure
+pub fn set_taskbar_finished(window: &WebviewWindow, success: bool) {
+    let state = if success {
+        ProgressBarState {
+            status: Some(ProgressBarStatus::None),
+            progress: None,
+        }
+    } else {
+        ProgressBarState {
+            status: Some(ProgressBarStatus::Error),
+            progress: Some(100),
+        }
+    };
+    let _ = window.set_progress_bar(state);
+
+    #[cfg(target_os = "macos")]
+    let _ = window.set_badge_label(if success { None } else { Some("!".to_string()) });
+}
+
+/// Remove any progress or error state left over from a previous build
+pub fn clear_taskbar_progress(window: &WebviewWindow) {
+    set_taskbar_finished(window, true);
+}
@@ -0,0 +1,282 @@
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{self, Write};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tauri::{AppHandle, Manager, State};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::{Event, Level, Subscriber};
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::filter::Targets;
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::Layer;
+
+use crate::run_logs;
+use crate::storage;
+
+/// Target of the spans and events emitted by the app itself
+const APP_TARGET: &str = env!("CARGO_CRATE_NAME");
+
+/// Invocations at least this slow are kept as slow operations
+const SLOW_THRESHOLD_MS: u64 = 1000;
+
+/// Slow operations kept, newest first
+const MAX_SLOW_OPERATIONS: usize = 50;
+
+/// Daily app log files kept
+const MAX_LOG_FILES: usize = 7;
+
+/// Longest argument summary kept for a slow operation
+const MAX_ARGS_LEN: usize = 300;
+
+#[derive(Default)]
+struct CommandStats {
+    calls: u64,
+    errors: u64,
+    total_ms: u64,
+    max_ms: u64,
+}
+
+#[derive(Serialize, Clone)]
+pub struct SlowOperation {
+    pub command: String,
+    pub duration_ms: u64,
+    /// The command's arguments as logged, e.g. `skin_path=/skins/a`
+    pub args: String,
+    pub failed: bool,
+    pub finished_at: u64,
+}
+
+#[derive(Serialize)]
+pub struct CommandMetrics {
+    pub command: String,
+    pub calls: u64,
+    pub errors: u64,
+    pub total_ms: u64,
+    pub mean_ms: u64,
+    pub max_ms: u64,
+}
+
+#[derive(Serialize)]
+pub struct PerformanceMetrics {
+    /// When collection started (app launch)
+    pub since: u64,
+    pub slow_threshold_ms: u64,
+    /// Slowest in total first
+    pub commands: Vec<CommandMetrics>,
+    pub slow_operations: Vec<SlowOperation>,
+    /// Folder of the rolling app log with every invocation
+    pub log_dir: Option<String>,
+}
+
+struct Metrics {
+    since: u64,
+    commands: HashMap<&'static str, CommandStats>,
+    slow_operations: VecDeque<SlowOperation>,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self {
+            since: storage::unix_now(),
+            commands: HashMap::new(),
+            slow_operations: VecDeque::new(),
+        }
+    }
+}
+
+/// Command timings collected since launch
+#[derive(Default)]
+pub struct TelemetryState {
+    metrics: Arc<Mutex<Metrics>>,
+    log_dir: Mutex<Option<PathBuf>>,
+}
+
+/// Timing of one command invocation, kept in its span
+struct Invocation {
+    started: Instant,
+    args: String,
+    failed: bool,
+}
+
+/// Formats a span's fields the way the log does: `name=value, ...`
+#[derive(Default)]
+struct ArgsVisitor(String);
+
+impl Visit for ArgsVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if !self.0.is_empty() {
+            self.0.push_str(", ");
+        }
+        let _ = write!(self.0, "{}={:?}", field.name(), value);
+    }
+}
+
+/// Times every span of the app's own commands into `Metrics`
+struct MetricsLayer {
+    metrics: Arc<Mutex<Metrics>>,
+}
+
+impl<S> Layer<S> for MetricsLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        if !attrs.metadata().target().starts_with(APP_TARGET) {
+            return;
+        }
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut visitor = ArgsVisitor::default();
+        attrs.record(&mut visitor);
+        span.extensions_mut().insert(Invocation {
+            started: Instant::now(),
+            args: visitor.0,
+            failed: false,
+        });
+    }
+
+    // `#[tracing::instrument(err)]` reports a returned error as an event inside the span
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        if *event.metadata().level() != Level::ERROR {
+            return;
+        }
+        if let Some(span) = ctx.event_span(event) {
+            if let Some(invocation) = span.extensions_mut().get_mut::<Invocation>() {
+                invocation.failed = true;
+            }
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let Some(invocation) = span.extensions_mut().remove::<Invocation>() else {
+            return;
+        };
+        let duration_ms = invocation.started.elapsed().as_millis() as u64;
+        let name = span.metadata().name();
+
+        let Ok(mut metrics) = self.metrics.lock() else {
+            return;
+        };
+        let stats = metrics.commands.entry(name).or_default();
+        stats.calls += 1;
+        stats.errors += u64::from(invocation.failed);
+        stats.total_ms += duration_ms;
+        stats.max_ms = stats.max_ms.max(duration_ms);
+
+        if duration_ms >= SLOW_THRESHOLD_MS {
+            metrics.slow_operations.push_front(SlowOperation {
+                command: name.to_string(),
+                duration_ms,
+                args: invocation.args.chars().take(MAX_ARGS_LEN).collect(),
+                failed: invocation.failed,
+                finished_at: storage::unix_now(),
+            });
+            metrics.slow_operations.truncate(MAX_SLOW_OPERATIONS);
+        }
+    }
+}
+
+fn app_log_dir(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    Ok(run_logs::logs_dir(app_handle)?.join("app"))
+}
+
+/// Log every command invocation (arguments, duration, errors) to a daily
+/// rolling `app.*.log` and collect timings for `get_performance_metrics`
+pub fn init(app_handle: &AppHandle) {
+    let state = app_handle.state::<TelemetryState>();
+    let metrics_layer = MetricsLayer {
+        metrics: state.metrics.clone(),
+    };
+
+    let appender = app_log_dir(app_handle).and_then(|dir| {
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| format!("Failed to create app log directory: {}", e))?;
+        let appender = RollingFileAppender::builder()
+            .rotation(Rotation::DAILY)
+            .filename_prefix("app")
+            .filename_suffix("log")
+            .max_log_files(MAX_LOG_FILES)
+            .build(&dir)
+            .map_err(|e| format!("Failed to open app log: {}", e))?;
+        Ok((dir, appender))
+    });
+    let log_layer = match appender {
+        Ok((dir, appender)) => {
+            if let Ok(mut log_dir) = state.log_dir.lock() {
+                *log_dir = Some(dir);
+            }
+            let layer = tracing_subscriber::fmt::layer()
+                .with_ansi(false)
+                .with_writer(appender)
+                .with_span_events(FmtSpan::CLOSE)
+                .with_filter(
+                    Targets::new()
+                        .with_target(APP_TARGET, Level::DEBUG)
+                        .with_default(Level::WARN),
+                );
+            Some(layer)
+        }
+        Err(e) => {
+            println!("[WARNING] App log disabled: {}", e);
+            None
+        }
+    };
+
+    if let Err(e) = tracing_subscriber::registry()
+        .with(log_layer)
+        .with(metrics_layer)
+        .try_init()
+    {
+        println!("[WARNING] Failed to start tracing: {}", e);
+    }
+}
+
+/// Call counts and timings per command since launch, and the slowest recent invocations
+#[tauri::command]
+#[tracing::instrument(skip(state), err)]
+pub fn get_performance_metrics(
+    state: State<'_, TelemetryState>,
+) -> Result<PerformanceMetrics, String> {
+    let metrics = state
+        .metrics
+        .lock()
+        .map_err(|e| format!("Failed to read performance metrics: {}", e))?;
+
+    let mut commands: Vec<CommandMetrics> = metrics
+        .commands
+        .iter()
+        .map(|(name, stats)| CommandMetrics {
+            command: name.to_string(),
+            calls: stats.calls,
+            errors: stats.errors,
+            total_ms: stats.total_ms,
+            mean_ms: stats.total_ms / stats.calls.max(1),
+            max_ms: stats.max_ms,
+        })
+        .collect();
+    commands.sort_by(|a, b| b.total_ms.cmp(&a.total_ms));
+
+    let log_dir = state
+        .log_dir
+        .lock()
+        .ok()
+        .and_then(|dir| dir.as_ref().map(|dir| dir.to_string_lossy().to_string()));
+
+    Ok(PerformanceMetrics {
+        since: metrics.since,
+        slow_threshold_ms: SLOW_THRESHOLD_MS,
+        commands,
+        slow_operations: metrics.slow_operations.iter().cloned().collect(),
+        log_dir,
+    })
+}
@@ -0,0 +1,400 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+use crate::path_guard;
+use crate::portable;
+use crate::serialized_file::{ObjectInfo, SerializedFile, Value};
+use crate::skins::modified_secs;
+use crate::storage;
+use crate::unity_bundle::{BundleEntry, BundleReader};
+
+const TEXTURE2D_CLASS_ID: i32 = 28;
+/// Longest side of a cached thumbnail
+const THUMBNAIL_SIZE: u32 = 256;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AssetPreview {
+    /// PNG thumbnail in the app cache
+    pub path: String,
+    pub name: String,
+    pub path_id: i64,
+    pub width: u32,
+    pub height: u32,
+    pub format: String,
+    pub cached: bool,
+}
+
+/// Block-compressed formats handled by texture2ddecoder, with ASTC block sizes
+enum Decoder {
+    Bc1,
+    Bc3,
+    Bc4,
+    Bc5,
+    Bc7,
+    Etc1,
+    Etc2Rgb,
+    Etc2Rgba8,
+    Astc(usize),
+}
+
+fn format_name(format: i64) -> String {
+    let name = match format {
+        1 => "Alpha8",
+        2 => "ARGB4444",
+        3 => "RGB24",
+        4 => "RGBA32",
+        5 => "ARGB32",
+        7 => "RGB565",
+        10 => "DXT1",
+        12 => "DXT5",
+        13 => "RGBA4444",
+        14 => "BGRA32",
+        25 => "BC7",
+        26 => "BC4",
+        27 => "BC5",
+        28 => "DXT1Crunched",
+        29 => "DXT5Crunched",
+        34 => "ETC_RGB4",
+        45 => "ETC2_RGB",
+        47 => "ETC2_RGBA8",
+        48..=59 => "ASTC",
+        63 => "R8",
+        _ => return format!("Format {}", format),
+    };
+    name.to_string()
+}
+
+/// Expand 16-bit pixels with the given (shift, bits) per RGBA channel;
+/// a channel with 0 bits is opaque
+fn decode_packed16(data: &[u8], pixels: usize, channels: [(u32, u32); 4]) -> Vec<u8> {
+    data.chunks_exact(2)
+        .take(pixels)
+        .flat_map(|bytes| {
+            let value = u16::from_le_bytes([bytes[0], bytes[1]]) as u32;
+            channels.map(|(shift, bits)| {
+                if bits == 0 {
+                    return 255;
+                }
+                let max = (1 << bits) - 1;
+                (((value >> shift) & max) * 255 / max) as u8
+            })
+        })
+        .collect()
+}
+
+/// Decode the top mip level into RGBA8, rows in Unity's bottom-up order
+fn decode_rgba(format: i64, data: &[u8], width: usize, height: usize) -> Result<Vec<u8>, String> {
+    let pixels = width * height;
+    let raw_size = match format {
+        1 | 63 => pixels,
+        2 | 7 | 13 => pixels * 2,
+        3 => pixels * 3,
+        4 | 5 | 14 => pixels * 4,
+        _ => 0,
+    };
+    if data.len() < raw_size {
+        return Err("Texture data is shorter than its dimensions".to_string());
+    }
+
+    let decoder = match format {
+        1 => {
+            return Ok(data[..pixels]
+                .iter()
+                .flat_map(|a| [255, 255, 255, *a])
+                .collect())
+        }
+        63 => {
+            return Ok(data[..pixels]
+                .iter()
+                .flat_map(|r| [*r, *r, *r, 255])
+                .collect())
+        }
+        3 => {
+            return Ok(data[..raw_size]
+                .chunks_exact(3)
+                .flat_map(|p| [p[0], p[1], p[2], 255])
+                .collect())
+        }
+        4 => return Ok(data[..raw_size].to_vec()),
+        5 => {
+            return Ok(data[..raw_size]
+                .chunks_exact(4)
+                .flat_map(|p| [p[1], p[2], p[3], p[0]])
+                .collect())
+        }
+        14 => {
+            return Ok(data[..raw_size]
+                .chunks_exact(4)
+                .flat_map(|p| [p[2], p[1], p[0], p[3]])
+                .collect())
+        }
+        2 => {
+            return Ok(decode_packed16(
+                data,
+                pixels,
+                [(8, 4), (4, 4), (0, 4), (12, 4)],
+            ))
+        }
+        7 => {
+            return Ok(decode_packed16(
+                data,
+                pixels,
+                [(11, 5), (5, 6), (0, 5), (0, 0)],
+            ))
+        }
+        13 => {
+            return Ok(decode_packed16(
+                data,
+                pixels,
+                [(12, 4), (8, 4), (4, 4), (0, 4)],
+            ))
+        }
+        10 => Decoder::Bc1,
+        12 => Decoder::Bc3,
+        26 => Decoder::Bc4,
+        27 => Decoder::Bc5,
+        25 => Decoder::Bc7,
+        34 => Decoder::Etc1,
+        45 => Decoder::Etc2Rgb,
+        47 => Decoder::Etc2Rgba8,
+        48..=59 => Decoder::Astc([4, 5, 6, 8, 10, 12][((format - 48) % 6) as usize]),
+        _ => {
+            return Err(format!(
+                "Previews aren't supported for {} textures",
+                format_name(format)
+            ))
+        }
+    };
+
+    let mut image = vec![0u32; pixels];
+    let result = match decoder {
+        Decoder::Bc1 => texture2ddecoder::decode_bc1(data, width, height, &mut image),
+        Decoder::Bc3 => texture2ddecoder::decode_bc3(data, width, height, &mut image),
+        Decoder::Bc4 => texture2ddecoder::decode_bc4(data, width, height, &mut image),
+        Decoder::Bc5 => texture2ddecoder::decode_bc5(data, width, height, &mut image),
+        Decoder::Bc7 => texture2ddecoder::decode_bc7(data, width, height, &mut image),
+        Decoder::Etc1 => texture2ddecoder::decode_etc1(data, width, height, &mut image),
+        Decoder::Etc2Rgb => texture2ddecoder::decode_etc2_rgb(data, width, height, &mut image),
+        Decoder::Etc2Rgba8 => texture2ddecoder::decode_etc2_rgba8(data, width, height, &mut image),
+        Decoder::Astc(block) => {
+            texture2ddecoder::decode_astc(data, width, height, block, block, &mut image)
+        }
+    };
+    result.map_err(|e| format!("Failed to decode {} texture: {}", format_name(format), e))?;
+
+    // The decoder writes BGRA pixels
+    Ok(image
+        .iter()
+        .flat_map(|pixel| {
+            let [b, g, r, a] = pixel.to_le_bytes();
+            [r, g, b, a]
+        })
+        .collect())
+}
+
+/// Flip rows to top-down and scale down to fit the thumbnail size
+fn thumbnail(rgba: &[u8], width: u32, height: u32) -> (Vec<u8>, u32, u32) {
+    let scale = (width.max(height) as f64 / THUMBNAIL_SIZE as f64).max(1.0);
+    let out_width = ((width as f64 / scale).round() as u32).max(1);
+    let out_height = ((height as f64 / scale).round() as u32).max(1);
+
+    let mut output = Vec::with_capacity((out_width * out_height * 4) as usize);
+    for y in 0..out_height {
+        let source_y = ((y as f64 * scale) as u32).min(height - 1);
+        let row = height - 1 - source_y;
+        for x in 0..out_width {
+            let source_x = ((x as f64 * scale) as u32).min(width - 1);
+            let offset = ((row * width + source_x) * 4) as usize;
+            output.extend_from_slice(&rgba[offset..offset + 4]);
+        }
+    }
+    (output, out_width, out_height)
+}
+
+fn write_png(path: &Path, rgba: &[u8], width: u32, height: u32) -> Result<(), String> {
+    let file =
+        std::fs::File::create(path).map_err(|e| format!("Failed to create preview: {}", e))?;
+    let mut encoder = png::Encoder::new(std::io::BufWriter::new(file), width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(rgba))
+        .map_err(|e| format!("Failed to write preview: {}", e))
+}
+
+/// Serialized asset files in the bundle, skipping `.resS`/`.resource` payloads
+fn serialized_entries(reader: &BundleReader) -> Vec<BundleEntry> {
+    reader
+        .inspection
+        .entries
+        .iter()
+        .filter(|entry| !entry.path.ends_with(".resS") && !entry.path.ends_with(".resource"))
+        .cloned()
+        .collect()
+}
+
+/// Find a texture by path id or (case-insensitive) name
+fn find_texture(
+    reader: &mut BundleReader,
+    asset: &str,
+) -> Result<(SerializedFile, ObjectInfo, String), String> {
+    let path_id = asset.parse::<i64>().ok();
+
+    for entry in serialized_entries(reader) {
+        let mut file = SerializedFile::parse(reader, &entry)?;
+        let objects = std::mem::take(&mut file.objects);
+        for object in objects
+            .into_iter()
+            .filter(|object| object.class_id == TEXTURE2D_CLASS_ID)
+        {
+            let name = file.object_name(reader, &object)?;
+            if path_id == Some(object.path_id) || name.eq_ignore_ascii_case(asset) {
+                return Ok((file, object, name));
+            }
+        }
+    }
+
+    Err(format!("No texture named \"{}\" in this bundle", asset))
+}
+
+/// Image bytes stored inline or streamed from the bundle's `.resS` payload
+fn texture_data(reader: &mut BundleReader, texture: &Value) -> Result<Vec<u8>, String> {
+    if let Some(data) = texture
+        .get("image data")
+        .and_then(Value::as_bytes)
+        .filter(|data| !data.is_empty())
+    {
+        return Ok(data.to_vec());
+    }
+
+    let stream_path = texture
+        .get("m_StreamData.path")
+        .and_then(Value::as_str)
+        .filter(|path| !path.is_empty())
+        .ok_or_else(|| "Texture has no image data".to_string())?;
+    let offset = texture
+        .get("m_StreamData.offset")
+        .and_then(Value::as_i64)
+        .unwrap_or(0);
+    let size = texture
+        .get("m_StreamData.size")
+        .and_then(Value::as_i64)
+        .unwrap_or(0);
+
+    let file_name = stream_path.rsplit('/').next().unwrap_or(stream_path);
+    let entry = reader
+        .find_entry(file_name)
+        .ok_or_else(|| format!("Texture data file {} is not in this bundle", file_name))?;
+    reader.read_entry(
+        &entry,
+        u64::try_from(offset).map_err(|_| "Invalid texture data offset".to_string())?,
+        usize::try_from(size).map_err(|_| "Invalid texture data size".to_string())?,
+    )
+}
+
+fn preview_cache_path(
+    app_handle: &AppHandle,
+    bundle: &Path,
+    asset: &str,
+) -> Result<PathBuf, String> {
+    let size = std::fs::metadata(bundle)
+        .map(|meta| meta.len())
+        .unwrap_or(0);
+    let key = format!(
+        "{}\n{}\n{:?}\n{}",
+        bundle.to_string_lossy(),
+        size,
+        modified_secs(bundle),
+        asset
+    );
+    let digest = Sha256::digest(key.as_bytes());
+    Ok(portable::app_cache_dir(app_handle)?
+        .join("previews")
+        .join(&format!("{:x}", digest)[..16]))
+}
+
+fn extract_preview(
+    app_handle: &AppHandle,
+    bundle: &Path,
+    asset: &str,
+) -> Result<AssetPreview, String> {
+    // Keyed on the bundle's size and mtime, so a game update invalidates it
+    let cache_base = preview_cache_path(app_handle, bundle, asset)?;
+    let png_path = cache_base.with_extension("png");
+    let info_path = cache_base.with_extension("json");
+    if png_path.is_file() {
+        if let Ok(content) = std::fs::read_to_string(&info_path) {
+            if let Ok(preview) = serde_json::from_str::<AssetPreview>(&content) {
+                return Ok(AssetPreview {
+                    cached: true,
+                    ..preview
+                });
+            }
+        }
+    }
+
+    let mut reader = BundleReader::open(bundle)?;
+    let (file, object, name) = find_texture(&mut reader, asset)?;
+    let texture = file.read_object(&mut reader, &object)?;
+
+    let dimension = |field: &str| {
+        texture
+            .get(field)
+            .and_then(Value::as_i64)
+            .and_then(|value| u32::try_from(value).ok())
+            .filter(|value| *value > 0 && *value <= 16384)
+            .ok_or_else(|| format!("Texture {} has an invalid {}", name, field))
+    };
+    let width = dimension("m_Width")?;
+    let height = dimension("m_Height")?;
+    let format = texture
+        .get("m_TextureFormat")
+        .and_then(Value::as_i64)
+        .unwrap_or(0);
+
+    let data = texture_data(&mut reader, &texture)?;
+    let rgba = decode_rgba(format, &data, width as usize, height as usize)?;
+    let (thumbnail, thumb_width, thumb_height) = thumbnail(&rgba, width, height);
+
+    if let Some(parent) = png_path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create preview cache: {}", e))?;
+    }
+    write_png(&png_path, &thumbnail, thumb_width, thumb_height)?;
+
+    let preview = AssetPreview {
+        path: png_path.to_string_lossy().to_string(),
+        name,
+        path_id: object.path_id,
+        width,
+        height,
+        format: format_name(format),
+        cached: false,
+    };
+    if let Err(e) = storage::save_json(&info_path, &preview) {
+        println!("[WARNING] Failed to cache preview details: {}", e);
+    }
+    Ok(preview)
+}
+
+/// Decode a texture from a bundle (by name or path id) into a PNG thumbnail
+/// under the app cache
+#[tauri::command]
+#[tracing::instrument(skip(app_handle), err)]
+pub async fn extract_asset_preview(
+    app_handle: AppHandle,
+    bundle: String,
+    asset: String,
+) -> Result<AssetPreview, String> {
+    let bundle_path = path_guard::existing_file(&bundle, "Bundle")?;
+    let asset = asset.trim().to_string();
+    if asset.is_empty() {
+        return Err("Asset name is required".to_string());
+    }
+    tokio::task::spawn_blocking(move || extract_preview(&app_handle, &bundle_path, &asset))
+        .await
+        .map_err(|e| format!("Preview task failed: {}", e))?
+}
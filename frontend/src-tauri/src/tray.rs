@@ -0,0 +1,208 @@
+use tauri::menu::{Menu, MenuItem, PredefinedMenuItem};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
+use tauri::{AppHandle, Manager, Window, Wry};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::cache::open_in_file_browser;
+use crate::history;
+use crate::i18n::t;
+use crate::process::{run_python_task, stop_python_task, ProcessState, TaskConfig};
+use crate::run_logs;
+use crate::settings;
+
+const TRAY_ID: &str = "main";
+
+/// Build state shown in the tray tooltip
+#[derive(Clone, Copy)]
+pub enum TrayStatus {
+    Idle,
+    Building,
+    Succeeded,
+    Failed,
+}
+
+impl TrayStatus {
+    fn label_key(self) -> &'static str {
+        match self {
+            TrayStatus::Idle => "tray.status.idle",
+            TrayStatus::Building => "tray.status.building",
+            TrayStatus::Succeeded => "tray.status.succeeded",
+            TrayStatus::Failed => "tray.status.failed",
+        }
+    }
+
+    fn tooltip(self, app_handle: &AppHandle) -> String {
+        let status = t(app_handle, self.label_key(), &[]);
+        t(app_handle, "tray.tooltip", &[("status", &status)])
+    }
+}
+
+fn show_main_window(app_handle: &AppHandle) {
+    if let Some(window) = app_handle.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+/// Re-run the most recent real (non-preview) build from history
+pub fn run_last_build(app_handle: &AppHandle) {
+    if app_handle.state::<ProcessState>().is_run_active() {
+        println!("[WARNING] Not running the last build from the tray: a build is already running");
+        return;
+    }
+    let Some(last) = history::load_history(app_handle)
+        .into_iter()
+        .rev()
+        .find(|record| !record.dry_run)
+    else {
+        println!("[WARNING] No previous build to run from the tray");
+        return;
+    };
+
+    let config = TaskConfig {
+        skin_path: last.skin_path,
+        bundles_path: last.bundles_path,
+        debug_export: false,
+        dry_run: false,
+        snapshot_before_build: false,
+        profile: None,
+        extra_args: Vec::new(),
+        project_id: None,
+        incremental: false,
+        bundles: Vec::new(),
+        verbosity: None,
+        output_mode: None,
+    };
+
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        let state = app_handle.state::<ProcessState>();
+        if let Err(e) = run_python_task(app_handle.clone(), config, state, None).await {
+            eprintln!("[RUST] Tray build failed: {}", e);
+        }
+    });
+}
+
+pub fn stop_build(app_handle: &AppHandle) {
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        let state = app_handle.state::<ProcessState>();
+        if let Err(e) = stop_python_task(app_handle.clone(), state).await {
+            eprintln!("[RUST] Failed to stop build from tray: {}", e);
+        }
+    });
+}
+
+fn open_logs(app_handle: &AppHandle) {
+    let result = run_logs::logs_dir(app_handle).and_then(|dir| {
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| format!("Failed to create logs directory: {}", e))?;
+        open_in_file_browser(app_handle, &dir).map_err(String::from)
+    });
+    if let Err(e) = result {
+        eprintln!("[RUST] {}", e);
+    }
+}
+
+fn build_menu(app_handle: &AppHandle) -> tauri::Result<Menu<Wry>> {
+    let item = |id: &str| {
+        let label = t(app_handle, &format!("tray.{}", id), &[]);
+        MenuItem::with_id(app_handle, id, label, true, None::<&str>)
+    };
+    let show = item("show")?;
+    let run_last = item("run_last_build")?;
+    let stop = item("stop")?;
+    let logs = item("open_logs")?;
+    let quit = item("quit")?;
+    let separator = PredefinedMenuItem::separator(app_handle)?;
+    Menu::with_items(
+        app_handle,
+        &[
+            &show, &separator, &run_last, &stop, &logs, &separator, &quit,
+        ],
+    )
+}
+
+/// Create the tray icon and its menu
+pub fn create_tray(app_handle: &AppHandle) -> tauri::Result<()> {
+    let menu = build_menu(app_handle)?;
+
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip(TrayStatus::Idle.tooltip(app_handle))
+        .menu(&menu)
+        .show_menu_on_left_click(false)
+        .on_menu_event(|app_handle, event| match event.id.as_ref() {
+            "show" => show_main_window(app_handle),
+            "run_last_build" => run_last_build(app_handle),
+            "stop" => stop_build(app_handle),
+            "open_logs" => open_logs(app_handle),
+            "quit" => app_handle.exit(0),
+            _ => {}
+        })
+        .on_tray_icon_event(|tray, event| {
+            if let TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } = event
+            {
+                show_main_window(tray.app_handle());
+            }
+        });
+
+    if let Some(icon) = app_handle.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+
+    builder.build(app_handle)?;
+    Ok(())
+}
+
+/// Reflect the current build state in the tray tooltip
+pub fn set_tray_status(app_handle: &AppHandle, status: TrayStatus) {
+    if let Some(tray) = app_handle.tray_by_id(TRAY_ID) {
+        let _ = tray.set_tooltip(Some(status.tooltip(app_handle)));
+    }
+}
+
+/// Rebuild the menu after the locale changes
+pub fn refresh_menu(app_handle: &AppHandle) {
+    let Some(tray) = app_handle.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    match build_menu(app_handle) {
+        Ok(menu) => {
+            let _ = tray.set_menu(Some(menu));
+            let _ = tray.set_tooltip(Some(TrayStatus::Idle.tooltip(app_handle)));
+        }
+        Err(e) => println!("[WARNING] Failed to rebuild tray menu: {}", e),
+    }
+}
+
+/// Keep running in the tray with the window hidden, e.g. to let a build finish
+/// after the window was closed
+pub fn move_to_background(app_handle: &AppHandle) {
+    if let Some(window) = app_handle.get_webview_window("main") {
+        let _ = window.hide();
+    }
+    // Otherwise closing the window looks like quitting
+    if let Err(e) = app_handle
+        .notification()
+        .builder()
+        .title(t(app_handle, "tray.background_title", &[]))
+        .body(t(app_handle, "tray.background_body", &[]))
+        .show()
+    {
+        println!("[WARNING] Failed to show notification: {}", e);
+    }
+}
+
+/// Hide the window instead of minimizing when "minimize to tray" is enabled
+pub fn handle_minimize(window: &Window) {
+    let minimize_to_tray =
+        settings::get_setting::<bool>(window.app_handle(), "minimizeToTray").unwrap_or(false);
+    if minimize_to_tray && window.is_minimized().unwrap_or(false) {
+        let _ = window.hide();
+    }
+}
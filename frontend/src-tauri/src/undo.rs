@@ -0,0 +1,115 @@
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::events::UndoableActionEvent;
+use crate::storage;
+
+/// How long an action can be undone after it is announced
+const UNDO_WINDOW: Duration = Duration::from_secs(30);
+
+/// What undoing an action does
+pub enum UndoAction {
+    /// Put a folder moved to the OS trash back where it was
+    RestoreFromTrash(PathBuf),
+}
+
+struct PendingUndo {
+    event: UndoableActionEvent,
+    action: UndoAction,
+    expires: Instant,
+}
+
+/// The most recent undoable action; announcing another replaces it
+#[derive(Default)]
+pub struct UndoState {
+    last: Mutex<Option<PendingUndo>>,
+}
+
+/// Remember an action and emit `undoable_action` so the UI can offer to undo it
+pub fn register(app_handle: &AppHandle, kind: &str, description: String, action: UndoAction) {
+    let event = UndoableActionEvent {
+        token: uuid::Uuid::new_v4().to_string(),
+        kind: kind.to_string(),
+        description,
+        expires_at: storage::unix_now() + UNDO_WINDOW.as_secs(),
+    };
+
+    let state = app_handle.state::<UndoState>();
+    let Ok(mut last) = state.last.lock() else {
+        return;
+    };
+    *last = Some(PendingUndo {
+        event: event.clone(),
+        action,
+        expires: Instant::now() + UNDO_WINDOW,
+    });
+    drop(last);
+
+    let _ = app_handle.emit("undoable_action", event);
+}
+
+/// Restore the most recently trashed item that came from `path`
+#[cfg(any(windows, target_os = "linux"))]
+fn restore_from_trash(path: &Path) -> Result<(), String> {
+    let item = trash::os_limited::list()
+        .map_err(|e| format!("Failed to read the trash: {}", e))?
+        .into_iter()
+        .filter(|item| item.original_path() == path)
+        .max_by_key(|item| item.time_deleted)
+        .ok_or_else(|| format!("{} is no longer in the trash", path.display()))?;
+    trash::os_limited::restore_all([item])
+        .map_err(|e| format!("Failed to restore from the trash: {}", e))
+}
+
+#[cfg(not(any(windows, target_os = "linux")))]
+fn restore_from_trash(path: &Path) -> Result<(), String> {
+    Err(format!(
+        "Restoring from the Trash isn't supported here; use Put Back in Finder to restore {}",
+        path.display()
+    ))
+}
+
+/// Undo the action announced with `token`, if it is still the latest one and
+/// its undo window hasn't passed. Returns the path that was restored.
+#[tauri::command]
+#[tracing::instrument(skip(state), err)]
+pub async fn undo_last_action(
+    state: State<'_, UndoState>,
+    token: String,
+) -> Result<String, String> {
+    let pending = {
+        let mut last = state
+            .last
+            .lock()
+            .map_err(|e| format!("Failed to lock undo state: {}", e))?;
+        match last.take() {
+            Some(pending) if pending.event.token == token => pending,
+            other => {
+                *last = other;
+                return Err("That action can no longer be undone".to_string());
+            }
+        }
+    };
+    if Instant::now() > pending.expires {
+        return Err("The time to undo this action has passed".to_string());
+    }
+
+    match pending.action {
+        UndoAction::RestoreFromTrash(path) => {
+            if path.exists() {
+                return Err(format!(
+                    "Can't restore {}: something else is already there",
+                    path.display()
+                ));
+            }
+            let restore_path = path.clone();
+            tokio::task::spawn_blocking(move || restore_from_trash(&restore_path))
+                .await
+                .map_err(|e| format!("Undo task failed: {}", e))??;
+            println!("[DEBUG] Restored {:?} from the trash", path);
+            Ok(path.to_string_lossy().to_string())
+        }
+    }
+}
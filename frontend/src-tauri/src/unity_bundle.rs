@@ -0,0 +1,455 @@
+use serde::Serialize;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+use crate::long_paths;
+use crate::path_guard;
+
+const UNITYFS_SIGNATURE: &str = "UnityFS";
+/// Far above any real block table; guards allocations against corrupt headers
+const MAX_BLOCKS_INFO_SIZE: usize = 64 * 1024 * 1024;
+/// The header is a few short strings and integers
+const MAX_HEADER_SIZE: u64 = 4096;
+
+/// Archive flag bits from the UnityFS header
+const COMPRESSION_MASK: u32 = 0x3f;
+const BLOCKS_INFO_AT_END: u32 = 0x80;
+const BLOCK_INFO_NEEDS_PADDING: u32 = 0x200;
+
+#[derive(Serialize, Clone)]
+pub struct BundleHeader {
+    pub signature: String,
+    pub format_version: u32,
+    pub unity_version: String,
+    pub unity_revision: String,
+    pub file_size: u64,
+    pub flags: u32,
+    /// Compression of the block/directory table
+    pub compression: String,
+}
+
+#[derive(Serialize, Clone)]
+pub struct BundleBlock {
+    pub uncompressed_size: u32,
+    pub compressed_size: u32,
+    pub compression: String,
+    #[serde(skip)]
+    flags: u32,
+}
+
+/// A file stored in the bundle: serialized asset files (`CAB-...`) and their
+/// `.resS`/`.resource` payloads
+#[derive(Serialize, Clone)]
+pub struct BundleEntry {
+    pub path: String,
+    pub offset: u64,
+    pub size: u64,
+    pub flags: u32,
+}
+
+#[derive(Serialize)]
+pub struct BundleInspection {
+    pub path: String,
+    pub header: BundleHeader,
+    pub blocks: Vec<BundleBlock>,
+    pub entries: Vec<BundleEntry>,
+    pub compressed_size: u64,
+    pub uncompressed_size: u64,
+}
+
+fn compression_name(flags: u32) -> String {
+    match flags & COMPRESSION_MASK {
+        0 => "none",
+        1 => "lzma",
+        2 => "lz4",
+        3 => "lz4hc",
+        _ => "unknown",
+    }
+    .to_string()
+}
+
+/// Reads integers and strings from an in-memory buffer in either byte order
+pub struct ByteCursor<'a> {
+    data: &'a [u8],
+    position: usize,
+    pub big_endian: bool,
+}
+
+impl<'a> ByteCursor<'a> {
+    pub fn new(data: &'a [u8], big_endian: bool) -> Self {
+        Self {
+            data,
+            position: 0,
+            big_endian,
+        }
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn bytes(&mut self, len: usize) -> Result<&'a [u8], String> {
+        let end = self
+            .position
+            .checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or_else(|| "Unexpected end of bundle data".to_string())?;
+        let bytes = &self.data[self.position..end];
+        self.position = end;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], String> {
+        let mut array: [u8; N] = self.bytes(N)?.try_into().unwrap_or([0; N]);
+        if !self.big_endian {
+            array.reverse();
+        }
+        Ok(array)
+    }
+
+    pub fn u8(&mut self) -> Result<u8, String> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    pub fn bool(&mut self) -> Result<bool, String> {
+        Ok(self.u8()? != 0)
+    }
+
+    pub fn u16(&mut self) -> Result<u16, String> {
+        Ok(u16::from_be_bytes(self.array()?))
+    }
+
+    pub fn i16(&mut self) -> Result<i16, String> {
+        Ok(i16::from_be_bytes(self.array()?))
+    }
+
+    pub fn u32(&mut self) -> Result<u32, String> {
+        Ok(u32::from_be_bytes(self.array()?))
+    }
+
+    pub fn i32(&mut self) -> Result<i32, String> {
+        Ok(i32::from_be_bytes(self.array()?))
+    }
+
+    pub fn u64(&mut self) -> Result<u64, String> {
+        Ok(u64::from_be_bytes(self.array()?))
+    }
+
+    pub fn i64(&mut self) -> Result<i64, String> {
+        Ok(i64::from_be_bytes(self.array()?))
+    }
+
+    pub fn f32(&mut self) -> Result<f32, String> {
+        Ok(f32::from_be_bytes(self.array()?))
+    }
+
+    pub fn f64(&mut self) -> Result<f64, String> {
+        Ok(f64::from_be_bytes(self.array()?))
+    }
+
+    /// Null-terminated string, capped so a corrupt file can't run away
+    pub fn cstring(&mut self) -> Result<String, String> {
+        let rest = &self.data[self.position..];
+        let len = rest
+            .iter()
+            .take(1024)
+            .position(|byte| *byte == 0)
+            .ok_or_else(|| "Unterminated string in bundle data".to_string())?;
+        let value = String::from_utf8_lossy(&rest[..len]).to_string();
+        self.position += len + 1;
+        Ok(value)
+    }
+
+    /// Length-prefixed byte array as used by serialized Unity objects
+    pub fn byte_array(&mut self) -> Result<&'a [u8], String> {
+        let len = self.i32()?;
+        let len = usize::try_from(len).map_err(|_| "Negative array length".to_string())?;
+        self.bytes(len)
+    }
+
+    pub fn align(&mut self, alignment: usize) {
+        self.position = self
+            .position
+            .next_multiple_of(alignment)
+            .min(self.data.len());
+    }
+}
+
+/// Decompress one block or the block table
+fn decompress(data: &[u8], flags: u32, uncompressed_size: usize) -> Result<Vec<u8>, String> {
+    let output = match flags & COMPRESSION_MASK {
+        0 => data.to_vec(),
+        2 | 3 => lz4_flex::block::decompress(data, uncompressed_size)
+            .map_err(|e| format!("Failed to decompress LZ4 data: {}", e))?,
+        1 => return Err("LZMA-compressed bundles are not supported".to_string()),
+        other => return Err(format!("Unknown bundle compression type: {}", other)),
+    };
+    if output.len() != uncompressed_size {
+        return Err(format!(
+            "Block decompressed to {} bytes, expected {}",
+            output.len(),
+            uncompressed_size
+        ));
+    }
+    Ok(output)
+}
+
+/// Random access to the files inside a UnityFS bundle, decompressing only the
+/// blocks a read touches
+pub struct BundleReader {
+    file: File,
+    file_len: u64,
+    pub inspection: BundleInspection,
+    /// (compressed file offset, uncompressed stream offset) of each block
+    block_starts: Vec<(u64, u64)>,
+    /// The most recently decompressed block, since reads cluster together
+    cached_block: Option<(usize, Vec<u8>)>,
+}
+
+impl BundleReader {
+    /// Parse the header and block and directory tables without reading any
+    /// asset data
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let mut file = File::open(long_paths::extended(path))
+            .map_err(|e| format!("Failed to open bundle: {}", e))?;
+        let file_len = file
+            .metadata()
+            .map_err(|e| format!("Failed to read bundle: {}", e))?
+            .len();
+
+        let mut header_bytes = Vec::new();
+        (&mut file)
+            .take(MAX_HEADER_SIZE)
+            .read_to_end(&mut header_bytes)
+            .map_err(|e| format!("Failed to read bundle: {}", e))?;
+        let mut reader = ByteCursor::new(&header_bytes, true);
+
+        let signature = reader.cstring()?;
+        if signature != UNITYFS_SIGNATURE {
+            return Err(format!(
+                "Not a UnityFS bundle (signature \"{}\")",
+                signature.chars().take(16).collect::<String>()
+            ));
+        }
+
+        let format_version = reader.u32()?;
+        let unity_version = reader.cstring()?;
+        let unity_revision = reader.cstring()?;
+        let file_size = reader.u64()?;
+        let compressed_info_size = reader.u32()? as u64;
+        let uncompressed_info_size = reader.u32()? as usize;
+        let flags = reader.u32()?;
+
+        let mut position = reader.position() as u64;
+        if format_version >= 7 {
+            position = position.next_multiple_of(16);
+        }
+
+        if compressed_info_size > file_len || uncompressed_info_size > MAX_BLOCKS_INFO_SIZE {
+            return Err("Bundle block table size is invalid; the file may be corrupt".to_string());
+        }
+        let info_at_end = flags & BLOCKS_INFO_AT_END != 0;
+        let info_offset = if info_at_end {
+            file_len - compressed_info_size
+        } else {
+            position
+        };
+        file.seek(SeekFrom::Start(info_offset))
+            .map_err(|e| format!("Failed to read bundle: {}", e))?;
+        let mut compressed_info = vec![0u8; compressed_info_size as usize];
+        file.read_exact(&mut compressed_info)
+            .map_err(|e| format!("Failed to read bundle block table: {}", e))?;
+
+        let info = decompress(&compressed_info, flags, uncompressed_info_size)?;
+        let mut info = ByteCursor::new(&info, true);
+
+        // 16-byte hash of the uncompressed data, unused by the player
+        info.bytes(16)?;
+
+        let block_count = info.u32()?;
+        let mut blocks = Vec::new();
+        for _ in 0..block_count {
+            let uncompressed_size = info.u32()?;
+            let compressed_size = info.u32()?;
+            let block_flags = info.u16()? as u32;
+            blocks.push(BundleBlock {
+                uncompressed_size,
+                compressed_size,
+                compression: compression_name(block_flags),
+                flags: block_flags,
+            });
+        }
+
+        let entry_count = info.u32()?;
+        let mut entries = Vec::new();
+        for _ in 0..entry_count {
+            let offset = info.u64()?;
+            let size = info.u64()?;
+            let entry_flags = info.u32()?;
+            let path = info.cstring()?;
+            entries.push(BundleEntry {
+                path,
+                offset,
+                size,
+                flags: entry_flags,
+            });
+        }
+
+        // Block data follows the header, or the block table when that comes first
+        let mut data_offset = if info_at_end {
+            position
+        } else {
+            position + compressed_info_size
+        };
+        if flags & BLOCK_INFO_NEEDS_PADDING != 0 {
+            data_offset = data_offset.next_multiple_of(16);
+        }
+        let mut block_starts = Vec::with_capacity(blocks.len());
+        let (mut compressed, mut uncompressed) = (data_offset, 0u64);
+        for block in &blocks {
+            block_starts.push((compressed, uncompressed));
+            compressed += block.compressed_size as u64;
+            uncompressed += block.uncompressed_size as u64;
+        }
+
+        Ok(Self {
+            file,
+            file_len,
+            inspection: BundleInspection {
+                path: path.to_string_lossy().to_string(),
+                compressed_size: blocks
+                    .iter()
+                    .map(|block| block.compressed_size as u64)
+                    .sum(),
+                uncompressed_size: uncompressed,
+                header: BundleHeader {
+                    signature,
+                    format_version,
+                    unity_version,
+                    unity_revision,
+                    file_size,
+                    flags,
+                    compression: compression_name(flags),
+                },
+                blocks,
+                entries,
+            },
+            block_starts,
+            cached_block: None,
+        })
+    }
+
+    /// The entry whose path (or file name) matches
+    pub fn find_entry(&self, name: &str) -> Option<BundleEntry> {
+        self.inspection
+            .entries
+            .iter()
+            .find(|entry| entry.path == name || entry.path.rsplit('/').next() == Some(name))
+            .cloned()
+    }
+
+    fn block(&mut self, index: usize) -> Result<&[u8], String> {
+        if self.cached_block.as_ref().map(|(cached, _)| *cached) != Some(index) {
+            let block = &self.inspection.blocks[index];
+            let (compressed_start, _) = self.block_starts[index];
+            let mut compressed = vec![0u8; block.compressed_size as usize];
+            self.file
+                .seek(SeekFrom::Start(compressed_start))
+                .and_then(|_| self.file.read_exact(&mut compressed))
+                .map_err(|e| format!("Failed to read bundle block: {}", e))?;
+            let data = decompress(&compressed, block.flags, block.uncompressed_size as usize)?;
+            self.cached_block = Some((index, data));
+        }
+        Ok(self
+            .cached_block
+            .as_ref()
+            .map(|(_, data)| data.as_slice())
+            .unwrap_or_default())
+    }
+
+    /// Check the container is complete and every block decompresses: catches
+    /// truncated or partially written bundles
+    pub fn verify(&mut self) -> Result<(), String> {
+        let header = &self.inspection.header;
+        if header.file_size != self.file_len {
+            return Err(format!(
+                "Header records {} bytes but the file is {} bytes",
+                header.file_size, self.file_len
+            ));
+        }
+
+        let blocks_end = self
+            .block_starts
+            .last()
+            .zip(self.inspection.blocks.last())
+            .map(|((start, _), block)| start + block.compressed_size as u64)
+            .unwrap_or(0);
+        if blocks_end > self.file_len {
+            return Err(format!(
+                "Block data runs {} bytes past the end of the file",
+                blocks_end - self.file_len
+            ));
+        }
+
+        let data_size = self.inspection.uncompressed_size;
+        if let Some(entry) = self
+            .inspection
+            .entries
+            .iter()
+            .find(|entry| entry.offset.saturating_add(entry.size) > data_size)
+        {
+            return Err(format!("{} extends past the bundle data", entry.path));
+        }
+
+        for index in 0..self.inspection.blocks.len() {
+            self.block(index)
+                .map_err(|e| format!("Block {}: {}", index, e))?;
+        }
+        Ok(())
+    }
+
+    /// Read `len` bytes starting `start` bytes into an entry
+    pub fn read_entry(
+        &mut self,
+        entry: &BundleEntry,
+        start: u64,
+        len: usize,
+    ) -> Result<Vec<u8>, String> {
+        if start.saturating_add(len as u64) > entry.size {
+            return Err(format!("Read past the end of {}", entry.path));
+        }
+
+        let mut output = Vec::with_capacity(len);
+        let mut offset = entry.offset + start;
+        let end = offset + len as u64;
+        while offset < end {
+            let index = self
+                .block_starts
+                .partition_point(|(_, block_start)| *block_start <= offset)
+                .checked_sub(1)
+                .ok_or_else(|| "Bundle data offset out of range".to_string())?;
+            let (_, block_start) = self.block_starts[index];
+            let block = self.block(index)?;
+            let from = (offset - block_start) as usize;
+            if from >= block.len() {
+                return Err("Bundle data offset out of range".to_string());
+            }
+            let to = block.len().min(from + (end - offset) as usize);
+            output.extend_from_slice(&block[from..to]);
+            offset += (to - from) as u64;
+        }
+        Ok(output)
+    }
+}
+
+/// List what a bundle holds (its serialized files and resource payloads)
+/// straight from the UnityFS container, without starting the backend
+#[tauri::command]
+#[tracing::instrument(err)]
+pub async fn inspect_bundle(path: String) -> Result<BundleInspection, String> {
+    let bundle = path_guard::existing_file(&path, "Bundle")?;
+    tokio::task::spawn_blocking(move || BundleReader::open(&bundle).map(|reader| reader.inspection))
+        .await
+        .map_err(|e| format!("Bundle inspection task failed: {}", e))?
+}
@@ -0,0 +1,514 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+use walkdir::WalkDir;
+
+use crate::long_paths;
+use crate::path_guard;
+use crate::skins::SKIN_CONFIG_FILE;
+
+/// Image formats the backend can import into bundles
+const SUPPORTED_IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "svg"];
+
+/// Image formats commonly found in skins that the backend cannot use
+const UNSUPPORTED_IMAGE_EXTENSIONS: &[&str] = &[
+    "webp", "gif", "bmp", "tga", "tif", "tiff", "psd", "dds", "heic", "avif",
+];
+
+/// Windows MAX_PATH, including the terminating NUL
+const WINDOWS_MAX_PATH: usize = 260;
+
+#[derive(Serialize, Clone)]
+pub struct ValidationIssue {
+    pub severity: String, // "error", "warning"
+    pub code: String,
+    pub message: String,
+    pub path: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct ValidationReport {
+    pub skin_path: String,
+    pub valid: bool,
+    pub error_count: usize,
+    pub warning_count: usize,
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    /// Count errors and warnings and strip long-path prefixes for display
+    pub fn from_issues(skin_dir: &Path, mut issues: Vec<ValidationIssue>) -> Self {
+        for issue in &mut issues {
+            issue.path = issue
+                .path
+                .take()
+                .map(|path| long_paths::strip_extended(&path));
+        }
+
+        let error_count = issues
+            .iter()
+            .filter(|issue| issue.severity == "error")
+            .count();
+        let warning_count = issues.len() - error_count;
+
+        Self {
+            skin_path: skin_dir.to_string_lossy().to_string(),
+            valid: error_count == 0,
+            error_count,
+            warning_count,
+            issues,
+        }
+    }
+}
+
+impl ValidationIssue {
+    pub fn error(code: &str, message: String, path: Option<&Path>) -> Self {
+        Self {
+            severity: "error".to_string(),
+            code: code.to_string(),
+            message,
+            path: path.map(|p| p.to_string_lossy().to_string()),
+        }
+    }
+
+    pub fn warning(code: &str, message: String, path: Option<&Path>) -> Self {
+        Self {
+            severity: "warning".to_string(),
+            code: code.to_string(),
+            message,
+            path: path.map(|p| p.to_string_lossy().to_string()),
+        }
+    }
+}
+
+/// Check config.json exists, parses, and only references paths that exist
+fn check_config(skin_dir: &Path, issues: &mut Vec<ValidationIssue>) {
+    let config_path = skin_dir.join(SKIN_CONFIG_FILE);
+
+    // The backend builds skins without a config from their folder layout
+    if !config_path.is_file() {
+        issues.push(ValidationIssue::warning(
+            "missing_config",
+            format!(
+                "Skin has no {}; its name and metadata come from the folder",
+                SKIN_CONFIG_FILE
+            ),
+            Some(&config_path),
+        ));
+        return;
+    }
+
+    let config: serde_json::Value = match std::fs::read_to_string(&config_path)
+        .map_err(|e| e.to_string())
+        .and_then(|content| serde_json::from_str(&content).map_err(|e| e.to_string()))
+    {
+        Ok(config) => config,
+        Err(e) => {
+            issues.push(ValidationIssue::error(
+                "invalid_config",
+                format!("{} could not be parsed: {}", SKIN_CONFIG_FILE, e),
+                Some(&config_path),
+            ));
+            return;
+        }
+    };
+
+    match config.get("schema_version").and_then(|v| v.as_u64()) {
+        None => issues.push(ValidationIssue::error(
+            "missing_schema_version",
+            "Config is missing schema_version".to_string(),
+            Some(&config_path),
+        )),
+        Some(version) if version < 2 => issues.push(ValidationIssue::warning(
+            "legacy_schema_version",
+            format!(
+                "Config uses schema_version {} (version 2 is current)",
+                version
+            ),
+            Some(&config_path),
+        )),
+        _ => {}
+    }
+
+    if config
+        .get("name")
+        .and_then(|v| v.as_str())
+        .is_none_or(|name| name.trim().is_empty())
+    {
+        issues.push(ValidationIssue::error(
+            "missing_name",
+            "Config is missing a skin name".to_string(),
+            Some(&config_path),
+        ));
+    }
+
+    if let Some(includes) = config.get("includes") {
+        match includes.as_array() {
+            Some(items) => {
+                for item in items {
+                    match item.as_str() {
+                        Some(include) if !skin_dir.join(include).exists() => {
+                            issues.push(ValidationIssue::error(
+                                "missing_include",
+                                format!("Included path does not exist: {}", include),
+                                Some(&skin_dir.join(include)),
+                            ))
+                        }
+                        Some(_) => {}
+                        None => issues.push(ValidationIssue::error(
+                            "invalid_include",
+                            format!("Include entry is not a string: {}", item),
+                            Some(&config_path),
+                        )),
+                    }
+                }
+            }
+            None => issues.push(ValidationIssue::error(
+                "invalid_includes",
+                "Config \"includes\" must be a list of paths".to_string(),
+                Some(&config_path),
+            )),
+        }
+    }
+}
+
+/// Build output (`packages/`) and hidden folders are not part of the skin source
+pub fn is_generated_dir(entry: &walkdir::DirEntry) -> bool {
+    if entry.depth() == 0 || !entry.file_type().is_dir() {
+        return false;
+    }
+    let name = entry.file_name().to_string_lossy();
+    name.starts_with('.') || (entry.depth() == 1 && name == "packages")
+}
+
+/// Check every file in the skin for readability, format, and path length
+fn check_files(skin_dir: &Path, issues: &mut Vec<ValidationIssue>) {
+    // Asset stems per folder, used to detect ambiguous replacements (crown.png + crown.svg)
+    let mut stems: HashMap<(String, String), Vec<String>> = HashMap::new();
+
+    for entry in WalkDir::new(skin_dir)
+        .into_iter()
+        .filter_entry(|entry| !is_generated_dir(entry))
+    {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                issues.push(ValidationIssue::error(
+                    "unreadable_path",
+                    format!("Failed to read: {}", e),
+                    e.path(),
+                ));
+                continue;
+            }
+        };
+
+        let path = entry.path();
+
+        if let Some(name) = path.file_name().and_then(|name| name.to_str()) {
+            if !long_paths::is_nfc_name(name) {
+                issues.push(ValidationIssue::warning(
+                    "unnormalized_name",
+                    "File name uses decomposed Unicode (common in macOS archives) and may not match references in config.json".to_string(),
+                    Some(path),
+                ));
+            }
+        }
+
+        if long_paths::strip_extended(&path.to_string_lossy()).len() >= WINDOWS_MAX_PATH {
+            issues.push(ValidationIssue::warning(
+                "path_too_long",
+                format!(
+                    "Path is {} characters long and may fail in tools without long path support (limit {})",
+                    long_paths::strip_extended(&path.to_string_lossy()).len(),
+                    WINDOWS_MAX_PATH - 1
+                ),
+                Some(path),
+            ));
+        }
+
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        if let Err(e) = std::fs::File::open(path) {
+            issues.push(ValidationIssue::error(
+                "unreadable_file",
+                format!("File cannot be opened: {}", e),
+                Some(path),
+            ));
+            continue;
+        }
+
+        if let Ok(metadata) = entry.metadata() {
+            if metadata.len() == 0 {
+                issues.push(ValidationIssue::warning(
+                    "empty_file",
+                    "File is empty".to_string(),
+                    Some(path),
+                ));
+            }
+        }
+
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_lowercase())
+            .unwrap_or_default();
+
+        if UNSUPPORTED_IMAGE_EXTENSIONS.contains(&extension.as_str()) {
+            issues.push(ValidationIssue::error(
+                "unsupported_image_format",
+                format!(
+                    "Unsupported image format .{} (use {})",
+                    extension,
+                    SUPPORTED_IMAGE_EXTENSIONS.join(", ")
+                ),
+                Some(path),
+            ));
+        }
+
+        if extension == "json" {
+            let parses = std::fs::read_to_string(path)
+                .ok()
+                .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+                .is_some();
+            if !parses {
+                issues.push(ValidationIssue::error(
+                    "invalid_json",
+                    "JSON file could not be parsed".to_string(),
+                    Some(path),
+                ));
+            }
+        }
+
+        if SUPPORTED_IMAGE_EXTENSIONS.contains(&extension.as_str()) {
+            let parent = path
+                .parent()
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let stem = path
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_lowercase())
+                .unwrap_or_default();
+            stems
+                .entry((parent, stem))
+                .or_default()
+                .push(long_paths::strip_extended(&path.to_string_lossy()));
+        }
+    }
+
+    for ((_, stem), paths) in stems {
+        if paths.len() > 1 {
+            issues.push(ValidationIssue::warning(
+                "duplicate_asset",
+                format!(
+                    "Multiple images share the name \"{}\": {}",
+                    stem,
+                    paths.join(", ")
+                ),
+                None,
+            ));
+        }
+    }
+}
+
+/// On-disk spelling of `relative` under `base` when it only exists with
+/// different letter case; `None` when it matches exactly or doesn't exist
+fn case_mismatch(base: &Path, relative: &str) -> Option<String> {
+    let mut current = base.to_path_buf();
+    let mut actual = Vec::new();
+    let mut mismatched = false;
+    for part in relative
+        .split(['/', '\\'])
+        .filter(|part| !part.is_empty() && *part != ".")
+    {
+        let names: Vec<String> = std::fs::read_dir(&current)
+            .ok()?
+            .flatten()
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .collect();
+        let name = match names.iter().find(|name| *name == part) {
+            Some(name) => name,
+            None => {
+                mismatched = true;
+                names
+                    .iter()
+                    .find(|name| name.to_lowercase() == part.to_lowercase())?
+            }
+        };
+        current.push(name);
+        actual.push(name.clone());
+    }
+    mismatched.then(|| actual.join("/"))
+}
+
+/// Strings in a mapping file: keys, values, and list items at the top level
+fn mapping_strings(mapping: &serde_json::Value) -> Vec<&str> {
+    let Some(object) = mapping.as_object() else {
+        return Vec::new();
+    };
+    let mut strings = Vec::new();
+    for (key, value) in object {
+        strings.push(key.as_str());
+        match value {
+            serde_json::Value::String(value) => strings.push(value),
+            serde_json::Value::Array(items) => {
+                strings.extend(items.iter().filter_map(|item| item.as_str()))
+            }
+            _ => {}
+        }
+    }
+    strings
+}
+
+/// Paths that differ only by case collide when the skin is unpacked on Windows
+/// or macOS, and references that only match a file ignoring case work there
+/// but fail on Linux
+fn check_case(skin_dir: &Path, issues: &mut Vec<ValidationIssue>) {
+    let mut by_lowercase: HashMap<String, Vec<String>> = HashMap::new();
+    let mut mapping_files = Vec::new();
+    for entry in WalkDir::new(skin_dir)
+        .into_iter()
+        .filter_entry(|entry| !is_generated_dir(entry))
+        .flatten()
+    {
+        let Ok(relative) = entry.path().strip_prefix(skin_dir) else {
+            continue;
+        };
+        let relative = relative.to_string_lossy().replace('\\', "/");
+        if relative.is_empty() {
+            continue;
+        }
+        by_lowercase
+            .entry(relative.to_lowercase())
+            .or_default()
+            .push(relative);
+        if entry.file_type().is_file() && entry.file_name() == "mapping.json" {
+            mapping_files.push(entry.into_path());
+        }
+    }
+
+    let mut collisions: Vec<Vec<String>> = by_lowercase
+        .into_values()
+        .filter(|paths| paths.len() > 1)
+        .collect();
+    collisions.sort();
+    for paths in collisions {
+        issues.push(ValidationIssue::error(
+            "case_collision",
+            format!(
+                "Paths differ only by letter case and will overwrite each other on Windows and macOS: {}",
+                paths.join(", ")
+            ),
+            Some(&skin_dir.join(&paths[0])),
+        ));
+    }
+
+    let config_path = skin_dir.join(SKIN_CONFIG_FILE);
+    let includes: Vec<String> = std::fs::read_to_string(&config_path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .and_then(|config| config.get("includes").and_then(|v| v.as_array()).cloned())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|include| include.as_str().map(str::to_string))
+        .collect();
+    for include in includes {
+        if let Some(actual) = case_mismatch(skin_dir, &include) {
+            issues.push(ValidationIssue::warning(
+                "case_mismatched_reference",
+                format!(
+                    "Include \"{}\" only matches \"{}\" ignoring letter case, which fails on Linux",
+                    include, actual
+                ),
+                Some(&config_path),
+            ));
+        }
+    }
+
+    // Mapping files name the images and stylesheets next to them, with or without extension
+    for mapping_path in mapping_files {
+        let Some(folder) = mapping_path.parent() else {
+            continue;
+        };
+        let Some(mapping) = std::fs::read_to_string(&mapping_path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        else {
+            continue;
+        };
+        let siblings: Vec<(String, String)> = std::fs::read_dir(folder)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|entry| {
+                        let path = entry.path();
+                        let name = entry.file_name().to_string_lossy().to_string();
+                        let stem = path
+                            .file_stem()
+                            .map(|stem| stem.to_string_lossy().to_string())
+                            .unwrap_or_else(|| name.clone());
+                        (name, stem)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        for reference in mapping_strings(&mapping) {
+            let exact = siblings
+                .iter()
+                .any(|(name, stem)| name == reference || stem == reference);
+            if exact {
+                continue;
+            }
+            let lowercase = reference.to_lowercase();
+            if let Some((name, _)) = siblings.iter().find(|(name, stem)| {
+                name.to_lowercase() == lowercase || stem.to_lowercase() == lowercase
+            }) {
+                issues.push(ValidationIssue::warning(
+                    "case_mismatched_reference",
+                    format!(
+                        "\"{}\" only matches {} ignoring letter case, which fails on Linux",
+                        reference, name
+                    ),
+                    Some(&mapping_path),
+                ));
+            }
+        }
+    }
+}
+
+/// Validate a skin folder before building it
+pub fn validate_skin_dir(skin_dir: &Path) -> ValidationReport {
+    let mut issues = Vec::new();
+
+    if !skin_dir.is_dir() {
+        issues.push(ValidationIssue::error(
+            "missing_skin_folder",
+            format!("Skin folder not found: {}", skin_dir.display()),
+            Some(skin_dir),
+        ));
+    } else {
+        check_config(skin_dir, &mut issues);
+        check_files(&long_paths::extended(skin_dir), &mut issues);
+        check_case(&long_paths::extended(skin_dir), &mut issues);
+    }
+
+    ValidationReport::from_issues(skin_dir, issues)
+}
+
+/// Check a skin for problems that would make a build fail part-way through
+#[tauri::command]
+#[tracing::instrument]
+pub fn validate_skin(path: String) -> ValidationReport {
+    match path_guard::clean_path(&path, "Skin folder") {
+        Ok(skin_dir) => validate_skin_dir(&skin_dir),
+        Err(e) => ValidationReport {
+            skin_path: path.trim().to_string(),
+            valid: false,
+            error_count: 1,
+            warning_count: 0,
+            issues: vec![ValidationIssue::error("invalid_path", e, None)],
+        },
+    }
+}
@@ -0,0 +1,262 @@
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tokio::sync::mpsc;
+use tokio::time::{timeout, Duration};
+
+use crate::events::{SkinChangedEvent, WatchStatusEvent};
+use crate::path_guard;
+use crate::process::{run_python_task, ProcessState, TaskConfig};
+use crate::projects;
+
+/// Quiet period after the last change before an auto-rebuild starts
+const REBUILD_DEBOUNCE: Duration = Duration::from_millis(1500);
+
+struct ActiveWatcher {
+    // Dropping the watcher closes the event channel and ends the listener task
+    _watcher: RecommendedWatcher,
+    skin_path: PathBuf,
+}
+
+#[derive(Default)]
+pub struct WatcherState {
+    active: Mutex<Option<ActiveWatcher>>,
+}
+
+/// Changes to build output and hidden folders must not trigger rebuilds
+fn is_relevant_change(skin_dir: &Path, path: &Path) -> bool {
+    let Ok(relative) = path.strip_prefix(skin_dir) else {
+        return false;
+    };
+
+    let mut components = relative.components();
+    match components.next() {
+        Some(first) => {
+            let first = first.as_os_str().to_string_lossy();
+            first != "packages"
+                && !relative
+                    .components()
+                    .any(|c| c.as_os_str().to_string_lossy().starts_with('.'))
+        }
+        None => false,
+    }
+}
+
+fn emit_watch_status(
+    app_handle: &AppHandle,
+    project_id: &str,
+    skin_dir: &Path,
+    status: &str,
+    message: Option<String>,
+) {
+    let _ = app_handle.emit(
+        "watch_status",
+        WatchStatusEvent {
+            project_id: project_id.to_string(),
+            skin_path: skin_dir.to_string_lossy().to_string(),
+            status: status.to_string(),
+            message,
+        },
+    );
+}
+
+/// Run the rebuild for a change, reporting it as watch mode status when
+/// watching a project
+async fn rebuild(
+    app_handle: &AppHandle,
+    skin_dir: &Path,
+    config: &TaskConfig,
+    watch_project: Option<&str>,
+) {
+    let state = app_handle.state::<ProcessState>();
+    if state.is_busy().await {
+        println!("[DEBUG] Skipping auto-rebuild: a build is already running");
+        if let Some(project_id) = watch_project {
+            emit_watch_status(app_handle, project_id, skin_dir, "watching", None);
+        }
+        return;
+    }
+
+    println!("[DEBUG] Auto-rebuilding skin after changes: {:?}", skin_dir);
+    if let Some(project_id) = watch_project {
+        emit_watch_status(app_handle, project_id, skin_dir, "building", None);
+    }
+    let result = run_python_task(app_handle.clone(), config.clone(), state, None).await;
+    if let Err(e) = &result {
+        println!("[WARNING] Auto-rebuild failed: {}", e);
+    }
+
+    if let Some(project_id) = watch_project {
+        match result {
+            Ok(output) if output.success => {
+                emit_watch_status(app_handle, project_id, skin_dir, "up_to_date", None)
+            }
+            Ok(output) => emit_watch_status(
+                app_handle,
+                project_id,
+                skin_dir,
+                "failed",
+                Some(format!("Build failed with exit code {}", output.status)),
+            ),
+            Err(e) => {
+                emit_watch_status(app_handle, project_id, skin_dir, "failed", Some(e.message))
+            }
+        }
+    }
+}
+
+async fn listen_for_changes(
+    app_handle: AppHandle,
+    skin_dir: PathBuf,
+    mut receiver: mpsc::UnboundedReceiver<Vec<PathBuf>>,
+    rebuild_config: Option<TaskConfig>,
+    watch_project: Option<String>,
+) {
+    // Watch mode starts by catching up on changes made since the last build
+    if let (Some(config), Some(project_id)) = (&rebuild_config, &watch_project) {
+        rebuild(&app_handle, &skin_dir, config, Some(project_id)).await;
+    }
+
+    while let Some(first_batch) = receiver.recv().await {
+        let mut changed = first_batch;
+
+        // Collect follow-up events until the folder has been quiet for the debounce window
+        while let Ok(Some(batch)) = timeout(REBUILD_DEBOUNCE, receiver.recv()).await {
+            changed.extend(batch);
+        }
+
+        changed.sort();
+        changed.dedup();
+
+        let _ = app_handle.emit(
+            "skin_changed",
+            SkinChangedEvent {
+                skin_path: skin_dir.to_string_lossy().to_string(),
+                paths: changed
+                    .iter()
+                    .map(|path| path.to_string_lossy().to_string())
+                    .collect(),
+                auto_rebuild: rebuild_config.is_some(),
+            },
+        );
+
+        if let Some(config) = &rebuild_config {
+            rebuild(&app_handle, &skin_dir, config, watch_project.as_deref()).await;
+        }
+    }
+}
+
+/// Start watching a skin folder, replacing any previous watcher
+fn watch_skin(
+    app_handle: AppHandle,
+    state: &WatcherState,
+    skin_dir: PathBuf,
+    rebuild_config: Option<TaskConfig>,
+    watch_project: Option<String>,
+) -> Result<(), String> {
+    let (sender, receiver) = mpsc::unbounded_channel::<Vec<PathBuf>>();
+    let watched_dir = skin_dir.clone();
+
+    let mut watcher = notify::recommended_watcher(move |result: notify::Result<notify::Event>| {
+        let Ok(event) = result else {
+            return;
+        };
+        if matches!(event.kind, EventKind::Access(_)) {
+            return;
+        }
+        let paths: Vec<PathBuf> = event
+            .paths
+            .into_iter()
+            .filter(|path| is_relevant_change(&watched_dir, path))
+            .collect();
+        if !paths.is_empty() {
+            let _ = sender.send(paths);
+        }
+    })
+    .map_err(|e| format!("Failed to create file watcher: {}", e))?;
+
+    watcher
+        .watch(&skin_dir, RecursiveMode::Recursive)
+        .map_err(|e| format!("Failed to watch skin folder: {}", e))?;
+
+    // Replacing the previous watcher stops it
+    let mut active = state
+        .active
+        .lock()
+        .map_err(|_| "Watcher state is poisoned".to_string())?;
+    *active = Some(ActiveWatcher {
+        _watcher: watcher,
+        skin_path: skin_dir.clone(),
+    });
+
+    tauri::async_runtime::spawn(listen_for_changes(
+        app_handle,
+        skin_dir,
+        receiver,
+        rebuild_config,
+        watch_project,
+    ));
+
+    Ok(())
+}
+
+/// Watch a skin folder, emitting `skin_changed` events and optionally re-running
+/// the patch task when assets change
+#[tauri::command]
+#[tracing::instrument(skip(app_handle, state, config), err)]
+pub fn start_skin_watcher(
+    app_handle: AppHandle,
+    state: State<'_, WatcherState>,
+    path: String,
+    auto_rebuild: bool,
+    config: Option<TaskConfig>,
+) -> Result<(), String> {
+    let skin_dir = path_guard::existing_dir(&path, "Skin folder")?;
+
+    let rebuild_config = if auto_rebuild {
+        Some(config.ok_or_else(|| "Auto-rebuild requires a build configuration".to_string())?)
+    } else {
+        None
+    };
+
+    watch_skin(app_handle, &state, skin_dir, rebuild_config, None)
+}
+
+/// Live-rebuild a saved project: an incremental build now and after every
+/// change to its skin folder, reported through `watch_status` events until
+/// `stop_skin_watcher`
+#[tauri::command]
+#[tracing::instrument(skip(app_handle, state), err)]
+pub fn start_watch_mode(
+    app_handle: AppHandle,
+    state: State<'_, WatcherState>,
+    project: String,
+) -> Result<(), String> {
+    let (_, projects) = projects::load_projects(&app_handle)?;
+    let project = projects
+        .into_iter()
+        .find(|saved| saved.id == project)
+        .ok_or_else(|| format!("Project not found: {}", project))?;
+    let skin_dir = path_guard::existing_dir(&project.skin_path, "Skin folder")?;
+
+    let mut config = project.task_config();
+    config.incremental = true;
+
+    emit_watch_status(&app_handle, &project.id, &skin_dir, "watching", None);
+    watch_skin(app_handle, &state, skin_dir, Some(config), Some(project.id))
+}
+
+/// Stop watching the current skin folder
+#[tauri::command]
+#[tracing::instrument(skip(state), err)]
+pub fn stop_skin_watcher(state: State<'_, WatcherState>) -> Result<Option<String>, String> {
+    let mut active = state
+        .active
+        .lock()
+        .map_err(|_| "Watcher state is poisoned".to_string())?;
+
+    Ok(active
+        .take()
+        .map(|watcher| watcher.skin_path.to_string_lossy().to_string()))
+}
@@ -0,0 +1,146 @@
+use serde_json::{json, Value};
+use std::path::Path;
+use std::time::Duration;
+use tauri::AppHandle;
+
+use crate::events::CompletionEvent;
+use crate::process::TaskConfig;
+use crate::projects;
+use crate::settings;
+
+/// Give up on a webhook that doesn't answer, so a slow endpoint can't pile up requests
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// What a finished build is reported as
+struct BuildNotice {
+    project: String,
+    success: bool,
+    message: String,
+    duration: Duration,
+    warnings: u32,
+    errors: u32,
+    run_id: String,
+}
+
+/// Project name for the notice, falling back to the skin folder name
+fn project_name(app_handle: &AppHandle, config: &TaskConfig) -> String {
+    let project = config.project_id.as_deref().and_then(|id| {
+        let (_, projects) = projects::load_projects(app_handle).ok()?;
+        projects
+            .into_iter()
+            .find(|project| project.id == id)
+            .map(|project| project.name)
+    });
+    project.unwrap_or_else(|| {
+        Path::new(config.skin_path.trim())
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| config.skin_path.clone())
+    })
+}
+
+/// One-line summary for chat payloads
+fn notice_text(notice: &BuildNotice) -> String {
+    format!(
+        "{} {}: {} ({:.0}s, {} warnings, {} errors)",
+        if notice.success { "✅" } else { "❌" },
+        notice.project,
+        notice.message,
+        notice.duration.as_secs_f64(),
+        notice.warnings,
+        notice.errors
+    )
+}
+
+/// Request body in the configured format: "discord", "slack" or plain "json"
+fn payload(format: &str, notice: &BuildNotice) -> Result<Value, String> {
+    match format {
+        "" | "json" => Ok(json!({
+            "event": "build_complete",
+            "project": notice.project,
+            "result": if notice.success { "succeeded" } else { "failed" },
+            "message": notice.message,
+            "duration_ms": notice.duration.as_millis() as u64,
+            "warnings": notice.warnings,
+            "errors": notice.errors,
+            "run_id": notice.run_id,
+        })),
+        "discord" => Ok(json!({ "content": notice_text(notice) })),
+        "slack" => Ok(json!({ "text": notice_text(notice) })),
+        other => Err(format!("Unknown webhook format: {}", other)),
+    }
+}
+
+async fn post(url: &str, format: &str, notice: &BuildNotice) -> Result<(), String> {
+    let url = url.trim();
+    if !url.starts_with("https://") && !url.starts_with("http://") {
+        return Err(format!("Webhook URL must be http or https: {}", url));
+    }
+    let body = payload(format.trim(), notice)?;
+
+    let response = reqwest::Client::new()
+        .post(url)
+        .timeout(WEBHOOK_TIMEOUT)
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to send webhook: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!(
+            "Webhook request failed with status: {}",
+            response.status()
+        ));
+    }
+    Ok(())
+}
+
+/// POST a summary of a finished build to the `webhookUrl` setting, if one is
+/// set. Previews aren't reported. Runs in the background; failures are only logged.
+pub fn notify_build(
+    app_handle: &AppHandle,
+    config: &TaskConfig,
+    completion: &CompletionEvent,
+    duration: Duration,
+) {
+    if config.dry_run {
+        return;
+    }
+    let Some(url) = settings::get_setting::<String>(app_handle, "webhookUrl")
+        .filter(|url| !url.trim().is_empty())
+    else {
+        return;
+    };
+    let format: String = settings::get_setting(app_handle, "webhookFormat").unwrap_or_default();
+
+    let notice = BuildNotice {
+        project: project_name(app_handle, config),
+        success: completion.success,
+        message: completion.message.clone(),
+        duration,
+        warnings: completion.summary.warnings,
+        errors: completion.summary.errors,
+        run_id: completion.run_id.clone(),
+    };
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = post(&url, &format, &notice).await {
+            println!("[WARNING] {}", e);
+        }
+    });
+}
+
+/// Send a sample notice so the user can check the webhook before a real build
+#[tauri::command]
+#[tracing::instrument(skip(url), err)]
+pub async fn send_test_webhook(url: String, format: Option<String>) -> Result<(), String> {
+    let notice = BuildNotice {
+        project: "FM Skin Builder".to_string(),
+        success: true,
+        message: "Webhook test".to_string(),
+        duration: Duration::ZERO,
+        warnings: 0,
+        errors: 0,
+        run_id: String::new(),
+    };
+    post(&url, format.as_deref().unwrap_or(""), &notice).await
+}
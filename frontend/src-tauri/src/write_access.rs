@@ -0,0 +1,129 @@
+use serde::Serialize;
+use std::io::ErrorKind;
+use std::path::{Component, Path, PathBuf};
+
+use crate::path_guard;
+
+#[derive(Serialize)]
+pub struct WriteAccessReport {
+    pub path: String,
+    /// "in_place" (bundles can be patched directly), "elevation" (patching
+    /// needs administrator rights) or "staging" (build to a staging folder and
+    /// copy the bundles over by hand)
+    pub mode: String,
+    /// Writes were silently redirected to the VirtualStore by UAC
+    pub virtualized: bool,
+    pub message: String,
+}
+
+fn report(path: &Path, mode: &str, virtualized: bool, message: String) -> WriteAccessReport {
+    WriteAccessReport {
+        path: path.to_string_lossy().to_string(),
+        mode: mode.to_string(),
+        virtualized,
+        message,
+    }
+}
+
+/// Store (Game Pass) installs live under WindowsApps, which not even
+/// administrators can write to
+fn is_windows_apps(dir: &Path) -> bool {
+    dir.components().any(|component| {
+        matches!(component, Component::Normal(name) if name.eq_ignore_ascii_case("WindowsApps"))
+    })
+}
+
+/// Where UAC file virtualization would put a write to `path`:
+/// `%LOCALAPPDATA%\VirtualStore\<path without the drive>`
+fn virtual_store_path(path: &Path) -> Option<PathBuf> {
+    if !cfg!(windows) {
+        return None;
+    }
+    let local = std::env::var_os("LOCALAPPDATA")?;
+    let relative: PathBuf = path
+        .components()
+        .filter(|component| matches!(component, Component::Normal(_)))
+        .collect();
+    Some(PathBuf::from(local).join("VirtualStore").join(relative))
+}
+
+/// Write, rename over and delete probe files the way an in-place patch
+/// replaces bundles. Returns whether the writes were virtualized: they succeed,
+/// and the process sees them in the folder, but they land in the VirtualStore.
+fn probe(dir: &Path) -> std::io::Result<bool> {
+    let id = std::process::id();
+    let written = dir.join(format!(".fm_skin_builder_probe_{}.tmp", id));
+    let target = dir.join(format!(".fm_skin_builder_probe_{}.bundle", id));
+
+    let result = std::fs::write(&target, b"original")
+        .and_then(|_| std::fs::write(&written, b"patched"))
+        .and_then(|_| std::fs::rename(&written, &target));
+
+    let redirected = virtual_store_path(&target);
+    let virtualized = result.is_ok() && redirected.is_some_and(|path| path.exists());
+
+    // Deleting through the same view also clears any VirtualStore copies
+    let _ = std::fs::remove_file(&written);
+    let _ = std::fs::remove_file(&target);
+
+    result.map(|_| virtualized)
+}
+
+/// Try a real write and rename in a bundles folder and report whether an
+/// in-place patch will work, needs elevation, or has to go through staging
+pub fn probe_access(dir: &Path) -> WriteAccessReport {
+    if is_windows_apps(dir) {
+        return report(
+            dir,
+            "staging",
+            false,
+            "Store and Game Pass installs can't be modified in place. Build to a staging folder and copy the bundles over manually.".to_string(),
+        );
+    }
+
+    match probe(dir) {
+        Ok(false) => report(
+            dir,
+            "in_place",
+            false,
+            "The bundles folder is writable; skins can be installed in place.".to_string(),
+        ),
+        Ok(true) => report(
+            dir,
+            "elevation",
+            true,
+            "Writes to the bundles folder are redirected by Windows; administrator rights are needed to install in place.".to_string(),
+        ),
+        Err(e) if e.kind() == ErrorKind::PermissionDenied => report(
+            dir,
+            "elevation",
+            false,
+            format!(
+                "The bundles folder isn't writable ({}); administrator rights are needed to install in place.",
+                e
+            ),
+        ),
+        Err(e) => {
+            println!("[WARNING] Write probe failed in {:?}: {}", dir, e);
+            report(
+                dir,
+                "staging",
+                false,
+                format!(
+                    "The bundles folder can't be written to ({}). Build to a staging folder and copy the bundles over manually.",
+                    e
+                ),
+            )
+        }
+    }
+}
+
+/// Check whether skins can be installed into a bundles folder in place
+#[tauri::command]
+#[tracing::instrument(err)]
+pub async fn check_write_access(path: String) -> Result<WriteAccessReport, String> {
+    let dir = path_guard::existing_dir(&path, "Bundles folder")?;
+    tokio::task::spawn_blocking(move || probe_access(&dir))
+        .await
+        .map_err(|e| format!("Write access check failed: {}", e))
+}
@@ -7,6 +7,9 @@ const invokeMock = vi.fn();
 
 vi.mock("@tauri-apps/api/core", () => ({
   invoke: (...args: unknown[]) => invokeMock(...args),
+  Channel: class {
+    onmessage: (message: unknown) => void = () => {};
+  },
 }));
 
 vi.mock("@tauri-apps/api/event", () => ({
@@ -124,6 +127,7 @@ describe("App shell", () => {
           debugExport: true,
           dryRun: false,
         },
+        onEvent: expect.anything(),
       });
     });
 
@@ -1,5 +1,5 @@
 import { useCallback, useEffect, useMemo, useRef, useState } from "react";
-import { invoke } from "@tauri-apps/api/core";
+import { Channel, invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
 import { getVersion } from "@tauri-apps/api/app";
 import {
@@ -35,12 +35,19 @@ import { ScrollArea } from "@/components/ui/scroll-area";
 import { ThemeToggle } from "@/components/theme-toggle";
 import { Logo } from "@/components/logo";
 import { Settings } from "@/components/Settings";
-import { useStore } from "@/hooks/useStore";
+import {
+  useStore,
+  type BuildVerbosity,
+  type OutputMode,
+  type WebhookFormat,
+} from "@/hooks/useStore";
+import { errorMessage, isAppError } from "@/lib/errors";
 
 type CommandResult = {
   stdout: string;
   stderr: string;
   status: number;
+  success: boolean;
 };
 
 type TaskMode = "preview" | "build";
@@ -50,6 +57,9 @@ type TaskConfig = {
   bundlesPath: string;
   debugExport: boolean;
   dryRun: boolean;
+  // Bundle file names to rebuild; omitted rebuilds every bundle
+  bundles?: string[];
+  verbosity?: BuildVerbosity;
 };
 
 type LogLevel = "info" | "error" | "warning";
@@ -66,6 +76,22 @@ type BuildProgress = {
   status: string;
 };
 
+type BuildLogPayload = {
+  message: string;
+  level: string;
+  source: string;
+  timestamp: string;
+  sequence: number;
+  run_id: string;
+};
+
+// Log lines and progress for a task started here arrive on its channel
+type BuildStreamEvent = {
+  seq: number;
+  event: string;
+  payload: unknown;
+};
+
 const detectTauriRuntime = () => {
   if (typeof window === "undefined") {
     return "unknown" as const;
@@ -197,6 +223,10 @@ function App() {
       progress?: () => void;
       complete?: () => void;
       taskStarted?: () => void;
+      exitRequested?: () => void;
+      skinOverwritten?: () => void;
+      cacheEviction?: () => void;
+      linkImport?: () => void;
     } = {};
 
     const setupListeners = async () => {
@@ -224,7 +254,9 @@ function App() {
               "[FRONTEND] task_started event received:",
               event.payload
             );
-            const timestamp = new Date().toLocaleTimeString();
+            const timestamp = new Date(
+              event.payload.timestamp
+            ).toLocaleTimeString();
             setLogs((prev) => [
               ...prev,
               {
@@ -238,16 +270,19 @@ function App() {
         if (!isMounted) return; // Check after each async operation
         console.log("[FRONTEND] task_started listener set up");
 
-        // Listen for log events
+        // Listen for log events from builds started outside this window
+        // (tray, automation, watch mode); our own builds stream over a channel
         console.log("[FRONTEND] Setting up build_log listener...");
-        unlisteners.log = await listen<{ message: string; level: string }>(
+        unlisteners.log = await listen<BuildLogPayload>(
           "build_log",
           (event) => {
             console.log(
               "[FRONTEND] build_log event received:",
               event.payload.message
             );
-            const timestamp = new Date().toLocaleTimeString();
+            const timestamp = new Date(
+              event.payload.timestamp
+            ).toLocaleTimeString();
             setLogs((prev) => [
               ...prev,
               {
@@ -309,6 +344,94 @@ function App() {
         });
         if (!isMounted) return;
         console.log("[FRONTEND] build_complete listener set up");
+
+        // Show how a build that finished while the window was hidden ended
+        const lastCompletion = await invoke<{
+          completion: { success: boolean; message: string };
+          finished_at: number;
+          in_background: boolean;
+        } | null>("get_last_completion").catch(() => null);
+        if (!isMounted) return;
+        if (lastCompletion?.in_background) {
+          setLastBuildSuccess(lastCompletion.completion.success);
+          setLogs((prev) => [
+            ...prev,
+            {
+              message: lastCompletion.completion.message,
+              level: lastCompletion.completion.success ? "info" : "error",
+              timestamp: new Date(
+                lastCompletion.finished_at * 1000
+              ).toLocaleTimeString(),
+            },
+          ]);
+        }
+
+        // Closing the app mid-build needs confirmation before the build is stopped
+        unlisteners.exitRequested = await listen<{
+          message: string;
+          skin_path: string | null;
+        }>("exit_requested", (event) => {
+          if (window.confirm(event.payload.message)) {
+            invoke("confirm_exit").catch((error) => {
+              console.error("[FRONTEND] Failed to confirm exit:", error);
+            });
+          }
+        });
+
+        // A game update replaced an installed skin's bundles: offer to rebuild and re-apply it
+        unlisteners.skinOverwritten = await listen<{
+          message: string;
+          skin_path: string;
+          bundles_path: string;
+        }>("skin_overwritten_by_update", (event) => {
+          if (window.confirm(event.payload.message)) {
+            invoke("reapply_last_skin", {
+              skinPath: event.payload.skin_path,
+              bundlesPath: event.payload.bundles_path,
+            }).catch((error) => {
+              console.error(
+                "[FRONTEND] Failed to re-apply skin:",
+                errorMessage(error)
+              );
+            });
+          }
+        });
+
+        // Not enough disk space to build: ask before clearing cache the build doesn't need
+        unlisteners.cacheEviction = await listen<{
+          request_id: string;
+          message: string;
+        }>("cache_eviction_proposed", (event) => {
+          invoke("respond_cache_eviction", {
+            requestId: event.payload.request_id,
+            approved: window.confirm(event.payload.message),
+          }).catch((error) => {
+            console.error(
+              "[FRONTEND] Failed to answer cache eviction prompt:",
+              errorMessage(error)
+            );
+          });
+        });
+
+        // A link asks to download and import a skin: nothing is fetched until the user allows it
+        unlisteners.linkImport = await listen<{
+          request_id: string;
+          url: string;
+          name: string;
+          in_repository: boolean;
+          message: string;
+        }>("link_import_proposed", (event) => {
+          invoke("respond_link_import", {
+            requestId: event.payload.request_id,
+            approved: window.confirm(event.payload.message),
+          }).catch((error) => {
+            console.error(
+              "[FRONTEND] Failed to answer link import prompt:",
+              errorMessage(error)
+            );
+          });
+        });
+        if (!isMounted) return;
         console.log("[FRONTEND] All listeners configured successfully");
       } catch (error) {
         console.error("[FRONTEND] Error setting up listeners:", error);
@@ -377,8 +500,9 @@ function App() {
       bundlesPath: bundlesPath.trim(),
       debugExport: debugMode,
       dryRun: mode === "preview",
+      verbosity: settings.buildVerbosity,
     }),
-    [bundlesPath, debugMode, skinPath]
+    [bundlesPath, debugMode, settings.buildVerbosity, skinPath]
   );
 
   const browseForFolder = useCallback(
@@ -445,15 +569,31 @@ function App() {
 
   const handleAutoDetectGame = useCallback(async () => {
     try {
-      // detect_game_installation now returns the bundles directory directly
-      const bundlesPath = await invoke<string | null>(
-        "detect_game_installation"
-      );
-      if (bundlesPath) {
+      const installation = await invoke<{
+        bundles_path: string;
+        platform_target: string | null;
+      } | null>("detect_game_installation");
+      if (installation) {
+        const bundlesPath = installation.bundles_path;
         setBundlesPath(bundlesPath);
         appendLog(`✓ Found bundles directory: ${bundlesPath}`, "info");
+        if (installation.platform_target) {
+          appendLog(
+            `Building for ${installation.platform_target} (detected from the install layout)`,
+            "info"
+          );
+        }
         setPathErrors((prev) => ({ ...prev, bundles: undefined }));
-        setPathWarnings((prev) => ({ ...prev, bundles: undefined }));
+        const access = await invoke<{ mode: string; message: string }>(
+          "check_write_access",
+          { path: bundlesPath }
+        );
+        if (access.mode === "in_place") {
+          setPathWarnings((prev) => ({ ...prev, bundles: undefined }));
+        } else {
+          appendLog(`⚠ ${access.message}`, "warning");
+          setPathWarnings((prev) => ({ ...prev, bundles: access.message }));
+        }
       } else {
         appendLog("⚠ Could not detect game installation", "warning");
         appendLog(
@@ -483,7 +623,7 @@ function App() {
       setPathErrors((prev) => ({ ...prev, skin: undefined }));
     } catch (error) {
       appendLog(
-        `Error getting default skins directory: ${String(error)}`,
+        `Error getting default skins directory: ${errorMessage(error)}`,
         "error"
       );
     }
@@ -498,6 +638,20 @@ function App() {
 
       const config = buildConfig(mode);
 
+      // Warn before building a skin known to be broken on the installed game version
+      if (mode === "build") {
+        const compatibility = await invoke<{ warning: string | null }>(
+          "check_skin_compatibility",
+          { skin: config.skinPath, bundlesPath: config.bundlesPath }
+        ).catch(() => null);
+        if (
+          compatibility?.warning &&
+          !window.confirm(`${compatibility.warning}\n\nBuild anyway?`)
+        ) {
+          return;
+        }
+      }
+
       setIsRunning(true);
       setLastBuildSuccess(null);
       setLastTaskType(mode);
@@ -517,10 +671,28 @@ function App() {
         appendLog("Debug mode: enabled");
       }
 
+      const onEvent = new Channel<BuildStreamEvent>();
+      onEvent.onmessage = ({ event, payload }) => {
+        if (event === "build_log") {
+          const log = payload as BuildLogPayload;
+          setLogs((prev) => [
+            ...prev,
+            {
+              message: log.message,
+              level: log.level as LogLevel,
+              timestamp: new Date(log.timestamp).toLocaleTimeString(),
+            },
+          ]);
+        } else if (event === "build_progress") {
+          setBuildProgress(payload as BuildProgress);
+        }
+      };
+
       try {
-        // The command will now stream logs in real-time via events
+        // Logs and progress stream in real-time over the channel
         const response = await invoke<CommandResult>("run_python_task", {
           config,
+          onEvent,
         });
         markRuntimeReady();
 
@@ -531,7 +703,10 @@ function App() {
           lines.forEach((line) => appendLog(line));
         }
       } catch (error) {
-        appendLog(`✗ Command failed: ${String(error)}`, "error");
+        // Cancelling already reports itself through stopTask
+        if (!isAppError(error) || error.kind !== "cancelled") {
+          appendLog(`✗ Command failed: ${errorMessage(error)}`, "error");
+        }
         setLastBuildSuccess(false);
       } finally {
         setIsRunning(false);
@@ -562,7 +737,7 @@ function App() {
       setBuildProgress(null);
       setLastBuildSuccess(false);
     } catch (error) {
-      appendLog(`Failed to stop task: ${String(error)}`, "error");
+      appendLog(`Failed to stop task: ${errorMessage(error)}`, "error");
     }
   }, [appendLog]);
 
@@ -786,6 +961,211 @@ function App() {
                   />
                 </div>
 
+                <div className="flex items-center justify-between rounded-lg border p-4">
+                  <div className="space-y-0.5">
+                    <Label htmlFor="build-verbosity" className="font-semibold">
+                      Log Output
+                    </Label>
+                    <p className="text-xs text-muted-foreground">
+                      How much the backend logs during builds
+                    </p>
+                  </div>
+                  <select
+                    id="build-verbosity"
+                    value={settings.buildVerbosity ?? "normal"}
+                    onChange={(event) =>
+                      saveSetting(
+                        "buildVerbosity",
+                        event.target.value as BuildVerbosity
+                      )
+                    }
+                    className="h-9 rounded-md border border-input bg-transparent px-3 text-sm shadow-sm"
+                  >
+                    <option value="quiet">Quiet</option>
+                    <option value="normal">Normal</option>
+                    <option value="verbose">Verbose</option>
+                    <option value="debug">Debug</option>
+                  </select>
+                </div>
+
+                <div className="flex items-center justify-between rounded-lg border p-4">
+                  <div className="space-y-0.5">
+                    <Label htmlFor="output-mode" className="font-semibold">
+                      Build Output
+                    </Label>
+                    <p className="text-xs text-muted-foreground">
+                      Where built bundles go after a successful build
+                    </p>
+                  </div>
+                  <select
+                    id="output-mode"
+                    value={settings.outputMode ?? ""}
+                    onChange={(event) =>
+                      saveSetting(
+                        "outputMode",
+                        (event.target.value || undefined) as
+                          | OutputMode
+                          | undefined
+                      )
+                    }
+                    className="h-9 rounded-md border border-input bg-transparent px-3 text-sm shadow-sm"
+                  >
+                    <option value="">Platform default</option>
+                    <option value="in_place">Install into the game</option>
+                    <option value="staging">Keep in output folder</option>
+                    <option value="package">Export a package</option>
+                  </select>
+                </div>
+
+                <div className="space-y-2 rounded-lg border p-4">
+                  <Label htmlFor="webhook-url" className="font-semibold">
+                    Build Webhook
+                  </Label>
+                  <p className="text-xs text-muted-foreground">
+                    POST a summary of each finished build to this URL
+                  </p>
+                  <div className="flex gap-2">
+                    <Input
+                      id="webhook-url"
+                      value={settings.webhookUrl ?? ""}
+                      onChange={(event) =>
+                        saveSetting(
+                          "webhookUrl",
+                          event.target.value.trim() ? event.target.value : undefined
+                        )
+                      }
+                      placeholder="https://..."
+                      className="flex-1"
+                    />
+                    <select
+                      id="webhook-format"
+                      value={settings.webhookFormat ?? "json"}
+                      onChange={(event) =>
+                        saveSetting(
+                          "webhookFormat",
+                          event.target.value as WebhookFormat
+                        )
+                      }
+                      className="h-9 rounded-md border border-input bg-transparent px-3 text-sm shadow-sm"
+                    >
+                      <option value="json">JSON</option>
+                      <option value="discord">Discord</option>
+                      <option value="slack">Slack</option>
+                    </select>
+                    <Button
+                      variant="outline"
+                      disabled={!settings.webhookUrl}
+                      onClick={() =>
+                        invoke("send_test_webhook", {
+                          url: settings.webhookUrl,
+                          format: settings.webhookFormat,
+                        })
+                          .then(() => appendLog("✓ Webhook test sent", "info"))
+                          .catch((error) =>
+                            appendLog(
+                              `Webhook test failed: ${errorMessage(error)}`,
+                              "error"
+                            )
+                          )
+                      }
+                    >
+                      Test
+                    </Button>
+                  </div>
+                </div>
+
+                <div className="space-y-2 rounded-lg border p-4">
+                  <Label htmlFor="backup-retention" className="font-semibold">
+                    Backups
+                  </Label>
+                  <p className="text-xs text-muted-foreground">
+                    Original bundles are kept in{" "}
+                    <code className="rounded bg-muted px-1 py-0.5">
+                      {settings.backupRoot ?? "the app data folder"}
+                    </code>
+                  </p>
+                  <div className="flex items-center gap-2">
+                    <Input
+                      id="backup-retention"
+                      type="number"
+                      min={0}
+                      value={settings.backupRetention ?? 0}
+                      onChange={(event) =>
+                        saveSetting(
+                          "backupRetention",
+                          Number(event.target.value) || undefined
+                        )
+                      }
+                      title="Restore points kept per game install (0 keeps all)"
+                      className="w-24"
+                    />
+                    <Label htmlFor="backup-compression" className="text-sm">
+                      Compress restore points
+                    </Label>
+                    <Switch
+                      id="backup-compression"
+                      checked={settings.backupCompression ?? false}
+                      onCheckedChange={(checked) =>
+                        saveSetting("backupCompression", checked)
+                      }
+                    />
+                  </div>
+                  <div className="flex gap-2">
+                    <Button
+                      variant="outline"
+                      onClick={async () => {
+                        const selected = await invoke<string | null>(
+                          "select_folder",
+                          {
+                            dialog_title: "Select Backup Folder",
+                            initial_path: settings.backupRoot,
+                          }
+                        );
+                        if (typeof selected !== "string") return;
+                        invoke<string>("set_backup_root", {
+                          destination: selected,
+                        })
+                          .then((root) => {
+                            appendLog(`✓ Backups moved to ${root}`, "info");
+                            // Keep local state in sync with the value Rust saved
+                            return saveSetting("backupRoot", root);
+                          })
+                          .catch((error) =>
+                            appendLog(
+                              `Moving backups failed: ${errorMessage(error)}`,
+                              "error"
+                            )
+                          );
+                      }}
+                    >
+                      Change Location
+                    </Button>
+                    <Button
+                      variant="outline"
+                      disabled={!settings.backupRetention}
+                      onClick={() =>
+                        invoke<{ removed: string[]; freed_bytes: number }>(
+                          "prune_backups"
+                        )
+                          .then((result) =>
+                            appendLog(
+                              `✓ Removed ${result.removed.length} restore point(s)`,
+                              "info"
+                            )
+                          )
+                          .catch((error) =>
+                            appendLog(
+                              `Pruning backups failed: ${errorMessage(error)}`,
+                              "error"
+                            )
+                          )
+                      }
+                    >
+                      Prune Now
+                    </Button>
+                  </div>
+                </div>
+
                 <div className="flex gap-3 pt-4">
                   <Button
                     onClick={() => runTask("preview")}
@@ -11,6 +11,7 @@ import {
 } from "./ui/card";
 import { Label } from "./ui/label";
 import { Switch } from "./ui/switch";
+import { errorMessage } from "@/lib/errors";
 import {
   Loader2,
   FolderOpen,
@@ -148,7 +149,7 @@ export function Settings({
       console.error("[FRONTEND DEBUG] Error clearing cache:", error);
 
       // Show error message using Tauri dialog
-      await message(`Failed to clear cache: ${String(error)}`, {
+      await message(`Failed to clear cache: ${errorMessage(error)}`, {
         title: "Error",
         kind: "error",
       });
@@ -161,7 +162,7 @@ export function Settings({
     try {
       await invoke("open_cache_dir");
     } catch (error) {
-      alert(`Failed to open cache directory: ${String(error)}`);
+      alert(`Failed to open cache directory: ${errorMessage(error)}`);
     }
   }, []);
 
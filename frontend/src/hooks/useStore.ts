@@ -1,18 +1,68 @@
 import { useEffect, useState } from 'react';
 import { Store } from '@tauri-apps/plugin-store';
+import { invoke } from '@tauri-apps/api/core';
 
 export type AppSettings = {
   skinPath?: string;
   bundlesPath?: string;
   betaUpdates?: boolean;
   checkForUpdates?: boolean;
+  minimizeToTray?: boolean;
+  /** What closing the window does to a running or scheduled build */
+  closePolicy?: 'prompt' | 'cancel' | 'background';
+  automationApiEnabled?: boolean;
+  automationPort?: number;
+  /** Skins library folder when moved out of Documents (see relocate_skins_dir) */
+  skinsDir?: string;
+  /** Extra folders skins are listed from, optionally with a label */
+  skinRoots?: (string | { path: string; label?: string })[];
+  /** Keyboard shortcut overrides: action → accelerator ("" unbinds); see set_shortcut */
+  shortcuts?: Record<string, string>;
+  /** Backend output during builds; quiet hides info lines from the live log */
+  buildVerbosity?: BuildVerbosity;
+  /** Where built bundles go; unset uses the platform default (staging on macOS, in place elsewhere) */
+  outputMode?: OutputMode;
+  /** URL a summary of each finished build is POSTed to (see webhook.rs) */
+  webhookUrl?: string;
+  webhookFormat?: WebhookFormat;
+  /** Minisign public keys whose signed skin packages are reported as verified on import */
+  trustedSigningKeys?: string[];
+  /** Where original bundle backups live; change it with set_backup_root, which moves them */
+  backupRoot?: string;
+  /** Restore points kept per game install; unset or 0 keeps every point */
+  backupRetention?: number;
+  /** Gzip restore point copies; the original backup is never compressed */
+  backupCompression?: boolean;
+};
+
+export type BuildVerbosity = 'quiet' | 'normal' | 'verbose' | 'debug';
+export type OutputMode = 'in_place' | 'staging' | 'package';
+export type WebhookFormat = 'json' | 'discord' | 'slack';
+
+type PortableInfo = {
+  portable: boolean;
+  settings_path: string | null;
 };
 
 let storeInstance: Store | null = null;
 
+/**
+ * Writes go through save_settings, which saves the file atomically under its
+ * lock; the store is only read here. Null removes a key.
+ */
+async function writeSettings(values: Partial<AppSettings>, clear = false): Promise<void> {
+  const payload = Object.fromEntries(
+    Object.entries(values).map(([key, value]) => [key, value ?? null])
+  );
+  await invoke('save_settings', { values: payload, clear });
+}
+
 async function getStore(): Promise<Store> {
   if (!storeInstance) {
-    storeInstance = await Store.load('settings.json', { autoSave: false });
+    // Portable builds keep settings next to the executable
+    const info = await invoke<PortableInfo>('get_portable_info').catch(() => null);
+    const storePath = info?.portable && info.settings_path ? info.settings_path : 'settings.json';
+    storeInstance = await Store.load(storePath, { autoSave: false });
   }
   return storeInstance;
 }
@@ -54,15 +104,7 @@ export function useStore() {
     value: AppSettings[K]
   ): Promise<void> => {
     try {
-      const store = await getStore();
-
-      if (value === undefined) {
-        await store.delete(key);
-      } else {
-        await store.set(key, value);
-      }
-
-      await store.save();
+      await writeSettings({ [key]: value } as Partial<AppSettings>);
 
       setSettings(prev => ({ ...prev, [key]: value }));
     } catch (error) {
@@ -74,17 +116,7 @@ export function useStore() {
   // Save multiple settings at once
   const saveSettings = async (newSettings: Partial<AppSettings>): Promise<void> => {
     try {
-      const store = await getStore();
-
-      for (const [key, value] of Object.entries(newSettings)) {
-        if (value === undefined) {
-          await store.delete(key);
-        } else {
-          await store.set(key, value);
-        }
-      }
-
-      await store.save();
+      await writeSettings(newSettings);
 
       setSettings(prev => ({ ...prev, ...newSettings }));
     } catch (error) {
@@ -101,9 +133,7 @@ export function useStore() {
   // Clear all settings
   const clearAllSettings = async (): Promise<void> => {
     try {
-      const store = await getStore();
-      await store.clear();
-      await store.save();
+      await writeSettings({}, true);
 
       setSettings({});
     } catch (error) {
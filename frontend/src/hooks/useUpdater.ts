@@ -4,6 +4,7 @@ import { relaunch } from "@tauri-apps/plugin-process";
 import { getVersion } from "@tauri-apps/api/app";
 import { fetch } from "@tauri-apps/plugin-http";
 import { invoke } from "@tauri-apps/api/core";
+import { errorMessage } from "@/lib/errors";
 
 type UpdaterOptions = {
   autoUpdate: boolean;
@@ -176,7 +177,7 @@ export function useUpdater({ autoUpdate, betaUpdates }: UpdaterOptions) {
             } catch (installError) {
               console.error("[UPDATER] Installation failed:", installError);
               await message(
-                `Failed to install update: ${String(installError)}`,
+                `Failed to install update: ${errorMessage(installError)}`,
                 {
                   title: "Installation Failed",
                   kind: "error",
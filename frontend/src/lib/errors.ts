@@ -0,0 +1,37 @@
+/** Error returned by Tauri commands (see src-tauri/src/error.rs) */
+export type ErrorKind =
+  | "invalid_input"
+  | "not_found"
+  | "permission_denied"
+  | "io"
+  | "network"
+  | "backend"
+  | "cancelled"
+  | "conflict"
+  | "unsupported"
+  | "internal";
+
+export interface AppError {
+  kind: ErrorKind;
+  message: string;
+  recoverable: boolean;
+  hint: string | null;
+  path: string | null;
+}
+
+export function isAppError(error: unknown): error is AppError {
+  return (
+    typeof error === "object" &&
+    error !== null &&
+    "kind" in error &&
+    "message" in error
+  );
+}
+
+/** Readable message for a failed command, with the remediation hint if any */
+export function errorMessage(error: unknown): string {
+  if (isAppError(error)) {
+    return error.hint ? `${error.message} ${error.hint}` : error.message;
+  }
+  return String(error);
+}
@@ -0,0 +1,61 @@
+from pathlib import Path
+
+from fm_skin_builder.core import bundle_paths
+from fm_skin_builder.core.bundle_paths import infer_bundle_files, resolve_bundle_dir
+
+
+def _make_bundles(folder: Path, *names: str) -> Path:
+    folder.mkdir(parents=True)
+    for name in names:
+        (folder / name).write_bytes(b"bundle")
+    return folder
+
+
+def test_resolve_bundle_dir_picks_target_inside_folder(tmp_path):
+    aa = tmp_path / "StreamingAssets" / "aa"
+    _make_bundles(aa / "StandaloneOSX", "ui_osx.bundle")
+    universal = _make_bundles(aa / "StandaloneOSXUniversal", "ui_universal.bundle")
+
+    assert resolve_bundle_dir(aa, "StandaloneOSXUniversal") == universal
+    assert resolve_bundle_dir(aa) == aa
+
+
+def test_resolve_bundle_dir_switches_to_sibling_target(tmp_path):
+    aa = tmp_path / "aa"
+    native = _make_bundles(aa / "StandaloneLinux64", "ui_linux.bundle")
+    windows = _make_bundles(aa / "StandaloneWindows64", "ui_windows.bundle")
+
+    assert resolve_bundle_dir(native, "StandaloneWindows64") == windows
+    assert resolve_bundle_dir(windows, "StandaloneWindows64") == windows
+
+
+def test_resolve_bundle_dir_keeps_folder_without_target_bundles(tmp_path):
+    native = _make_bundles(tmp_path / "aa" / "StandaloneLinux64", "ui_linux.bundle")
+    (tmp_path / "aa" / "StandaloneWindows64").mkdir()
+
+    assert resolve_bundle_dir(native, "StandaloneWindows64") == native
+
+
+def test_infer_bundle_files_finds_proton_install_only_with_target(
+    tmp_path, monkeypatch
+):
+    proton = _make_bundles(
+        tmp_path
+        / ".local/share/Steam/steamapps/common/Football Manager 26"
+        / "data/StreamingAssets/aa/StandaloneWindows64",
+        "ui_windows.bundle",
+    )
+    monkeypatch.setattr("platform.system", lambda: "Linux")
+    monkeypatch.setattr(bundle_paths.os.path, "expanduser", _expand_to(tmp_path))
+
+    assert infer_bundle_files(tmp_path) == []
+    assert infer_bundle_files(tmp_path, "StandaloneWindows64") == [
+        proton / "ui_windows.bundle"
+    ]
+
+
+def _expand_to(home: Path):
+    def expand(path: str) -> str:
+        return str(home / path[2:]) if path.startswith("~/") else path
+
+    return expand
@@ -0,0 +1,21 @@
+import logging
+
+from fm_skin_builder.core import logger as fm_logger
+
+
+def test_set_verbosity_updates_existing_and_new_loggers():
+    existing = fm_logger.get_logger("fm_skin_builder.test_logger.existing")
+    try:
+        fm_logger.set_verbosity(quiet=True)
+        assert existing.level == fm_logger.PROGRESS
+        assert not existing.isEnabledFor(logging.INFO)
+        assert existing.isEnabledFor(fm_logger.PROGRESS)
+
+        fm_logger.set_verbosity(verbose=1)
+        assert existing.level == logging.DEBUG
+        created = fm_logger.get_logger("fm_skin_builder.test_logger.created")
+        assert created.level == logging.DEBUG
+    finally:
+        fm_logger.set_verbosity()
+
+    assert existing.level == logging.INFO
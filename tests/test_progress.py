@@ -0,0 +1,26 @@
+import json
+
+from fm_skin_builder.core.progress import PROGRESS_FILE_ENV, report_progress
+
+
+def test_report_progress_appends_json_lines(tmp_path, monkeypatch):
+    path = tmp_path / "progress.jsonl"
+    monkeypatch.setenv(PROGRESS_FILE_ENV, str(path))
+
+    report_progress(1, 3, "Processing bundle ui.bundle")
+    report_progress(2, 3, "Processing bundle icons.bundle")
+
+    records = [json.loads(line) for line in path.read_text().splitlines()]
+    assert records == [
+        {"current": 1, "total": 3, "status": "Processing bundle ui.bundle"},
+        {"current": 2, "total": 3, "status": "Processing bundle icons.bundle"},
+    ]
+
+
+def test_report_progress_without_file_is_a_no_op(tmp_path, monkeypatch):
+    monkeypatch.delenv(PROGRESS_FILE_ENV, raising=False)
+    monkeypatch.chdir(tmp_path)
+
+    report_progress(1, 1, "Processing bundle ui.bundle")
+
+    assert list(tmp_path.iterdir()) == []